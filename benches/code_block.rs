@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use doclog::blocks::CodeBlock;
+
+/// Builds a code of `lines` lines, each of them ending in a `;`.
+fn build_code(lines: usize) -> String {
+    (0..lines)
+        .map(|i| format!("let variable_{i} = compute(variable_{i}, {i});\n"))
+        .collect()
+}
+
+fn highlight_sections(c: &mut Criterion) {
+    let mut group = c.benchmark_group("highlight_sections");
+    group.sample_size(10);
+
+    for lines in [1_000, 10_000, 100_000] {
+        let code = build_code(lines);
+        let offsets: Vec<usize> = code.match_indices(';').map(|(i, _)| i).collect();
+
+        group.bench_with_input(BenchmarkId::from_parameter(lines), &code, |b, code| {
+            b.iter(|| {
+                offsets
+                    .iter()
+                    .fold(CodeBlock::new(code.as_str()), |block, offset| {
+                        block.highlight_section(*offset..*offset + 1, None)
+                    })
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn highlight_cursors(c: &mut Criterion) {
+    let code = build_code(100_000);
+    let offsets: Vec<usize> = code
+        .match_indices(';')
+        .map(|(i, _)| i)
+        .step_by(100)
+        .collect();
+
+    c.bench_function("highlight_cursors", |b| {
+        b.iter(|| {
+            offsets
+                .iter()
+                .fold(CodeBlock::new(black_box(code.as_str())), |block, offset| {
+                    block.highlight_cursor(*offset, None)
+                })
+        });
+    });
+}
+
+criterion_group!(benches, highlight_sections, highlight_cursors);
+criterion_main!(benches);
@@ -0,0 +1,257 @@
+//! Derive macro for the `DocLog` trait of [doclog](https://docs.rs/doclog).
+//!
+//! It is re-exported by `doclog` when its `derive` feature is enabled, so it should not
+//! be used directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, LitStr, Result};
+
+/// Implements `doclog::DocLog` for a struct or an enum.
+///
+/// The struct, or every variant of the enum, must be annotated with a `#[doclog(...)]`
+/// attribute with the following arguments:
+/// - `message = "..."`: the title of the log. It can reference the fields like [format!],
+///   e.g. `"Unexpected {token}"`. Fields of tuples are named `_0`, `_1`, etc.
+/// - `code = "..."`: the code of the log. Optional.
+/// - `level = "..."`: the level of the log, i.e. `trace`, `debug`, `info`, `warn` or `error`.
+///   Defaults to `error`.
+/// - `label(span = "...", text = "...")`: a section of the source to highlight. `span` is
+///   the name of a field that implements `doclog::Span`, optionally prefixed by `self.`.
+///   `text` is the message of the section, which can reference the fields like `message`,
+///   and it is optional. It can be repeated. Labels that collide with others are
+///   discarded, and if any label is out of bounds of the source, the code is printed
+///   without labels and with the error as its final message.
+#[proc_macro_derive(DocLog, attributes(doclog))]
+pub fn derive_doc_log(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// The arguments of a `#[doclog(...)]` attribute.
+struct LogAttribute {
+    message: LitStr,
+    code: Option<LitStr>,
+    level: Ident,
+    labels: Vec<Label>,
+}
+
+/// A `label(...)` argument of a `#[doclog(...)]` attribute.
+struct Label {
+    span: LitStr,
+    text: Option<LitStr>,
+}
+
+/// The levels that can be set with `level = "..."`.
+const LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let attribute = parse_attribute(&input.attrs, name)?;
+            let (pattern, bindings) = bind_fields(quote!(Self), &data.fields);
+            let log = build_log(&attribute, &bindings)?;
+
+            quote! {
+                let #pattern = *self;
+                #log
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    let attribute = parse_attribute(&variant.attrs, variant_name)?;
+                    let (pattern, bindings) =
+                        bind_fields(quote!(Self::#variant_name), &variant.fields);
+                    let log = build_log(&attribute, &bindings)?;
+
+                    Ok(quote! {
+                        #pattern => { #log }
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            quote! {
+                match *self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                "DocLog cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::doclog::DocLog for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn to_log<'doclog>(&self, __doclog_source: &'doclog str) -> ::doclog::Log<'doclog> {
+                #body
+            }
+        }
+    })
+}
+
+/// Parses the `#[doclog(...)]` attribute of a struct or a variant.
+fn parse_attribute(attributes: &[Attribute], owner: &Ident) -> Result<LogAttribute> {
+    let attribute = attributes
+        .iter()
+        .find(|v| v.path().is_ident("doclog"))
+        .ok_or_else(|| Error::new(owner.span(), "missing #[doclog(...)] attribute"))?;
+
+    let mut message = None;
+    let mut code = None;
+    let mut level = None;
+    let mut labels = Vec::new();
+
+    attribute.parse_nested_meta(|meta| {
+        if meta.path.is_ident("message") {
+            message = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("code") {
+            code = Some(meta.value()?.parse::<LitStr>()?);
+        } else if meta.path.is_ident("level") {
+            let value = meta.value()?.parse::<LitStr>()?;
+
+            if !LEVELS.contains(&value.value().as_str()) {
+                return Err(Error::new(
+                    value.span(),
+                    format!("the level must be one of: {}", LEVELS.join(", ")),
+                ));
+            }
+
+            level = Some(Ident::new(&value.value(), value.span()));
+        } else if meta.path.is_ident("label") {
+            let mut span = None;
+            let mut text = None;
+
+            meta.parse_nested_meta(|meta| {
+                if meta.path.is_ident("span") {
+                    span = Some(meta.value()?.parse::<LitStr>()?);
+                } else if meta.path.is_ident("text") {
+                    text = Some(meta.value()?.parse::<LitStr>()?);
+                } else {
+                    return Err(meta.error("unknown label argument, expected `span` or `text`"));
+                }
+
+                Ok(())
+            })?;
+
+            labels.push(Label {
+                span: span.ok_or_else(|| meta.error("missing `span` argument"))?,
+                text,
+            });
+        } else {
+            return Err(
+                meta.error("unknown argument, expected `message`, `code`, `level` or `label`")
+            );
+        }
+
+        Ok(())
+    })?;
+
+    Ok(LogAttribute {
+        message: message
+            .ok_or_else(|| Error::new(attribute.span(), "missing `message` argument"))?,
+        code,
+        level: level.unwrap_or_else(|| format_ident!("error")),
+        labels,
+    })
+}
+
+/// Builds the pattern that binds every field by reference, and returns it along with
+/// the names of the bindings.
+fn bind_fields(path: TokenStream2, fields: &Fields) -> (TokenStream2, Vec<Ident>) {
+    match fields {
+        Fields::Named(fields) => {
+            let bindings: Vec<Ident> = fields
+                .named
+                .iter()
+                .map(|v| v.ident.clone().unwrap())
+                .collect();
+
+            (quote!(#path { #(ref #bindings),* }), bindings)
+        }
+        Fields::Unnamed(fields) => {
+            let bindings: Vec<Ident> = (0..fields.unnamed.len())
+                .map(|i| format_ident!("_{}", i))
+                .collect();
+
+            (quote!(#path ( #(ref #bindings),* )), bindings)
+        }
+        Fields::Unit => (path, Vec::new()),
+    }
+}
+
+/// Builds the expression that creates the log.
+fn build_log(attribute: &LogAttribute, bindings: &[Ident]) -> Result<TokenStream2> {
+    let message = &attribute.message;
+    let level = &attribute.level;
+    let code = attribute.code.iter();
+
+    let mut log = quote! {
+        ::doclog::Log::new(::doclog::LogLevel::#level()).add_block(
+            ::doclog::blocks::HeaderBlock::new()
+                .title(::std::format!(#message))
+                #(.code(#code))*
+        )
+    };
+
+    if !attribute.labels.is_empty() {
+        let labels = attribute
+            .labels
+            .iter()
+            .map(|label| {
+                let field = label.span.value();
+                let field = field.strip_prefix("self.").unwrap_or(&field);
+                let field = match field.parse::<usize>() {
+                    Ok(index) => format!("_{}", index),
+                    Err(_) => field.to_string(),
+                };
+                let binding = bindings.iter().find(|v| *v == &field).ok_or_else(|| {
+                    Error::new(label.span.span(), format!("unknown field `{}`", field))
+                })?;
+
+                let text = match &label.text {
+                    Some(text) => quote!(::std::format!(#text)),
+                    None => quote!(::doclog::blocks::TextBlock::new()),
+                };
+
+                Ok(quote! {
+                    ::doclog::blocks::Label::new(#binding, #text)
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        log = quote! {
+            #log.add_block(
+                match ::doclog::blocks::CodeBlock::try_with_labels(__doclog_source, [#(#labels),*]) {
+                    ::std::result::Result::Ok(block) => block,
+                    ::std::result::Result::Err(error) => {
+                        ::doclog::blocks::CodeBlock::new(__doclog_source)
+                            .final_message(::std::format!("Invalid label: {}", error))
+                    }
+                }
+            )
+        };
+    }
+
+    Ok(log)
+}
@@ -0,0 +1,434 @@
+use crate::blocks::{CodeBlock, CodeBlockStyle, HighlightError, LineNumberFormatter, TextBlock};
+use crate::{LogLevel, Span};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use yansi::{Color, Style};
+
+/// The state of a [CodeBlockBuilder] that has no code yet.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct NoCode;
+
+/// The state of a [CodeBlockBuilder] that already has code, so sections can be highlighted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct WithCode;
+
+/// A typed builder of [CodeBlock]s that only allows to highlight sections and build the block
+/// once the code has been set, checking it at compile time.
+///
+/// The highlight methods never panic: the first invalid span is returned by
+/// [CodeBlockBuilder::build] and the later highlights are ignored.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::{CodeBlockBuilder, HighlightError};
+///
+/// let block = CodeBlockBuilder::new()
+///     .title("Invalid variable")
+///     .with_code("let a = b")
+///     .highlight_section(8..9, None)
+///     .build();
+/// assert!(block.is_ok());
+///
+/// let block = CodeBlockBuilder::new()
+///     .with_code("let a = b")
+///     .highlight_section(8..20, None)
+///     .build();
+/// assert_eq!(
+///     block.unwrap_err(),
+///     HighlightError::OutOfBounds {
+///         end: 20,
+///         code_length: 9
+///     }
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct CodeBlockBuilder<'a, S> {
+    block: CodeBlock<'a>,
+    error: Option<HighlightError>,
+    state: PhantomData<S>,
+}
+
+impl<'a> CodeBlockBuilder<'a, NoCode> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [CodeBlockBuilder] without code.
+    pub fn new() -> Self {
+        Self {
+            block: CodeBlock::new(""),
+            error: None,
+            state: PhantomData,
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the code, allowing to highlight sections on it.
+    pub fn with_code(mut self, code: impl Into<Cow<'a, str>>) -> CodeBlockBuilder<'a, WithCode> {
+        self.block.code = code.into();
+
+        CodeBlockBuilder {
+            block: self.block,
+            error: self.error,
+            state: PhantomData,
+        }
+    }
+}
+
+impl<'a> Default for CodeBlockBuilder<'a, NoCode> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, S> CodeBlockBuilder<'a, S> {
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the title. See [CodeBlock::title].
+    #[inline(always)]
+    pub fn title(mut self, title: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.title(title);
+        self
+    }
+
+    /// Sets the file path. See [CodeBlock::file_path].
+    #[inline(always)]
+    pub fn file_path(mut self, file_path: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.file_path(file_path);
+        self
+    }
+
+    /// Sets the final message. See [CodeBlock::final_message].
+    #[inline(always)]
+    pub fn final_message(mut self, final_message: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.final_message(final_message);
+        self
+    }
+
+    /// Sets whether to show new line chars. See [CodeBlock::show_new_line_chars].
+    #[inline(always)]
+    pub fn show_new_line_chars(mut self, show_new_line_chars: bool) -> Self {
+        self.block = self.block.show_new_line_chars(show_new_line_chars);
+        self
+    }
+
+    /// Sets the secondary color. See [CodeBlock::secondary_color].
+    #[inline(always)]
+    pub fn secondary_color(mut self, secondary_color: Color) -> Self {
+        self.block = self.block.secondary_color(secondary_color);
+        self
+    }
+
+    /// Sets the previous lines. See [CodeBlock::previous_lines].
+    #[inline(always)]
+    pub fn previous_lines(mut self, previous_lines: usize) -> Self {
+        self.block = self.block.previous_lines(previous_lines);
+        self
+    }
+
+    /// Sets the next lines. See [CodeBlock::next_lines].
+    #[inline(always)]
+    pub fn next_lines(mut self, next_lines: usize) -> Self {
+        self.block = self.block.next_lines(next_lines);
+        self
+    }
+
+    /// Sets the middle lines. See [CodeBlock::middle_lines].
+    #[inline(always)]
+    pub fn middle_lines(mut self, middle_lines: usize) -> Self {
+        self.block = self.block.middle_lines(middle_lines);
+        self
+    }
+
+    /// Sets the maximum lines shown inside multiline sections.
+    /// See [CodeBlock::max_section_lines].
+    #[inline(always)]
+    pub fn max_section_lines(mut self, max_section_lines: usize) -> Self {
+        self.block = self.block.max_section_lines(max_section_lines);
+        self
+    }
+
+    /// Sets the maximum messages printed per line. See [CodeBlock::max_inline_messages].
+    #[inline(always)]
+    pub fn max_inline_messages(mut self, max_inline_messages: usize) -> Self {
+        self.block = self.block.max_inline_messages(max_inline_messages);
+        self
+    }
+
+    /// Sets the colors of the sections. See [CodeBlock::section_palette].
+    #[inline(always)]
+    pub fn section_palette(mut self, section_palette: Vec<Color>) -> Self {
+        self.block = self.block.section_palette(section_palette);
+        self
+    }
+
+    /// Sets the maximum highlighted spans printed. See [CodeBlock::max_sections].
+    #[inline(always)]
+    pub fn max_sections(mut self, max_sections: usize) -> Self {
+        self.block = self.block.max_sections(max_sections);
+        self
+    }
+
+    /// Sets whether to print a column ruler. See [CodeBlock::show_column_ruler].
+    #[inline(always)]
+    pub fn show_column_ruler(mut self, show_column_ruler: bool) -> Self {
+        self.block = self.block.show_column_ruler(show_column_ruler);
+        self
+    }
+
+    /// Sets whether to print in compact mode. See [CodeBlock::compact].
+    #[inline(always)]
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.block = self.block.compact(compact);
+        self
+    }
+
+    /// Sets the visual style. See [CodeBlock::style].
+    #[inline(always)]
+    pub fn style(mut self, style: CodeBlockStyle) -> Self {
+        self.block = self.block.style(style);
+        self
+    }
+
+    /// Sets whether to align messages. See [CodeBlock::align_messages].
+    #[inline(always)]
+    pub fn align_messages(mut self, align_messages: bool) -> Self {
+        self.block = self.block.align_messages(align_messages);
+        self
+    }
+
+    /// Sets the first line number, which cannot be 0. See [CodeBlock::first_line_number].
+    #[inline(always)]
+    pub fn first_line_number(mut self, first_line_number: NonZeroUsize) -> Self {
+        self.block.first_line_number = first_line_number.get();
+        self
+    }
+
+    /// Sets the formatter of the line labels. See [CodeBlock::line_number_formatter].
+    #[inline(always)]
+    pub fn line_number_formatter(mut self, formatter: LineNumberFormatter) -> Self {
+        self.block = self.block.line_number_formatter(formatter);
+        self
+    }
+
+    /// Adds a note. See [CodeBlock::note].
+    #[inline(always)]
+    pub fn note(mut self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.note(text);
+        self
+    }
+
+    /// Adds a help message. See [CodeBlock::help].
+    #[inline(always)]
+    pub fn help(mut self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.help(text);
+        self
+    }
+
+    /// Adds a suggestion. See [CodeBlock::suggestion].
+    #[inline(always)]
+    pub fn suggestion(mut self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.block = self.block.suggestion(text);
+        self
+    }
+
+    /// Adds a gutter marker to a line. See [CodeBlock::annotate_line].
+    #[inline(always)]
+    pub fn annotate_line(mut self, line: usize, marker: char, color: Color) -> Self {
+        self.block = self.block.annotate_line(line, marker, color);
+        self
+    }
+
+    /// Adds a related block. See [CodeBlock::related].
+    #[inline(always)]
+    pub fn related(mut self, block: CodeBlock<'a>) -> Self {
+        self.block = self.block.related(block);
+        self
+    }
+}
+
+impl<'a> CodeBlockBuilder<'a, WithCode> {
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Highlights a cursor. See [CodeBlock::highlight_cursor].
+    #[inline(always)]
+    pub fn highlight_cursor(self, position: usize, color: Option<Color>) -> Self {
+        self.try_with(|block| block.try_highlight_cursor(position, color))
+    }
+
+    /// Highlights a cursor with a message. See [CodeBlock::highlight_cursor_message].
+    #[inline(always)]
+    pub fn highlight_cursor_message(
+        self,
+        position: usize,
+        color: Option<Color>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_highlight_cursor_message(position, color, message))
+    }
+
+    /// Highlights a cursor at a line and column with a message.
+    /// See [CodeBlock::highlight_line_column].
+    #[inline(always)]
+    pub fn highlight_line_column(
+        self,
+        line: usize,
+        column: usize,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_highlight_line_column(line, column, message))
+    }
+
+    /// Highlights a section. See [CodeBlock::highlight_section].
+    #[inline(always)]
+    pub fn highlight_section(self, span: impl Span, color: Option<Color>) -> Self {
+        self.try_with(|block| block.try_highlight_section(span, color))
+    }
+
+    /// Highlights a section with a message. See [CodeBlock::highlight_section_message].
+    #[inline(always)]
+    pub fn highlight_section_message(
+        self,
+        span: impl Span,
+        color: Option<Color>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_highlight_section_message(span, color, message))
+    }
+
+    /// Highlights a section with a message colored by its level.
+    /// See [CodeBlock::highlight_section_with_level].
+    #[inline(always)]
+    pub fn highlight_section_with_level(
+        self,
+        span: impl Span,
+        level: LogLevel,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_highlight_section_with_level(span, level, message))
+    }
+
+    /// Highlights a section with styled sub-spans. See [CodeBlock::highlight_section_styled].
+    #[inline(always)]
+    pub fn highlight_section_styled(
+        self,
+        span: impl Span,
+        styles: Vec<(Range<usize>, Style)>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_highlight_section_styled(span, styles, message))
+    }
+
+    /// Highlights a section suggesting a replacement. See [CodeBlock::suggest_replacement].
+    #[inline(always)]
+    pub fn suggest_replacement(
+        self,
+        span: impl Span,
+        replacement: impl Into<Cow<'a, str>>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.try_with(|block| block.try_suggest_replacement(span, replacement, message))
+    }
+
+    /// Sets the priority of a section. See [CodeBlock::section_priority].
+    #[inline(always)]
+    pub fn section_priority(self, span: impl Span, priority: u8) -> Self {
+        self.try_with(|block| block.try_section_priority(span, priority))
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the [CodeBlock], failing with the first invalid highlight.
+    #[inline(always)]
+    pub fn build(self) -> Result<CodeBlock<'a>, HighlightError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.block),
+        }
+    }
+
+    /// Applies the fallible `action` to the block unless a previous one failed, keeping
+    /// its error otherwise.
+    fn try_with(
+        mut self,
+        action: impl for<'b> FnOnce(
+            &'b mut CodeBlock<'a>,
+        ) -> Result<&'b mut CodeBlock<'a>, HighlightError>,
+    ) -> Self {
+        if self.error.is_none() {
+            self.error = action(&mut self.block).err();
+        }
+
+        self
+    }
+}
+
+impl<'a> TryFrom<CodeBlockBuilder<'a, WithCode>> for CodeBlock<'a> {
+    type Error = HighlightError;
+
+    fn try_from(builder: CodeBlockBuilder<'a, WithCode>) -> Result<Self, Self::Error> {
+        builder.build()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::{Printable, PrinterFormat};
+
+    #[test]
+    fn test_build() {
+        let code = "Line 1\nLine 2\nLine 3";
+        let block = CodeBlockBuilder::new()
+            .title("Title")
+            .previous_lines(1)
+            .first_line_number(NonZeroUsize::new(10).unwrap())
+            .with_code(code)
+            .highlight_section_message(7..11, None, "Message")
+            .build()
+            .unwrap();
+        let expected = CodeBlock::new(code)
+            .title("Title")
+            .previous_lines(1)
+            .first_line_number(10)
+            .highlight_section_message(7..11, None, "Message");
+
+        assert_eq!(
+            block.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            expected.print_to_string(LogLevel::error(), PrinterFormat::Plain)
+        );
+    }
+
+    #[test]
+    fn test_build_error() {
+        let result = CodeBlockBuilder::new()
+            .with_code("let a = b;")
+            .highlight_section(8..9, None)
+            .highlight_section(7..9, None)
+            .highlight_line_column(3, 1, "Ignored")
+            .build();
+
+        assert_eq!(
+            result.unwrap_err(),
+            HighlightError::Collision {
+                range: 7..9,
+                section: 8..9
+            }
+        );
+
+        let result = CodeBlockBuilder::new()
+            .with_code("let a = b;")
+            .section_priority(8..9, 1)
+            .build();
+
+        assert_eq!(
+            result.unwrap_err(),
+            HighlightError::MissingSection { range: 8..9 }
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+use std::ops::Range;
+
+/// The error returned by the fallible highlight methods of
+/// [CodeBlock](crate::blocks::CodeBlock), e.g.
+/// [CodeBlock::try_highlight_section](crate::blocks::CodeBlock::try_highlight_section).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum HighlightError {
+    /// The start index is greater than the end index.
+    InvertedRange { start: usize, end: usize },
+
+    /// The end index is greater than the length of the code.
+    OutOfBounds { end: usize, code_length: usize },
+
+    /// The index is not at a char boundary of the code.
+    NotCharBoundary { index: usize },
+
+    /// The range collides with the byte range of an already highlighted section.
+    Collision {
+        range: Range<usize>,
+        section: Range<usize>,
+    },
+
+    /// The 1-based line or column is outside the code.
+    PositionOutOfBounds { line: usize, column: usize },
+
+    /// There is no highlighted section inside the range.
+    MissingSection { range: Range<usize> },
+}
+
+impl Display for HighlightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HighlightError::InvertedRange { start, end } => write!(
+                f,
+                "The start index must be less or equal than the end index: {start} > {end}"
+            ),
+            HighlightError::OutOfBounds { end, code_length } => write!(
+                f,
+                "The end index must be less or equal than the code length: {end} > {code_length}"
+            ),
+            HighlightError::NotCharBoundary { index } => {
+                write!(f, "The index {index} is not at a char boundary of the code")
+            }
+            HighlightError::Collision { range, section } => write!(
+                f,
+                "Sections cannot collide with others: {range:?} collides with {section:?}"
+            ),
+            HighlightError::PositionOutOfBounds { line, column } => write!(
+                f,
+                "The position {line}:{column} is outside the code, lines start at 1 and columns can be at most the length of the line plus 1"
+            ),
+            HighlightError::MissingSection { range } => {
+                write!(f, "There is no section highlighted at the span {range:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HighlightError {}
@@ -0,0 +1,49 @@
+use crate::blocks::TextBlock;
+use crate::Span;
+use std::ops::Range;
+use yansi::Color;
+
+/// A labeled span of a [CodeBlock](crate::blocks::CodeBlock), see
+/// [CodeBlock::with_labels](crate::blocks::CodeBlock::with_labels).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Label<'a> {
+    /// The byte range inside the code.
+    pub range: Range<usize>,
+    pub message: TextBlock<'a>,
+    pub color: Option<Color>,
+
+    /// The priority of the label, used both to choose which labels are kept when they
+    /// overlap and to choose which are printed, see
+    /// [CodeBlock::section_priority](crate::blocks::CodeBlock::section_priority).
+    pub priority: u8,
+}
+
+impl<'a> Label<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [Label] without color and with the lowest priority.
+    pub fn new(span: impl Span, message: impl Into<TextBlock<'a>>) -> Self {
+        Self {
+            range: span.range(),
+            message: message.into(),
+            color: None,
+            priority: 0,
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the color.
+    #[inline(always)]
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the priority.
+    #[inline(always)]
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
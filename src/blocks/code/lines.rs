@@ -0,0 +1,200 @@
+use crate::blocks::code::{
+    group_sections_in_same_line, CodeBlock, CodeSection, GUTTER_COLUMNS, MIN_CODE_COLUMNS,
+};
+use crate::printer::{Printer, PrinterFormat, PrinterLine, RenderedLine};
+use crate::LogLevel;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use yansi::Color;
+
+/// An iterator over the rendered lines of a [CodeBlock], see [CodeBlock::render_lines].
+///
+/// The block is rendered in chunks, one per line with sections, so only the lines of the
+/// current chunk are kept in memory.
+pub struct CodeBlockLines<'s, 'a> {
+    /// The block to render or, if some sections are hidden by [CodeBlock::max_sections],
+    /// a copy with only the printed ones.
+    block: Cow<'s, CodeBlock<'a>>,
+    printer: Printer<'a>,
+    max_line_digits: usize,
+    state: RenderState,
+    /// The index of the first section not rendered yet.
+    next_section: usize,
+    last_line: usize,
+    palette_colors: Option<HashMap<(usize, usize), Color>>,
+    ready_lines: VecDeque<PrinterLine<'a>>,
+}
+
+/// The next part of the [CodeBlock] to render.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum RenderState {
+    Header,
+    Sections,
+    Footer,
+    Done,
+}
+
+impl<'s, 'a> CodeBlockLines<'s, 'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    pub(crate) fn new(block: &'s CodeBlock<'a>, level: LogLevel) -> Self {
+        Self::with_printer(
+            block,
+            Printer::new(level, PrinterFormat::Plain),
+            block.line_number_width(),
+        )
+    }
+
+    /// Renders `block` with the configuration of `printer`, which must be empty, and the
+    /// given width of the line numbers, e.g. to align them with other blocks.
+    pub(crate) fn with_printer(
+        block: &'s CodeBlock<'a>,
+        printer: Printer<'a>,
+        max_line_digits: usize,
+    ) -> Self {
+        let block = match block.limit_sections() {
+            Some(block) => Cow::Owned(block),
+            None => Cow::Borrowed(block),
+        };
+
+        Self {
+            printer,
+            max_line_digits,
+            state: RenderState::Header,
+            next_section: 0,
+            last_line: block.get_sections().first().map_or(0, |v| v.start.line),
+            palette_colors: block.palette_colors(),
+            ready_lines: VecDeque::new(),
+            block,
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Renders the next chunk of the block.
+    fn render_next_chunk(&mut self) {
+        match self.state {
+            RenderState::Header => {
+                if self.block.compact || self.block.inline {
+                    self.block.print_compact(&mut self.printer);
+                    self.state = RenderState::Done;
+                } else {
+                    self.block
+                        .print_header(&mut self.printer, self.max_line_digits);
+                    self.state = RenderState::Sections;
+                }
+            }
+            RenderState::Sections => {
+                let mut sections: &[CodeSection] = &self.block.get_sections()[self.next_section..];
+
+                if sections.is_empty() {
+                    self.state = RenderState::Footer;
+                    return;
+                }
+
+                let mut current_line_sections = Vec::new();
+                group_sections_in_same_line(&mut sections, &mut current_line_sections);
+                self.next_section += current_line_sections.len();
+                self.last_line = self.block.print_line_group(
+                    &mut self.printer,
+                    self.max_line_digits,
+                    self.last_line,
+                    &current_line_sections,
+                    self.palette_colors.as_ref(),
+                    self.block.style.glyphs(),
+                );
+            }
+            RenderState::Footer => {
+                self.block
+                    .print_footer(&mut self.printer, self.max_line_digits);
+                self.state = RenderState::Done;
+            }
+            RenderState::Done => return,
+        }
+
+        // The last line can still be extended by the next chunk, so it is kept
+        // in the printer until the whole block is rendered.
+        let last_line = if self.state == RenderState::Done {
+            None
+        } else {
+            self.printer.lines.pop()
+        };
+
+        // Clip the lines that do not fit in the width budget. The gutter is never clipped
+        // so narrow widths degrade to showing only the start of every line.
+        if let Some(max_width) = self.printer.max_width {
+            if !self.block.compact && !self.block.inline {
+                let min_width = self.max_line_digits + GUTTER_COLUMNS + MIN_CODE_COLUMNS;
+                self.printer.clip_lines_from(0, max_width.max(min_width));
+            }
+        }
+
+        self.ready_lines.extend(self.printer.lines.drain(..));
+        self.printer.lines.extend(last_line);
+    }
+}
+
+impl<'s, 'a> Iterator for CodeBlockLines<'s, 'a> {
+    type Item = RenderedLine<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sections) = self.ready_lines.pop_front() {
+                return Some(RenderedLine { sections });
+            }
+
+            if self.state == RenderState::Done {
+                return None;
+            }
+
+            self.render_next_chunk();
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::Printable;
+
+    #[test]
+    fn test_same_as_print() {
+        let code = "let a = b;\nlet c = d;\nlet e = f;\nlet g = h;\nlet i = j;";
+        let blocks = [
+            CodeBlock::new(code),
+            CodeBlock::new(code)
+                .title("Title")
+                .file_path("src/main.rs")
+                .previous_lines(1)
+                .next_lines(1)
+                .highlight_section_message(8..9, None, "Undefined")
+                .highlight_section_message(19..30, None, "Multiline\nmessage")
+                .highlight_cursor(45, None)
+                .note("A note")
+                .final_message("End"),
+            CodeBlock::new(code)
+                .compact(true)
+                .highlight_section(8..9, None),
+            CodeBlock::new(code)
+                .max_sections(2)
+                .highlight_section_message(8..9, None, "First")
+                .highlight_section_message(19..20, None, "Second")
+                .highlight_section_message(30..31, None, "Third")
+                .section_priority(30..31, 1),
+        ];
+
+        for block in blocks {
+            let expected = block.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+            let lines: Vec<String> = block
+                .render_lines(LogLevel::error())
+                .map(|v| v.to_string())
+                .collect();
+
+            assert_eq!(lines.join("\n"), expected);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use yansi::Color;
+
+/// A marker printed in the gutter of a [CodeBlock](crate::blocks::CodeBlock), between the
+/// line number and the code, to flag a whole line, e.g. `+`, `-` or `●`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineMarker {
+    /// The 1-based line of the code, regardless of
+    /// [CodeBlock::first_line_number](crate::blocks::CodeBlock::first_line_number).
+    pub line: usize,
+    pub marker: char,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::color"))]
+    pub color: Color,
+}
+
+impl LineMarker {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LineMarker].
+    #[inline(always)]
+    pub fn new(line: usize, marker: char, color: Color) -> Self {
+        Self {
+            line,
+            marker,
+            color,
+        }
+    }
+}
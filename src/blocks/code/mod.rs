@@ -1,65 +1,508 @@
+#[cfg(feature = "typed-builders")]
+pub use builder::*;
+pub use error::*;
+pub use label::*;
+pub use lines::*;
+pub use marker::*;
+pub use note::*;
+pub use resolved::*;
 use section::*;
+use style::CodeBlockGlyphs;
+pub use style::*;
+
+#[cfg(feature = "typed-builders")]
+mod builder;
+mod error;
+mod label;
+mod lines;
+mod marker;
+mod note;
+mod resolved;
 mod section;
+#[cfg(feature = "serde")]
+mod serialization;
+mod style;
 
 use crate::blocks::TextBlock;
-use crate::constants::{
-    BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, NEW_LINE_LEFT, TOP_RIGHT_CORNER, VERTICAL_BAR,
-};
+use crate::constants::{ELLIPSIS, NEW_LINE_LEFT, UP_POINTER};
 use crate::printer::{Printable, Printer, PrinterFormat};
 use crate::utils::cursor::Cursor;
+use crate::utils::line_index::LineIndex;
+use crate::utils::mask::MaskedText;
+use crate::utils::text::{
+    column_byte_offset, count_columns, count_multiline_columns, replace_lone_carriage_returns,
+    split_lines,
+};
 use crate::utils::whitespaces::{build_space_string, build_whitespace_string};
-use crate::LogLevel;
+use crate::{LogLevel, Span, Suggestion, TextPosition, Theme};
 use const_format::concatcp;
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Display;
 use std::ops::Range;
 use std::option::Option::Some;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
 use yansi::{Color, Style};
 
+/// The number of columns of the gutter after the line number, i.e. `" │    "`.
+const GUTTER_COLUMNS: usize = 6;
+
+/// The minimum number of columns of code shown in every line, including the ellipsis.
+const MIN_CODE_COLUMNS: usize = 2;
+
+/// The environment variable that sets the default context lines of [CodeBlock]s.
+const CONTEXT_LINES_ENV_VAR: &str = "DOCLOG_CONTEXT";
+
+/// The value of [DEFAULT_CONTEXT_LINES] when it is not set.
+const UNSET_CONTEXT_LINES: usize = usize::MAX;
+
+static DEFAULT_CONTEXT_LINES: AtomicUsize = AtomicUsize::new(UNSET_CONTEXT_LINES);
+static ENV_CONTEXT_LINES: OnceLock<usize> = OnceLock::new();
+
+/// Parses the value of the `DOCLOG_CONTEXT` environment variable.
+fn parse_context_lines(value: &str) -> Option<usize> {
+    value.trim().parse().ok()
+}
+
 /// A block that prints a section of a document.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(remote = "Self")
+)]
 pub struct CodeBlock<'a> {
     code: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(with = "serialization::sections"))]
     sections: Vec<CodeSection<'a>>,
     pub title: TextBlock<'a>,
     pub file_path: TextBlock<'a>,
     pub final_message: TextBlock<'a>,
     pub show_new_line_chars: bool,
+    /// The secondary color, only used instead of the one of the printer's
+    /// [Theme](crate::Theme) when [CodeBlock::fixed_secondary_color] is set.
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::color"))]
     pub secondary_color: Color,
+
+    /// Whether [CodeBlock::secondary_color] is used instead of the secondary color of the
+    /// printer's [Theme](crate::Theme). Set by the [CodeBlock::secondary_color] builder.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fixed_secondary_color: bool,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, with = "crate::utils::serialization::colors")
+    )]
+    pub section_palette: Vec<Color>,
     pub previous_lines: usize,
     pub next_lines: usize,
     pub middle_lines: usize,
+    pub max_section_lines: Option<usize>,
+    pub max_inline_messages: Option<usize>,
+    pub max_sections: Option<usize>,
+    pub show_column_ruler: bool,
+    pub compact: bool,
+    /// Whether to print the block in the one-line form of [CodeBlock::inline].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inline: bool,
+    pub style: CodeBlockStyle,
     pub align_messages: bool,
+    pub first_line_number: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub line_number_formatter: Option<LineNumberFormatter>,
+    pub notes: Vec<CodeNote<'a>>,
+    pub line_markers: Vec<LineMarker>,
+    suggestions: Vec<Suggestion<'a>>,
+    related: Vec<CodeBlock<'a>>,
+    /// The line offsets of `code`, built the first time a position is resolved.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    line_index: OnceLock<LineIndex>,
 }
 
+/// Formats the label shown in the gutter of a [CodeBlock] for a line, given its line number
+/// (see [CodeBlock::first_line_number]) and the byte offset of its start inside the code.
+pub type LineNumberFormatter = fn(line: usize, start_byte: usize) -> String;
+
 impl<'a> CodeBlock<'a> {
     // CONSTRUCTORS -----------------------------------------------------------
 
     /// Creates a new [CodeBlock] with the given code.
+    ///
+    /// Lines can end with `\n`, `\r\n` or a lone `\r`. Lone carriage returns are replaced
+    /// with `\n`, which keeps the byte offsets of the code, and the `\r` of `\r\n` is
+    /// never printed.
+    ///
+    /// The previous, next and middle lines are initialized to the default context lines,
+    /// see [CodeBlock::default_context_lines].
     pub fn new(code: impl Into<Cow<'a, str>>) -> Self {
+        let context_lines = Self::default_context_lines();
+
         Self {
-            code: code.into(),
+            code: replace_lone_carriage_returns(code.into()),
             sections: Vec::new(),
             title: TextBlock::new(),
             file_path: TextBlock::new(),
             final_message: TextBlock::new(),
             show_new_line_chars: false,
             secondary_color: Color::Magenta,
-            previous_lines: 0,
-            next_lines: 0,
-            middle_lines: 0,
+            fixed_secondary_color: false,
+            section_palette: Vec::new(),
+            previous_lines: context_lines,
+            next_lines: context_lines,
+            middle_lines: context_lines,
+            max_section_lines: None,
+            max_inline_messages: None,
+            max_sections: None,
+            show_column_ruler: false,
+            compact: false,
+            inline: false,
+            style: CodeBlockStyle::Doclog,
             align_messages: false,
+            first_line_number: 1,
+            line_number_formatter: None,
+            notes: Vec::new(),
+            line_markers: Vec::new(),
+            suggestions: Vec::new(),
+            related: Vec::new(),
+            line_index: OnceLock::new(),
+        }
+    }
+
+    /// Creates a new [CodeBlock] that highlights `span` of a single line of code in the
+    /// classic one-line form, i.e. a `--> file:line:column` line followed by the line of
+    /// code and a caret underline with the message, without gutter nor frame. Useful for
+    /// quick assertions and small tools that do not need the full layout.
+    ///
+    /// The location includes the [CodeBlock::file_path] if set, and the line number is
+    /// taken from [CodeBlock::first_line_number]. Like in [CodeBlock::compact] mode, other
+    /// sections highlighted later are printed in the same form.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::blocks::CodeBlock;
+    /// use doclog::{LogLevel, Printable, PrinterFormat};
+    ///
+    /// let block = CodeBlock::inline("let a = b;", 8..9, "Undefined variable")
+    ///     .file_path("src/main.rs")
+    ///     .first_line_number(3);
+    ///
+    /// assert_eq!(
+    ///     block.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+    ///     "--> src/main.rs:3:9\nlet a = b;\n        ^ Undefined variable"
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    /// This method panics if the indexes of `span` are out of bounds.
+    pub fn inline(
+        code_line: impl Into<Cow<'a, str>>,
+        span: impl Span,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        unwrap_highlight(Self::try_inline(code_line, span, message))
+    }
+
+    /// Creates a new [CodeBlock] like [CodeBlock::inline], failing if the indexes of `span`
+    /// are out of bounds.
+    pub fn try_inline(
+        code_line: impl Into<Cow<'a, str>>,
+        span: impl Span,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<Self, HighlightError> {
+        let mut block = Self::new(code_line);
+        block.try_highlight_section_message(span, None, message)?;
+        block.inline = true;
+        Ok(block)
+    }
+
+    /// Creates a new [CodeBlock] with the given code highlighting all `labels` at once,
+    /// which is faster than highlighting them one by one, e.g. for parsers that emit
+    /// many labels.
+    ///
+    /// Labels that collide with others are discarded instead of panicking. The ones with
+    /// the highest priority are kept first and, between equal priorities, the ones that
+    /// start first.
+    ///
+    /// # Panics
+    /// This method panics if the indexes of any label are out of bounds.
+    pub fn with_labels(
+        code: impl Into<Cow<'a, str>>,
+        labels: impl IntoIterator<Item = Label<'a>>,
+    ) -> Self {
+        unwrap_highlight(Self::try_with_labels(code, labels))
+    }
+
+    /// Creates a new [CodeBlock] like [CodeBlock::with_labels], failing if the indexes of
+    /// any label are out of bounds, e.g. for labels that come from untrusted input.
+    pub fn try_with_labels(
+        code: impl Into<Cow<'a, str>>,
+        labels: impl IntoIterator<Item = Label<'a>>,
+    ) -> Result<Self, HighlightError> {
+        let mut block = Self::new(code);
+        let mut labels: Vec<_> = labels.into_iter().collect();
+
+        for label in &labels {
+            block.check_range(&label.range)?;
+        }
+
+        labels.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.range.start.cmp(&b.range.start))
+                .then(a.range.end.cmp(&b.range.end))
+        });
+
+        // The accepted labels by their range. As they do not collide, only the closest
+        // ones can collide with a new label.
+        let mut accepted: BTreeMap<(usize, usize), Label<'a>> = BTreeMap::new();
+
+        for label in labels {
+            let key = (label.range.start, label.range.end);
+            let collides = accepted
+                .range(..key)
+                .next_back()
+                .into_iter()
+                .chain(accepted.range(key..).next())
+                .any(|(_, other)| ranges_collide(&label.range, &other.range));
+
+            if !collides {
+                accepted.insert(key, label);
+            }
+        }
+
+        block.sections.reserve(accepted.len());
+
+        for label in accepted.into_values() {
+            let (mut section, end_section) = block.build_sections(
+                label.range,
+                Some(label.message),
+                label.color,
+                None,
+                Vec::new(),
+            );
+
+            section.priority = label.priority;
+            block.sections.push(section);
+
+            if let Some(mut end_section) = end_section {
+                end_section.priority = label.priority;
+                block.sections.push(end_section);
+            }
+        }
+
+        Ok(block)
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns the number of context lines, i.e. previous, next and middle lines, that new
+    /// [CodeBlock]s get by default. It is the value set by [CodeBlock::set_default_context_lines]
+    /// or, if not set, the value of the `DOCLOG_CONTEXT` environment variable, or 0.
+    pub fn default_context_lines() -> usize {
+        match DEFAULT_CONTEXT_LINES.load(Ordering::Relaxed) {
+            UNSET_CONTEXT_LINES => *ENV_CONTEXT_LINES.get_or_init(|| {
+                std::env::var(CONTEXT_LINES_ENV_VAR)
+                    .ok()
+                    .and_then(|v| parse_context_lines(&v))
+                    .unwrap_or(0)
+            }),
+            value => value,
         }
     }
 
+    /// Sets the number of context lines that new [CodeBlock]s get by default, overriding the
+    /// `DOCLOG_CONTEXT` environment variable. `None` restores the environment variable value.
+    pub fn set_default_context_lines(context_lines: Option<usize>) {
+        let value = match context_lines {
+            Some(v) => v.min(UNSET_CONTEXT_LINES - 1),
+            None => UNSET_CONTEXT_LINES,
+        };
+
+        DEFAULT_CONTEXT_LINES.store(value, Ordering::Relaxed);
+    }
+
     // GETTERS ----------------------------------------------------------------
 
     /// Returns the maximum line to print.
     pub(crate) fn max_line(&self) -> usize {
-        self.sections
-            .last()
+        self.last_printed_section()
             .map(|v| v.end.line.saturating_add(self.next_lines))
-            .unwrap_or(1)
+            .map(|v| self.display_line(v))
+            .unwrap_or(self.first_line_number)
+    }
+
+    /// Returns the number of columns of the line labels shown in the gutter, including
+    /// the ones of the related blocks.
+    pub(crate) fn line_number_width(&self) -> usize {
+        self.related
+            .iter()
+            .map(|v| v.line_number_width())
+            .fold(self.own_line_number_width(), usize::max)
+    }
+
+    /// Returns the number of columns of the line labels of this block.
+    fn own_line_number_width(&self) -> usize {
+        let Some(formatter) = self.line_number_formatter else {
+            return format!("{}", self.max_line()).len();
+        };
+
+        // Labels are expected to grow with the line, so the last printed one is the widest.
+        let Some(last_section) = self.last_printed_section() else {
+            return 1;
+        };
+
+        let mut cursor = last_section.end.start_line_cursor(&self.code);
+        for _ in 0..self.next_lines {
+            match cursor.next_start_line_cursor(&self.code) {
+                Some(v) => cursor = v,
+                None => break,
+            }
+        }
+
+        count_columns(&formatter(
+            self.display_line(cursor.line),
+            cursor.byte_offset,
+        ))
+    }
+
+    /// Returns the label shown in the gutter for a line of the code starting at `start_byte`.
+    fn line_label(&self, line: usize, start_byte: usize) -> String {
+        match self.line_number_formatter {
+            Some(formatter) => formatter(self.display_line(line), start_byte),
+            None => self.display_line(line).to_string(),
+        }
+    }
+
+    /// Returns the last section that is printed, see [CodeBlock::max_sections].
+    fn last_printed_section(&self) -> Option<&CodeSection<'a>> {
+        match self.printed_sections() {
+            Some((indexes, _)) => indexes.last().map(|v| &self.sections[*v]),
+            None => self.sections.last(),
+        }
+    }
+
+    /// Returns the indexes of the sections that are printed when there are more than
+    /// [CodeBlock::max_sections], along with the number of hidden spans, or [None] if no
+    /// section must be hidden. Both sections of a multiline span are kept or hidden together.
+    fn printed_sections(&self) -> Option<(Vec<usize>, usize)> {
+        let max_sections = self.max_sections?;
+
+        // The first and last section of every span, along with its priority.
+        let mut spans: Vec<(usize, Option<usize>, u8)> = Vec::new();
+        let mut open_spans = Vec::new();
+
+        for (index, section) in self.sections.iter().enumerate() {
+            if section.is_multiline_start {
+                open_spans.push(spans.len());
+                spans.push((index, None, section.priority));
+            } else if let (true, Some(span_index)) = (section.is_multiline_end, open_spans.pop()) {
+                let span = &mut spans[span_index];
+                span.1 = Some(index);
+                span.2 = span.2.max(section.priority);
+            } else {
+                spans.push((index, None, section.priority));
+            }
+        }
+
+        if spans.len() <= max_sections {
+            return None;
+        }
+
+        // Stable so the first spans are kept in case of a tie.
+        let mut span_indexes: Vec<usize> = (0..spans.len()).collect();
+        span_indexes.sort_by_key(|index| std::cmp::Reverse(spans[*index].2));
+
+        let mut indexes: Vec<usize> = span_indexes[..max_sections]
+            .iter()
+            .flat_map(|index| std::iter::once(spans[*index].0).chain(spans[*index].1))
+            .collect();
+        indexes.sort_unstable();
+
+        Some((indexes, spans.len() - max_sections))
+    }
+
+    /// Returns the colors of the sections in the [CodeBlock::section_palette], by their byte
+    /// range, or [None] if the palette is empty. Both sections of a multiline span get the
+    /// same color.
+    fn palette_colors(&self) -> Option<HashMap<(usize, usize), Color>> {
+        if self.section_palette.is_empty() {
+            return None;
+        }
+
+        let mut colors = HashMap::new();
+        let mut label_colors: HashMap<String, Color> = HashMap::new();
+        let mut open_spans = Vec::new();
+        let mut next_color = 0;
+        let key = |section: &CodeSection| (section.start.byte_offset, section.end.byte_offset);
+
+        for section in &self.sections {
+            if section.is_multiline_start {
+                open_spans.push(section);
+                continue;
+            }
+
+            let color = if section.message.is_empty() {
+                None
+            } else {
+                label_colors.get(&section.message.to_string()).copied()
+            };
+            let color = color.unwrap_or_else(|| {
+                let color = self.section_palette[next_color % self.section_palette.len()];
+                next_color += 1;
+
+                if !section.message.is_empty() {
+                    label_colors.insert(section.message.to_string(), color);
+                }
+
+                color
+            });
+
+            if section.is_multiline_end {
+                if let Some(start) = open_spans.pop() {
+                    colors.insert(key(start), color);
+                }
+            }
+
+            colors.insert(key(section), color);
+        }
+
+        Some(colors)
+    }
+
+    /// Returns a copy of this block without the sections that exceed
+    /// [CodeBlock::max_sections] and with a note that summarizes them, or [None] if no
+    /// section must be hidden.
+    fn limit_sections(&self) -> Option<CodeBlock<'a>> {
+        let (indexes, hidden_spans) = self.printed_sections()?;
+        let mut block = self.clone();
+
+        block.max_sections = None;
+        block.sections = indexes
+            .into_iter()
+            .map(|index| self.sections[index].clone())
+            .collect();
+        block.notes.push(CodeNote::new(
+            CodeNoteKind::Note,
+            format!(
+                "{ELLIPSIS} and {} more span{} omitted",
+                hidden_spans,
+                if hidden_spans == 1 { "" } else { "s" }
+            ),
+        ));
+
+        Some(block)
+    }
+
+    /// Returns the line offsets of the code, building them the first time.
+    fn line_index(&self) -> &LineIndex {
+        self.line_index.get_or_init(|| LineIndex::new(&self.code))
+    }
+
+    /// Maps a line of the code to the line number shown in the gutter.
+    #[inline(always)]
+    pub(crate) fn display_line(&self, line: usize) -> usize {
+        line.saturating_add(self.first_line_number)
+            .saturating_sub(1)
     }
 
     /// Returns the actual code the block will use.
@@ -74,6 +517,113 @@ impl<'a> CodeBlock<'a> {
         &self.sections
     }
 
+    /// Returns the sections mutably, e.g. to visit their messages.
+    #[inline(always)]
+    pub(crate) fn sections_mut(&mut self) -> &mut [CodeSection<'a>] {
+        &mut self.sections
+    }
+
+    /// Returns the suggested replacements of this block and its related ones, see
+    /// [CodeBlock::suggest_replacement]. Their positions are lines of the file, i.e. shifted
+    /// by [CodeBlock::first_line_number], and their URI is the [CodeBlock::file_path], while
+    /// their byte ranges are still relative to the code of the block.
+    pub fn get_suggestions(&self) -> Vec<Suggestion<'a>> {
+        let line_offset = self.first_line_number.saturating_sub(1);
+        let uri = (!self.file_path.is_empty()).then(|| self.file_path.to_string());
+        let mut result = Vec::with_capacity(self.suggestions.len());
+
+        for suggestion in &self.suggestions {
+            let mut suggestion = suggestion.clone();
+            suggestion.start.line += line_offset;
+            suggestion.end.line += line_offset;
+
+            if let Some(uri) = &uri {
+                suggestion.uri = Cow::Owned(uri.clone());
+            }
+
+            result.push(suggestion);
+        }
+
+        for related in &self.related {
+            result.extend(related.get_suggestions());
+        }
+
+        result
+    }
+
+    /// Returns the related blocks, see [CodeBlock::related].
+    #[inline(always)]
+    pub fn get_related(&self) -> &[CodeBlock<'a>] {
+        &self.related
+    }
+
+    /// Returns the related blocks mutably, e.g. to visit them.
+    #[inline(always)]
+    pub(crate) fn related_mut(&mut self) -> &mut [CodeBlock<'a>] {
+        &mut self.related
+    }
+
+    /// Returns the highlighted sections with the color they are printed with for `level`
+    /// in the global theme, i.e. after applying their own color or level, the
+    /// [CodeBlock::section_palette] or the alternation of colors, so external tools like
+    /// editors can mirror the rendering. The sections hidden by [CodeBlock::max_sections]
+    /// are excluded.
+    ///
+    /// Use [CodeBlock::resolved_sections_with_theme] for blocks printed with another theme.
+    #[inline(always)]
+    pub fn resolved_sections(&self, level: LogLevel) -> Vec<ResolvedSection> {
+        self.resolved_sections_with_theme(level, &Theme::global())
+    }
+
+    /// Same as [CodeBlock::resolved_sections] but resolving the colors with `theme`.
+    pub fn resolved_sections_with_theme(
+        &self,
+        level: LogLevel,
+        theme: &Theme,
+    ) -> Vec<ResolvedSection> {
+        if let Some(block) = self.limit_sections() {
+            return block.resolved_sections_with_theme(level, theme);
+        }
+
+        let level_color = theme.level_color(level);
+        let secondary_color = self.secondary_color_in(theme);
+        let palette_colors = self.palette_colors();
+        let mut result = Vec::with_capacity(self.sections.len());
+        let mut sections: &[CodeSection] = &self.sections;
+        let mut current_line_sections = Vec::new();
+
+        while !sections.is_empty() {
+            group_sections_in_same_line(&mut sections, &mut current_line_sections);
+
+            let mut next_color = secondary_color;
+
+            for section in &current_line_sections {
+                // Compact blocks do not alternate the colors.
+                let default_color = if self.compact || next_color == secondary_color {
+                    level_color
+                } else {
+                    secondary_color
+                };
+                next_color =
+                    resolve_section_color(section, theme, palette_colors.as_ref(), default_color);
+
+                result.push(ResolvedSection {
+                    bytes: section.start.byte_offset..section.end.byte_offset,
+                    start_line: section.start.line,
+                    start_column: section.start.column,
+                    end_line: section.end.line,
+                    end_column: section.end.column,
+                    color: next_color,
+                    message: (!section.message.is_empty()).then(|| section.message.to_string()),
+                    is_multiline_start: section.is_multiline_start,
+                    is_multiline_end: section.is_multiline_end,
+                });
+            }
+        }
+
+        result
+    }
+
     // BUILDERS ---------------------------------------------------------------
 
     /// Sets the title.
@@ -104,10 +654,22 @@ impl<'a> CodeBlock<'a> {
         self
     }
 
-    /// Sets the secondary color to highlight blocks.
+    /// Sets the secondary color to highlight blocks. Defaults to the secondary color of
+    /// the printer's [Theme](crate::Theme).
     #[inline(always)]
     pub fn secondary_color(mut self, secondary_color: Color) -> Self {
         self.secondary_color = secondary_color;
+        self.fixed_secondary_color = true;
+        self
+    }
+
+    /// Sets the colors of the sections, replacing the alternation of the level and secondary
+    /// colors. The spans are colored cycling through the palette in order, and the spans
+    /// with the same message get the same color even in different lines. Sections with
+    /// their own color or level keep it. An empty palette restores the alternation.
+    #[inline(always)]
+    pub fn section_palette(mut self, section_palette: Vec<Color>) -> Self {
+        self.section_palette = section_palette;
         self
     }
 
@@ -132,6 +694,62 @@ impl<'a> CodeBlock<'a> {
         self
     }
 
+    /// Sets the maximum number of lines to show at the start and at the end of the inside
+    /// of multiline sections. The rest are folded into a single `··· (K lines hidden)` row,
+    /// unless it would hide only one line.
+    /// When set, it takes precedence over [CodeBlock::middle_lines] inside multiline sections.
+    #[inline(always)]
+    pub fn max_section_lines(mut self, max_section_lines: usize) -> Self {
+        self.max_section_lines = Some(max_section_lines);
+        self
+    }
+
+    /// Sets the maximum number of messages to print for the sections of a single line.
+    /// The messages with the lowest priority, see [CodeBlock::section_priority], are hidden
+    /// and summarized in a trailing `··· and N more labels` row.
+    #[inline(always)]
+    pub fn max_inline_messages(mut self, max_inline_messages: usize) -> Self {
+        self.max_inline_messages = Some(max_inline_messages);
+        self
+    }
+
+    /// Sets the maximum number of highlighted spans to print, counting both parts of a
+    /// multiline section as one. The spans with the highest priority, see
+    /// [CodeBlock::section_priority], are printed, preferring the first ones in case of a
+    /// tie, and the rest are summarized in a trailing `= note: … and N more spans omitted`.
+    #[inline(always)]
+    pub fn max_sections(mut self, max_sections: usize) -> Self {
+        self.max_sections = Some(max_sections);
+        self
+    }
+
+    /// Sets whether to print a column ruler above the first line of code, to help
+    /// locating the columns of the highlighted sections.
+    #[inline(always)]
+    pub fn show_column_ruler(mut self, show_column_ruler: bool) -> Self {
+        self.show_column_ruler = show_column_ruler;
+        self
+    }
+
+    /// Sets whether to print the block in compact mode, i.e. every highlighted section
+    /// as a `file:line:column: message` line followed by its line of code and a caret
+    /// underneath, without gutter nor frame. Useful when many diagnostics are printed at once.
+    ///
+    /// The title is used as the message of the sections without one, while context
+    /// lines, the final message and notes are not printed.
+    #[inline(always)]
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets the visual style of the frame, underlines and message connectors.
+    #[inline(always)]
+    pub fn style(mut self, style: CodeBlockStyle) -> Self {
+        self.style = style;
+        self
+    }
+
     /// Sets whether to align messages or not.
     #[inline(always)]
     pub fn align_messages(mut self, align_messages: bool) -> Self {
@@ -139,6 +757,75 @@ impl<'a> CodeBlock<'a> {
         self
     }
 
+    /// Sets the line number of the first line of the code. Useful when the code is
+    /// only a snippet of a larger file, so the gutter shows the lines of the original file.
+    ///
+    /// # Panics
+    /// This method panics if `first_line_number` is 0.
+    #[inline(always)]
+    pub fn first_line_number(mut self, first_line_number: usize) -> Self {
+        assert_ne!(
+            first_line_number, 0,
+            "The first line number must be at least 1"
+        );
+        self.first_line_number = first_line_number;
+        self
+    }
+
+    /// Sets the formatter of the line labels shown in the gutter, e.g. to show hexadecimal
+    /// addresses or byte offsets instead of decimal line numbers.
+    #[inline(always)]
+    pub fn line_number_formatter(mut self, formatter: LineNumberFormatter) -> Self {
+        self.line_number_formatter = Some(formatter);
+        self
+    }
+
+    /// Adds a note printed after the code as `= note: ...`.
+    #[inline(always)]
+    pub fn note(self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.add_note(CodeNote::new(CodeNoteKind::Note, text))
+    }
+
+    /// Adds a help message printed after the code as `= help: ...`.
+    #[inline(always)]
+    pub fn help(self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.add_note(CodeNote::new(CodeNoteKind::Help, text))
+    }
+
+    /// Adds a suggestion printed after the code as `= suggestion: ...`.
+    #[inline(always)]
+    pub fn suggestion(self, text: impl Into<TextBlock<'a>>) -> Self {
+        self.add_note(CodeNote::new(CodeNoteKind::Suggestion, text))
+    }
+
+    /// Adds a note printed after the code.
+    #[inline(always)]
+    pub fn add_note(mut self, note: CodeNote<'a>) -> Self {
+        self.notes.push(note);
+        self
+    }
+
+    /// Adds a marker, e.g. `+`, `-` or `●`, in the gutter of the 1-based `line` of the code,
+    /// between its line number and its content, to flag the whole line without highlighting
+    /// it. The marker is only visible if the line is printed, e.g. as a context line of a
+    /// section. Annotating a line again replaces its marker.
+    #[inline]
+    pub fn annotate_line(mut self, line: usize, marker: char, color: Color) -> Self {
+        self.line_markers.retain(|v| v.line != line);
+        self.line_markers.push(LineMarker::new(line, marker, color));
+        self
+    }
+
+    /// Adds a related block, e.g. the snippet of another file involved in the same
+    /// diagnostic. It is printed inside the frame of this block, after its lines and
+    /// sharing its gutter, starting with a `├─` connector followed by its file path.
+    /// Only the file path and the lines of the related block are printed.
+    #[inline(always)]
+    pub fn related(mut self, block: CodeBlock<'a>) -> Self {
+        self.related.push(block);
+        self
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Highlights a cursor adding a colored dot at its position.
@@ -146,8 +833,21 @@ impl<'a> CodeBlock<'a> {
     /// # Panics
     /// This method panics if the section collides with another section or if the indexes are out of bounds.
     #[inline(always)]
-    pub fn highlight_cursor(self, position: usize, color: Option<Color>) -> Self {
-        self.highlight_section_inner(position..position, None, color)
+    pub fn highlight_cursor(mut self, position: usize, color: Option<Color>) -> Self {
+        unwrap_highlight(self.try_highlight_cursor(position, color));
+        self
+    }
+
+    /// Highlights a cursor adding a colored dot at its position, failing if the section
+    /// collides with another section or if the indexes are out of bounds. The block is
+    /// left unchanged on failure.
+    #[inline(always)]
+    pub fn try_highlight_cursor(
+        &mut self,
+        position: usize,
+        color: Option<Color>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(position..position, None, color, None, Vec::new())
     }
 
     /// Highlights a cursor adding a colored dot at its position and including a message.
@@ -156,163 +856,548 @@ impl<'a> CodeBlock<'a> {
     /// This method panics if the section collides with another section or if the indexes are out of bounds.
     #[inline(always)]
     pub fn highlight_cursor_message(
-        self,
+        mut self,
+        position: usize,
+        color: Option<Color>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        unwrap_highlight(self.try_highlight_cursor_message(position, color, message));
+        self
+    }
+
+    /// Highlights a cursor adding a colored dot at its position and including a message,
+    /// failing if the section collides with another section or if the indexes are out of
+    /// bounds.
+    #[inline(always)]
+    pub fn try_highlight_cursor_message(
+        &mut self,
         position: usize,
         color: Option<Color>,
         message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(
+            position..position,
+            Some(message.into()),
+            color,
+            None,
+            Vec::new(),
+        )
+    }
+
+    /// Highlights a cursor at a 1-based `line` and `column` of the code, including a message.
+    /// Columns are measured in chars or, when the `unicode-segmentation` feature is enabled,
+    /// in grapheme clusters, and the column right after the end of the line is allowed.
+    ///
+    /// # Panics
+    /// This method panics if the position is out of bounds or if the section collides
+    /// with another section.
+    pub fn highlight_line_column(
+        mut self,
+        line: usize,
+        column: usize,
+        message: impl Into<TextBlock<'a>>,
     ) -> Self {
-        self.highlight_section_inner(position..position, Some(message.into()), color)
+        unwrap_highlight(self.try_highlight_line_column(line, column, message));
+        self
+    }
+
+    /// Highlights a cursor at a 1-based `line` and `column` of the code, including a message,
+    /// failing if the position is out of bounds or if the section collides with another
+    /// section. See [CodeBlock::highlight_line_column].
+    pub fn try_highlight_line_column(
+        &mut self,
+        line: usize,
+        column: usize,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        let position = self.line_column_byte_offset(line, column)?;
+        self.try_highlight_section_inner(
+            position..position,
+            Some(message.into()),
+            None,
+            None,
+            Vec::new(),
+        )
     }
 
     /// Highlights a code section coloring the text.
     ///
     /// # Panics
     /// This method panics if the section collides with another section or if the indexes are out of bounds.
-    pub fn highlight_section(self, range: Range<usize>, color: Option<Color>) -> Self {
-        assert!(
-            range.start <= range.end,
-            "The start index must be less or equal than the end index"
-        );
+    #[inline(always)]
+    pub fn highlight_section(mut self, span: impl Span, color: Option<Color>) -> Self {
+        unwrap_highlight(self.try_highlight_section(span, color));
+        self
+    }
 
-        self.highlight_section_inner(range, None, color)
+    /// Highlights a code section coloring the text, failing if the section collides with
+    /// another section or if the indexes are out of bounds, e.g. for spans that come from
+    /// untrusted input. The block is left unchanged on failure.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::blocks::{CodeBlock, HighlightError};
+    ///
+    /// let mut block = CodeBlock::new("let a = b;");
+    ///
+    /// assert_eq!(
+    ///     block.try_highlight_section(8..20, None).unwrap_err(),
+    ///     HighlightError::OutOfBounds {
+    ///         end: 20,
+    ///         code_length: 10
+    ///     }
+    /// );
+    /// assert!(block.try_highlight_section(8..9, None).is_ok());
+    /// ```
+    #[inline(always)]
+    pub fn try_highlight_section(
+        &mut self,
+        span: impl Span,
+        color: Option<Color>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(span.range(), None, color, None, Vec::new())
     }
 
     /// Highlights a code section coloring the text and including a message.
     ///
     /// # Panics
     /// This method panics if the section collides with another section or if the indexes are out of bounds.
+    #[inline(always)]
     pub fn highlight_section_message(
-        self,
-        range: Range<usize>,
+        mut self,
+        span: impl Span,
         color: Option<Color>,
         message: impl Into<TextBlock<'a>>,
     ) -> Self {
-        assert!(
-            range.start <= range.end,
-            "The start index must be less or equal than the end index"
-        );
+        unwrap_highlight(self.try_highlight_section_message(span, color, message));
+        self
+    }
 
-        self.highlight_section_inner(range, Some(message.into()), color)
+    /// Highlights a code section coloring the text and including a message, failing if the
+    /// section collides with another section or if the indexes are out of bounds.
+    #[inline(always)]
+    pub fn try_highlight_section_message(
+        &mut self,
+        span: impl Span,
+        color: Option<Color>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(
+            span.range(),
+            Some(message.into()),
+            color,
+            None,
+            Vec::new(),
+        )
     }
 
-    /// Highlights a section.
+    /// Highlights a code section including a message, both colored with the color of `level`
+    /// instead of alternating the primary and secondary colors. This allows, for example,
+    /// warning-level labels inside an error-level block.
     ///
     /// # Panics
     /// This method panics if the section collides with another section or if the indexes are out of bounds.
-    fn highlight_section_inner(
+    #[inline(always)]
+    pub fn highlight_section_with_level(
         mut self,
-        range: Range<usize>,
-        message: Option<TextBlock<'a>>,
-        color: Option<Color>,
+        span: impl Span,
+        level: LogLevel,
+        message: impl Into<TextBlock<'a>>,
     ) -> Self {
-        assert!(
-            range.end <= self.code.len(),
-            "The end index must be less or equal than the code length"
-        );
-
-        let index = self
-            .sections
-            .binary_search_by(|section| {
-                // Special case to detect the addition of two equal cursors.
-                assert!(
-                    range.start != section.start.byte_offset
-                        || range.end != section.end.byte_offset,
-                    "Sections cannot collide with others"
-                );
+        unwrap_highlight(self.try_highlight_section_with_level(span, level, message));
+        self
+    }
 
-                if range.end <= section.start.byte_offset {
-                    std::cmp::Ordering::Greater
-                } else if section.end.byte_offset <= range.start {
-                    std::cmp::Ordering::Less
-                } else {
-                    std::cmp::Ordering::Equal
-                }
-            })
-            .expect_err("Sections cannot collide with others");
+    /// Highlights a code section including a message, both colored with the color of
+    /// `level`, failing if the section collides with another section or if the indexes are
+    /// out of bounds. See [CodeBlock::highlight_section_with_level].
+    #[inline(always)]
+    pub fn try_highlight_section_with_level(
+        &mut self,
+        span: impl Span,
+        level: LogLevel,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(
+            span.range(),
+            Some(message.into()),
+            None,
+            Some(level),
+            Vec::new(),
+        )
+    }
 
-        let start = if let Some(section) = self.sections.get(index) {
-            Cursor::from_byte_offset_and_cursor(&self.code, range.start, &section.start)
-        } else {
-            Cursor::from_byte_offset(&self.code, range.start)
+    /// Highlights a code section including a message, printing the sub-spans in `styles`
+    /// with their own style instead of the color of the section, e.g. to bold a function
+    /// name inside the highlighted expression. The underline is still drawn over the whole
+    /// section.
+    ///
+    /// The sub-spans are byte ranges of the code like `span`, and they are clipped to it.
+    ///
+    /// # Panics
+    /// This method panics if the section collides with another section or if the indexes are out of bounds.
+    pub fn highlight_section_styled(
+        mut self,
+        span: impl Span,
+        styles: Vec<(Range<usize>, Style)>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        unwrap_highlight(self.try_highlight_section_styled(span, styles, message));
+        self
+    }
+
+    /// Highlights a code section including a message and styled sub-spans, failing if the
+    /// section collides with another section or if the indexes are out of bounds. See
+    /// [CodeBlock::highlight_section_styled].
+    pub fn try_highlight_section_styled(
+        &mut self,
+        span: impl Span,
+        styles: Vec<(Range<usize>, Style)>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.try_highlight_section_inner(span.range(), Some(message.into()), None, None, styles)
+    }
+
+    /// Highlights a code section including a message and suggests replacing it with
+    /// `replacement`. The code with the replacement applied is printed after the highlighted
+    /// code, and the suggestion is available through [CodeBlock::get_suggestions] so tools
+    /// can apply it automatically.
+    ///
+    /// # Panics
+    /// This method panics if the section collides with another section or if the indexes are out of bounds.
+    pub fn suggest_replacement(
+        mut self,
+        span: impl Span,
+        replacement: impl Into<Cow<'a, str>>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        unwrap_highlight(self.try_suggest_replacement(span, replacement, message));
+        self
+    }
+
+    /// Highlights a code section including a message and suggests replacing it with
+    /// `replacement`, failing if the section collides with another section or if the
+    /// indexes are out of bounds. See [CodeBlock::suggest_replacement].
+    pub fn try_suggest_replacement(
+        &mut self,
+        span: impl Span,
+        replacement: impl Into<Cow<'a, str>>,
+        message: impl Into<TextBlock<'a>>,
+    ) -> Result<&mut Self, HighlightError> {
+        let range = span.range();
+        let message = message.into();
+        let title = message.to_string();
+
+        self.try_highlight_section_inner(range.clone(), Some(message), None, None, Vec::new())?;
+
+        let suggestion = Suggestion::new(&self.code, range, replacement).title(title);
+        self.suggestions.push(suggestion);
+        Ok(self)
+    }
+
+    /// Sets the priority of the section highlighted at `span`, 0 by default. When a line
+    /// has more messages than [CodeBlock::max_inline_messages], the ones with the highest
+    /// priority are printed, preferring the first ones in case of a tie. The same applies to
+    /// the spans of the block when there are more than [CodeBlock::max_sections].
+    ///
+    /// # Panics
+    /// This method panics if there is no section highlighted at `span`.
+    pub fn section_priority(mut self, span: impl Span, priority: u8) -> Self {
+        unwrap_highlight(self.try_section_priority(span, priority));
+        self
+    }
+
+    /// Sets the priority of the section highlighted at `span`, failing if there is no
+    /// section highlighted at it. See [CodeBlock::section_priority].
+    pub fn try_section_priority(
+        &mut self,
+        span: impl Span,
+        priority: u8,
+    ) -> Result<&mut Self, HighlightError> {
+        let range = span.range();
+        let mut found = false;
+
+        for section in &mut self.sections {
+            if section.start.byte_offset >= range.start
+                && section.end.byte_offset <= range.end
+                && (range.is_empty() || !section.is_cursor())
+            {
+                section.priority = priority;
+                found = true;
+            }
+        }
+
+        if !found {
+            return Err(HighlightError::MissingSection { range });
+        }
+
+        Ok(self)
+    }
+
+    /// Highlights a section, failing if it collides with another section or if the indexes
+    /// are out of bounds.
+    fn try_highlight_section_inner(
+        &mut self,
+        range: Range<usize>,
+        message: Option<TextBlock<'a>>,
+        color: Option<Color>,
+        level: Option<LogLevel>,
+        styles: Vec<(Range<usize>, Style)>,
+    ) -> Result<&mut Self, HighlightError> {
+        self.check_range(&range)?;
+
+        // Clip the styles to the section.
+        let mut styles: Vec<_> = styles
+            .into_iter()
+            .map(|(v, style)| (v.start.max(range.start)..v.end.min(range.end), style))
+            .filter(|(v, _)| !v.is_empty())
+            .collect();
+        styles.sort_by_key(|(v, _)| v.start);
+
+        for (range, _) in &styles {
+            self.check_range(range)?;
+        }
+
+        let index = match self.sections.binary_search_by(|section| {
+            // Special case to detect the addition of two equal cursors.
+            if range.start == section.start.byte_offset && range.end == section.end.byte_offset {
+                std::cmp::Ordering::Equal
+            } else if range.end <= section.start.byte_offset {
+                std::cmp::Ordering::Greater
+            } else if section.end.byte_offset <= range.start {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        }) {
+            Ok(index) => {
+                let section = &self.sections[index];
+
+                return Err(HighlightError::Collision {
+                    range,
+                    section: section.start.byte_offset..section.end.byte_offset,
+                });
+            }
+            Err(index) => index,
+        };
+
+        let (section, end_section) = self.build_sections(range, message, color, level, styles);
+
+        match end_section {
+            Some(end_section) => {
+                self.sections.splice(index..index, [section, end_section]);
+            }
+            None => self.sections.insert(index, section),
+        }
+
+        Ok(self)
+    }
+
+    /// Checks that `range` is a valid byte range of the code.
+    fn check_range(&self, range: &Range<usize>) -> Result<(), HighlightError> {
+        if range.start > range.end {
+            return Err(HighlightError::InvertedRange {
+                start: range.start,
+                end: range.end,
+            });
+        }
+
+        if range.end > self.code.len() {
+            return Err(HighlightError::OutOfBounds {
+                end: range.end,
+                code_length: self.code.len(),
+            });
+        }
+
+        for index in [range.start, range.end] {
+            if !self.code.is_char_boundary(index) {
+                return Err(HighlightError::NotCharBoundary { index });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the sections that highlight `range`: a single one or, for multiline spans
+    /// that do not end at a line break, the start and end parts.
+    fn build_sections(
+        &self,
+        range: Range<usize>,
+        message: Option<TextBlock<'a>>,
+        color: Option<Color>,
+        level: Option<LogLevel>,
+        styles: Vec<(Range<usize>, Style)>,
+    ) -> (CodeSection<'a>, Option<CodeSection<'a>>) {
+        let line_index = self.line_index();
+        let start = line_index.cursor(&self.code, range.start);
+        let section = |start, end, message: Option<TextBlock<'a>>, styles| CodeSection {
+            start,
+            end,
+            message: message.unwrap_or_default(),
+            color,
+            level,
+            styles,
+            priority: 0,
+            is_multiline_start: false,
+            is_multiline_end: false,
         };
 
         if range.is_empty() {
             // Cursor
-            self.sections.insert(
-                index,
-                CodeSection {
-                    start,
-                    end: start,
-                    message: message.unwrap_or_default(),
-                    color,
-                    is_multiline_start: false,
-                    is_multiline_end: false,
-                },
+            return (section(start, start, message, styles), None);
+        }
+
+        let end = line_index.cursor(&self.code, range.end);
+
+        if start.line == end.line {
+            return (section(start, end, message, styles), None);
+        }
+
+        let start_line_end = start
+            .next_start_line_cursor(&self.code)
+            .unwrap_or_else(|| start.end_line_cursor(&self.code));
+
+        // When the end cursor is at the start of a line, it means the section finishes at
+        // a new line character, therefore we need to add only one section.
+        if end.column == 1 {
+            return (section(start, start_line_end, message, styles), None);
+        }
+
+        (
+            CodeSection {
+                is_multiline_start: true,
+                ..section(start, start_line_end, None, styles.clone())
+            },
+            Some(CodeSection {
+                is_multiline_end: true,
+                ..section(end.start_line_cursor(&self.code), end, message, styles)
+            }),
+        )
+    }
+
+    /// Replaces the ranges returned by `secret_ranges` for each line of the code with
+    /// `mask`, e.g. to redact secrets, moving the sections, their styles and the
+    /// suggestions to the masked code. Sections that start or end inside a masked range
+    /// are extended to cover the whole mask. The texts of the suggestions are masked too.
+    pub(crate) fn mask_code(
+        &mut self,
+        secret_ranges: impl Fn(&str) -> Vec<Range<usize>>,
+        mask: &str,
+    ) {
+        let mask_cow = |text: &mut Cow<'a, str>| {
+            let masked = MaskedText::new(text, secret_ranges(text), mask);
+
+            if masked.is_masked() {
+                *text = Cow::Owned(masked.text.into_owned());
+            }
+        };
+
+        for suggestion in &mut self.suggestions {
+            mask_cow(&mut suggestion.title);
+            mask_cow(&mut suggestion.replacement);
+        }
+
+        // Lines are masked independently so the masks never include line breaks and the
+        // sections keep their lines.
+        let mut ranges = Vec::new();
+        let mut line_start = 0;
+
+        for line in self.code.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            ranges.extend(
+                secret_ranges(content)
+                    .into_iter()
+                    .map(|v| v.start + line_start..v.end + line_start),
             );
-        } else {
-            let end = Cursor::from_byte_offset_and_cursor(&self.code, range.end, &start);
-            let is_multiline = start.line != end.line;
-
-            if is_multiline {
-                // When the end cursor is at the start of a line, it means the section finishes at
-                // a new line character, therefore we need to add only one section.
-                if end.column == 1 {
-                    self.sections.insert(
-                        index,
-                        CodeSection {
-                            start,
-                            end: start
-                                .next_start_line_cursor(&self.code)
-                                .unwrap_or_else(|| start.end_line_cursor(&self.code)),
-                            message: message.unwrap_or_default(),
-                            color,
-                            is_multiline_start: false,
-                            is_multiline_end: false,
-                        },
-                    );
-                } else {
-                    self.sections.splice(
-                        index..index,
-                        [
-                            CodeSection {
-                                start,
-                                end: start
-                                    .next_start_line_cursor(&self.code)
-                                    .unwrap_or_else(|| start.end_line_cursor(&self.code)),
-                                message: TextBlock::new(),
-                                color,
-                                is_multiline_start: true,
-                                is_multiline_end: false,
-                            },
-                            CodeSection {
-                                start: end.start_line_cursor(&self.code),
-                                end,
-                                message: message.unwrap_or_default(),
-                                color,
-                                is_multiline_start: false,
-                                is_multiline_end: true,
-                            },
-                        ],
-                    );
-                }
+            line_start += line.len();
+        }
+
+        let old_code = std::mem::take(&mut self.code);
+        let masked = MaskedText::new(&old_code, ranges, mask);
+
+        if !masked.is_masked() {
+            self.code = old_code;
+            return;
+        }
+
+        let code = masked.text.to_string();
+        let line_index = LineIndex::new(&code);
+        let mut previous_end = 0;
+
+        for section in &mut self.sections {
+            let start = masked
+                .map_offset(section.start.byte_offset, false)
+                .max(previous_end);
+            let end = if section.is_cursor() {
+                start
             } else {
-                self.sections.insert(
-                    index,
-                    CodeSection {
-                        start,
-                        end,
-                        message: message.unwrap_or_default(),
-                        color,
-                        is_multiline_start: false,
-                        is_multiline_end: false,
-                    },
-                );
+                masked.map_offset(section.end.byte_offset, true).max(start)
+            };
+
+            section.start = line_index.cursor(&code, start);
+            section.end = line_index.cursor(&code, end);
+            previous_end = end;
+
+            for (range, _) in &mut section.styles {
+                *range = masked.map_offset(range.start, false)..masked.map_offset(range.end, true);
             }
-        };
-        self
+        }
+
+        for suggestion in &mut self.suggestions {
+            let range = &suggestion.byte_range;
+            let range = masked.map_offset(range.start, false)..masked.map_offset(range.end, true);
+
+            suggestion.start = TextPosition::from_byte_offset(&code, range.start);
+            suggestion.end = TextPosition::from_byte_offset(&code, range.end);
+            suggestion.byte_range = range;
+        }
+
+        drop(masked);
+        self.code = Cow::Owned(code);
+        self.line_index = OnceLock::from(line_index);
     }
 
+    /// Prints the block with the given width of the line numbers, e.g. to align them with
+    /// other blocks. The block is rendered by [CodeBlockLines], like [CodeBlock::render_lines].
     pub(crate) fn print_with_options(&self, printer: &mut Printer<'a>, max_line_digits: usize) {
+        let mut block_printer = printer.derive();
+        block_printer.lines.extend(
+            CodeBlockLines::with_printer(self, printer.derive(), max_line_digits)
+                .map(|line| line.sections),
+        );
+
+        printer.append(block_printer);
+    }
+
+    /// Prints all the lines with sections.
+    fn print_sections(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
+        let mut last_line = self.sections.first().map_or(0, |v| v.start.line);
+        let mut sections: &[CodeSection] = &self.sections;
+        let mut current_line_sections = Vec::new();
+        let palette_colors = self.palette_colors();
+
+        while !sections.is_empty() {
+            group_sections_in_same_line(&mut sections, &mut current_line_sections);
+            last_line = self.print_line_group(
+                printer,
+                max_line_digits,
+                last_line,
+                &current_line_sections,
+                palette_colors.as_ref(),
+                glyphs,
+            );
+        }
+    }
+
+    /// Prints the title, the first line of the frame and the lines before the first section.
+    fn print_header(&self, printer: &mut Printer<'a>, max_line_digits: usize) {
+        let glyphs = self.style.glyphs();
+
         // Title
         let code_indent = TextBlock::new_plain(build_space_string(max_line_digits + 1));
 
@@ -320,13 +1405,13 @@ impl<'a> CodeBlock<'a> {
             printer.push_styled_text(
                 format!(
                     "{:>width$} ",
-                    printer.level.symbol(),
+                    printer.level_symbol(),
                     width = max_line_digits
                 ),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
-            let mut title_printer = printer.derive();
+            let mut title_printer = printer.derive_indented(code_indent.column_count());
 
             self.title.print(&mut title_printer);
             title_printer.indent(&code_indent.sections, false);
@@ -339,33 +1424,44 @@ impl<'a> CodeBlock<'a> {
                 printer.push_styled_text(
                     format!(
                         "{:>width$} ",
-                        printer.level.symbol(),
+                        printer.level_symbol(),
                         width = max_line_digits
                     ),
-                    Style::new().bold().fg(printer.level.color()),
+                    printer.border_style(),
                 );
             } else {
-                printer.push_plain_text("\n");
+                printer.new_line();
                 code_indent.print(printer);
             }
 
             if self.file_path.is_empty() {
-                printer.push_styled_text(
-                    Cow::Borrowed(concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR)),
-                    Style::new().bold(),
-                );
+                printer.push_styled_text(Cow::Borrowed(glyphs.frame_start), Style::new().bold());
             } else {
                 printer.push_styled_text(
-                    Cow::Borrowed(concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, '[')),
+                    format!("{}{}", glyphs.frame_start, glyphs.path_open),
                     Style::new().bold(),
                 );
-                self.file_path.single_lined().print(printer);
-                printer.push_styled_text(Cow::Borrowed(concatcp!(']')), Style::new().bold());
+                self.file_path
+                    .print_single_lined(printer, count_columns(glyphs.path_close));
+                printer.push_styled_text(Cow::Borrowed(glyphs.path_close), Style::new().bold());
             }
         }
 
-        // Sections.
+        self.print_leading_lines(printer, max_line_digits, glyphs);
+    }
+
+    /// Prints the column ruler and the lines before the first section.
+    fn print_leading_lines(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
         if !self.sections.is_empty() {
+            if self.show_column_ruler {
+                self.print_column_ruler(printer, max_line_digits, glyphs);
+            }
+
             // Show previous lines.
             if self.previous_lines > 0 {
                 let first_section_start_cursor = self.sections.first().unwrap().start;
@@ -379,13 +1475,14 @@ impl<'a> CodeBlock<'a> {
 
                 for line in start_line..first_section_start_cursor.line {
                     printer.push_styled_text(
-                        format!("\n{:>width$} ", line, width = max_line_digits),
-                        Style::new().bold().fg(Color::BrightBlack),
-                    );
-                    printer.push_styled_text(
-                        Cow::Borrowed(concatcp!(VERTICAL_BAR, "    ")),
-                        Style::new().bold(),
+                        format!(
+                            "\n{:>width$} ",
+                            self.line_label(line, next_line_start_cursor.byte_offset),
+                            width = max_line_digits
+                        ),
+                        printer.theme.gutter_style(),
                     );
+                    self.print_code_gutter(printer, line, glyphs);
                     printer.push_plain_text({
                         if self.show_new_line_chars {
                             Cow::Owned(format!(
@@ -409,417 +1506,488 @@ impl<'a> CodeBlock<'a> {
                         .unwrap();
                 }
             }
+        }
+    }
 
-            // Show highlighted sections.
-            {
-                let mut last_line = self.sections.first().unwrap().start.line;
-                let mut sections: &[CodeSection] = &self.sections;
-                let mut current_line_sections = Vec::new();
+    /// Prints a line with sections, preceded by the middle lines that separate it from
+    /// `last_line`, and followed by its underline and message rows. Returns the printed line.
+    fn print_line_group(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        last_line: usize,
+        current_line_sections: &[&CodeSection<'a>],
+        palette_colors: Option<&HashMap<(usize, usize), Color>>,
+        glyphs: &CodeBlockGlyphs,
+    ) -> usize {
+        let secondary_color = self.secondary_color_in(&printer.theme);
+        let level_color = printer.level_color();
+        let theme = printer.theme;
+
+        // Returns the color of a section given the color of the previous one in the line.
+        let section_color = |section: &CodeSection<'a>, previous_color: Color| {
+            resolve_section_color(
+                section,
+                &theme,
+                palette_colors,
+                if previous_color == secondary_color {
+                    level_color
+                } else {
+                    secondary_color
+                },
+            )
+        };
 
-                while !sections.is_empty() {
-                    group_sections_in_same_line(&mut sections, &mut current_line_sections);
+        // Hide the messages that exceed the limit.
+        let limited_sections = self.limit_inline_messages(current_line_sections);
+        let limited_section_refs: Vec<&CodeSection<'a>>;
+        let (current_line_sections, hidden_messages) = match &limited_sections {
+            Some((sections, hidden_messages)) => {
+                limited_section_refs = sections.iter().collect();
+                (limited_section_refs.as_slice(), *hidden_messages)
+            }
+            None => (current_line_sections, 0),
+        };
 
-                    let line_start_cursor = current_line_sections
-                        .first()
-                        .unwrap()
-                        .start
-                        .start_line_cursor(&self.code);
-
-                    // Print middle lines.
-                    let middle_lines = (line_start_cursor.line - last_line).saturating_sub(1);
-                    if middle_lines >= 1 {
-                        if self.middle_lines >= middle_lines {
-                            // Print lines.
-                            let mut next_line_start_cursor = line_start_cursor
-                                .find_line_start(&self.code, last_line)
-                                .unwrap();
-
-                            for line in (last_line + 1)..line_start_cursor.line {
-                                printer.push_styled_text(
-                                    format!("\n{:>width$} ", line, width = max_line_digits),
-                                    Style::new().bold().fg(Color::BrightBlack),
-                                );
-                                printer.push_styled_text(
-                                    Cow::Borrowed(concatcp!(VERTICAL_BAR, "    ")),
-                                    Style::new().bold(),
-                                );
-                                printer.push_plain_text({
-                                    if self.show_new_line_chars {
-                                        Cow::Owned(format!(
-                                            "{}{NEW_LINE_LEFT}",
-                                            next_line_start_cursor.slice_to_line_end(&self.code)
-                                        ))
-                                    } else {
-                                        match &self.code {
-                                            Cow::Borrowed(v) => Cow::Borrowed(
-                                                next_line_start_cursor.slice_to_line_end(v),
-                                            ),
-                                            Cow::Owned(v) => Cow::Owned(
-                                                next_line_start_cursor
-                                                    .slice_to_line_end(v)
-                                                    .to_string(),
-                                            ),
-                                        }
-                                    }
-                                });
-
-                                next_line_start_cursor = next_line_start_cursor
-                                    .next_start_line_cursor(&self.code)
-                                    .unwrap();
-                            }
-                        } else {
-                            // Skip lines.
-                            printer.push_styled_text(
-                                build_whitespace_string(1, max_line_digits),
-                                Style::new(),
-                            );
-                            printer.push_styled_text(Cow::Borrowed("···    "), Style::new().bold());
-                        }
-                    }
-                    last_line = line_start_cursor.line;
+        let line_start_cursor = current_line_sections
+            .first()
+            .unwrap()
+            .start
+            .start_line_cursor(&self.code);
+
+        // Print middle lines.
+        let middle_lines = (line_start_cursor.line - last_line).saturating_sub(1);
+        let max_section_lines = self
+            .max_section_lines
+            .filter(|_| current_line_sections.first().unwrap().is_multiline_end);
+
+        if let (true, Some(max_section_lines)) = (middle_lines >= 1, max_section_lines) {
+            // Fold the inside of a multiline section.
+            let mut head_lines = max_section_lines.min(middle_lines);
+            let tail_lines = max_section_lines.min(middle_lines - head_lines);
+            let mut hidden_lines = middle_lines - head_lines - tail_lines;
+
+            // Folding a single line does not save any row.
+            if hidden_lines == 1 {
+                head_lines += 1;
+                hidden_lines = 0;
+            }
+
+            let mut next_line_start_cursor = line_start_cursor
+                .find_line_start(&self.code, last_line + 1)
+                .unwrap();
+
+            for _ in 0..head_lines {
+                self.print_plain_line(printer, &next_line_start_cursor, max_line_digits, glyphs);
+                next_line_start_cursor = next_line_start_cursor
+                    .next_start_line_cursor(&self.code)
+                    .unwrap();
+            }
+
+            if hidden_lines > 0 {
+                printer.push_styled_text(build_whitespace_string(1, max_line_digits), Style::new());
+                printer.push_styled_text(
+                    format!(
+                        "··· ({} line{} hidden)",
+                        hidden_lines,
+                        if hidden_lines == 1 { "" } else { "s" }
+                    ),
+                    Style::new().bold(),
+                );
+            }
+
+            next_line_start_cursor = line_start_cursor
+                .find_line_start(&self.code, line_start_cursor.line - tail_lines)
+                .unwrap();
+
+            for _ in 0..tail_lines {
+                self.print_plain_line(printer, &next_line_start_cursor, max_line_digits, glyphs);
+                next_line_start_cursor = next_line_start_cursor
+                    .next_start_line_cursor(&self.code)
+                    .unwrap();
+            }
+        } else if middle_lines >= 1 {
+            if self.middle_lines >= middle_lines {
+                // Print lines.
+                let mut next_line_start_cursor = line_start_cursor
+                    .find_line_start(&self.code, last_line)
+                    .unwrap();
 
-                    // Print code line.
+                for line in (last_line + 1)..line_start_cursor.line {
                     printer.push_styled_text(
                         format!(
                             "\n{:>width$} ",
-                            line_start_cursor.line,
+                            self.line_label(line, next_line_start_cursor.byte_offset),
                             width = max_line_digits
                         ),
-                        Style::new().bold().fg(Color::BrightBlack),
-                    );
-                    printer.push_styled_text(
-                        Cow::Borrowed(concatcp!(VERTICAL_BAR, "    ")),
-                        Style::new().bold(),
+                        printer.theme.gutter_style(),
                     );
+                    self.print_code_gutter(printer, line, glyphs);
+                    printer.push_plain_text({
+                        if self.show_new_line_chars {
+                            Cow::Owned(format!(
+                                "{}{NEW_LINE_LEFT}",
+                                next_line_start_cursor.slice_to_line_end(&self.code)
+                            ))
+                        } else {
+                            match &self.code {
+                                Cow::Borrowed(v) => {
+                                    Cow::Borrowed(next_line_start_cursor.slice_to_line_end(v))
+                                }
+                                Cow::Owned(v) => Cow::Owned(
+                                    next_line_start_cursor.slice_to_line_end(v).to_string(),
+                                ),
+                            }
+                        }
+                    });
 
-                    let mut next_color = self.secondary_color;
-                    let mut previous_cursor = line_start_cursor;
+                    next_line_start_cursor = next_line_start_cursor
+                        .next_start_line_cursor(&self.code)
+                        .unwrap();
+                }
+            } else {
+                // Skip lines.
+                printer.push_styled_text(build_whitespace_string(1, max_line_digits), Style::new());
+                printer.push_styled_text(Cow::Borrowed("···    "), Style::new().bold());
+            }
+        }
 
-                    for section in &current_line_sections {
-                        // Print previous content.
-                        printer.push_plain_text(match &self.code {
-                            Cow::Borrowed(v) => {
-                                Cow::Borrowed(previous_cursor.slice(v, &section.start))
-                            }
-                            Cow::Owned(v) => {
-                                Cow::Owned(previous_cursor.slice(v, &section.start).to_string())
-                            }
-                        });
+        // Print code line.
+        printer.push_styled_text(
+            format!(
+                "\n{:>width$} ",
+                self.line_label(line_start_cursor.line, line_start_cursor.byte_offset),
+                width = max_line_digits
+            ),
+            printer.theme.gutter_style(),
+        );
+        self.print_code_gutter(printer, line_start_cursor.line, glyphs);
 
-                        next_color =
-                            section
-                                .color
-                                .unwrap_or(if next_color == self.secondary_color {
-                                    printer.level.color()
-                                } else {
-                                    self.secondary_color
-                                });
+        let mut next_color = secondary_color;
+        let mut previous_cursor = line_start_cursor;
 
-                        section.print_content(printer, self, next_color);
-                        previous_cursor = section.end;
-                    }
+        for section in current_line_sections {
+            // Print previous content.
+            printer.push_plain_text(match &self.code {
+                Cow::Borrowed(v) => Cow::Borrowed(previous_cursor.slice(v, &section.start)),
+                Cow::Owned(v) => Cow::Owned(previous_cursor.slice(v, &section.start).to_string()),
+            });
 
-                    if previous_cursor.line == line_start_cursor.line {
-                        let line_end_cursor = previous_cursor.end_line_cursor(&self.code);
-                        printer.push_plain_text(match &self.code {
-                            Cow::Borrowed(v) => {
-                                Cow::Borrowed(previous_cursor.slice(v, &line_end_cursor))
-                            }
-                            Cow::Owned(v) => {
-                                Cow::Owned(previous_cursor.slice(v, &line_end_cursor).to_string())
-                            }
-                        });
+            next_color = section_color(section, next_color);
 
-                        if self.show_new_line_chars {
-                            printer.push_plain_text(Cow::Borrowed(concatcp!(NEW_LINE_LEFT)));
-                        }
-                    }
+            section.print_content(printer, self, next_color);
+            previous_cursor = section.end;
+        }
 
-                    // Print underline.
-                    {
-                        let mut prefix = TextBlock::new()
-                            .add_plain_text(build_space_string(max_line_digits + 1))
-                            .add_styled_text(
-                                Cow::Borrowed(concatcp!(VERTICAL_BAR)),
-                                Style::new().bold(),
-                            );
+        if previous_cursor.line == line_start_cursor.line {
+            let line_end_cursor = previous_cursor.end_line_cursor(&self.code);
+            printer.push_plain_text(match &self.code {
+                Cow::Borrowed(v) => Cow::Borrowed(previous_cursor.slice(v, &line_end_cursor)),
+                Cow::Owned(v) => Cow::Owned(previous_cursor.slice(v, &line_end_cursor).to_string()),
+            });
 
-                        printer.push_plain_text(build_whitespace_string(1, max_line_digits + 1));
-                        printer.push_styled_text(
-                            if current_line_sections.first().unwrap().is_multiline_end {
-                                Cow::Borrowed(concatcp!(VERTICAL_BAR, "  "))
-                            } else {
-                                Cow::Borrowed(concatcp!(VERTICAL_BAR, "    "))
-                            },
-                            Style::new().bold(),
-                        );
+            if self.show_new_line_chars {
+                printer.push_plain_text(Cow::Borrowed(concatcp!(NEW_LINE_LEFT)));
+            }
+        }
 
-                        next_color = self.secondary_color;
-                        previous_cursor = line_start_cursor;
+        // Print underline.
+        {
+            let mut prefix = TextBlock::new()
+                .add_plain_text(build_space_string(max_line_digits + 1))
+                .add_styled_text(glyphs.gutter.to_string(), Style::new().bold());
 
-                        let mut space_count = 4;
+            printer.push_plain_text(build_whitespace_string(1, max_line_digits + 1));
+            printer.push_styled_text(
+                if current_line_sections.first().unwrap().is_multiline_end {
+                    format!("{}  ", glyphs.gutter)
+                } else {
+                    format!("{}    ", glyphs.gutter)
+                },
+                Style::new().bold(),
+            );
 
-                        for (section_index, section) in current_line_sections.iter().enumerate() {
-                            // Print previous content.
-                            printer.push_plain_text(build_space_string(
-                                section.start.char_offset - previous_cursor.char_offset,
-                            ));
-                            space_count += section.start.char_offset - previous_cursor.char_offset;
+            next_color = secondary_color;
+            previous_cursor = line_start_cursor;
 
-                            if !section.message.is_empty() {
-                                prefix = prefix.add_plain_text(build_space_string(space_count));
-                                space_count = 0;
-                            }
+            let mut space_count = 4;
 
-                            next_color =
-                                section
-                                    .color
-                                    .unwrap_or(if next_color == self.secondary_color {
-                                        printer.level.color()
-                                    } else {
-                                        self.secondary_color
-                                    });
-
-                            if !section.message.is_empty()
-                                && section_index == current_line_sections.len() - 1
-                            {
-                                section.print_underline_with_message(printer, next_color);
-                                prefix = prefix
-                                    .add_plain_text(build_space_string(section.char_len() + 3));
-
-                                let mut message_printer = printer.derive();
-                                section.message.print(&mut message_printer);
-                                message_printer.indent(&prefix.sections, false);
-                                printer.append(message_printer);
-                            } else {
-                                if section.message.is_empty() {
-                                    space_count += section.char_len();
-                                } else {
-                                    prefix = prefix.add_styled_text(
-                                        Cow::Borrowed(concatcp!(VERTICAL_BAR)),
-                                        Style::new().bold().fg(next_color),
-                                    );
+            for (section_index, section) in current_line_sections.iter().enumerate() {
+                // Print previous content.
+                printer.push_plain_text(build_space_string(
+                    section.start.char_offset - previous_cursor.char_offset,
+                ));
+                space_count += section.start.char_offset - previous_cursor.char_offset;
 
-                                    space_count += section.char_len() - 1;
-                                }
+                if !section.message.is_empty() {
+                    prefix = prefix.add_plain_text(build_space_string(space_count));
+                    space_count = 0;
+                }
 
-                                section.print_underline(printer, next_color);
-                            }
-                            previous_cursor = section.end;
-                        }
-                    }
+                next_color = section_color(section, next_color);
 
-                    // Print message lines.
-                    let alignment = if self.align_messages {
-                        current_line_sections
-                            .iter()
-                            .rev()
-                            .find(|v| !v.message.is_empty())
-                            .map(|v| v.start.char_offset + 1)
-                    } else {
-                        None
-                    };
+                if !section.message.is_empty() && section_index == current_line_sections.len() - 1 {
+                    section.print_underline_with_message(printer, glyphs, next_color);
+                    prefix = prefix.add_plain_text(build_space_string(
+                        section.char_len() + glyphs.message_bar_width + 1,
+                    ));
 
-                    let current_line_sections_until_last_message = if let Some((index, _)) =
-                        current_line_sections
-                            .iter()
-                            .enumerate()
-                            .rev()
-                            .find(|(_, v)| !v.message.is_empty())
-                    {
-                        &current_line_sections[..index + 1]
+                    let mut message_printer = printer.derive_indented(prefix.column_count());
+                    section.message.print(&mut message_printer);
+                    message_printer.indent(&prefix.sections, false);
+                    printer.append(message_printer);
+                } else {
+                    if section.message.is_empty() {
+                        space_count += section.char_len();
                     } else {
-                        &[]
-                    };
-
-                    let number_of_messages = current_line_sections
-                        .iter()
-                        .filter(|v| !v.message.is_empty())
-                        .count()
-                        .saturating_sub(
-                            !current_line_sections.last().unwrap().message.is_empty() as usize
+                        prefix = prefix.add_styled_text(
+                            glyphs.connector.to_string(),
+                            Style::new().bold().fg(next_color),
                         );
 
-                    for row in 0..number_of_messages {
-                        printer.push_plain_text(Cow::Borrowed("\n"));
-                        let mut prefix = TextBlock::new()
-                            .add_plain_text(build_space_string(max_line_digits + 1))
-                            .add_styled_text(
-                                Cow::Borrowed(concatcp!(VERTICAL_BAR)),
-                                Style::new().bold(),
-                            );
+                        space_count += section.char_len() - 1;
+                    }
 
-                        next_color = self.secondary_color;
-                        previous_cursor = line_start_cursor;
+                    section.print_underline(printer, glyphs, next_color);
+                }
+                previous_cursor = section.end;
+            }
+        }
+
+        // Print message lines.
+        let alignment = if self.align_messages {
+            current_line_sections
+                .iter()
+                .rev()
+                .find(|v| !v.message.is_empty())
+                .map(|v| v.start.char_offset + 1)
+        } else {
+            None
+        };
 
-                        let mut space_count = 4;
-                        let mut current_message_index = number_of_messages;
+        let current_line_sections_until_last_message = if let Some((index, _)) =
+            current_line_sections
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, v)| !v.message.is_empty())
+        {
+            &current_line_sections[..index + 1]
+        } else {
+            &[]
+        };
 
-                        for (section_index, section) in current_line_sections.iter().enumerate() {
-                            // Add previous content to the space count.
-                            space_count += section.start.char_offset - previous_cursor.char_offset;
+        let number_of_messages = current_line_sections
+            .iter()
+            .filter(|v| !v.message.is_empty())
+            .count()
+            .saturating_sub(!current_line_sections.last().unwrap().message.is_empty() as usize);
 
-                            if !section.message.is_empty() {
-                                prefix = prefix.add_plain_text(build_space_string(space_count));
-                                space_count = 0;
-                            }
+        for row in 0..number_of_messages {
+            printer.new_line();
+            let mut prefix = TextBlock::new()
+                .add_plain_text(build_space_string(max_line_digits + 1))
+                .add_styled_text(glyphs.gutter.to_string(), Style::new().bold());
 
-                            next_color =
-                                section
-                                    .color
-                                    .unwrap_or(if next_color == self.secondary_color {
-                                        printer.level.color()
-                                    } else {
-                                        self.secondary_color
-                                    });
-
-                            if section.message.is_empty() {
-                                space_count += section.char_len();
-                            } else {
-                                if row + 1 == current_message_index {
-                                    prefix.print(printer);
-
-                                    if let Some(alignment) = alignment {
-                                        let forward_cursors =
-                                            current_line_sections_until_last_message
-                                                .iter()
-                                                .skip(section_index)
-                                                .filter(|v| v.is_cursor())
-                                                .count();
-
-                                        printer.push_styled_text(
-                                            Cow::Owned(format!(
-                                                "{TOP_RIGHT_CORNER}{} ",
-                                                concatcp!(HORIZONTAL_BAR).repeat(
-                                                    (alignment - section.start.char_offset)
-                                                        + forward_cursors
-                                                        + 1
-                                                )
-                                            )),
-                                            Style::new().bold().fg(next_color),
-                                        );
-
-                                        prefix = prefix.add_plain_text(build_space_string(
-                                            (alignment - section.start.char_offset)
-                                                + forward_cursors
-                                                + 3,
-                                        ));
-                                    } else {
-                                        printer.push_styled_text(
-                                            Cow::Borrowed(concatcp!(
-                                                TOP_RIGHT_CORNER,
-                                                HORIZONTAL_BAR,
-                                                HORIZONTAL_BAR,
-                                                ' '
-                                            )),
-                                            Style::new().bold().fg(next_color),
-                                        );
-
-                                        prefix = prefix.add_plain_text("    ");
-                                    }
-
-                                    let mut message_printer = printer.derive();
-                                    section.message.print(&mut message_printer);
-                                    message_printer.indent(&prefix.sections, false);
-                                    printer.append(message_printer);
-                                    break;
-                                }
+            next_color = secondary_color;
+            previous_cursor = line_start_cursor;
 
-                                prefix = prefix.add_styled_text(
-                                    Cow::Borrowed(concatcp!(VERTICAL_BAR)),
-                                    Style::new().bold().fg(next_color),
-                                );
+            let mut space_count = 4;
+            let mut current_message_index = number_of_messages;
 
-                                space_count += section.char_len() - 1;
-                                current_message_index -= 1;
-                            }
+            for (section_index, section) in current_line_sections.iter().enumerate() {
+                // Add previous content to the space count.
+                space_count += section.start.char_offset - previous_cursor.char_offset;
 
-                            previous_cursor = section.end;
-                        }
-                    }
+                if !section.message.is_empty() {
+                    prefix = prefix.add_plain_text(build_space_string(space_count));
+                    space_count = 0;
                 }
-            }
 
-            // Show next lines.
-            if self.next_lines > 0 {
-                let mut last_section_start_cursor = self.sections.last().unwrap().start;
-                let last_line = last_section_start_cursor
-                    .line
-                    .saturating_add(self.next_lines);
+                next_color = section_color(section, next_color);
 
-                for line in last_section_start_cursor.line..last_line {
-                    let next_line_start_cursor =
-                        match last_section_start_cursor.next_start_line_cursor(&self.code) {
-                            Some(v) => v,
-                            None => break,
-                        };
+                if section.message.is_empty() {
+                    space_count += section.char_len();
+                } else {
+                    if row + 1 == current_message_index {
+                        prefix.print(printer);
+
+                        if let Some(alignment) = alignment {
+                            let forward_cursors = current_line_sections_until_last_message
+                                .iter()
+                                .skip(section_index)
+                                .filter(|v| v.is_cursor())
+                                .count();
+                            let bar_width = (alignment - section.start.char_offset)
+                                + forward_cursors
+                                + glyphs.message_bar_width
+                                - 1;
 
-                    printer.push_styled_text(
-                        format!("\n{:>width$} ", line + 1, width = max_line_digits),
-                        Style::new().bold().fg(Color::BrightBlack),
+                            printer.push_styled_text(
+                                Cow::Owned(format!(
+                                    "{}{} ",
+                                    glyphs.message_corner,
+                                    glyphs.message_bar.to_string().repeat(bar_width)
+                                )),
+                                Style::new().bold().fg(next_color),
+                            );
+
+                            prefix = prefix.add_plain_text(build_space_string(bar_width + 2));
+                        } else {
+                            printer.push_styled_text(
+                                format!(
+                                    "{}{} ",
+                                    glyphs.message_corner,
+                                    glyphs
+                                        .message_bar
+                                        .to_string()
+                                        .repeat(glyphs.message_bar_width)
+                                ),
+                                Style::new().bold().fg(next_color),
+                            );
+
+                            prefix = prefix
+                                .add_plain_text(build_space_string(glyphs.message_bar_width + 2));
+                        }
+
+                        let mut message_printer = printer.derive_indented(prefix.column_count());
+                        section.message.print(&mut message_printer);
+                        message_printer.indent(&prefix.sections, false);
+                        printer.append(message_printer);
+                        break;
+                    }
+
+                    prefix = prefix.add_styled_text(
+                        glyphs.connector.to_string(),
+                        Style::new().bold().fg(next_color),
                     );
-                    printer.push_styled_text(
-                        Cow::Borrowed(concatcp!(VERTICAL_BAR, "    ")),
-                        Style::new().bold(),
-                    );
-                    printer.push_plain_text({
-                        match &self.code {
-                            Cow::Borrowed(v) => {
-                                if self.show_new_line_chars {
-                                    let slice = next_line_start_cursor.slice_to_line_end(v);
-
-                                    if slice.len() + next_line_start_cursor.byte_offset
-                                        == self.code.len()
-                                    {
-                                        Cow::Borrowed(slice)
-                                    } else {
-                                        Cow::Owned(format!("{}{NEW_LINE_LEFT}", slice))
-                                    }
-                                } else {
-                                    Cow::Borrowed(next_line_start_cursor.slice_to_line_end(v))
-                                }
-                            }
-                            Cow::Owned(v) => {
-                                if self.show_new_line_chars {
-                                    let slice = next_line_start_cursor.slice_to_line_end(v);
-
-                                    if slice.len() + next_line_start_cursor.byte_offset
-                                        == self.code.len()
-                                    {
-                                        Cow::Owned(slice.to_string())
-                                    } else {
-                                        Cow::Owned(format!("{}{NEW_LINE_LEFT}", slice))
-                                    }
-                                } else {
-                                    Cow::Owned(
-                                        next_line_start_cursor.slice_to_line_end(v).to_string(),
-                                    )
-                                }
-                            }
-                        }
-                    });
 
-                    last_section_start_cursor = next_line_start_cursor;
+                    space_count += section.char_len() - 1;
+                    current_message_index -= 1;
                 }
+
+                previous_cursor = section.end;
+            }
+        }
+
+        // Print the summary of the hidden messages.
+        if hidden_messages > 0 {
+            printer.push_plain_text(build_whitespace_string(1, max_line_digits + 1));
+            printer.push_styled_text(format!("{}    ", glyphs.gutter), Style::new().bold());
+            printer.push_styled_text(
+                format!(
+                    "··· and {} more label{}",
+                    hidden_messages,
+                    if hidden_messages == 1 { "" } else { "s" }
+                ),
+                Style::new().bold(),
+            );
+        }
+
+        line_start_cursor.line
+    }
+
+    /// Returns a copy of the sections of a line without the messages that exceed
+    /// [CodeBlock::max_inline_messages], along with the number of hidden messages, or
+    /// [None] if no message must be hidden.
+    fn limit_inline_messages(
+        &self,
+        current_line_sections: &[&CodeSection<'a>],
+    ) -> Option<(Vec<CodeSection<'a>>, usize)> {
+        let max_inline_messages = self.max_inline_messages?;
+        let mut message_indexes: Vec<usize> = current_line_sections
+            .iter()
+            .enumerate()
+            .filter(|(_, section)| !section.message.is_empty())
+            .map(|(index, _)| index)
+            .collect();
+
+        if message_indexes.len() <= max_inline_messages {
+            return None;
+        }
+
+        // Stable so the first messages are kept in case of a tie.
+        message_indexes
+            .sort_by_key(|index| std::cmp::Reverse(current_line_sections[*index].priority));
+
+        let hidden_indexes = &message_indexes[max_inline_messages..];
+        let sections = current_line_sections
+            .iter()
+            .enumerate()
+            .map(|(index, section)| {
+                let mut section = (*section).clone();
+
+                if hidden_indexes.contains(&index) {
+                    section.message = TextBlock::new();
+                }
+
+                section
+            })
+            .collect();
+
+        Some((sections, hidden_indexes.len()))
+    }
+
+    /// Prints the lines after the last section, the suggestions, the last line of the frame
+    /// and the notes.
+    fn print_footer(&self, printer: &mut Printer<'a>, max_line_digits: usize) {
+        let glyphs = self.style.glyphs();
+        let code_indent = TextBlock::new_plain(build_space_string(max_line_digits + 1));
+
+        self.print_trailing_lines(printer, max_line_digits, glyphs);
+
+        // Related blocks.
+        for related in &self.related {
+            related.print_related(printer, max_line_digits, glyphs);
+        }
+
+        // Suggestions.
+        for suggestion in &self.suggestions {
+            let range = &suggestion.byte_range;
+            let line_start =
+                memchr::memrchr(b'\n', &self.code.as_bytes()[..range.start]).map_or(0, |v| v + 1);
+            let line_end = memchr::memchr(b'\n', &self.code.as_bytes()[range.end..])
+                .map_or(self.code.len(), |v| range.end + v);
+            let patched = format!(
+                "{}{}{}",
+                &self.code[line_start..range.start],
+                suggestion.replacement,
+                &self.code[range.end..line_end]
+            );
+            let style = Style::new().fg(Color::Green);
+
+            for (i, line) in patched.split('\n').enumerate() {
+                let line = line.strip_suffix('\r').unwrap_or(line);
+                let label = if i == 0 {
+                    self.line_label(suggestion.start.line + 1, line_start)
+                } else {
+                    String::new()
+                };
+
+                printer.new_line();
+                printer.push_styled_text(
+                    format!("{:>width$} ", label, width = max_line_digits),
+                    style.bold(),
+                );
+                printer.push_styled_text(Cow::Borrowed("+    "), style.bold());
+                printer.push_styled_text(line.to_string(), style);
             }
         }
 
         // Final line + message.
         {
-            let mut final_line_printer = printer.derive();
+            let mut final_line_printer = printer.derive_indented(code_indent.column_count());
             if self.final_message.is_empty() {
-                final_line_printer.push_styled_text(
-                    Cow::Borrowed(concatcp!(TOP_RIGHT_CORNER, HORIZONTAL_BAR)),
-                    Style::new().bold(),
-                );
+                final_line_printer
+                    .push_styled_text(Cow::Borrowed(glyphs.frame_end), Style::new().bold());
             } else {
-                final_line_printer.push_styled_text(
-                    Cow::Borrowed(concatcp!(TOP_RIGHT_CORNER, HORIZONTAL_BAR, ' ')),
-                    Style::new().bold(),
-                );
+                final_line_printer
+                    .push_styled_text(format!("{} ", glyphs.frame_end), Style::new().bold());
 
-                let message_indent = TextBlock::new_plain(Cow::Borrowed("   "));
-                let mut message_printer = final_line_printer.derive();
+                let message_indent =
+                    TextBlock::new_plain(build_space_string(count_columns(glyphs.frame_end) + 1));
+                let mut message_printer =
+                    final_line_printer.derive_indented(message_indent.column_count());
 
                 self.final_message.print(&mut message_printer);
                 message_printer.indent(&message_indent.sections, false);
@@ -829,6 +1997,335 @@ impl<'a> CodeBlock<'a> {
             final_line_printer.indent(&code_indent.sections, true);
             printer.append_lines(final_line_printer);
         }
+
+        // Notes.
+        for note in &self.notes {
+            let label = format!("= {}: ", note.kind.label());
+            let note_indent = TextBlock::new_plain(build_space_string(
+                code_indent.column_count() + count_columns(&label),
+            ));
+
+            printer.new_line();
+            code_indent.print(printer);
+            printer.push_styled_text(label, Style::new().bold());
+
+            let mut note_printer = printer.derive_indented(note_indent.column_count());
+            note.text.print(&mut note_printer);
+            note_printer.indent(&note_indent.sections, false);
+            printer.append(note_printer);
+        }
+    }
+
+    /// Prints the lines after the last section.
+    fn print_trailing_lines(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
+        if !self.sections.is_empty() && self.next_lines > 0 {
+            let mut last_section_start_cursor = self.sections.last().unwrap().start;
+            let last_line = last_section_start_cursor
+                .line
+                .saturating_add(self.next_lines);
+
+            for line in last_section_start_cursor.line..last_line {
+                let next_line_start_cursor =
+                    match last_section_start_cursor.next_start_line_cursor(&self.code) {
+                        Some(v) => v,
+                        None => break,
+                    };
+
+                printer.push_styled_text(
+                    format!(
+                        "\n{:>width$} ",
+                        self.line_label(line + 1, next_line_start_cursor.byte_offset),
+                        width = max_line_digits
+                    ),
+                    printer.theme.gutter_style(),
+                );
+                self.print_code_gutter(printer, line + 1, glyphs);
+                printer.push_plain_text({
+                    match &self.code {
+                        Cow::Borrowed(v) => {
+                            if self.show_new_line_chars {
+                                let slice = next_line_start_cursor.slice_to_line_end(v);
+
+                                if slice.len() + next_line_start_cursor.byte_offset
+                                    == self.code.len()
+                                {
+                                    Cow::Borrowed(slice)
+                                } else {
+                                    Cow::Owned(format!("{}{NEW_LINE_LEFT}", slice))
+                                }
+                            } else {
+                                Cow::Borrowed(next_line_start_cursor.slice_to_line_end(v))
+                            }
+                        }
+                        Cow::Owned(v) => {
+                            if self.show_new_line_chars {
+                                let slice = next_line_start_cursor.slice_to_line_end(v);
+
+                                if slice.len() + next_line_start_cursor.byte_offset
+                                    == self.code.len()
+                                {
+                                    Cow::Owned(slice.to_string())
+                                } else {
+                                    Cow::Owned(format!("{}{NEW_LINE_LEFT}", slice))
+                                }
+                            } else {
+                                Cow::Owned(next_line_start_cursor.slice_to_line_end(v).to_string())
+                            }
+                        }
+                    }
+                });
+
+                last_section_start_cursor = next_line_start_cursor;
+            }
+        }
+    }
+
+    /// Prints this block as a related block of another one, i.e. a frame connector with
+    /// its file path followed by its lines, using the gutter width and glyphs of the other block.
+    fn print_related(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
+        printer.new_line();
+        printer.push_plain_text(build_space_string(max_line_digits + 1));
+
+        if self.file_path.is_empty() {
+            printer.push_styled_text(Cow::Borrowed(glyphs.frame_related), Style::new().bold());
+        } else {
+            printer.push_styled_text(
+                format!("{}{}", glyphs.frame_related, glyphs.path_open),
+                Style::new().bold(),
+            );
+            self.file_path
+                .print_single_lined(printer, count_columns(glyphs.path_close));
+            printer.push_styled_text(Cow::Borrowed(glyphs.path_close), Style::new().bold());
+        }
+
+        self.print_leading_lines(printer, max_line_digits, glyphs);
+        self.print_sections(printer, max_line_digits, glyphs);
+        self.print_trailing_lines(printer, max_line_digits, glyphs);
+    }
+
+    /// Returns the secondary color of this block when printed with `theme`.
+    fn secondary_color_in(&self, theme: &Theme) -> Color {
+        if self.fixed_secondary_color {
+            self.secondary_color
+        } else {
+            theme.secondary
+        }
+    }
+
+    /// Returns the byte offset of a 1-based `line` and `column` of the code, failing if the
+    /// position is out of bounds.
+    fn line_column_byte_offset(&self, line: usize, column: usize) -> Result<usize, HighlightError> {
+        let error = HighlightError::PositionOutOfBounds { line, column };
+        let line_start = self.line_index().line_start(line).ok_or(error.clone())?;
+        let line_start_cursor = self.line_index().cursor(&self.code, line_start);
+        let line_offset =
+            column_byte_offset(line_start_cursor.slice_to_line_end(&self.code), column)
+                .ok_or(error)?;
+
+        Ok(line_start_cursor.byte_offset + line_offset)
+    }
+
+    /// Prints the sections in compact mode, see [CodeBlock::compact], or in the one-line
+    /// form of [CodeBlock::inline].
+    fn print_compact(&self, printer: &mut Printer<'a>) {
+        let level_color = printer.level_color();
+        let palette_colors = self.palette_colors();
+        let mut sections = self.sections.iter();
+
+        while let Some(section) = sections.next() {
+            // The message of a multiline section is placed in its end part.
+            let message = if section.is_multiline_start {
+                &sections.next().unwrap().message
+            } else {
+                &section.message
+            };
+            let message = if message.is_empty() {
+                &self.title
+            } else {
+                message
+            };
+
+            if !printer.lines.is_empty() {
+                printer.new_line();
+            }
+
+            // Location and message.
+            if self.inline {
+                printer.push_styled_text(Cow::Borrowed("--> "), Style::new().bold());
+            }
+
+            if !self.file_path.is_empty() {
+                self.file_path.print_single_lined(printer, 1);
+                printer.push_styled_text(Cow::Borrowed(":"), Style::new().bold());
+            }
+
+            printer.push_styled_text(
+                format!(
+                    "{}:{}{}",
+                    self.display_line(section.start.line),
+                    section.start.column,
+                    if self.inline { "" } else { ":" }
+                ),
+                Style::new().bold(),
+            );
+
+            if !self.inline && !message.is_empty() {
+                printer.push_plain_text(" ");
+                message.single_lined().print(printer);
+            }
+
+            // Code.
+            let line_start_cursor = section.start.start_line_cursor(&self.code);
+
+            printer.new_line();
+            printer.push_plain_text(match &self.code {
+                Cow::Borrowed(v) => Cow::Borrowed(line_start_cursor.slice_to_line_end(v)),
+                Cow::Owned(v) => Cow::Owned(line_start_cursor.slice_to_line_end(v).to_string()),
+            });
+
+            // Caret.
+            let caret_columns = if section.is_cursor() {
+                1
+            } else {
+                count_multiline_columns(
+                    section
+                        .start
+                        .slice(&self.code, &section.end)
+                        .trim_end_matches(['\r', '\n']),
+                )
+                .max(1)
+            };
+
+            printer.new_line();
+            printer.push_plain_text(build_space_string(count_columns(
+                section.start.slice_from_line_start(&self.code),
+            )));
+            printer.push_styled_text(
+                concatcp!(UP_POINTER).repeat(caret_columns),
+                Style::new().bold().fg(resolve_section_color(
+                    section,
+                    &printer.theme,
+                    palette_colors.as_ref(),
+                    level_color,
+                )),
+            );
+
+            if self.inline && !message.is_empty() {
+                printer.push_plain_text(" ");
+                message.single_lined().print(printer);
+            }
+        }
+
+        for related in &self.related {
+            related.print_compact(printer);
+        }
+    }
+
+    /// Prints a ruler marking the columns of the code, e.g. `----+----1----+`.
+    fn print_column_ruler(
+        &self,
+        printer: &mut Printer<'a>,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
+        let width = split_lines(&self.code)
+            .map(count_columns)
+            .max()
+            .unwrap_or(0);
+        let ruler: String = (1..=width)
+            .map(|column| match column {
+                v if v % 10 == 0 => char::from_digit((v / 10 % 10) as u32, 10).unwrap(),
+                v if v % 5 == 0 => '+',
+                _ => '-',
+            })
+            .collect();
+
+        printer.push_plain_text(build_whitespace_string(1, max_line_digits + 1));
+        printer.push_styled_text(
+            Cow::Owned(format!("{}    ", glyphs.gutter)),
+            Style::new().bold(),
+        );
+        printer.push_styled_text(ruler, Style::new().dim());
+    }
+
+    /// Prints the gutter of a code line followed by its marker, if any.
+    fn print_code_gutter(&self, printer: &mut Printer<'a>, line: usize, glyphs: &CodeBlockGlyphs) {
+        match self.line_markers.iter().find(|v| v.line == line) {
+            Some(marker) => {
+                printer.push_styled_text(format!("{} ", glyphs.gutter), Style::new().bold());
+                printer.push_styled_text(
+                    marker.marker.to_string(),
+                    Style::new().bold().fg(marker.color),
+                );
+                printer.push_plain_text(Cow::Borrowed("  "));
+            }
+            None => printer.push_styled_text(
+                Cow::Owned(format!("{}    ", glyphs.gutter)),
+                Style::new().bold(),
+            ),
+        }
+    }
+
+    /// Prints the line that starts at `line_start_cursor` without highlighting it.
+    fn print_plain_line(
+        &self,
+        printer: &mut Printer<'a>,
+        line_start_cursor: &Cursor,
+        max_line_digits: usize,
+        glyphs: &CodeBlockGlyphs,
+    ) {
+        printer.push_styled_text(
+            format!(
+                "\n{:>width$} ",
+                self.line_label(line_start_cursor.line, line_start_cursor.byte_offset),
+                width = max_line_digits
+            ),
+            printer.theme.gutter_style(),
+        );
+        self.print_code_gutter(printer, line_start_cursor.line, glyphs);
+        printer.push_plain_text({
+            if self.show_new_line_chars {
+                Cow::Owned(format!(
+                    "{}{NEW_LINE_LEFT}",
+                    line_start_cursor.slice_to_line_end(&self.code)
+                ))
+            } else {
+                match &self.code {
+                    Cow::Borrowed(v) => Cow::Borrowed(line_start_cursor.slice_to_line_end(v)),
+                    Cow::Owned(v) => Cow::Owned(line_start_cursor.slice_to_line_end(v).to_string()),
+                }
+            }
+        });
+    }
+
+    /// Renders the block line by line, so the lines of very large blocks can be written
+    /// as they are produced instead of being collected in a [Printer] first.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::blocks::CodeBlock;
+    /// use doclog::{LogLevel, PrinterFormat};
+    ///
+    /// let block = CodeBlock::new("let a = b;").highlight_section(8..9, None);
+    /// let mut output = Vec::new();
+    ///
+    /// for line in block.render_lines(LogLevel::error()) {
+    ///     line.write_to(&mut output, PrinterFormat::Plain).unwrap();
+    ///     output.push(b'\n');
+    /// }
+    /// ```
+    pub fn render_lines<'s>(&'s self, level: LogLevel) -> CodeBlockLines<'s, 'a> {
+        CodeBlockLines::new(self, level)
     }
 
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
@@ -841,10 +2338,30 @@ impl<'a> CodeBlock<'a> {
             final_message: self.final_message.make_owned(),
             show_new_line_chars: self.show_new_line_chars,
             secondary_color: self.secondary_color,
+            fixed_secondary_color: self.fixed_secondary_color,
+            section_palette: self.section_palette,
             previous_lines: self.previous_lines,
             next_lines: self.next_lines,
             middle_lines: self.middle_lines,
+            max_section_lines: self.max_section_lines,
+            max_inline_messages: self.max_inline_messages,
+            max_sections: self.max_sections,
+            show_column_ruler: self.show_column_ruler,
+            compact: self.compact,
+            inline: self.inline,
+            style: self.style,
             align_messages: self.align_messages,
+            first_line_number: self.first_line_number,
+            line_number_formatter: self.line_number_formatter,
+            notes: self.notes.into_iter().map(|v| v.make_owned()).collect(),
+            line_markers: self.line_markers,
+            suggestions: self
+                .suggestions
+                .into_iter()
+                .map(|v| v.make_owned())
+                .collect(),
+            related: self.related.into_iter().map(|v| v.make_owned()).collect(),
+            line_index: self.line_index,
         }
     }
 }
@@ -854,7 +2371,7 @@ impl<'a> Printable<'a> for CodeBlock<'a> {
     where
         'a: 's,
     {
-        let max_line_digits = format!("{}", self.max_line()).len();
+        let max_line_digits = self.line_number_width();
 
         self.print_with_options(printer, max_line_digits)
     }
@@ -872,6 +2389,34 @@ impl<'a> Display for CodeBlock<'a> {
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 
+/// Unwraps the result of a fallible highlight method, panicking with the error message.
+fn unwrap_highlight<T>(result: Result<T, HighlightError>) -> T {
+    result.unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Returns whether two highlighted ranges collide, including two equal cursors.
+fn ranges_collide(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a == b || (a.start < b.end && b.start < a.end)
+}
+
+/// Returns the color of a section: its own color or level, its color in the palette or,
+/// otherwise, `default_color`.
+fn resolve_section_color(
+    section: &CodeSection,
+    theme: &Theme,
+    palette_colors: Option<&HashMap<(usize, usize), Color>>,
+    default_color: Color,
+) -> Color {
+    section
+        .resolve_color(theme)
+        .or_else(|| {
+            palette_colors?
+                .get(&(section.start.byte_offset, section.end.byte_offset))
+                .copied()
+        })
+        .unwrap_or(default_color)
+}
+
 // This method will panic if sections is empty.
 fn group_sections_in_same_line<'s, 'a>(
     sections: &mut &'s [CodeSection<'a>],
@@ -1118,6 +2663,787 @@ mod tests {
         assert_eq!(text, " × This is\n   a title\n   ╭─[This is a file path]\n 2 │    Line 2↩\n 3 │    L·i·ne 3·↩\n   │    ││││├──╯│╰── This is\n   │    │││││   │    a message\n   │    │││││   ╰─── This is\n   │    │││││        a message\n   │    ││││╰─────── This is\n   │    ││││         a message\n   │    │││╰──────── This is\n   │    │││          a message\n   │    ││╰───────── This is\n   │    ││           a message\n   │    │╰────────── This is\n   │    │            a message\n   │    ╰─────────── This is\n   │                 a message\n  ···    \n 6 │    Line 6↩\n   │     ╰───┴── This is\n   │             a message\n 7 │    Line 6↩\n 8 │    Line 8↩\n   │       ╰────▶\n 9 │    Li·n·e 9↩\n   │  ▶─┬╯^ ^\n   │    ╰── This is\n   │        a message\n10 │    Line 10\n   ╰─ This is\n      a message");
     }
 
+    #[test]
+    fn test_narrow_widths() {
+        let code = "let value = compute(1, 2, 3);\nother line here";
+        let log = CodeBlock::new(code)
+            .highlight_section(4..9, None)
+            .highlight_section_message(12..19, None, "call");
+
+        // Narrower than the gutter.
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(3));
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "× ╭─\n1 │    l…\n  │     …\n  ╰─");
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(0));
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "× ╭─\n1 │    l…\n  │     …\n  ╰─");
+
+        // Wider than the gutter.
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(20));
+        log.print(&mut printer);
+
+        assert_eq!(
+            printer.to_string(),
+            "× ╭─\n1 │    let value = …\n  │        ╰───╯   …\n  ╰─"
+        );
+    }
+
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_graphemes() {
+        let code = "let e\u{301}moji = \"👩\u{200d}💻\";";
+        let start = code.find('👩').unwrap();
+        let log = CodeBlock::new(code)
+            .highlight_section(4..11, None)
+            .highlight_section_message(start..start + "👩\u{200d}💻".len(), None, "emoji");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let e\u{301}moji = \"👩\u{200d}💻\";\n  │        ╰───╯    ╰── emoji\n  ╰─"
+        );
+    }
+
+    #[test]
+    fn test_file_path_max_width() {
+        let log = CodeBlock::new("let value = 1;")
+            .file_path("src/very/long/path/main.rs")
+            .highlight_section(4..9, None);
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(16));
+        log.print(&mut printer);
+
+        assert_eq!(
+            printer.to_string(),
+            "× ╭─[src/very/…]\n1 │    let valu…\n  │        ╰───╯\n  ╰─"
+        );
+    }
+
+    #[test]
+    fn test_annotate_line() {
+        let log = CodeBlock::new("let a = 1;\nlet b = 2;\nlet c = 3;")
+            .highlight_section(15..16, None)
+            .previous_lines(1)
+            .next_lines(1)
+            .annotate_line(1, '-', Color::Red)
+            .annotate_line(2, '●', Color::Yellow)
+            .annotate_line(2, '+', Color::Green);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │ -  let a = 1;\n2 │ +  let b = 2;\n  │        ^\n3 │    let c = 3;\n  ╰─"
+        );
+    }
+
+    #[test]
+    fn test_carriage_returns() {
+        let log = CodeBlock::new("let a = 1;\r\nlet b = 2;\rlet c = 3;\r\n")
+            .highlight_section_message(4..5, None, "a")
+            .highlight_section_message(16..17, None, "b")
+            .highlight_section_message(27..28, None, "c");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "× ╭─\n1 │    let a = 1;\n  │        ╰── a\n2 │    let b = 2;\n  │        ╰── b\n3 │    let c = 3;\n  │        ╰── c\n  ╰─");
+
+        let log = CodeBlock::new("let a = 1;\r\nlet b = 2;")
+            .highlight_section(9..12, None)
+            .show_new_line_chars(true);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "× ╭─\n1 │    let a = 1;↩\n  │             ╰╯\n  ╰─");
+    }
+
+    #[test]
+    fn test_only_new_lines() {
+        let log = CodeBlock::new("\n\n\n")
+            .highlight_section(1..2, None)
+            .highlight_cursor(3, None)
+            .final_message("message");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n2 │    \n  │    ^\n ···    \n4 │    ·\n  │    ^\n  ╰─ message"
+        );
+    }
+
+    #[test]
+    fn test_max_section_lines() {
+        let code = (1..=12)
+            .map(|v| format!("Line {}", v))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let log = CodeBlock::new(code.as_str())
+            .previous_lines(0)
+            .next_lines(0)
+            .middle_lines(0)
+            .max_section_lines(1)
+            .highlight_section_message(9..75, None, "Message");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, " × ╭─\n 2 │    Line 2\n   │      ╰─────▶\n 3 │    Line 3\n  ··· (6 lines hidden)\n10 │    Line 10\n11 │    Line 11\n   │  ▶────┴── Message\n   ╰─");
+
+        // Short sections are not folded.
+        let log = log.max_section_lines(5);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert!(!text.contains("hidden"));
+        assert!(text.contains(" 7 │    Line 7"));
+
+        // A single line is not folded.
+        let log = CodeBlock::new(code.as_str())
+            .previous_lines(0)
+            .next_lines(0)
+            .middle_lines(0)
+            .max_section_lines(3)
+            .highlight_section_message(9..code.find("Line 10").unwrap() + 4, None, "Message");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert!(!text.contains("hidden"));
+        assert!(text.contains(" 6 │    Line 6"));
+        assert_eq!(text.lines().count(), 13);
+    }
+
+    #[test]
+    fn test_highlight_line_column() {
+        let code = "let a = 1;\nlet ñb = c;";
+        let log = CodeBlock::new(code)
+            .previous_lines(0)
+            .next_lines(0)
+            .show_column_ruler(true)
+            .highlight_line_column(2, 10, "Undefined");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(log.get_sections()[0].start.byte_offset, 21);
+        assert_eq!(
+            text,
+            "× ╭─\n  │    ----+----1-\n2 │    let ñb = ·c;\n  │             ╰── Undefined\n  ╰─"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The position 1:7 is outside the code")]
+    fn test_highlight_line_column_out_of_bounds() {
+        CodeBlock::new("let a").highlight_line_column(1, 7, "Message");
+    }
+
+    #[test]
+    fn test_compact() {
+        let code = "let a = b;\nlet c = d;\nlet e = f;";
+        let log = CodeBlock::new(code)
+            .title("Undefined variable")
+            .file_path("src/main.rs")
+            .compact(true)
+            .highlight_section(8..9, None)
+            .highlight_section_message(19..30, None, "Invalid multiline")
+            .note("Never printed");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "src/main.rs:1:9: Undefined variable\nlet a = b;\n        ^\nsrc/main.rs:2:9: Invalid multiline\nlet c = d;\n        ^^"
+        );
+
+        let log = CodeBlock::new(code).compact(true).highlight_cursor(0, None);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "1:1:\nlet a = b;\n^");
+    }
+
+    #[test]
+    fn test_inline() {
+        let log = CodeBlock::inline("let a = b;", 8..9, "Undefined variable");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "--> 1:9\nlet a = b;\n        ^ Undefined variable");
+
+        let log = CodeBlock::inline("let abc = d;", 4..7, "Unused")
+            .file_path("src/main.rs")
+            .first_line_number(12)
+            .highlight_section(10..11, None);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "--> src/main.rs:12:5\nlet abc = d;\n    ^^^ Unused\n--> src/main.rs:12:11\nlet abc = d;\n          ^"
+        );
+    }
+
+    #[test]
+    fn test_styles() {
+        let code = "let a = b;\nlet abc = d;";
+        let log = CodeBlock::new(code)
+            .file_path("src/main.rs")
+            .highlight_section_message(8..9, None, "Undefined")
+            .highlight_section_message(15..18, None, "Unused");
+
+        let text = log
+            .clone()
+            .style(CodeBlockStyle::Rustc)
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+        assert_eq!(
+            text,
+            "× --> src/main.rs\n1 |    let a = b;\n  |            ^ Undefined\n2 |    let abc = d;\n  |        ^^^ Unused\n  |"
+        );
+
+        let text = CodeBlock::new(code)
+            .style(CodeBlockStyle::Rustc)
+            .highlight_section_message(4..16, None, "Multiline")
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+        assert_eq!(
+            text,
+            "× -->\n1 |    let a = b;\n  |        ^________\n2 |    let abc = d;\n  |  ______^ Multiline\n  |"
+        );
+
+        let text = log
+            .style(CodeBlockStyle::Ariadne)
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+        assert_eq!(
+            text,
+            "× ╭─[src/main.rs]\n1 │    let a = b;\n  │            ╰── Undefined\n2 │    let abc = d;\n  │        ──┴── Unused\n  ╰─"
+        );
+    }
+
+    #[test]
+    fn test_highlight_section_styled() {
+        let code = "let a = foo(b);";
+        let log = CodeBlock::new(code).highlight_section_styled(
+            8..14,
+            vec![
+                (8..11, Style::new().italic()),
+                (13..20, Style::new().bold()),
+            ],
+            "Call",
+        );
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+        let expected = CodeBlock::new(code)
+            .highlight_section_message(8..14, None, "Call")
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, expected);
+
+        let line = log.render_lines(LogLevel::error()).nth(1).unwrap();
+        let sections: Vec<_> = line
+            .sections
+            .iter()
+            .map(|v| (v.text.as_ref(), v.style))
+            .filter(|(text, _)| ["foo", "(b", ")"].contains(text))
+            .collect();
+        let base_style = sections[1].1;
+
+        assert_eq!(
+            sections,
+            vec![
+                ("foo", Style::new().italic()),
+                ("(b", base_style),
+                (")", Style::new().bold())
+            ]
+        );
+        assert_ne!(base_style, Style::new().italic());
+    }
+
+    #[test]
+    fn test_max_inline_messages() {
+        let code = "let a = b + c + d;";
+        let log = CodeBlock::new(code)
+            .highlight_section_message(4..5, None, "A")
+            .highlight_section_message(8..9, None, "B")
+            .highlight_section_message(12..13, None, "C")
+            .highlight_section_message(16..17, None, "D")
+            .section_priority(12..13, 1)
+            .max_inline_messages(2);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let a = b + c + d;\n  │        │   ^   │   ^\n  │        │       ╰── C\n  │        ╰── A\n  │    ··· and 2 more labels\n  ╰─"
+        );
+
+        let log = log.max_inline_messages(4);
+        let expected = log.clone().max_inline_messages(10);
+
+        assert_eq!(
+            log.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            expected.print_to_string(LogLevel::error(), PrinterFormat::Plain)
+        );
+    }
+
+    #[test]
+    fn test_max_sections() {
+        let code = "let a = b + c + d;\nlet e = f;\nlet g = h;";
+        let log = CodeBlock::new(code)
+            .highlight_section(4..5, None)
+            .highlight_section(8..9, None)
+            .highlight_section(16..22, None)
+            .highlight_section(34..35, None)
+            .section_priority(34..35, 1)
+            .max_sections(2);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let a = b + c + d;\n  │        ^\n ···    \n3 │    let g = h;\n  │        ^\n  ╰─\n  = note: … and 2 more spans omitted"
+        );
+
+        // Multiline sections count as one span.
+        let log = log.section_priority(16..22, 1).max_sections(3);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let a = b + c + d;\n  │        ^           ╰───▶\n2 │    let e = f;\n  │  ▶───╯\n3 │    let g = h;\n  │        ^\n  ╰─\n  = note: … and 1 more span omitted"
+        );
+
+        let log = log.max_sections(4);
+        let expected = CodeBlock {
+            max_sections: None,
+            ..log.clone()
+        };
+
+        assert_eq!(
+            log.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            expected.print_to_string(LogLevel::error(), PrinterFormat::Plain)
+        );
+    }
+
+    #[test]
+    fn test_section_palette() {
+        let code = "let a = b + c;\nlet d = a;";
+        let log = CodeBlock::new(code)
+            .highlight_section_message(4..5, None, "Variable")
+            .highlight_section(8..9, None)
+            .highlight_section(12..13, Some(Color::Green))
+            .highlight_section_message(23..24, None, "Variable")
+            .section_palette(vec![Color::Blue, Color::Magenta]);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+
+        // Custom colors are kept, and labels with the same text get the same color.
+        assert!(text.contains(
+            "let \u{1b}[1;34ma\u{1b}[0m = \u{1b}[1;35mb\u{1b}[0m + \u{1b}[1;32mc\u{1b}[0m;"
+        ));
+        assert!(text.contains("let d = \u{1b}[1;34ma\u{1b}[0m;"));
+        assert!(!text.contains("\u{1b}[1;31ma"));
+
+        // The palette cycles.
+        let colors = log.palette_colors().unwrap();
+        assert_eq!(colors.get(&(4, 5)), Some(&Color::Blue));
+        assert_eq!(colors.get(&(8, 9)), Some(&Color::Magenta));
+        assert_eq!(colors.get(&(12, 13)), Some(&Color::Blue));
+        assert_eq!(colors.get(&(23, 24)), Some(&Color::Blue));
+
+        // An empty palette alternates the colors.
+        assert!(log.section_palette(Vec::new()).palette_colors().is_none());
+    }
+
+    #[test]
+    fn test_resolved_sections() {
+        let code = "let a = b + c;\nlet d = a;";
+        let log = CodeBlock::new(code)
+            .highlight_section_message(4..5, None, "Variable")
+            .highlight_section(8..9, None)
+            .highlight_section(12..13, Some(Color::Green))
+            .highlight_section_message(19..24, None, "Value");
+        let level_color = Theme::global().level_color(LogLevel::error());
+        let secondary_color = Theme::global().secondary;
+
+        assert_eq!(
+            log.resolved_sections(LogLevel::error()),
+            vec![
+                ResolvedSection {
+                    bytes: 4..5,
+                    start_line: 1,
+                    start_column: 5,
+                    end_line: 1,
+                    end_column: 6,
+                    color: level_color,
+                    message: Some("Variable".to_string()),
+                    is_multiline_start: false,
+                    is_multiline_end: false,
+                },
+                ResolvedSection {
+                    bytes: 8..9,
+                    start_line: 1,
+                    start_column: 9,
+                    end_line: 1,
+                    end_column: 10,
+                    color: secondary_color,
+                    message: None,
+                    is_multiline_start: false,
+                    is_multiline_end: false,
+                },
+                ResolvedSection {
+                    bytes: 12..13,
+                    start_line: 1,
+                    start_column: 13,
+                    end_line: 1,
+                    end_column: 14,
+                    color: Color::Green,
+                    message: None,
+                    is_multiline_start: false,
+                    is_multiline_end: false,
+                },
+                ResolvedSection {
+                    bytes: 19..24,
+                    start_line: 2,
+                    start_column: 5,
+                    end_line: 2,
+                    end_column: 10,
+                    color: level_color,
+                    message: Some("Value".to_string()),
+                    is_multiline_start: false,
+                    is_multiline_end: false,
+                },
+            ]
+        );
+
+        // Multiline spans are split and the palette is applied.
+        let log = CodeBlock::new(code)
+            .highlight_section_message(8..19, None, "Span")
+            .section_palette(vec![Color::Blue]);
+
+        assert_eq!(
+            log.resolved_sections(LogLevel::error())
+                .into_iter()
+                .map(|v| (v.bytes, v.color, v.message, v.is_multiline_start))
+                .collect::<Vec<_>>(),
+            vec![
+                (8..15, Color::Blue, None, true),
+                (15..19, Color::Blue, Some("Span".to_string()), false),
+            ]
+        );
+
+        // Hidden sections are excluded.
+        let log = CodeBlock::new(code)
+            .highlight_section(4..5, None)
+            .highlight_section(8..9, None)
+            .max_sections(1);
+
+        assert_eq!(log.resolved_sections(LogLevel::error()).len(), 1);
+
+        // The colors are resolved with the given theme.
+        let log = CodeBlock::new(code)
+            .highlight_section(4..5, None)
+            .highlight_section(8..9, None);
+        let theme = Theme::light();
+
+        assert_eq!(
+            log.resolved_sections_with_theme(LogLevel::error(), &theme)
+                .into_iter()
+                .map(|v| v.color)
+                .collect::<Vec<_>>(),
+            vec![theme.error, theme.secondary]
+        );
+    }
+
+    #[test]
+    fn test_try_highlight_section() {
+        let code = "let ñ = b;";
+        let mut log = CodeBlock::new(code);
+        log.try_highlight_section(8..9, None).unwrap();
+
+        assert_eq!(
+            log.try_highlight_section(Range { start: 9, end: 8 }, None)
+                .unwrap_err(),
+            HighlightError::InvertedRange { start: 9, end: 8 }
+        );
+        assert_eq!(
+            log.try_highlight_cursor(20, None).unwrap_err(),
+            HighlightError::OutOfBounds {
+                end: 20,
+                code_length: 11
+            }
+        );
+        assert_eq!(
+            log.try_highlight_section_message(4..5, None, "Half char")
+                .unwrap_err(),
+            HighlightError::NotCharBoundary { index: 5 }
+        );
+        assert_eq!(
+            log.try_highlight_section_with_level(7..9, LogLevel::warn(), "Overlap")
+                .unwrap_err(),
+            HighlightError::Collision {
+                range: 7..9,
+                section: 8..9
+            }
+        );
+
+        // The block is kept after the errors.
+        assert_eq!(log.sections.len(), 1);
+
+        log.try_highlight_cursor(8, None).unwrap();
+        assert_eq!(
+            log.try_highlight_cursor_message(8, None, "Twice")
+                .unwrap_err(),
+            HighlightError::Collision {
+                range: 8..8,
+                section: 8..8
+            }
+        );
+        assert_eq!(
+            log.try_highlight_section_message(4..6, None, "Variable")
+                .unwrap()
+                .sections
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_try_methods() {
+        let mut log = CodeBlock::new("let a = b;\nlet c = d;");
+
+        assert_eq!(
+            log.try_highlight_line_column(3, 1, "Message").unwrap_err(),
+            HighlightError::PositionOutOfBounds { line: 3, column: 1 }
+        );
+        assert_eq!(
+            log.try_highlight_line_column(1, 12, "Message").unwrap_err(),
+            HighlightError::PositionOutOfBounds {
+                line: 1,
+                column: 12
+            }
+        );
+        assert_eq!(
+            log.try_section_priority(4..5, 1).unwrap_err(),
+            HighlightError::MissingSection { range: 4..5 }
+        );
+        assert_eq!(
+            log.try_suggest_replacement(8..30, "c", "Replace")
+                .unwrap_err(),
+            HighlightError::OutOfBounds {
+                end: 30,
+                code_length: 21
+            }
+        );
+        assert_eq!(
+            log.try_highlight_section_styled(4..25, Vec::new(), "Styled")
+                .unwrap_err(),
+            HighlightError::OutOfBounds {
+                end: 25,
+                code_length: 21
+            }
+        );
+        assert!(log.sections.is_empty());
+        assert!(log.get_suggestions().is_empty());
+
+        log.try_highlight_line_column(2, 5, "Variable")
+            .unwrap()
+            .try_suggest_replacement(8..9, "c", "Replace")
+            .unwrap()
+            .try_section_priority(8..9, 2)
+            .unwrap();
+
+        assert_eq!(log.sections.len(), 2);
+        assert_eq!(log.sections[0].priority, 2);
+        assert_eq!(log.sections[1].start.byte_offset, 15);
+        assert_eq!(log.get_suggestions().len(), 1);
+
+        assert_eq!(
+            CodeBlock::try_inline("let a", 4..9, "Message").unwrap_err(),
+            HighlightError::OutOfBounds {
+                end: 9,
+                code_length: 5
+            }
+        );
+        assert_eq!(
+            CodeBlock::try_with_labels("let a", [Label::new(2..7, "Message")]).unwrap_err(),
+            HighlightError::OutOfBounds {
+                end: 7,
+                code_length: 5
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Sections cannot collide with others: 7..9 collides with 8..9")]
+    fn test_highlight_section_collision() {
+        let _ = CodeBlock::new("let a = b;")
+            .highlight_section(8..9, None)
+            .highlight_section(7..9, None);
+    }
+
+    #[test]
+    fn test_with_labels() {
+        let code = "let a = b + c;\nlet d = a\n  + 1;";
+        let log = CodeBlock::with_labels(
+            code,
+            [
+                Label::new(19..30, "Multiline").priority(1),
+                Label::new(12..13, "Right").color(Color::Green),
+                Label::new(4..5, "Variable"),
+                Label::new(8..9, ""),
+                // Collides with the multiline label, which has a higher priority.
+                Label::new(23..24, "Discarded"),
+                // Collides with the previous label of equal priority.
+                Label::new(4..9, "Discarded"),
+            ],
+        );
+        let expected = CodeBlock::new(code)
+            .highlight_section_message(4..5, None, "Variable")
+            .highlight_section_message(8..9, None, "")
+            .highlight_section_message(12..13, Some(Color::Green), "Right")
+            .highlight_section_message(19..30, None, "Multiline")
+            .section_priority(19..30, 1);
+
+        assert_eq!(log.sections, expected.sections);
+        assert_eq!(
+            log.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            expected.print_to_string(LogLevel::error(), PrinterFormat::Plain)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The end index must be less or equal than the code length")]
+    fn test_with_labels_out_of_bounds() {
+        let _ = CodeBlock::with_labels("let a = b;", [Label::new(4..20, "Label")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "There is no section highlighted at the span")]
+    fn test_section_priority_without_section() {
+        let _ = CodeBlock::new("let a = b;")
+            .highlight_section(4..5, None)
+            .section_priority(8..9, 1);
+    }
+
+    #[test]
+    fn test_related() {
+        let log = CodeBlock::new("fn foo() {}")
+            .file_path("src/a.rs")
+            .highlight_section_message(3..6, None, "first defined here")
+            .related(
+                CodeBlock::new("\n\n\n\n\n\n\n\n\nfn foo() {}")
+                    .file_path("src/b.rs")
+                    .highlight_section_message(12..15, None, "duplicate here"),
+            )
+            .final_message("Duplicate function");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            " × ╭─[src/a.rs]\n 1 │    fn foo() {}\n   │       ╰─┴── first defined here\n   ├─[src/b.rs]\n10 │    fn foo() {}\n   │       ╰─┴── duplicate here\n   ╰─ Duplicate function"
+        );
+
+        let text = log
+            .style(CodeBlockStyle::Rustc)
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            " × --> src/a.rs\n 1 |    fn foo() {}\n   |       ^^^ first defined here\n   ::: src/b.rs\n10 |    fn foo() {}\n   |       ^^^ duplicate here\n   | Duplicate function"
+        );
+    }
+
+    #[test]
+    fn test_parse_context_lines() {
+        assert_eq!(parse_context_lines("3"), Some(3));
+        assert_eq!(parse_context_lines(" 10\n"), Some(10));
+        assert_eq!(parse_context_lines("-1"), None);
+        assert_eq!(parse_context_lines("three"), None);
+    }
+
+    #[test]
+    fn test_suggest_replacement() {
+        let code = "let a = b + c;\nlet d = a;";
+        let log = CodeBlock::new(code).suggest_replacement(8..9, "c", "Use c");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let a = b + c;\n  │            ╰── Use c\n1 +    let a = c + c;\n  ╰─"
+        );
+
+        let suggestions = log.get_suggestions();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].title, "Use c");
+        assert_eq!(suggestions[0].apply(code), "let a = c + c;\nlet d = a;");
+
+        // Located in the file.
+        let log = CodeBlock::new(code)
+            .file_path("src/x.rs")
+            .first_line_number(40)
+            .suggest_replacement(23..24, "b", "Use b");
+        let suggestions = log.get_suggestions();
+        assert_eq!(suggestions[0].uri, "src/x.rs");
+        assert_eq!(suggestions[0].start.line, 40);
+        assert_eq!(suggestions[0].end.line, 40);
+        assert_eq!(suggestions[0].apply(code), "let a = b + c;\nlet d = b;");
+
+        let content = crate::LogContent::new().add_block(log);
+        let log = crate::Log::error().add_block(crate::blocks::PrefixBlock::new().content(content));
+        assert_eq!(log.all_suggestions(), suggestions);
+        assert!(log
+            .suggestions_to_lsp_code_actions()
+            .contains("{\"src/x.rs\":[{\"range\":{\"start\":{\"line\":40,"));
+    }
+
+    #[test]
+    fn test_notes() {
+        let code = "let a = b + c;";
+        let log = CodeBlock::new(code)
+            .highlight_section(8..9, None)
+            .final_message("Message")
+            .note("This is\na note")
+            .help("This is a help")
+            .suggestion("This is a suggestion");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "× ╭─\n1 │    let a = b + c;\n  │            ^\n  ╰─ Message\n  = note: This is\n          a note\n  = help: This is a help\n  = suggestion: This is a suggestion"
+        );
+    }
+
+    #[test]
+    fn test_section_with_level() {
+        let code = "let a = b + c;";
+        let log = CodeBlock::new(code)
+            .highlight_section_message(8..9, None, "Error label")
+            .highlight_section_with_level(12..13, LogLevel::warn(), "Warning label");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(text, "\u{1b}[1;31m× \u{1b}[0m\u{1b}[1m╭─\n\u{1b}[0m\u{1b}[1;90m1 \u{1b}[0m\u{1b}[1m│    \u{1b}[0mlet a = \u{1b}[1;31mb\u{1b}[0m + \u{1b}[1;33mc\u{1b}[0m;\n  \u{1b}[1m│            \u{1b}[0m\u{1b}[1;31m│   \u{1b}[0m\u{1b}[1;33m╰── \u{1b}[0mWarning label\n  \u{1b}[1m│            \u{1b}[0m\u{1b}[1;31m╰── \u{1b}[0mError label\n  \u{1b}[1m╰─\u{1b}[0m");
+    }
+
+    #[test]
+    fn test_line_number_formatter() {
+        let code = "Line 1\nLine 2\nLine 3\nLine 4";
+        let log = CodeBlock::new(code)
+            .previous_lines(1)
+            .next_lines(1)
+            .line_number_formatter(|_, start_byte| format!("0x{:04x}", start_byte))
+            .highlight_section(14..18, None);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "     × ╭─\n0x0007 │    Line 2\n0x000e │    Line 3\n       │    ╰──╯\n0x0015 │    Line 4\n       ╰─"
+        );
+    }
+
+    #[test]
+    fn test_first_line_number() {
+        let code =
+            "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\nLine 6\nLine 7\nLine 8\nLine 9\nLine 10";
+
+        // Sections + previous_lines + next_lines
+        let log = CodeBlock::new(code)
+            // Line 3
+            .highlight_section(14..20, None)
+            // Line 6
+            .highlight_section(36..41, None)
+            .previous_lines(1)
+            .next_lines(1)
+            .first_line_number(98);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "  × ╭─\n 99 │    Line 2\n100 │    Line 3\n    │    ╰────╯\n   ···    \n103 │    Line 6\n    │     ╰───╯\n104 │    Line 7\n    ╰─");
+    }
+
     #[test]
     fn test_styled() {
         let code =
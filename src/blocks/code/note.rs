@@ -0,0 +1,54 @@
+use crate::blocks::TextBlock;
+
+/// The kind of a [CodeNote].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeNoteKind {
+    Note,
+    Help,
+    Suggestion,
+}
+
+impl CodeNoteKind {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the label printed before the text of the note.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            CodeNoteKind::Note => "note",
+            CodeNoteKind::Help => "help",
+            CodeNoteKind::Suggestion => "suggestion",
+        }
+    }
+}
+
+/// A note printed after the code of a [CodeBlock](crate::blocks::CodeBlock),
+/// e.g. `= help: ...`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeNote<'a> {
+    pub kind: CodeNoteKind,
+    pub text: TextBlock<'a>,
+}
+
+impl<'a> CodeNote<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [CodeNote].
+    pub fn new(kind: CodeNoteKind, text: impl Into<TextBlock<'a>>) -> Self {
+        Self {
+            kind,
+            text: text.into(),
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> CodeNote<'static> {
+        CodeNote {
+            kind: self.kind,
+            text: self.text.make_owned(),
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::ops::Range;
+use yansi::Color;
+
+/// A highlighted section of a [CodeBlock](crate::blocks::CodeBlock) with the color it is
+/// printed with, see [CodeBlock::resolved_sections](crate::blocks::CodeBlock::resolved_sections).
+///
+/// A multiline span is printed in two parts, the start and the end of the span, so it is
+/// resolved into two sections, each one with its own color.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ResolvedSection {
+    /// The byte range inside the code.
+    pub bytes: Range<usize>,
+
+    /// The 1-based line of the start.
+    pub start_line: usize,
+
+    /// The 1-based column of the start.
+    pub start_column: usize,
+
+    /// The 1-based line of the end.
+    pub end_line: usize,
+
+    /// The 1-based column of the end, exclusive.
+    pub end_column: usize,
+
+    /// The color of the section and its message.
+    pub color: Color,
+
+    /// The message of the section as plain text, if any.
+    pub message: Option<String>,
+
+    /// Whether this section is the start of a multiline span.
+    pub is_multiline_start: bool,
+
+    /// Whether this section is the end of a multiline span.
+    pub is_multiline_end: bool,
+}
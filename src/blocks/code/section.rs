@@ -1,13 +1,14 @@
+use crate::blocks::code::style::CodeBlockGlyphs;
 use crate::blocks::code::CodeBlock;
 use crate::blocks::TextBlock;
-use crate::constants::{
-    HORIZONTAL_BAR, HORIZONTAL_BOTTOM_BAR, HORIZONTAL_TOP_BAR, MIDDLE_DOT, NEW_LINE_LEFT,
-    RIGHT_ARROW, TOP_LEFT_CORNER, TOP_RIGHT_CORNER, UP_POINTER, VERTICAL_BAR, VERTICAL_RIGHT_BAR,
-};
+use crate::constants::{MIDDLE_DOT, NEW_LINE_LEFT};
 use crate::printer::Printer;
 use crate::utils::cursor::Cursor;
+use crate::utils::text::slice_cow;
+use crate::{LogLevel, Theme};
 use const_format::concatcp;
 use std::borrow::Cow;
+use std::ops::Range;
 use yansi::{Color, Style};
 
 /// A highlighted code section in a code block.
@@ -18,6 +19,10 @@ pub struct CodeSection<'a> {
     pub(crate) end: Cursor,
     pub(crate) message: TextBlock<'a>,
     pub(crate) color: Option<Color>,
+    pub(crate) level: Option<LogLevel>,
+    /// The sub-spans printed with their own style, sorted by their start.
+    pub(crate) styles: Vec<(Range<usize>, Style)>,
+    pub(crate) priority: u8,
     pub(crate) is_multiline_start: bool,
     pub(crate) is_multiline_end: bool,
 }
@@ -34,11 +39,22 @@ impl<'a> CodeSection<'a> {
         }
     }
 
+    /// Returns the priority of the section, see [CodeBlock::section_priority].
+    #[inline(always)]
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+
     /// Returns whether this section is a cursor.
     pub fn is_cursor(&self) -> bool {
         self.start == self.end
     }
 
+    /// Returns the color of the section in `theme`, if it has one.
+    pub(crate) fn resolve_color(&self, theme: &Theme) -> Option<Color> {
+        self.color.or(self.level.map(|v| theme.level_color(v)))
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Prints the actual code of the section.
@@ -51,50 +67,69 @@ impl<'a> CodeSection<'a> {
         if self.is_cursor() {
             printer.push_styled_text(concatcp!(MIDDLE_DOT), Style::new().bold().fg(next_color))
         } else {
-            let content = match &block.code {
-                Cow::Borrowed(code) => {
-                    if !block.show_new_line_chars {
-                        Cow::Borrowed(self.start.slice(code, &self.end).trim_end_matches('\n'))
-                    } else {
-                        Cow::Owned(
-                            self.start
-                                .slice(code, &self.end)
-                                .replace('\n', concatcp!(NEW_LINE_LEFT)),
-                        )
-                    }
+            let base_style = Style::new().bold().fg(next_color);
+            let end = self.end.byte_offset;
+            let mut start = self.start.byte_offset;
+
+            for (range, style) in &self.styles {
+                let range = range.start.max(start)..range.end.min(end);
+
+                if range.is_empty() {
+                    continue;
                 }
-                Cow::Owned(code) => {
-                    if !block.show_new_line_chars {
-                        Cow::Owned(
-                            self.start
-                                .slice(code, &self.end)
-                                .trim_end_matches('\n')
-                                .to_string(),
-                        )
-                    } else {
-                        Cow::Owned(
-                            self.start
-                                .slice(code, &self.end)
-                                .replace('\n', concatcp!(NEW_LINE_LEFT)),
-                        )
-                    }
+
+                if start < range.start {
+                    printer
+                        .push_styled_text(self.slice_code(block, start..range.start), base_style);
                 }
-            };
 
-            printer.push_styled_text(content, Style::new().bold().fg(next_color))
+                printer.push_styled_text(self.slice_code(block, range.clone()), *style);
+                start = range.end;
+            }
+
+            if start < end {
+                printer.push_styled_text(self.slice_code(block, start..end), base_style);
+            }
         }
     }
 
-    /// Prints the actual code of the section.
-    pub(crate) fn print_underline(&self, printer: &mut Printer<'a>, next_color: Color) {
+    /// Returns the code in `range` as it must be printed.
+    fn slice_code(&self, block: &CodeBlock<'a>, range: Range<usize>) -> Cow<'a, str> {
+        let content = slice_cow(&block.code, range);
+
+        if block.show_new_line_chars {
+            Cow::Owned(
+                content
+                    .replace("\r\n", "\n")
+                    .replace(['\r', '\n'], concatcp!(NEW_LINE_LEFT)),
+            )
+        } else {
+            match content {
+                Cow::Borrowed(v) => Cow::Borrowed(v.trim_end_matches(['\r', '\n'])),
+                Cow::Owned(v) => Cow::Owned(v.trim_end_matches(['\r', '\n']).to_string()),
+            }
+        }
+    }
+
+    /// Prints the underline of the section.
+    pub(crate) fn print_underline(
+        &self,
+        printer: &mut Printer<'a>,
+        glyphs: &CodeBlockGlyphs,
+        next_color: Color,
+    ) {
+        let style = Style::new().bold().fg(next_color);
+
         // Print start multiline connection.
         if self.is_multiline_start {
             printer.push_styled_text(
                 format!(
-                    "{TOP_RIGHT_CORNER}{}{RIGHT_ARROW}",
-                    concatcp!(HORIZONTAL_BAR).repeat(self.char_len())
+                    "{}{}{}",
+                    glyphs.underline_start,
+                    repeat_char(glyphs.multiline_underline, self.char_len()),
+                    glyphs.arrow
                 ),
-                Style::new().bold().fg(next_color),
+                style,
             );
             return;
         }
@@ -104,18 +139,27 @@ impl<'a> CodeSection<'a> {
             if self.message.is_empty() {
                 printer.push_styled_text(
                     format!(
-                        "{RIGHT_ARROW}{}{TOP_LEFT_CORNER}",
-                        concatcp!(HORIZONTAL_BAR).repeat(self.char_len())
+                        "{}{}{}",
+                        glyphs.arrow,
+                        repeat_char(glyphs.multiline_underline, self.char_len()),
+                        glyphs.underline_end
                     ),
-                    Style::new().bold().fg(next_color),
+                    style,
                 );
             } else {
                 printer.push_styled_text(
                     format!(
-                        "{RIGHT_ARROW}{HORIZONTAL_BAR}{HORIZONTAL_BOTTOM_BAR}{}{TOP_LEFT_CORNER}",
-                        concatcp!(HORIZONTAL_BAR).repeat(self.char_len().saturating_sub(2))
+                        "{}{}{}{}{}",
+                        glyphs.arrow,
+                        glyphs.multiline_underline,
+                        glyphs.junction_down,
+                        repeat_char(
+                            glyphs.multiline_underline,
+                            self.char_len().saturating_sub(2)
+                        ),
+                        glyphs.underline_end
                     ),
-                    Style::new().bold().fg(next_color),
+                    style,
                 );
             }
             return;
@@ -124,10 +168,9 @@ impl<'a> CodeSection<'a> {
         // Print single character.
         if self.char_len() == 1 {
             if self.message.is_empty() {
-                printer.push_styled_text(concatcp!(UP_POINTER), Style::new().bold().fg(next_color));
+                printer.push_styled_text(glyphs.pointer.to_string(), style);
             } else {
-                printer
-                    .push_styled_text(concatcp!(VERTICAL_BAR), Style::new().bold().fg(next_color));
+                printer.push_styled_text(glyphs.label_pointer.to_string(), style);
             }
 
             return;
@@ -136,24 +179,28 @@ impl<'a> CodeSection<'a> {
         // Print multiple characters.
         printer.push_styled_text(
             format!(
-                "{}{}{TOP_LEFT_CORNER}",
+                "{}{}{}",
                 if self.message.is_empty() {
-                    TOP_RIGHT_CORNER
+                    glyphs.underline_start
                 } else {
-                    VERTICAL_RIGHT_BAR
+                    glyphs.label_start
                 },
-                concatcp!(HORIZONTAL_BAR).repeat(self.char_len() - 2)
+                repeat_char(glyphs.underline, self.char_len() - 2),
+                glyphs.underline_end
             ),
-            Style::new().bold().fg(next_color),
+            style,
         );
     }
 
-    /// Prints the actual code of the section.
+    /// Prints the underline of the section followed by the start of its message.
     pub(crate) fn print_underline_with_message(
         &self,
         printer: &mut Printer<'a>,
+        glyphs: &CodeBlockGlyphs,
         next_color: Color,
     ) {
+        let style = Style::new().bold().fg(next_color);
+
         // Print start multiline connection.
         if self.is_multiline_start {
             panic!("Multiline start not supported with message.");
@@ -163,10 +210,13 @@ impl<'a> CodeSection<'a> {
         if self.is_multiline_end {
             printer.push_styled_text(
                 format!(
-                    "{RIGHT_ARROW}{}{HORIZONTAL_TOP_BAR}{HORIZONTAL_BAR}{HORIZONTAL_BAR} ",
-                    concatcp!(HORIZONTAL_BAR).repeat(self.char_len())
+                    "{}{}{}{} ",
+                    glyphs.arrow,
+                    repeat_char(glyphs.multiline_underline, self.char_len()),
+                    glyphs.junction_up,
+                    repeat_char(glyphs.message_bar, glyphs.message_bar_width)
                 ),
-                Style::new().bold().fg(next_color),
+                style,
             );
             return;
         }
@@ -174,8 +224,12 @@ impl<'a> CodeSection<'a> {
         // Print single character.
         if self.char_len() == 1 {
             printer.push_styled_text(
-                concatcp!(TOP_RIGHT_CORNER, HORIZONTAL_BAR, HORIZONTAL_BAR, ' '),
-                Style::new().bold().fg(next_color),
+                format!(
+                    "{}{} ",
+                    glyphs.inline_pointer,
+                    repeat_char(glyphs.message_bar, glyphs.message_bar_width)
+                ),
+                style,
             );
             return;
         }
@@ -183,10 +237,13 @@ impl<'a> CodeSection<'a> {
         // Print multiple characters.
         printer.push_styled_text(
             format!(
-                "{TOP_RIGHT_CORNER}{}{HORIZONTAL_TOP_BAR}{HORIZONTAL_BAR}{HORIZONTAL_BAR} ",
-                concatcp!(HORIZONTAL_BAR).repeat(self.char_len() - 2)
+                "{}{}{}{} ",
+                glyphs.underline_start,
+                repeat_char(glyphs.underline, self.char_len() - 2),
+                glyphs.junction_up,
+                repeat_char(glyphs.message_bar, glyphs.message_bar_width)
             ),
-            Style::new().bold().fg(next_color),
+            style,
         );
     }
 
@@ -197,8 +254,16 @@ impl<'a> CodeSection<'a> {
             end: self.end,
             message: self.message.make_owned(),
             color: self.color,
+            level: self.level,
+            styles: self.styles,
+            priority: self.priority,
             is_multiline_start: self.is_multiline_start,
             is_multiline_end: self.is_multiline_end,
         }
     }
 }
+
+/// Builds a string with `character` repeated `count` times.
+fn repeat_char(character: char, count: usize) -> String {
+    std::iter::repeat_n(character, count).collect()
+}
@@ -0,0 +1,214 @@
+//! The serde representation of [CodeBlock]s. The highlighted sections are stored by their
+//! byte range instead of their resolved positions, and they are highlighted again when the
+//! block is deserialized, so invalid spans are rejected instead of panicking when printed.
+
+use crate::blocks::code::{CodeBlock, CodeSection};
+use crate::blocks::TextBlock;
+use crate::utils::cursor::Cursor;
+use crate::LogLevel;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::Range;
+use yansi::{Color, Style};
+
+/// The representation of a highlighted span, i.e. a section or both parts of a multiline
+/// one.
+#[derive(Serialize, Deserialize)]
+struct CodeSpanRepr<'a> {
+    range: Range<usize>,
+    #[serde(default, skip_serializing_if = "TextBlock::is_empty")]
+    message: TextBlock<'a>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::utils::serialization::option_color"
+    )]
+    color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level: Option<LogLevel>,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::utils::serialization::range_styles"
+    )]
+    styles: Vec<(Range<usize>, Style)>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    priority: u8,
+}
+
+impl<'a> Serialize for CodeBlock<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CodeBlock::serialize(self, serializer)
+    }
+}
+
+impl<'de, 'a> Deserialize<'de> for CodeBlock<'a> {
+    /// Deserializes a [CodeBlock], highlighting its spans again and checking that its
+    /// suggestions are inside the code.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut block = CodeBlock::deserialize(deserializer)?;
+        let spans = std::mem::take(&mut block.sections);
+
+        for span in spans {
+            let range = span.start.byte_offset..span.end.byte_offset;
+
+            block
+                .try_highlight_section_inner(
+                    range.clone(),
+                    Some(span.message),
+                    span.color,
+                    span.level,
+                    span.styles,
+                )
+                .map_err(D::Error::custom)?;
+
+            if span.priority != 0 {
+                block
+                    .try_section_priority(range, span.priority)
+                    .map_err(D::Error::custom)?;
+            }
+        }
+
+        for suggestion in &block.suggestions {
+            block
+                .check_range(&suggestion.byte_range)
+                .map_err(D::Error::custom)?;
+        }
+
+        Ok(block)
+    }
+}
+
+/// Serializes the sections of a [CodeBlock] as spans, merging the parts of the multiline
+/// ones. The deserialized sections only have the byte offsets of their spans and must be
+/// highlighted again, see the [Deserialize] implementation of [CodeBlock].
+pub(super) mod sections {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        sections: &[CodeSection],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut spans = Vec::with_capacity(sections.len());
+        let mut multiline_starts = Vec::new();
+
+        for section in sections {
+            // Multiline sections are nested, so the end part always belongs to the last
+            // unfinished start part.
+            if section.is_multiline_end {
+                if let Some(index) = multiline_starts.pop() {
+                    let span: &mut CodeSpanRepr = &mut spans[index];
+                    span.range.end = section.end.byte_offset;
+                    span.message = section.message.clone();
+                    continue;
+                }
+            }
+
+            if section.is_multiline_start {
+                multiline_starts.push(spans.len());
+            }
+
+            spans.push(CodeSpanRepr {
+                range: section.start.byte_offset..section.end.byte_offset,
+                message: section.message.clone(),
+                color: section.color,
+                level: section.level,
+                styles: section.styles.clone(),
+                priority: section.priority,
+            });
+        }
+
+        spans.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<CodeSection<'a>>, D::Error> {
+        let cursor = |byte_offset| Cursor {
+            byte_offset,
+            char_offset: 0,
+            line: 0,
+            column: 0,
+        };
+
+        Ok(Vec::<CodeSpanRepr<'a>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|span| CodeSection {
+                start: cursor(span.range.start),
+                end: cursor(span.range.end),
+                message: span.message,
+                color: span.color,
+                level: span.level,
+                styles: span.styles,
+                priority: span.priority,
+                is_multiline_start: false,
+                is_multiline_end: false,
+            })
+            .collect())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Returns whether `value` is zero, to skip default priorities.
+fn is_zero(value: &u8) -> bool {
+    *value == 0
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::{Printable, PrinterFormat};
+
+    #[test]
+    fn test_serde() {
+        let block = CodeBlock::new("let a = b;\nlet c = {\n    d\n};")
+            .highlight_section_message(8..9, Some(Color::Cyan), "Undefined")
+            .highlight_section_message(19..29, None, "Block")
+            .highlight_section_styled(4..5, vec![(4..5, Style::new().italic())], "Variable")
+            .section_priority(8..9, 2)
+            .suggest_replacement(25..26, "e", "Use e")
+            .title("Errors");
+
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(!json.contains("char_offset"), "{json}");
+
+        let deserialized: CodeBlock = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.get_sections(), block.get_sections());
+        assert_eq!(
+            deserialized.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            block.print_to_string(LogLevel::error(), PrinterFormat::Plain)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_invalid_spans() {
+        let json =
+            serde_json::to_string(&CodeBlock::new("let a = b;").highlight_section(8..9, None))
+                .unwrap();
+
+        for (from, to) in [
+            (r#"{"start":8,"end":9}"#, r#"{"start":8,"end":20}"#),
+            (r#"{"start":8,"end":9}"#, r#"{"start":9,"end":8}"#),
+        ] {
+            let json = json.replacen(from, to, 1);
+            assert!(serde_json::from_str::<CodeBlock>(&json).is_err(), "{json}");
+        }
+
+        let json = serde_json::to_string(
+            &CodeBlock::new("let a = b;")
+                .highlight_section(0..3, None)
+                .highlight_section(8..9, None),
+        )
+        .unwrap()
+        .replacen(r#"{"start":8,"end":9}"#, r#"{"start":1,"end":2}"#, 1);
+        assert!(serde_json::from_str::<CodeBlock>(&json).is_err(), "{json}");
+    }
+}
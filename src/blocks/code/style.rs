@@ -0,0 +1,174 @@
+use crate::constants::{
+    BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, HORIZONTAL_BOTTOM_BAR, HORIZONTAL_TOP_BAR, RIGHT_ARROW,
+    TOP_LEFT_CORNER, TOP_RIGHT_CORNER, UP_POINTER, VERTICAL_BAR, VERTICAL_RIGHT_BAR,
+};
+use const_format::concatcp;
+
+/// The visual language used to print a [CodeBlock](crate::blocks::CodeBlock), i.e. the
+/// characters of its frame and underlines and the layout of its message connectors.
+/// Changing the style does not require changing the builder code.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeBlockStyle {
+    /// The default style, with rounded box-drawing characters.
+    ///
+    /// ```text
+    /// × ╭─[src/main.rs]
+    /// 1 │    let abc = d;
+    ///   │        ╰─┴── Unused
+    ///   ╰─
+    /// ```
+    #[default]
+    Doclog,
+
+    /// ASCII characters and carets, like the diagnostics of `rustc`. Messages follow the
+    /// underlines directly and multiline sections are joined by underscores.
+    ///
+    /// ```text
+    /// × --> src/main.rs
+    /// 1 |    let abc = d;
+    ///   |        ^^^ Unused
+    ///   |
+    /// ```
+    Rustc,
+
+    /// Flat underlines with labels hanging from them, like the diagnostics of `ariadne`.
+    ///
+    /// ```text
+    /// × ╭─[src/main.rs]
+    /// 1 │    let abc = d;
+    ///   │        ──┴── Unused
+    ///   ╰─
+    /// ```
+    Ariadne,
+}
+
+impl CodeBlockStyle {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the characters used to print this style.
+    pub(crate) const fn glyphs(&self) -> &'static CodeBlockGlyphs {
+        match self {
+            CodeBlockStyle::Doclog => &DOCLOG_GLYPHS,
+            CodeBlockStyle::Rustc => &RUSTC_GLYPHS,
+            CodeBlockStyle::Ariadne => &ARIADNE_GLYPHS,
+        }
+    }
+}
+
+/// The characters used to print a [CodeBlockStyle].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct CodeBlockGlyphs {
+    /// The start of the first line of the frame.
+    pub frame_start: &'static str,
+    /// The start of the last line of the frame.
+    pub frame_end: &'static str,
+    /// The start of the line that introduces a related block inside the frame.
+    pub frame_related: &'static str,
+    /// The text around the file path in the first line of the frame.
+    pub path_open: &'static str,
+    pub path_close: &'static str,
+    /// The vertical bar between the line numbers and the code.
+    pub gutter: char,
+    /// The first, middle and last characters of an underline.
+    pub underline_start: char,
+    pub underline: char,
+    pub underline_end: char,
+    /// The first character of an underline whose message is printed in a later row.
+    pub label_start: char,
+    /// The underline of a single character without message.
+    pub pointer: char,
+    /// The underline of a single character whose message is printed in a later row
+    /// and in the same row, respectively.
+    pub label_pointer: char,
+    pub inline_pointer: char,
+    /// The vertical connector between an underline and its message.
+    pub connector: char,
+    /// The joints of an underline with the message printed in the same row and with
+    /// the start of a multiline section, respectively.
+    pub junction_up: char,
+    pub junction_down: char,
+    /// The arrow that points to the start and end of multiline sections.
+    pub arrow: char,
+    /// The underline between a multiline section and its arrows.
+    pub multiline_underline: char,
+    /// The corner that turns a connector towards its message.
+    pub message_corner: char,
+    /// The horizontal bar that leads to a message, also used to align them.
+    pub message_bar: char,
+    /// The number of [CodeBlockGlyphs::message_bar] between an underline or connector and
+    /// its message.
+    pub message_bar_width: usize,
+}
+
+const DOCLOG_GLYPHS: CodeBlockGlyphs = CodeBlockGlyphs {
+    frame_start: concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR),
+    frame_end: concatcp!(TOP_RIGHT_CORNER, HORIZONTAL_BAR),
+    frame_related: concatcp!(VERTICAL_RIGHT_BAR, HORIZONTAL_BAR),
+    path_open: "[",
+    path_close: "]",
+    gutter: VERTICAL_BAR,
+    underline_start: TOP_RIGHT_CORNER,
+    underline: HORIZONTAL_BAR,
+    underline_end: TOP_LEFT_CORNER,
+    label_start: VERTICAL_RIGHT_BAR,
+    pointer: UP_POINTER,
+    label_pointer: VERTICAL_BAR,
+    inline_pointer: TOP_RIGHT_CORNER,
+    connector: VERTICAL_BAR,
+    junction_up: HORIZONTAL_TOP_BAR,
+    junction_down: HORIZONTAL_BOTTOM_BAR,
+    arrow: RIGHT_ARROW,
+    multiline_underline: HORIZONTAL_BAR,
+    message_corner: TOP_RIGHT_CORNER,
+    message_bar: HORIZONTAL_BAR,
+    message_bar_width: 2,
+};
+
+const RUSTC_GLYPHS: CodeBlockGlyphs = CodeBlockGlyphs {
+    frame_start: "-->",
+    frame_end: "|",
+    frame_related: ":::",
+    path_open: " ",
+    path_close: "",
+    gutter: '|',
+    underline_start: UP_POINTER,
+    underline: UP_POINTER,
+    underline_end: UP_POINTER,
+    label_start: UP_POINTER,
+    pointer: UP_POINTER,
+    label_pointer: UP_POINTER,
+    inline_pointer: UP_POINTER,
+    connector: '|',
+    junction_up: UP_POINTER,
+    junction_down: UP_POINTER,
+    arrow: '_',
+    multiline_underline: '_',
+    message_corner: '|',
+    message_bar: ' ',
+    message_bar_width: 0,
+};
+
+const ARIADNE_GLYPHS: CodeBlockGlyphs = CodeBlockGlyphs {
+    frame_start: concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR),
+    frame_end: concatcp!(TOP_RIGHT_CORNER, HORIZONTAL_BAR),
+    frame_related: concatcp!(VERTICAL_RIGHT_BAR, HORIZONTAL_BAR),
+    path_open: "[",
+    path_close: "]",
+    gutter: VERTICAL_BAR,
+    underline_start: HORIZONTAL_BAR,
+    underline: HORIZONTAL_BAR,
+    underline_end: HORIZONTAL_BAR,
+    label_start: HORIZONTAL_BOTTOM_BAR,
+    pointer: HORIZONTAL_BOTTOM_BAR,
+    label_pointer: HORIZONTAL_BOTTOM_BAR,
+    inline_pointer: TOP_RIGHT_CORNER,
+    connector: VERTICAL_BAR,
+    junction_up: HORIZONTAL_TOP_BAR,
+    junction_down: HORIZONTAL_BOTTOM_BAR,
+    arrow: RIGHT_ARROW,
+    multiline_underline: HORIZONTAL_BAR,
+    message_corner: TOP_RIGHT_CORNER,
+    message_bar: HORIZONTAL_BAR,
+    message_bar_width: 2,
+};
@@ -0,0 +1,97 @@
+use crate::printer::Printable;
+use std::fmt::Debug;
+
+/// An object-safe version of [Printable] to implement custom blocks outside this crate.
+/// See [LogBlock::Custom](crate::blocks::LogBlock::Custom).
+///
+/// ```
+/// use doclog::blocks::{DynPrintable, LogBlock};
+/// use doclog::{Printable, Printer};
+///
+/// #[derive(Debug, Clone)]
+/// struct Counter(usize);
+///
+/// impl<'a> Printable<'a> for Counter {
+///     fn print<'s>(&'s self, printer: &mut Printer<'a>)
+///     where
+///         'a: 's,
+///     {
+///         printer.push_plain_text(format!("Count: {}", self.0));
+///     }
+/// }
+///
+/// impl<'a> DynPrintable<'a> for Counter {
+///     fn clone_box(&self) -> Box<dyn DynPrintable<'a> + 'a> {
+///         Box::new(self.clone())
+///     }
+///
+///     fn make_owned_box(self: Box<Self>) -> Box<dyn DynPrintable<'static>> {
+///         self
+///     }
+/// }
+///
+/// let block = LogBlock::custom(Counter(3));
+/// ```
+pub trait DynPrintable<'a>: Printable<'a> + Debug + Send + Sync {
+    /// Clones the block into a new box.
+    fn clone_box(&self) -> Box<dyn DynPrintable<'a> + 'a>;
+
+    /// Makes the block owned, i.e. changing the lifetime to `'static`.
+    fn make_owned_box(self: Box<Self>) -> Box<dyn DynPrintable<'static>>;
+}
+
+impl<'a> Clone for Box<dyn DynPrintable<'a> + 'a> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::LogBlock;
+    use crate::printer::{Printer, PrinterFormat};
+    use crate::{Log, LogLevel};
+    use std::borrow::Cow;
+
+    #[derive(Debug, Clone)]
+    struct QuoteBlock<'a>(Cow<'a, str>);
+
+    impl<'a> Printable<'a> for QuoteBlock<'a> {
+        fn print<'s>(&'s self, printer: &mut Printer<'a>)
+        where
+            'a: 's,
+        {
+            printer.push_plain_text("> ");
+            printer.push_plain_text(self.0.clone());
+        }
+    }
+
+    impl<'a> DynPrintable<'a> for QuoteBlock<'a> {
+        fn clone_box(&self) -> Box<dyn DynPrintable<'a> + 'a> {
+            Box::new(self.clone())
+        }
+
+        fn make_owned_box(self: Box<Self>) -> Box<dyn DynPrintable<'static>> {
+            Box::new(QuoteBlock(Cow::Owned(self.0.into_owned())))
+        }
+    }
+
+    #[test]
+    fn test_custom_block() {
+        let text = String::from("quoted");
+        let log = Log::info().add_block(LogBlock::custom(QuoteBlock(Cow::Borrowed(&text))));
+        let owned = log.clone().make_owned();
+        drop(log);
+        drop(text);
+
+        assert_eq!(
+            owned.print_to_string(LogLevel::info(), PrinterFormat::Plain),
+            "> quoted"
+        );
+    }
+}
@@ -0,0 +1,629 @@
+use crate::constants::{MIDDLE_DOT, VERTICAL_BAR};
+use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::utils::text::count_columns;
+use crate::utils::whitespaces::build_space_string;
+use crate::LogLevel;
+use const_format::concatcp;
+use std::borrow::Cow;
+use std::fmt::Display;
+use yansi::{Color, Style};
+
+/// The maximum number of changed lines [diff_lines] searches the shortest edit script for.
+const MAX_EDIT_DISTANCE: usize = 1024;
+
+/// A block that prints the differences between an expected and an actual text,
+/// line by line.
+///
+/// # Examples
+/// ```text
+/// 1 1 │   line 1
+/// 2   │ - line 2
+///   2 │ + line two
+/// 3 3 │   line 3
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffBlock<'a> {
+    expected: Cow<'a, str>,
+    actual: Cow<'a, str>,
+    pub context_lines: Option<usize>,
+    pub side_by_side: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::color"))]
+    pub insertion_color: Color,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::color"))]
+    pub deletion_color: Color,
+}
+
+impl<'a> DiffBlock<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [DiffBlock] comparing `expected` against `actual`.
+    pub fn new(expected: impl Into<Cow<'a, str>>, actual: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            expected: expected.into(),
+            actual: actual.into(),
+            context_lines: None,
+            side_by_side: false,
+            insertion_color: Color::Green,
+            deletion_color: Color::Red,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the expected text.
+    #[inline(always)]
+    pub fn get_expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Returns the actual text.
+    #[inline(always)]
+    pub fn get_actual(&self) -> &str {
+        &self.actual
+    }
+
+    /// Returns whether both texts are equal.
+    #[inline(always)]
+    pub fn is_equal(&self) -> bool {
+        self.expected == self.actual
+    }
+
+    /// Returns the expected and actual texts mutably, e.g. to redact them.
+    #[inline(always)]
+    pub(crate) fn texts_mut(&mut self) -> [&mut Cow<'a, str>; 2] {
+        [&mut self.expected, &mut self.actual]
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the number of unchanged lines to show around each change.
+    /// By default, all lines are shown.
+    #[inline(always)]
+    pub fn context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = Some(context_lines);
+        self
+    }
+
+    /// Sets whether to print both texts side by side instead of in a unified view.
+    #[inline(always)]
+    pub fn side_by_side(mut self, side_by_side: bool) -> Self {
+        self.side_by_side = side_by_side;
+        self
+    }
+
+    /// Sets the color of inserted lines.
+    #[inline(always)]
+    pub fn insertion_color(mut self, insertion_color: Color) -> Self {
+        self.insertion_color = insertion_color;
+        self
+    }
+
+    /// Sets the color of deleted lines.
+    #[inline(always)]
+    pub fn deletion_color(mut self, deletion_color: Color) -> Self {
+        self.deletion_color = deletion_color;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Computes the rows to print, `None` representing a group of hidden lines.
+    fn visible_rows(&self, operations: &[DiffOperation]) -> Vec<Option<DiffRow>> {
+        let mut rows = Vec::new();
+        let mut index = 0;
+
+        while index < operations.len() {
+            let operation = operations[index];
+
+            if let Some(context_lines) = self.context_lines {
+                if let DiffOperation::Equal(..) = operation {
+                    let is_near_change = operations[index.saturating_sub(context_lines)
+                        ..operations.len().min(index + context_lines + 1)]
+                        .iter()
+                        .any(|v| !matches!(v, DiffOperation::Equal(..)));
+
+                    if !is_near_change {
+                        if rows.last() != Some(&None) {
+                            rows.push(None);
+                        }
+
+                        index += 1;
+                        continue;
+                    }
+                }
+            }
+
+            match operation {
+                DiffOperation::Equal(old, new) => rows.push(Some(DiffRow {
+                    old: Some(old),
+                    new: Some(new),
+                    is_change: false,
+                })),
+                DiffOperation::Delete(_) | DiffOperation::Insert(_) => {
+                    // Group consecutive changes to pair deletions with insertions.
+                    let end = operations[index..]
+                        .iter()
+                        .position(|v| matches!(v, DiffOperation::Equal(..)))
+                        .map_or(operations.len(), |v| v + index);
+                    let deletions = operations[index..end].iter().filter_map(|v| match v {
+                        DiffOperation::Delete(old) => Some(*old),
+                        _ => None,
+                    });
+                    let insertions = operations[index..end].iter().filter_map(|v| match v {
+                        DiffOperation::Insert(new) => Some(*new),
+                        _ => None,
+                    });
+
+                    if self.side_by_side {
+                        let deletions: Vec<_> = deletions.collect();
+                        let insertions: Vec<_> = insertions.collect();
+
+                        for i in 0..deletions.len().max(insertions.len()) {
+                            rows.push(Some(DiffRow {
+                                old: deletions.get(i).copied(),
+                                new: insertions.get(i).copied(),
+                                is_change: true,
+                            }));
+                        }
+                    } else {
+                        rows.extend(deletions.map(|old| {
+                            Some(DiffRow {
+                                old: Some(old),
+                                new: None,
+                                is_change: true,
+                            })
+                        }));
+                        rows.extend(insertions.map(|new| {
+                            Some(DiffRow {
+                                old: None,
+                                new: Some(new),
+                                is_change: true,
+                            })
+                        }));
+                    }
+
+                    index = end;
+                    continue;
+                }
+            }
+
+            index += 1;
+        }
+
+        rows
+    }
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> DiffBlock<'static> {
+        DiffBlock {
+            expected: Cow::Owned(self.expected.into_owned()),
+            actual: Cow::Owned(self.actual.into_owned()),
+            context_lines: self.context_lines,
+            side_by_side: self.side_by_side,
+            insertion_color: self.insertion_color,
+            deletion_color: self.deletion_color,
+        }
+    }
+}
+
+impl<'a> Printable<'a> for DiffBlock<'a> {
+    fn print<'s>(&'s self, printer: &mut Printer<'a>)
+    where
+        'a: 's,
+    {
+        let expected_lines = split_lines(&self.expected);
+        let actual_lines = split_lines(&self.actual);
+        let operations = diff_lines(&expected_lines, &actual_lines);
+        let rows = self.visible_rows(&operations);
+        let max_line_digits = format!("{}", expected_lines.len().max(actual_lines.len())).len();
+        let max_expected_columns = expected_lines
+            .iter()
+            .map(|v| count_columns(v))
+            .max()
+            .unwrap_or(0);
+        let line_number_style = printer.theme.gutter_style();
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                printer.new_line();
+            }
+
+            let row = match row {
+                Some(v) => v,
+                None => {
+                    printer.push_styled_text(
+                        format!(
+                            "{}{}",
+                            build_space_string(max_line_digits),
+                            concatcp!(MIDDLE_DOT, MIDDLE_DOT, MIDDLE_DOT)
+                        ),
+                        Style::new().bold(),
+                    );
+                    continue;
+                }
+            };
+
+            if self.side_by_side {
+                // Expected side.
+                print_line_number(printer, row.old, max_line_digits, line_number_style);
+                printer.push_styled_text(concatcp!(VERTICAL_BAR, ' '), Style::new().bold());
+
+                let old_columns = match row.old {
+                    Some(old) => {
+                        let style = if row.is_change {
+                            Style::new().bold().fg(self.deletion_color)
+                        } else {
+                            Style::new()
+                        };
+                        printer.push_styled_text(expected_lines[old].clone(), style);
+                        count_columns(&expected_lines[old])
+                    }
+                    None => 0,
+                };
+                printer.push_plain_text(build_space_string(max_expected_columns - old_columns + 1));
+
+                // Actual side.
+                printer.push_styled_text(concatcp!(VERTICAL_BAR, ' '), Style::new().bold());
+                print_line_number(printer, row.new, max_line_digits, line_number_style);
+                printer.push_styled_text(concatcp!(VERTICAL_BAR, ' '), Style::new().bold());
+
+                if let Some(new) = row.new {
+                    let style = if row.is_change {
+                        Style::new().bold().fg(self.insertion_color)
+                    } else {
+                        Style::new()
+                    };
+                    printer.push_styled_text(actual_lines[new].clone(), style);
+                }
+            } else {
+                print_line_number(printer, row.old, max_line_digits, line_number_style);
+                print_line_number(printer, row.new, max_line_digits, line_number_style);
+                printer.push_styled_text(concatcp!(VERTICAL_BAR, ' '), Style::new().bold());
+
+                match (row.old, row.new) {
+                    (Some(_), Some(new)) => {
+                        printer.push_plain_text("  ");
+                        printer.push_plain_text(actual_lines[new].clone());
+                    }
+                    (Some(old), None) => {
+                        let style = Style::new().bold().fg(self.deletion_color);
+                        printer.push_styled_text("- ", style);
+                        printer.push_styled_text(expected_lines[old].clone(), style);
+                    }
+                    (None, Some(new)) => {
+                        let style = Style::new().bold().fg(self.insertion_color);
+                        printer.push_styled_text("+ ", style);
+                        printer.push_styled_text(actual_lines[new].clone(), style);
+                    }
+                    (None, None) => unreachable!("A diff row must contain at least one line"),
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Display for DiffBlock<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut printer = Printer::new(LogLevel::trace(), PrinterFormat::Plain);
+        self.print(&mut printer);
+        printer.fmt(f, PrinterFormat::Plain)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// An operation of a line diff. The indexes are 0-based line indexes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum DiffOperation {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// A row to print in a diff block.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct DiffRow {
+    old: Option<usize>,
+    new: Option<usize>,
+    is_change: bool,
+}
+
+/// Splits a text into lines keeping them borrowed when possible.
+fn split_lines<'a>(text: &Cow<'a, str>) -> Vec<Cow<'a, str>> {
+    match text {
+        Cow::Borrowed(v) => v.lines().map(Cow::Borrowed).collect(),
+        Cow::Owned(v) => v.lines().map(|v| Cow::Owned(v.to_string())).collect(),
+    }
+}
+
+/// Prints a right-aligned line number or its space if missing.
+fn print_line_number(
+    printer: &mut Printer,
+    line: Option<usize>,
+    max_line_digits: usize,
+    style: Style,
+) {
+    match line {
+        Some(line) => printer.push_styled_text(
+            format!("{:>width$} ", line + 1, width = max_line_digits),
+            style,
+        ),
+        None => printer.push_plain_text(build_space_string(max_line_digits + 1)),
+    }
+}
+
+/// Computes the list of operations to transform `old` into `new` with the shortest edit
+/// script of lines, using the algorithm of Myers in O((n + m) · d) time, where `d` is
+/// the number of changed lines. Over [MAX_EDIT_DISTANCE] changes, the lines between the
+/// common prefix and suffix are replaced as a whole.
+///
+/// Within each group of changes, the deletions come before the insertions.
+pub(crate) fn diff_lines(old: &[Cow<str>], new: &[Cow<str>]) -> Vec<DiffOperation> {
+    // Skip common prefix and suffix to reduce the size of the search.
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut operations: Vec<_> = (0..prefix).map(|i| DiffOperation::Equal(i, i)).collect();
+    let middle = shortest_edit_script(old_middle, new_middle).unwrap_or_else(|| {
+        (0..old_middle.len())
+            .map(DiffOperation::Delete)
+            .chain((0..new_middle.len()).map(DiffOperation::Insert))
+            .collect()
+    });
+
+    // Deletions first inside each group of changes.
+    let sort_changes = |changes: &mut [DiffOperation]| {
+        changes.sort_by_key(|v| matches!(v, DiffOperation::Insert(_)));
+    };
+    let mut group_start = operations.len();
+
+    for operation in middle {
+        let operation = match operation {
+            DiffOperation::Equal(i, j) => DiffOperation::Equal(prefix + i, prefix + j),
+            DiffOperation::Delete(i) => DiffOperation::Delete(prefix + i),
+            DiffOperation::Insert(j) => DiffOperation::Insert(prefix + j),
+        };
+        let is_equal = matches!(operation, DiffOperation::Equal(..));
+
+        if is_equal {
+            sort_changes(&mut operations[group_start..]);
+        }
+
+        operations.push(operation);
+
+        if is_equal {
+            group_start = operations.len();
+        }
+    }
+
+    sort_changes(&mut operations[group_start..]);
+    operations.extend(
+        (0..suffix).map(|k| DiffOperation::Equal(old.len() - suffix + k, new.len() - suffix + k)),
+    );
+
+    operations
+}
+
+/// Computes the shortest edit script that transforms `old` into `new` with the algorithm
+/// of Myers, or `None` if it needs more than [MAX_EDIT_DISTANCE] changes.
+fn shortest_edit_script(old: &[Cow<str>], new: &[Cow<str>]) -> Option<Vec<DiffOperation>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max_distance = (old.len() + new.len()).min(MAX_EDIT_DISTANCE) as isize;
+
+    // The furthest x reached in each diagonal k = x - y, indexed by k + max_distance + 1,
+    // and its value after each step d, indexed by k + d.
+    let offset = max_distance + 1;
+    let mut furthest = vec![0isize; 2 * offset as usize + 1];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let index = |k: isize| (k + offset) as usize;
+
+    for d in 0..=max_distance {
+        for k in (-d..=d).step_by(2) {
+            // Move down, i.e. insert, from the diagonal above or right, i.e. delete, from
+            // the one below, whichever reached further.
+            let mut x = if d == 0 {
+                0
+            } else if k == -d || (k != d && furthest[index(k - 1)] < furthest[index(k + 1)]) {
+                furthest[index(k + 1)]
+            } else {
+                furthest[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+
+            furthest[index(k)] = x;
+
+            if x >= n && y >= m {
+                return Some(backtrack_edit_script(&trace, n, m, d));
+            }
+        }
+
+        trace.push(furthest[index(-d)..=index(d)].to_vec());
+    }
+
+    None
+}
+
+/// Builds the edit script of [shortest_edit_script] walking back the furthest points of
+/// every step, from the end of both texts after `distance` changes.
+fn backtrack_edit_script(
+    trace: &[Vec<isize>],
+    n: isize,
+    m: isize,
+    distance: isize,
+) -> Vec<DiffOperation> {
+    let mut operations = Vec::with_capacity((n + m) as usize);
+    let (mut x, mut y) = (n, m);
+
+    for d in (1..=distance).rev() {
+        let previous = &trace[(d - 1) as usize];
+        let furthest = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && furthest(k - 1) < furthest(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest(previous_k);
+        let previous_y = previous_x - previous_k;
+
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            operations.push(DiffOperation::Equal(x as usize, y as usize));
+        }
+
+        if x == previous_x {
+            operations.push(DiffOperation::Insert(previous_y as usize));
+        } else {
+            operations.push(DiffOperation::Delete(previous_x as usize));
+        }
+
+        (x, y) = (previous_x, previous_y);
+    }
+
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        operations.push(DiffOperation::Equal(x as usize, y as usize));
+    }
+
+    operations.reverse();
+    operations
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        // Equal
+        let log = DiffBlock::new("Line 1\nLine 2", "Line 1\nLine 2");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "1 1 │   Line 1\n2 2 │   Line 2");
+
+        // Changes
+        let log = DiffBlock::new(
+            "Line 1\nLine 2\nLine 3\nLine 4",
+            "Line 1\nLine two\nLine 3\nLine 4\nLine 5",
+        );
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "1 1 │   Line 1\n2   │ - Line 2\n  2 │ + Line two\n3 3 │   Line 3\n4 4 │   Line 4\n  5 │ + Line 5");
+
+        // Context lines
+        let log = DiffBlock::new(
+            "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\nLine 6\nLine 7",
+            "Line 1\nLine two\nLine 3\nLine 4\nLine 5\nLine 6\nLine 7",
+        )
+        .context_lines(1);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "1 1 │   Line 1\n2   │ - Line 2\n  2 │ + Line two\n3 3 │   Line 3\n ···"
+        );
+
+        // Side by side
+        let log = DiffBlock::new("Line 1\nLine 2\nLine 3", "Line 1\nLine two\nLine 3\nLine 4")
+            .side_by_side(true);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "1 │ Line 1 │ 1 │ Line 1\n2 │ Line 2 │ 2 │ Line two\n3 │ Line 3 │ 3 │ Line 3\n  │        │ 4 │ Line 4");
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        // Checks that the operations rebuild both texts and keep `common` lines.
+        let check = |old: &[Cow<str>], new: &[Cow<str>], common: usize| {
+            let operations = diff_lines(old, new);
+            let (mut i, mut j) = (0, 0);
+
+            for operation in &operations {
+                match *operation {
+                    DiffOperation::Equal(a, b) => {
+                        assert_eq!((a, b), (i, j));
+                        assert_eq!(old[a], new[b]);
+                        i += 1;
+                        j += 1;
+                    }
+                    DiffOperation::Delete(a) => {
+                        assert_eq!(a, i);
+                        i += 1;
+                    }
+                    DiffOperation::Insert(b) => {
+                        assert_eq!(b, j);
+                        j += 1;
+                    }
+                }
+            }
+
+            assert_eq!((i, j), (old.len(), new.len()));
+            assert_eq!(
+                operations
+                    .iter()
+                    .filter(|v| matches!(v, DiffOperation::Equal(..)))
+                    .count(),
+                common
+            );
+        };
+        let lines = |text: &'static str| {
+            text.chars()
+                .map(|c| Cow::Owned(c.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        check(&lines("abcabba"), &lines("cbabac"), 4);
+        check(&lines(""), &lines("abc"), 0);
+        check(&lines("abc"), &lines(""), 0);
+        check(&lines("axbycz"), &lines("abc"), 3);
+        check(&lines("abc"), &lines("xaybzc"), 3);
+
+        assert_eq!(
+            diff_lines(&lines("ab"), &lines("xb")),
+            vec![
+                DiffOperation::Delete(0),
+                DiffOperation::Insert(0),
+                DiffOperation::Equal(1, 1)
+            ]
+        );
+
+        // Over the maximum distance, the middle lines are replaced.
+        let old: Vec<_> = (0..MAX_EDIT_DISTANCE)
+            .map(|i| Cow::Owned(format!("old {i}")))
+            .collect();
+        let new: Vec<_> = (0..MAX_EDIT_DISTANCE)
+            .map(|i| Cow::Owned(format!("new {i}")))
+            .collect();
+        check(&old, &new, 0);
+    }
+
+    #[test]
+    fn test_styled() {
+        let log = DiffBlock::new("Line 1\nLine 2", "Line 1\nLine two");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(text, "\u{1b}[1;90m1 1 \u{1b}[0m\u{1b}[1m│   \u{1b}[0mLine 1\n\u{1b}[1;90m2   \u{1b}[0m\u{1b}[1m│ \u{1b}[0m\u{1b}[1;31m- Line 2\n  \u{1b}[0m\u{1b}[1;90m2 \u{1b}[0m\u{1b}[1m│ \u{1b}[0m\u{1b}[1;32m+ Line two\u{1b}[0m");
+    }
+}
@@ -1,17 +1,20 @@
 use crate::blocks::TextBlock;
 use crate::constants::NEW_LINE_RIGHT;
 use crate::printer::{Printable, Printer, PrinterFormat};
-use crate::utils::text::remove_jump_lines;
+use crate::utils::text::{count_columns, remove_jump_lines};
+use crate::utils::time::format_duration;
 use crate::utils::whitespaces::build_space_string;
 use crate::LogLevel;
+#[cfg(feature = "date")]
 use chrono::{SecondsFormat, Utc};
 use const_format::concatcp;
 use std::borrow::Cow;
 use std::fmt::Display;
+use std::time::Duration;
 use yansi::Style;
 
 /// A block that prints a title, showing the type of log and the message.
-/// It optionally shows the current date and thread.
+/// It optionally shows the current date, thread and elapsed time.
 ///
 /// When printed, code will get all newline characters `\n`
 /// replaced by whitespaces to only occupy one line.
@@ -21,13 +24,20 @@ use yansi::Style;
 /// info[code] in /path/to/file.rs
 /// ```
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeaderBlock<'a> {
     pub title: TextBlock<'a>,
     pub code: Cow<'a, str>,
     pub location: TextBlock<'a>,
+
+    /// Whether to show the current date. Without the `date` feature nothing is printed.
     pub show_date: bool,
+
+    /// Whether to show the current thread. Without the `thread` feature nothing is printed.
     pub show_thread: bool,
+    pub elapsed: Option<Duration>,
     pub extra_messages: Vec<TextBlock<'a>>,
+    pub explain_hint: bool,
 }
 
 impl<'a> HeaderBlock<'a> {
@@ -62,20 +72,29 @@ impl<'a> HeaderBlock<'a> {
         self
     }
 
-    /// Sets whether the date should be shown.
+    /// Sets whether the date should be shown. It is a no-op without the `date` feature,
+    /// so tests that expect the date must be gated by it.
     #[inline(always)]
     pub fn show_date(mut self, show_date: bool) -> Self {
         self.show_date = show_date;
         self
     }
 
-    /// Sets whether the thread should be shown.
+    /// Sets whether the thread should be shown. It is a no-op without the `thread` feature,
+    /// so tests that expect the thread must be gated by it.
     #[inline(always)]
     pub fn show_thread(mut self, show_thread: bool) -> Self {
         self.show_thread = show_thread;
         self
     }
 
+    /// Sets the elapsed time to show, e.g. the duration of the task that is being logged.
+    #[inline(always)]
+    pub fn show_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
     /// Adds an extra message.
     #[inline(always)]
     pub fn add_extra_message(mut self, message: impl Into<TextBlock<'a>>) -> Self {
@@ -83,6 +102,15 @@ impl<'a> HeaderBlock<'a> {
         self
     }
 
+    /// Sets whether a hint to run with `--explain <code>` should be shown, pointing to the
+    /// explanation of the code registered in the [CodeRegistry](crate::CodeRegistry).
+    /// It is only shown if the header has a code.
+    #[inline(always)]
+    pub fn explain_hint(mut self, explain_hint: bool) -> Self {
+        self.explain_hint = explain_hint;
+        self
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
@@ -93,11 +121,13 @@ impl<'a> HeaderBlock<'a> {
             location: self.location.make_owned(),
             show_date: self.show_date,
             show_thread: self.show_thread,
+            elapsed: self.elapsed,
             extra_messages: self
                 .extra_messages
                 .into_iter()
                 .map(|v| v.make_owned())
                 .collect(),
+            explain_hint: self.explain_hint,
         }
     }
 }
@@ -108,59 +138,101 @@ impl<'a> Printable<'a> for HeaderBlock<'a> {
         'a: 's,
     {
         // Add tag.
-        printer.push_styled_text(
-            printer.level.tag().to_ascii_uppercase(),
-            Style::new().bold().fg(printer.level.color()),
-        );
+        let tag = printer.level_tag();
+        let tag_columns = count_columns(&tag);
+        printer.push_styled_text(tag, printer.border_style());
 
         // Add code.
         if !self.code.is_empty() {
-            printer.push_styled_text(
-                format!("[{}]", remove_jump_lines(self.code.as_ref())),
-                Style::new().bold(),
-            );
+            if let Some(columns) = printer.remaining_columns() {
+                // Omit the code when not even one column of it fits between the brackets.
+                let code = TextBlock::new()
+                    .add_styled_text(self.code.clone(), Style::new().bold())
+                    .fit_single_lined(columns.saturating_sub(2));
+
+                if let Some(code) = code {
+                    printer.push_styled_text(Cow::Borrowed("["), Style::new().bold());
+                    code.print(printer);
+                    printer.push_styled_text(Cow::Borrowed("]"), Style::new().bold());
+                }
+            } else {
+                printer.push_styled_text(
+                    format!("[{}]", remove_jump_lines(self.code.as_ref())),
+                    Style::new().bold(),
+                );
+            }
         }
 
         // Add title.
-        if !self.title.is_empty() {
+        // Omit the title when not even one column of it fits after the space.
+        if !self.title.is_empty() && printer.remaining_columns().is_none_or(|v| v >= 3) {
             printer.push_plain_text(Cow::Borrowed(" "));
 
-            let prefix = TextBlock::new_plain(build_space_string(printer.level.tag().len() + 1));
-            let mut location_printer = printer.derive();
+            let prefix = TextBlock::new_plain(build_space_string(tag_columns + 1));
+            let mut title_printer = printer.derive_indented(prefix.column_count());
 
-            self.title.print(&mut location_printer);
-            location_printer.indent(&prefix.sections, false);
-            printer.append(location_printer);
+            self.title.print(&mut title_printer);
+
+            // Clip the title to the width, the first line being shared with the tag and code.
+            if let (Some(max_width), Some(first_line_columns)) =
+                (title_printer.max_width, printer.remaining_columns())
+            {
+                title_printer.clip_lines_from(1, max_width);
+                title_printer.clip_first_line(first_line_columns);
+            }
+
+            title_printer.indent(&prefix.sections, false);
+            printer.append(title_printer);
         }
 
         // Add location.
         if !self.location.is_empty() {
-            printer.push_styled_text(
-                Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " in ")),
-                Style::new().bold().fg(printer.level.color()),
-            );
-
-            let prefix = TextBlock::new_plain(Cow::Borrowed("      "));
-            let mut location_printer = printer.derive();
-
-            self.location.print(&mut location_printer);
-            location_printer.indent(&prefix.sections, false);
-            printer.append(location_printer);
+            let location_prefix = concatcp!(" ", NEW_LINE_RIGHT, " in ");
+
+            // Keep the location in a single line when the width is limited, omitting the
+            // row when not even one column of it fits.
+            if let Some(max_width) = printer.max_width {
+                let location = self
+                    .location
+                    .fit_single_lined(max_width.saturating_sub(count_columns(location_prefix)));
+
+                if let Some(location) = location {
+                    printer.push_styled_text(
+                        Cow::Owned(format!("\n{location_prefix}")),
+                        printer.border_style(),
+                    );
+                    location.print(printer);
+                }
+            } else {
+                printer.push_styled_text(
+                    Cow::Owned(format!("\n{location_prefix}")),
+                    printer.border_style(),
+                );
+
+                let prefix = TextBlock::new_plain(Cow::Borrowed("      "));
+                let mut location_printer = printer.derive_indented(prefix.column_count());
+
+                self.location.print(&mut location_printer);
+                location_printer.indent(&prefix.sections, false);
+                printer.append(location_printer);
+            }
         }
 
         // Add date.
+        #[cfg(feature = "date")]
         if self.show_date {
             let date = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
 
             printer.push_styled_text(
                 Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " at ")),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
             printer.push_styled_text(Cow::Owned(date), Style::new().bold());
         }
 
         // Add thread.
+        #[cfg(feature = "thread")]
         if self.show_thread {
             let thread = std::thread::current()
                 .name()
@@ -169,26 +241,49 @@ impl<'a> Printable<'a> for HeaderBlock<'a> {
 
             printer.push_styled_text(
                 Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " in thread ")),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
             printer.push_styled_text(Cow::Owned(thread), Style::new().bold());
         }
 
+        // Add elapsed time.
+        if let Some(elapsed) = self.elapsed {
+            printer.push_styled_text(
+                Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " took ")),
+                printer.border_style(),
+            );
+
+            printer.push_styled_text(Cow::Owned(format_duration(elapsed)), Style::new().bold());
+        }
+
         // Add extra messages.
         let prefix = TextBlock::new_plain(Cow::Borrowed("   "));
         for message in &self.extra_messages {
             printer.push_styled_text(
                 Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " ")),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
-            let mut location_printer = printer.derive();
+            let mut location_printer = printer.derive_indented(prefix.column_count());
 
             message.print(&mut location_printer);
             location_printer.indent(&prefix.sections, false);
             printer.append(location_printer);
         }
+
+        // Add explain hint.
+        if self.explain_hint && !self.code.is_empty() {
+            printer.push_styled_text(
+                Cow::Borrowed(concatcp!("\n ", NEW_LINE_RIGHT, " run with ")),
+                printer.border_style(),
+            );
+
+            printer.push_styled_text(
+                format!("--explain {}", remove_jump_lines(self.code.as_ref())),
+                Style::new().bold(),
+            );
+        }
     }
 }
 
@@ -206,11 +301,12 @@ impl<'a> Display for HeaderBlock<'a> {
 
 #[cfg(test)]
 mod tests {
-    use crate::LogLevel;
+    use crate::{LevelTags, LogLevel, Theme};
 
     use super::*;
 
     #[test]
+    #[cfg(all(feature = "date", feature = "thread"))]
     fn test_plain() {
         // Empty
         let log = HeaderBlock::new();
@@ -254,12 +350,29 @@ mod tests {
 
         assert_eq!(text, format!("ERROR\n ↪ in thread {thread}"));
 
+        // Elapsed
+        let log = HeaderBlock::new().show_elapsed(Duration::from_millis(1234));
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "ERROR\n ↪ took 1.2s");
+
         // Extra messages
         let log = HeaderBlock::new().add_extra_message("Line1\nLine2");
         let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
 
         assert_eq!(text, format!("ERROR\n ↪ Line1\n   Line2"));
 
+        // Explain hint
+        let log = HeaderBlock::new().explain_hint(true);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "ERROR");
+
+        let log = HeaderBlock::new().code("E0123").explain_hint(true);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "ERROR[E0123]\n ↪ run with --explain E0123");
+
         // All
         let log = HeaderBlock::new()
             .title("This is\na title")
@@ -280,6 +393,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(all(feature = "date", feature = "thread"))]
     fn test_styled() {
         // Empty
         let log = HeaderBlock::new();
@@ -379,4 +493,66 @@ mod tests {
             )
         );
     }
+    #[test]
+    fn test_max_width() {
+        let log = HeaderBlock::new()
+            .code("c-xxxxxxxx")
+            .location(TextBlock::new_plain("src/blocks/\nheader.rs:3:26"));
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(12));
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "ERROR[c-xx…]\n ↪ in src/b…");
+    }
+
+    #[test]
+    fn test_narrow_width() {
+        let log = HeaderBlock::new()
+            .code("E0001")
+            .title("Invalid\nvariable")
+            .location(TextBlock::new_plain("src/main.rs:1:1"));
+
+        let text = |width| {
+            let mut printer =
+                Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(width));
+            log.print(&mut printer);
+            printer.to_string()
+        };
+
+        assert_eq!(text(7), "ERROR");
+        assert_eq!(text(8), "ERROR I…\n      v…\n ↪ in s…");
+        assert_eq!(text(9), "ERROR[E…]\n ↪ in sr…");
+        assert_eq!(text(12), "ERROR[E0001]\n ↪ in src/m…");
+        assert_eq!(text(15), "ERROR[E0001] I…\n      variable\n ↪ in src/main…");
+    }
+
+    #[test]
+    fn test_theme_tags() {
+        let log = HeaderBlock::new().title("Título\nlargo");
+        let theme = Theme::dark().tags(LevelTags {
+            error: "ERRÓNEO",
+            ..LevelTags::english()
+        });
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).theme(theme);
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "ERRÓNEO Título\n        largo");
+
+        let log = HeaderBlock::new().title("Title\nline");
+        let fatal = LogLevel::custom("FATAL", "fatal", '‼', yansi::Color::Magenta);
+        let text = log.print_to_string(fatal, PrinterFormat::Plain);
+
+        assert_eq!(text, "FATAL Title\n      line");
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "date", feature = "thread")))]
+    fn test_without_date_and_thread() {
+        let log = HeaderBlock::new()
+            .title("Title")
+            .show_date(true)
+            .show_thread(true);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "ERROR Title");
+    }
 }
@@ -0,0 +1,181 @@
+use crate::blocks::TextBlock;
+use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::utils::whitespaces::build_space_string;
+use crate::LogLevel;
+use std::fmt::Display;
+use yansi::Style;
+
+/// A block that prints a list of key/value pairs with the keys right-aligned,
+/// so all values start at the same column.
+///
+/// Keys are printed in a single line and unstyled keys take the border style
+/// of the level. Values that span several lines, or that are wrapped because
+/// they exceed the width of the printer, continue at the value column.
+///
+/// # Examples
+/// ```text
+/// expected: <value>
+///    found: <value>
+///     hint: <value>
+///           <value continuation>
+/// ```
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyValueBlock<'a> {
+    pub entries: Vec<(TextBlock<'a>, TextBlock<'a>)>,
+}
+
+impl<'a> KeyValueBlock<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [KeyValueBlock].
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the width of the widest key.
+    fn key_width(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|(key, _)| key.single_lined().column_count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Adds a new entry.
+    #[inline(always)]
+    pub fn add_entry(
+        mut self,
+        key: impl Into<TextBlock<'a>>,
+        value: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.entries.push((key.into(), value.into()));
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> KeyValueBlock<'static> {
+        KeyValueBlock {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|(key, value)| (key.make_owned(), value.make_owned()))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Printable<'a> for KeyValueBlock<'a> {
+    fn print<'s>(&'s self, printer: &mut Printer<'a>)
+    where
+        'a: 's,
+    {
+        let key_width = self.key_width();
+        let value_column = key_width + 2;
+        let border_style = printer.border_style();
+
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i != 0 {
+                printer.new_line();
+            }
+
+            // Key.
+            let key = key.single_lined();
+            printer.push_plain_text(build_space_string(key_width - key.column_count()));
+
+            for section in key.sections {
+                let style = if section.style == Style::new() {
+                    border_style
+                } else {
+                    section.style
+                };
+
+                printer.push_styled_text(section.text, style);
+            }
+
+            printer.push_styled_text(": ", border_style);
+
+            // Value.
+            let mut value_printer = printer.derive_indented(value_column);
+            value.print(&mut value_printer);
+
+            if let Some(max_width) = value_printer.max_width {
+                value_printer.wrap_lines(max_width);
+            }
+
+            value_printer.indent(
+                &TextBlock::new_plain(build_space_string(value_column)).sections,
+                false,
+            );
+            printer.append(value_printer);
+        }
+    }
+}
+
+impl<'a> Display for KeyValueBlock<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut printer = Printer::new(LogLevel::trace(), PrinterFormat::Plain);
+        self.print(&mut printer);
+        printer.fmt(f, PrinterFormat::Plain)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        let log = KeyValueBlock::new()
+            .add_entry("expected", "a number")
+            .add_entry("found", "a string\nin the input")
+            .add_entry("hint", "convert it first");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "expected: a number\n   found: a string\n          in the input\n    hint: convert it first"
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        let log = KeyValueBlock::new()
+            .add_entry("hint", "convert the value first")
+            .add_entry("id", "abcdefghijklmnop");
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(16));
+        log.print(&mut printer);
+        let text = printer.to_string();
+
+        assert_eq!(
+            text,
+            "hint: convert\n      the value\n      first\n  id: abcdefghij\n      klmnop"
+        );
+    }
+
+    #[test]
+    fn test_styled() {
+        let log = KeyValueBlock::new().add_entry("key", "value").add_entry(
+            TextBlock::new().add_styled_text("k", Style::new().blue()),
+            "v",
+        );
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(
+            text,
+            "\u{1b}[1;31mkey: \u{1b}[0mvalue\n  \u{1b}[34mk\u{1b}[0m\u{1b}[1;31m: \u{1b}[0mv"
+        );
+    }
+}
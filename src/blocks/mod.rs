@@ -1,28 +1,41 @@
 use crate::printer::{Printable, Printer};
 
 pub use code::*;
+pub use custom::*;
+pub use diff::*;
 pub use header::*;
+pub use key_value::*;
 pub use note::*;
 pub use prefix::*;
+pub use progress::*;
 pub use separator::*;
 pub use stack::*;
 pub use stack_trace::*;
 pub use step::*;
+pub use table::*;
 pub use text::*;
 
+pub(crate) use diff::{diff_lines, DiffOperation};
+
 mod code;
+mod custom;
+mod diff;
 mod header;
+mod key_value;
 mod note;
 mod prefix;
+mod progress;
 mod separator;
 mod stack;
 mod stack_trace;
 mod step;
+mod table;
 mod text;
 
 /// A block log.
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LogBlock<'a> {
     // Basic blocks.
     Text(TextBlock<'a>),
@@ -35,9 +48,24 @@ pub enum LogBlock<'a> {
     Stack(StackBlock<'a>),
     Code(CodeBlock<'a>),
     Steps(StepsBlock<'a>),
+    Diff(DiffBlock<'a>),
+    Table(TableBlock<'a>),
+    KeyValue(KeyValueBlock<'a>),
+    Progress(ProgressBlock<'a>),
+
+    // User-defined blocks.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(Box<dyn DynPrintable<'a> + 'a>),
 }
 
 impl<'a> LogBlock<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LogBlock] from a user-defined block.
+    pub fn custom(block: impl DynPrintable<'a> + 'a) -> Self {
+        LogBlock::Custom(Box::new(block))
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
@@ -54,6 +82,13 @@ impl<'a> LogBlock<'a> {
             LogBlock::Stack(v) => LogBlock::Stack(v.make_owned()),
             LogBlock::Code(v) => LogBlock::Code(v.make_owned()),
             LogBlock::Steps(v) => LogBlock::Steps(v.make_owned()),
+            LogBlock::Diff(v) => LogBlock::Diff(v.make_owned()),
+            LogBlock::Table(v) => LogBlock::Table(v.make_owned()),
+            LogBlock::KeyValue(v) => LogBlock::KeyValue(v.make_owned()),
+            LogBlock::Progress(v) => LogBlock::Progress(v.make_owned()),
+
+            // User-defined blocks.
+            LogBlock::Custom(v) => LogBlock::Custom(v.make_owned_box()),
         }
     }
 }
@@ -75,10 +110,23 @@ impl<'a> Printable<'a> for LogBlock<'a> {
             LogBlock::Stack(v) => v.print(printer),
             LogBlock::Code(v) => v.print(printer),
             LogBlock::Steps(v) => v.print(printer),
+            LogBlock::Diff(v) => v.print(printer),
+            LogBlock::Table(v) => v.print(printer),
+            LogBlock::KeyValue(v) => v.print(printer),
+            LogBlock::Progress(v) => v.print(printer),
+
+            // User-defined blocks.
+            LogBlock::Custom(v) => v.print(printer),
         }
     }
 }
 
+impl<'a> From<Box<dyn DynPrintable<'a> + 'a>> for LogBlock<'a> {
+    fn from(block: Box<dyn DynPrintable<'a> + 'a>) -> Self {
+        LogBlock::Custom(block)
+    }
+}
+
 impl<'a> From<TextBlock<'a>> for LogBlock<'a> {
     fn from(block: TextBlock<'a>) -> Self {
         LogBlock::Text(block)
@@ -126,3 +174,27 @@ impl<'a> From<StepsBlock<'a>> for LogBlock<'a> {
         LogBlock::Steps(block)
     }
 }
+
+impl<'a> From<DiffBlock<'a>> for LogBlock<'a> {
+    fn from(block: DiffBlock<'a>) -> Self {
+        LogBlock::Diff(block)
+    }
+}
+
+impl<'a> From<TableBlock<'a>> for LogBlock<'a> {
+    fn from(block: TableBlock<'a>) -> Self {
+        LogBlock::Table(block)
+    }
+}
+
+impl<'a> From<KeyValueBlock<'a>> for LogBlock<'a> {
+    fn from(block: KeyValueBlock<'a>) -> Self {
+        LogBlock::KeyValue(block)
+    }
+}
+
+impl<'a> From<ProgressBlock<'a>> for LogBlock<'a> {
+    fn from(block: ProgressBlock<'a>) -> Self {
+        LogBlock::Progress(block)
+    }
+}
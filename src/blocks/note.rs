@@ -1,18 +1,24 @@
-use crate::blocks::TextBlock;
+use crate::blocks::{TextBlock, TextSection};
 use crate::printer::{Printable, Printer, PrinterFormat};
 use crate::LogLevel;
+use std::borrow::Cow;
 use std::fmt::Display;
-use yansi::Style;
+use yansi::{Color, Style};
+
+/// The style of the url of a [NoteBlock].
+const URL_STYLE: Style = Style::new().fg(Color::Cyan).underline();
 
 /// A block that prints a note, i.e. a text prefixed by an equal sign.
 ///
 /// # Examples
 /// ```text
-/// = <text>
+/// = <text> see: <url>
 /// ```
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoteBlock<'a> {
     pub text: TextBlock<'a>,
+    pub url: Option<Cow<'a, str>>,
 }
 
 impl<'a> NoteBlock<'a> {
@@ -33,12 +39,21 @@ impl<'a> NoteBlock<'a> {
         self
     }
 
+    /// Sets the url of a documentation page, printed after the text as `see: <url>`.
+    /// In styled outputs it is a hyperlink when [Printer::hyperlinks] is enabled.
+    #[inline(always)]
+    pub fn url(mut self, url: impl Into<Cow<'a, str>>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
     pub fn make_owned(self) -> NoteBlock<'static> {
         NoteBlock {
             text: self.text.make_owned(),
+            url: self.url.map(|v| Cow::Owned(v.into_owned())),
         }
     }
 }
@@ -48,8 +63,64 @@ impl<'a> Printable<'a> for NoteBlock<'a> {
     where
         'a: 's,
     {
-        printer.push_styled_text("= ", Style::new().bold().fg(printer.level.color()));
-        self.text.print(printer);
+        printer.push_styled_text("= ", printer.border_style());
+
+        let prefix = TextBlock::new_plain("  ");
+        let mut content_printer = printer.derive_indented(prefix.column_count());
+        self.text.print(&mut content_printer);
+
+        if let Some(url) = &self.url {
+            if !self.text.is_empty() {
+                content_printer.push_plain_text(" ");
+            }
+
+            content_printer.push_styled_text("see: ", Style::new().bold());
+            content_printer.push_styled_text(url.clone(), URL_STYLE);
+        }
+
+        if let Some(max_width) = content_printer.max_width {
+            content_printer.wrap_lines(max_width);
+        }
+
+        if let Some(url) = &self.url {
+            if printer.format.is_styled() && Printer::hyperlinks() {
+                add_hyperlink(&mut content_printer, url);
+            }
+        }
+
+        content_printer.indent(&prefix.sections, false);
+        printer.append(content_printer);
+    }
+}
+
+/// Surrounds the trailing url sections of `printer`, which can be split across lines when
+/// wrapped, with OSC 8 escape sequences that make them a hyperlink to `url`.
+fn add_hyperlink(printer: &mut Printer, url: &str) {
+    for line in printer.lines.iter_mut().rev() {
+        let url_start = line
+            .iter()
+            .rposition(|v| v.style != URL_STYLE)
+            .map_or(0, |v| v + 1);
+
+        if url_start == line.len() {
+            break;
+        }
+
+        line.insert(
+            url_start,
+            TextSection {
+                text: Cow::Owned(format!("\x1b]8;;{url}\x1b\\")),
+                style: Style::new(),
+            },
+        );
+        line.push(TextSection {
+            text: Cow::Borrowed("\x1b]8;;\x1b\\"),
+            style: Style::new(),
+        });
+
+        if url_start != 0 {
+            break;
+        }
     }
 }
 
@@ -91,4 +162,38 @@ mod tests {
         println!("{}", text);
         assert_eq!(text, "\u{1b}[1;31m= \u{1b}[0m\u{1b}[4;33mNOTE\u{1b}[0m");
     }
+    #[test]
+    fn test_url() {
+        let log = NoteBlock::new()
+            .text("Read the docs")
+            .url("https://docs.rs/doclog");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "= Read the docs see: https://docs.rs/doclog");
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(20));
+        log.print(&mut printer);
+
+        assert_eq!(
+            printer.to_string(),
+            "= Read the docs see:\n  https://docs.rs/do\n  clog"
+        );
+    }
+
+    #[test]
+    fn test_url_hyperlink() {
+        Printer::set_hyperlinks(Some(true));
+
+        let log = NoteBlock::new().url("https://docs.rs");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+        let plain = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        Printer::set_hyperlinks(None);
+
+        assert_eq!(
+            text,
+            "\u{1b}[1;31m= \u{1b}[0m\u{1b}[1msee: \u{1b}[0m\u{1b}]8;;https://docs.rs\u{1b}\\\u{1b}[4;36mhttps://docs.rs\u{1b}[0m\u{1b}]8;;\u{1b}\\"
+        );
+        assert_eq!(plain, "= see: https://docs.rs");
+    }
 }
@@ -1,15 +1,28 @@
-use crate::blocks::TextBlock;
+use crate::blocks::{TextBlock, TextSection};
 use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::utils::whitespaces::build_space_string;
 use crate::{LogContent, LogLevel};
 use std::fmt::Display;
+use yansi::Style;
 
 /// Prints any content prefixed with a text block.
 ///
-/// When printed, prefix will get all newline characters `\n`
+/// The middle and last lines can have their own prefixes, e.g. to draw a tree-like
+/// frame `╭─`, `│ `, `╰─` around the content. Otherwise, all lines use the first prefix.
+///
+/// The lines of the content after the first one can be indented further than the first
+/// one with a hanging indent, so paragraphs that span several lines are easy to read.
+///
+/// When printed, prefixes will get all newline characters `\n`
 /// replaced by whitespaces to only occupy one line.
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrefixBlock<'a> {
     pub prefix: TextBlock<'a>,
+    pub middle_prefix: Option<TextBlock<'a>>,
+    pub last_prefix: Option<TextBlock<'a>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hanging_indent: usize,
     pub content: Box<LogContent<'a>>,
 }
 
@@ -31,6 +44,29 @@ impl<'a> PrefixBlock<'a> {
         self
     }
 
+    /// Sets a different prefix for the first, middle and last lines. A single line
+    /// content only uses the first prefix.
+    #[inline(always)]
+    pub fn prefixes(
+        mut self,
+        first: impl Into<TextBlock<'a>>,
+        middle: impl Into<TextBlock<'a>>,
+        last: impl Into<TextBlock<'a>>,
+    ) -> Self {
+        self.prefix = first.into();
+        self.middle_prefix = Some(middle.into());
+        self.last_prefix = Some(last.into());
+        self
+    }
+
+    /// Sets the number of columns the lines of the content after the first one are
+    /// indented, besides their prefix. Empty lines are not indented.
+    #[inline(always)]
+    pub fn hanging_indent(mut self, hanging_indent: usize) -> Self {
+        self.hanging_indent = hanging_indent;
+        self
+    }
+
     /// Sets the inner content.
     #[inline(always)]
     pub fn content(mut self, content: LogContent<'a>) -> Self {
@@ -44,9 +80,33 @@ impl<'a> PrefixBlock<'a> {
     pub fn make_owned(self) -> PrefixBlock<'static> {
         PrefixBlock {
             prefix: self.prefix.make_owned(),
+            middle_prefix: self.middle_prefix.map(|v| v.make_owned()),
+            last_prefix: self.last_prefix.map(|v| v.make_owned()),
+            hanging_indent: self.hanging_indent,
             content: Box::new(self.content.make_owned()),
         }
     }
+
+    /// Applies the hanging indent to the lines after the first one.
+    fn indent_continuation_lines(&self, printer: &mut Printer<'a>) {
+        if self.hanging_indent == 0 {
+            return;
+        }
+
+        for line in printer.lines.iter_mut().skip(1) {
+            if line.iter().all(|section| section.text.is_empty()) {
+                continue;
+            }
+
+            line.insert(
+                0,
+                TextSection {
+                    text: build_space_string(self.hanging_indent),
+                    style: Style::new(),
+                },
+            );
+        }
+    }
 }
 
 impl<'a> Printable<'a> for PrefixBlock<'a> {
@@ -54,11 +114,46 @@ impl<'a> Printable<'a> for PrefixBlock<'a> {
     where
         'a: 's,
     {
-        let mut content_printer = printer.derive();
+        let first_prefix = self.prefix.single_lined();
+
+        if self.middle_prefix.is_none() && self.last_prefix.is_none() {
+            let mut content_printer =
+                printer.derive_indented(first_prefix.column_count() + self.hanging_indent);
+            self.content.print(&mut content_printer);
+            self.indent_continuation_lines(&mut content_printer);
+
+            content_printer.indent(&first_prefix.sections, true);
+            printer.append(content_printer);
+            return;
+        }
+
+        let middle_prefix = match &self.middle_prefix {
+            Some(v) => v.single_lined(),
+            None => first_prefix.clone(),
+        };
+        let last_prefix = match &self.last_prefix {
+            Some(v) => v.single_lined(),
+            None => middle_prefix.clone(),
+        };
+        let columns = first_prefix
+            .column_count()
+            .max(middle_prefix.column_count())
+            .max(last_prefix.column_count());
+        let mut content_printer = printer.derive_indented(columns + self.hanging_indent);
         self.content.print(&mut content_printer);
+        self.indent_continuation_lines(&mut content_printer);
+
+        let last_index = content_printer.lines.len().saturating_sub(1);
+        for (i, line) in content_printer.lines.iter_mut().enumerate() {
+            let prefix = match i {
+                0 => &first_prefix,
+                i if i == last_index => &last_prefix,
+                _ => &middle_prefix,
+            };
+
+            line.insert_many(0, prefix.sections.iter().cloned());
+        }
 
-        let prefix = self.prefix.single_lined();
-        content_printer.indent(&prefix.sections, true);
         printer.append(content_printer);
     }
 }
@@ -115,4 +210,48 @@ mod tests {
         println!("{}", text);
         assert_eq!(text, "\u{1b}[1;34m | -> \u{1b}[0m\u{1b}[1;31mThe message\n\u{1b}[0m\u{1b}[1;34m | -> \u{1b}[0m\u{1b}[1;31min\n\u{1b}[0m\u{1b}[1;34m | -> \u{1b}[0m\u{1b}[1;31mmultiple\n\u{1b}[0m\u{1b}[1;34m | -> \u{1b}[0m\u{1b}[1;31mlines\u{1b}[0m");
     }
+
+    #[test]
+    fn test_prefixes() {
+        let content =
+            LogContent::new().add_block(TextBlock::new_plain("The message\nin\nmultiple\nlines"));
+        let log = PrefixBlock::new()
+            .prefixes("╭─ ", "│  ", "╰─ ")
+            .content(content);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─ The message\n│  in\n│  multiple\n╰─ lines");
+
+        let log = PrefixBlock::new()
+            .prefixes("╭─ ", "│  ", "╰─ ")
+            .content(LogContent::new().add_block(TextBlock::new_plain("Single")));
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─ Single");
+    }
+
+    #[test]
+    fn test_hanging_indent() {
+        let content = LogContent::new().add_block(TextBlock::new_plain(
+            "A long message\nthat continues\n\nin another paragraph",
+        ));
+        let log = PrefixBlock::new()
+            .prefixes("╰─▶ ", "    ", "    ")
+            .hanging_indent(2)
+            .content(content);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "╰─▶ A long message\n      that continues\n    \n      in another paragraph"
+        );
+
+        let log = PrefixBlock::new()
+            .prefix("> ")
+            .hanging_indent(1)
+            .content(LogContent::new().add_block(TextBlock::new_plain("First\nSecond")));
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "> First\n>  Second");
+    }
 }
@@ -0,0 +1,221 @@
+use crate::blocks::{StepStatus, TextBlock};
+use crate::constants::SPINNER_FRAMES;
+use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::LogLevel;
+use std::fmt::Display;
+use yansi::Style;
+
+/// A block that prints the progress of a long-running task. While running, it shows a spinner
+/// and, optionally, the number of completed units of work. Once finished, it shows
+/// a `✓` or `✗` mark, see [Theme::status_symbol](crate::Theme::status_symbol).
+///
+/// To refresh it in place, print the log containing it using a [LiveWriter](crate::LiveWriter).
+///
+/// # Examples
+/// ```text
+/// ⠹ Compiling (3/10)
+/// ✓ Compiling
+/// ```
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgressBlock<'a> {
+    pub message: TextBlock<'a>,
+    pub status: ProgressStatus,
+    pub current: usize,
+    pub total: Option<usize>,
+    pub tick: usize,
+}
+
+/// The status of a [ProgressBlock].
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProgressStatus {
+    #[default]
+    Running,
+    Success,
+    Failure,
+}
+
+impl<'a> ProgressBlock<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new running [ProgressBlock] with the given message.
+    #[inline(always)]
+    pub fn new(message: impl Into<TextBlock<'a>>) -> Self {
+        Self {
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns whether the task has finished, either successfully or not.
+    #[inline(always)]
+    pub fn is_finished(&self) -> bool {
+        self.status != ProgressStatus::Running
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the message.
+    #[inline(always)]
+    pub fn message(mut self, message: impl Into<TextBlock<'a>>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets the status.
+    #[inline(always)]
+    pub fn status(mut self, status: ProgressStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the number of completed units of work out of `total`.
+    #[inline(always)]
+    pub fn progress(mut self, current: usize, total: usize) -> Self {
+        self.current = current;
+        self.total = Some(total);
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Advances the spinner one frame and, if a total is set, the number of
+    /// completed units of work.
+    pub fn advance(&mut self) {
+        self.tick = self.tick.wrapping_add(1);
+
+        if let Some(total) = self.total {
+            self.current = (self.current + 1).min(total);
+        }
+    }
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> ProgressBlock<'static> {
+        ProgressBlock {
+            message: self.message.make_owned(),
+            status: self.status,
+            current: self.current,
+            total: self.total,
+            tick: self.tick,
+        }
+    }
+}
+
+impl<'a> Printable<'a> for ProgressBlock<'a> {
+    fn print<'s>(&'s self, printer: &mut Printer<'a>)
+    where
+        'a: 's,
+    {
+        match self.status {
+            ProgressStatus::Running => printer.push_styled_text(
+                format!("{} ", SPINNER_FRAMES[self.tick % SPINNER_FRAMES.len()]),
+                printer.border_style(),
+            ),
+            ProgressStatus::Success | ProgressStatus::Failure => {
+                let status = if self.status == ProgressStatus::Success {
+                    StepStatus::Success
+                } else {
+                    StepStatus::Failure
+                };
+
+                printer.push_styled_text(
+                    format!("{} ", printer.theme.status_symbol(status)),
+                    Style::new()
+                        .bold()
+                        .fg(printer.theme.level_color(status.level())),
+                );
+            }
+        }
+
+        self.message.single_lined().print(printer);
+
+        if let (ProgressStatus::Running, Some(total)) = (self.status, self.total) {
+            printer.push_styled_text(
+                format!(" ({}/{})", self.current, total),
+                printer.theme.gutter_style(),
+            );
+        }
+    }
+}
+
+impl<'a> Display for ProgressBlock<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut printer = Printer::new(LogLevel::trace(), PrinterFormat::Plain);
+        self.print(&mut printer);
+        printer.fmt(f, PrinterFormat::Plain)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LevelSymbols, Theme};
+
+    #[test]
+    fn test_plain() {
+        // Running
+        let mut log = ProgressBlock::new("Compiling").progress(2, 3);
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(text, "⠋ Compiling (2/3)");
+
+        log.advance();
+        log.advance();
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(text, "⠹ Compiling (3/3)");
+
+        // Success
+        let log = log.status(ProgressStatus::Success);
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(text, "✓ Compiling");
+
+        // Failure
+        let log = log.status(ProgressStatus::Failure);
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(text, "✗ Compiling");
+    }
+
+    #[test]
+    fn test_theme() {
+        let theme = Theme::light().symbols(LevelSymbols::ascii());
+        let log = ProgressBlock::new("Compiling").status(ProgressStatus::Success);
+        let mut printer = Printer::new(LogLevel::info(), PrinterFormat::Styled).theme(theme);
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "\u{1b}[1;38;5;28m+ \u{1b}[0mCompiling");
+
+        let log = log.status(ProgressStatus::Failure);
+        let mut printer = Printer::new(LogLevel::info(), PrinterFormat::Plain).theme(theme);
+        log.print(&mut printer);
+
+        assert_eq!(printer.to_string(), "x Compiling");
+    }
+
+    #[test]
+    fn test_styled() {
+        let log = ProgressBlock::new("Compiling").progress(2, 3);
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(
+            text,
+            "\u{1b}[1;34m⠋ \u{1b}[0mCompiling\u{1b}[1;90m (2/3)\u{1b}[0m"
+        );
+
+        let log = log.status(ProgressStatus::Success);
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(text, "\u{1b}[1;32m✓ \u{1b}[0mCompiling");
+    }
+}
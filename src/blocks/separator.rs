@@ -1,5 +1,7 @@
 use crate::constants::HORIZONTAL_BAR;
 use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::render_session::detect_terminal_width;
+use crate::utils::text::count_columns;
 use crate::LogLevel;
 use const_format::{concatcp, formatcp};
 use std::borrow::Cow;
@@ -12,10 +14,20 @@ const _: () = {
     assert!(HORIZONTAL_BARS.len() == HORIZONTAL_BAR.len_utf8() * N_HORIZONTAL_BARS);
 };
 
-/// A block that prints a line separator repeating a character.
+/// A block that prints a line separator repeating a character, optionally with
+/// a label centered inside it.
+///
+/// # Examples
+/// ```text
+/// ──────────────
+/// ─── tests ────
+/// ```
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeparatorBlock {
     pub width: usize,
+    pub label: Option<Cow<'static, str>>,
+    pub full_width: bool,
     character: char,
 }
 
@@ -32,7 +44,12 @@ impl SeparatorBlock {
             character, '\n',
             "The character cannot be a newline character."
         );
-        Self { width, character }
+        Self {
+            width,
+            label: None,
+            full_width: false,
+            character,
+        }
     }
 
     /// Creates a new [SeparatorBlock] with a width of `width` using the [HORIZONTAL_BAR] character.
@@ -40,6 +57,8 @@ impl SeparatorBlock {
     pub fn with_width(width: usize) -> Self {
         Self {
             width,
+            label: None,
+            full_width: false,
             character: HORIZONTAL_BAR,
         }
     }
@@ -49,6 +68,8 @@ impl SeparatorBlock {
     pub fn with_white() -> Self {
         Self {
             width: 0,
+            label: None,
+            full_width: false,
             character: ' ',
         }
     }
@@ -98,6 +119,41 @@ impl SeparatorBlock {
         self.character = character;
         self
     }
+
+    /// Sets a label to print centered inside the separator. Newline characters
+    /// are replaced by whitespaces.
+    #[inline(always)]
+    pub fn with_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Makes the separator span the whole width available: the width budget of the
+    /// printer if set, otherwise the width of the terminal. When neither is known,
+    /// [SeparatorBlock::width] is used.
+    #[inline(always)]
+    pub fn full_width(mut self) -> Self {
+        self.full_width = true;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the separator character repeated `width` times.
+    fn repeat_character(&self, width: usize) -> Cow<'static, str> {
+        match self.character {
+            // Whitespaces are not seen in the terminal, so we use an empty string to skip it.
+            c if c.is_whitespace() => Cow::Borrowed(""),
+            HORIZONTAL_BAR => {
+                if width < N_HORIZONTAL_BARS {
+                    Cow::Borrowed(&HORIZONTAL_BARS[0..(width * HORIZONTAL_BAR.len_utf8())])
+                } else {
+                    Cow::Owned(concatcp!(HORIZONTAL_BAR).repeat(width))
+                }
+            }
+            _ => Cow::Owned(format!("{}", self.character).repeat(width)),
+        }
+    }
 }
 
 impl<'a> Printable<'a> for SeparatorBlock {
@@ -105,23 +161,37 @@ impl<'a> Printable<'a> for SeparatorBlock {
     where
         'a: 's,
     {
-        if self.width == 0 {
+        // The separator never exceeds the width budget of the printer.
+        let width = match (printer.max_width, self.full_width) {
+            (Some(max_width), true) => max_width,
+            (None, true) => detect_terminal_width().unwrap_or(self.width),
+            (Some(max_width), false) => self.width.min(max_width),
+            (None, false) => self.width,
+        };
+
+        if width == 0 {
             return;
         }
 
-        let separator = match self.character {
-            // Whitespaces are not seen in the terminal, so we use an empty string to skip it.
-            c if c.is_whitespace() => Cow::Borrowed(""),
-            HORIZONTAL_BAR => {
-                if self.width < N_HORIZONTAL_BARS {
-                    Cow::Borrowed(&HORIZONTAL_BARS[0..(self.width * HORIZONTAL_BAR.len_utf8())])
-                } else {
-                    Cow::Owned(concatcp!(HORIZONTAL_BAR).repeat(self.width))
-                }
-            }
-            _ => Cow::Owned(format!("{}", self.character).repeat(self.width)),
+        let Some(label) = &self.label else {
+            printer.push_styled_text(self.repeat_character(width), printer.border_style());
+            return;
         };
-        printer.push_styled_text(separator, Style::new().bold().fg(printer.level.color()));
+
+        // The label is surrounded by a whitespace at each side and clipped to the width.
+        let label: String = format!(" {} ", label.replace('\n', " "))
+            .chars()
+            .take(width)
+            .collect();
+        let remaining = width - count_columns(&label);
+        let left = remaining / 2;
+
+        printer.push_styled_text(self.repeat_character(left), printer.border_style());
+        printer.push_styled_text(label, Style::new().bold());
+        printer.push_styled_text(
+            self.repeat_character(remaining - left),
+            printer.border_style(),
+        );
     }
 }
 
@@ -140,9 +210,49 @@ impl Display for SeparatorBlock {
 #[cfg(test)]
 mod tests {
     use crate::blocks::SeparatorBlock;
-    use crate::printer::{Printable, PrinterFormat};
+    use crate::printer::{Printable, Printer, PrinterFormat};
     use crate::LogLevel;
 
+    #[test]
+    fn test_max_width() {
+        let log = SeparatorBlock::new(10, '/');
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(4));
+        log.print(&mut printer);
+        assert_eq!(printer.to_string(), "////");
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(0));
+        log.print(&mut printer);
+        assert_eq!(printer.to_string(), "");
+    }
+
+    #[test]
+    fn test_label() {
+        let log = SeparatorBlock::with_width(14).with_label("tests");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "─── tests ────");
+
+        let log = SeparatorBlock::new(4, '/').with_label("tests");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, " tes");
+    }
+
+    #[test]
+    fn test_full_width() {
+        let log = SeparatorBlock::new(2, '/').full_width();
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(6));
+        log.print(&mut printer);
+        assert_eq!(printer.to_string(), "//////");
+
+        let log = log.with_label("a");
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(6));
+        log.print(&mut printer);
+        assert_eq!(printer.to_string(), "/ a //");
+    }
+
     #[test]
     fn test_plain() {
         let log = SeparatorBlock::new(0, '/');
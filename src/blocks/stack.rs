@@ -1,19 +1,22 @@
 use crate::blocks::{StackTraceBlock, TextBlock};
 use crate::constants::{
-    BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, RIGHT_ARROW, TOP_RIGHT_CORNER, VERTICAL_BAR,
+    BOTTOM_RIGHT_CORNER, ELLIPSIS, HORIZONTAL_BAR, RIGHT_ARROW, TOP_RIGHT_CORNER, VERTICAL_BAR,
     VERTICAL_RIGHT_BAR,
 };
 use crate::printer::{Printable, Printer, PrinterFormat};
 use crate::utils::whitespaces::build_space_string;
 use crate::LogLevel;
 use const_format::concatcp;
+use std::backtrace::{Backtrace, BacktraceStatus};
 use std::borrow::Cow;
+use std::error::Error;
 use std::fmt::Display;
 use std::mem;
-use yansi::Style;
+use std::ops::Range;
 
 /// An error stack block.
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackBlock<'a> {
     pub message: TextBlock<'a>,
     pub traces: Vec<StackTraceBlock<'a>>,
@@ -22,6 +25,11 @@ pub struct StackBlock<'a> {
 
     /// Whether to print the stack in the wrapped-by format.
     pub wrapped_by_format: bool,
+
+    /// The ranges of consecutive traces printed as a single summary row, sorted and
+    /// without overlaps. See [StackBlock::collapse_frames].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collapsed_traces: Vec<Range<usize>>,
 }
 
 impl<'a> StackBlock<'a> {
@@ -33,6 +41,18 @@ impl<'a> StackBlock<'a> {
         StackBlock::default()
     }
 
+    /// Creates a new [StackBlock] from an error, walking its [Error::source] chain
+    /// to build the nested causes.
+    pub fn from_error(error: &dyn Error) -> Self {
+        let mut block = StackBlock::new().message(error.to_string());
+
+        if let Some(source) = error.source() {
+            block = block.cause(StackBlock::from_error(source));
+        }
+
+        block
+    }
+
     // BUILDERS ---------------------------------------------------------------
 
     /// Sets the message.
@@ -49,6 +69,16 @@ impl<'a> StackBlock<'a> {
         self
     }
 
+    /// Adds the frames of a backtrace as stack traces. Nothing is added if the backtrace
+    /// has not been captured.
+    pub fn backtrace(mut self, backtrace: &Backtrace) -> Self {
+        if backtrace.status() == BacktraceStatus::Captured {
+            self.traces.extend(parse_backtrace(&backtrace.to_string()));
+        }
+
+        self
+    }
+
     /// Sets the cause.
     #[inline(always)]
     pub fn cause(mut self, cause: StackBlock<'a>) -> Self {
@@ -70,8 +100,47 @@ impl<'a> StackBlock<'a> {
         self
     }
 
+    /// Collapses the runs of consecutive traces matching `predicate`, e.g. the frames from
+    /// `std::` or `tokio::`, into a single `… N frames from tokio …` row, so the frames of
+    /// the user code stand out. Runs of a single trace are not collapsed. It also applies
+    /// to the causes, and can be called several times with different predicates.
+    pub fn collapse_frames(mut self, predicate: impl Fn(&StackTraceBlock<'a>) -> bool) -> Self {
+        self.collapse_frames_with(&predicate);
+        self
+    }
+
     // METHODS ----------------------------------------------------------------
 
+    /// Collapses the matching traces of this stack and its causes recursively.
+    fn collapse_frames_with(&mut self, predicate: &dyn Fn(&StackTraceBlock<'a>) -> bool) {
+        let mut collapsed_traces = Vec::new();
+        let mut run_start = None;
+
+        for index in 0..=self.traces.len() {
+            let is_collapsed = self.traces.get(index).is_some_and(|trace| {
+                predicate(trace) || self.collapsed_traces.iter().any(|v| v.contains(&index))
+            });
+
+            match (is_collapsed, run_start) {
+                (true, None) => run_start = Some(index),
+                (false, Some(start)) => {
+                    if index - start > 1 {
+                        collapsed_traces.push(start..index);
+                    }
+
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+
+        self.collapsed_traces = collapsed_traces;
+
+        if let Some(cause) = &mut self.cause {
+            cause.collapse_frames_with(predicate);
+        }
+    }
+
     /// Count traces of the stack and its cause recursively.
     fn count_traces(&self) -> usize {
         self.traces.len() + self.cause.as_ref().map_or(0, |v| v.count_traces())
@@ -98,75 +167,45 @@ impl<'a> StackBlock<'a> {
                     RIGHT_ARROW,
                     " Caused by: "
                 ),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         } else if self.message.is_empty() {
             printer.push_styled_text(
                 concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, ' '),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         } else {
             printer.push_styled_text(
                 concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, RIGHT_ARROW, ' '),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         }
 
         {
-            let mut message_printer = printer.derive();
-            self.message.print(&mut message_printer);
-
             let prefix = TextBlock::new().add_styled_text(
                 if is_cause {
                     concatcp!(VERTICAL_BAR, "     ")
                 } else {
                     concatcp!(VERTICAL_BAR, "   ")
                 },
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
+            let mut message_printer = printer.derive_indented(prefix.column_count());
+            self.message.print(&mut message_printer);
+
             message_printer.indent(&prefix.sections, false);
             printer.append(message_printer);
         }
 
         // Traces
-        let trace_prefix = TextBlock::new().add_styled_text(
-            concatcp!(VERTICAL_BAR, "  "),
-            Style::new().bold().fg(printer.level.color()),
-        );
-        let full_trace_prefix = trace_prefix.clone().add_styled_text(
-            build_space_string(max_trace_digits + 2),
-            Style::new().bold().fg(printer.level.color()),
-        );
-
-        let mut trace_printer = printer.derive();
-        let mut next_trace_number = 0;
-        for trace in self.traces.iter() {
-            printer.push_plain_text(Cow::Borrowed("\n"));
-            trace_prefix.print(printer);
-
-            let number = self.traces.len() - next_trace_number + initial_trace_number;
-            next_trace_number += 1;
-
-            if self.show_stack_numbers {
-                printer.push_styled_text(
-                    format!("[{:>width$}] ", number, width = max_trace_digits),
-                    Style::new().bold().fg(printer.level.color()),
-                );
-            } else {
-                printer.push_styled_text(" at ", Style::new().bold().fg(printer.level.color()));
-            }
-
-            trace.print(&mut trace_printer);
-            trace_printer.indent(&full_trace_prefix.sections, false);
-            printer.append(mem::replace(&mut trace_printer, printer.derive()));
-        }
+        self.print_traces(printer, initial_trace_number, max_trace_digits);
 
         // Cause
         if let Some(cause) = &self.cause {
             cause.print_as_caused_by(
                 printer,
-                next_trace_number + initial_trace_number,
+                self.traces.len() + initial_trace_number,
                 max_trace_digits,
                 true,
             );
@@ -176,7 +215,7 @@ impl<'a> StackBlock<'a> {
         if !is_cause {
             printer.push_styled_text(
                 concatcp!('\n', TOP_RIGHT_CORNER, HORIZONTAL_BAR),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         }
     }
@@ -215,73 +254,107 @@ impl<'a> StackBlock<'a> {
                     RIGHT_ARROW,
                     " Wrapped by: "
                 ),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         } else if self.message.is_empty() {
             printer.push_styled_text(
                 concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, ' '),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         } else {
             printer.push_styled_text(
                 concatcp!(BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, RIGHT_ARROW, ' '),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
         }
 
         {
-            let mut message_printer = printer.derive();
-            self.message.print(&mut message_printer);
-
             let prefix = TextBlock::new().add_styled_text(
                 if is_cause {
                     concatcp!(VERTICAL_BAR, "     ")
                 } else {
                     concatcp!(VERTICAL_BAR, "   ")
                 },
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
+            let mut message_printer = printer.derive_indented(prefix.column_count());
+            self.message.print(&mut message_printer);
+
             message_printer.indent(&prefix.sections, false);
             printer.append(message_printer);
         }
 
         // Traces
-        let trace_prefix = TextBlock::new().add_styled_text(
-            concatcp!(VERTICAL_BAR, "  "),
-            Style::new().bold().fg(printer.level.color()),
-        );
+        self.print_traces(printer, initial_trace_number, max_trace_digits);
+
+        // Final line
+        if is_root {
+            printer.push_styled_text(
+                concatcp!('\n', TOP_RIGHT_CORNER, HORIZONTAL_BAR),
+                printer.border_style(),
+            );
+        }
+    }
+
+    /// Prints the traces of the stack, replacing the collapsed ones with a summary row.
+    fn print_traces(
+        &self,
+        printer: &mut Printer<'a>,
+        initial_trace_number: usize,
+        max_trace_digits: usize,
+    ) {
+        let trace_prefix =
+            TextBlock::new().add_styled_text(concatcp!(VERTICAL_BAR, "  "), printer.border_style());
         let full_trace_prefix = trace_prefix.clone().add_styled_text(
             build_space_string(max_trace_digits + 2),
-            Style::new().bold().fg(printer.level.color()),
+            printer.border_style(),
         );
 
-        let mut trace_printer = printer.derive();
-        for (next_trace_number, trace) in self.traces.iter().enumerate() {
-            printer.push_plain_text(Cow::Borrowed("\n"));
+        let mut trace_printer = printer.derive_indented(full_trace_prefix.column_count());
+        let mut next_trace_number = 0;
+        while let Some(trace) = self.traces.get(next_trace_number) {
+            printer.new_line();
             trace_prefix.print(printer);
 
+            // Collapsed traces.
+            if let Some(range) = self
+                .collapsed_traces
+                .iter()
+                .find(|v| v.start == next_trace_number && v.end <= self.traces.len())
+            {
+                printer.push_plain_text(if self.show_stack_numbers {
+                    build_space_string(max_trace_digits + 3)
+                } else {
+                    Cow::Borrowed("    ")
+                });
+                printer.push_styled_text(
+                    collapsed_traces_summary(&self.traces[range.clone()]),
+                    printer.theme.gutter_style(),
+                );
+
+                next_trace_number = range.end;
+                continue;
+            }
+
             if self.show_stack_numbers {
                 let number = self.traces.len() - next_trace_number + initial_trace_number;
                 printer.push_styled_text(
                     format!("[{:>width$}] ", number, width = max_trace_digits),
-                    Style::new().bold().fg(printer.level.color()),
+                    printer.border_style(),
                 );
             } else {
-                printer.push_styled_text(" at ", Style::new().bold().fg(printer.level.color()));
+                printer.push_styled_text(" at ", printer.border_style());
             }
 
+            next_trace_number += 1;
+
             trace.print(&mut trace_printer);
             trace_printer.indent(&full_trace_prefix.sections, false);
-            printer.append(mem::replace(&mut trace_printer, printer.derive()));
-        }
-
-        // Final line
-        if is_root {
-            printer.push_styled_text(
-                concatcp!('\n', TOP_RIGHT_CORNER, HORIZONTAL_BAR),
-                Style::new().bold().fg(printer.level.color()),
-            );
+            printer.append(mem::replace(
+                &mut trace_printer,
+                printer.derive_indented(full_trace_prefix.column_count()),
+            ));
         }
     }
 
@@ -293,6 +366,7 @@ impl<'a> StackBlock<'a> {
             cause: self.cause.map(|v| Box::new(v.make_owned())),
             show_stack_numbers: self.show_stack_numbers,
             wrapped_by_format: self.wrapped_by_format,
+            collapsed_traces: self.collapsed_traces,
         }
     }
 }
@@ -320,6 +394,102 @@ impl<'a> Display for StackBlock<'a> {
     }
 }
 
+#[cfg(feature = "anyhow")]
+impl<'a> From<&anyhow::Error> for StackBlock<'a> {
+    fn from(error: &anyhow::Error) -> Self {
+        StackBlock::from_error(error.as_ref()).backtrace(error.backtrace())
+    }
+}
+
+#[cfg(feature = "eyre")]
+impl<'a> From<&eyre::Report> for StackBlock<'a> {
+    fn from(error: &eyre::Report) -> Self {
+        StackBlock::from_error(error.as_ref())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Returns the summary row of collapsed traces, e.g. `… 12 frames from tokio …`. The crate is
+/// only included if all the traces belong to the same one.
+fn collapsed_traces_summary(traces: &[StackTraceBlock]) -> String {
+    let crate_names: Vec<String> = traces
+        .iter()
+        .map(|trace| {
+            let code_path = trace.code_path.to_string();
+            let code_path = code_path.trim_start_matches('<');
+
+            code_path
+                .split_once("::")
+                .map_or(code_path, |(crate_name, _)| crate_name)
+                .to_string()
+        })
+        .collect();
+
+    match crate_names.first() {
+        Some(crate_name)
+            if !crate_name.is_empty() && crate_names.iter().all(|v| v == crate_name) =>
+        {
+            format!(
+                "{ELLIPSIS} {} frames from {} {ELLIPSIS}",
+                traces.len(),
+                crate_name
+            )
+        }
+        _ => format!("{ELLIPSIS} {} frames {ELLIPSIS}", traces.len()),
+    }
+}
+
+/// Parses the textual representation of a [Backtrace] into stack traces.
+///
+/// Each frame has the form `N: path::to::function` optionally followed by
+/// a line `at /path/to/file.rs:line:column`.
+pub(crate) fn parse_backtrace<'a>(backtrace: &str) -> Vec<StackTraceBlock<'a>> {
+    let mut traces: Vec<StackTraceBlock<'a>> = Vec::new();
+
+    for line in backtrace.lines() {
+        let line = line.trim();
+
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some(trace) = traces.last_mut() {
+                if trace.file_location.is_empty() {
+                    let (path, line, column) = parse_location(location);
+
+                    trace.file_location = TextBlock::new_plain(path.to_string());
+                    trace.line = line;
+                    trace.column = column;
+                }
+            }
+        } else if let Some((number, code_path)) = line.split_once(": ") {
+            if !number.is_empty() && number.bytes().all(|v| v.is_ascii_digit()) {
+                traces.push(StackTraceBlock::new().code_path(code_path.to_string()));
+            }
+        }
+    }
+
+    traces
+}
+
+/// Splits a `path:line:column` location into its parts. The line and column are optional.
+fn parse_location(location: &str) -> (&str, Option<usize>, Option<usize>) {
+    let Some((rest, last)) = location
+        .rsplit_once(':')
+        .and_then(|(rest, last)| Some((rest, last.parse().ok()?)))
+    else {
+        return (location, None, None);
+    };
+
+    match rest
+        .rsplit_once(':')
+        .and_then(|(path, line)| Some((path, line.parse().ok()?)))
+    {
+        Some((path, line)) => (path, Some(line), Some(last)),
+        None => (rest, Some(last), None),
+    }
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
@@ -786,4 +956,106 @@ mod tests {
         println!("{}", text);
         assert_eq!(text, "\u{1b}[1;31m╭─▶ \u{1b}[0mCause\n\u{1b}[1;31m│   \u{1b}[0mnumber2\n\u{1b}[1;31m│  [6] \u{1b}[0m/a/b/c\u{1b}[1;31m(\u{1b}[0mcrate::x\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message\n\u{1b}[1;31m│  [5] \u{1b}[0m/a/b/c/2\u{1b}[1;31m(\u{1b}[0mcrate::x::2\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message2\n\u{1b}[1;31m├───▶ Wrapped by: \u{1b}[0mCause\n\u{1b}[1;31m│     \u{1b}[0mnumber1\n\u{1b}[1;31m│   at \u{1b}[0m/a/b/c\u{1b}[1;31m(\u{1b}[0mcrate::x\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message\n\u{1b}[1;31m│   at \u{1b}[0m/a/b/c/2\u{1b}[1;31m(\u{1b}[0mcrate::x::2\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message2\n\u{1b}[1;31m├───▶ Wrapped by: \u{1b}[0mThis is\n\u{1b}[1;31m│     \u{1b}[0ma message\n\u{1b}[1;31m│  [2] \u{1b}[0m/a/b/c\u{1b}[1;31m(\u{1b}[0mcrate::x\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message\n\u{1b}[1;31m│  [1] \u{1b}[0m/a/b/c/2\u{1b}[1;31m(\u{1b}[0mcrate::x::2\u{1b}[1;31m) - \u{1b}[0mThis is a \n\u{1b}[1;31m│     \u{1b}[0m message2\n\u{1b}[1;31m╰─\u{1b}[0m");
     }
+
+    #[test]
+    fn test_from_error() {
+        #[derive(Debug)]
+        struct TestError(&'static str, Option<Box<TestError>>);
+
+        impl Display for TestError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl Error for TestError {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                self.1.as_deref().map(|v| v as &(dyn Error + 'static))
+            }
+        }
+
+        let error = TestError(
+            "Cannot read config",
+            Some(Box::new(TestError("File not found", None))),
+        );
+        let log = StackBlock::from_error(&error);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "╭─▶ Cannot read config\n├───▶ Caused by: File not found\n╰─"
+        );
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_from_anyhow() {
+        let error = anyhow::anyhow!("File not found").context("Cannot read config");
+        let log = StackBlock::from(&error);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert!(text.starts_with("╭─▶ Cannot read config\n"));
+        assert!(text.contains("├───▶ Caused by: File not found"));
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn test_from_eyre() {
+        let error = eyre::eyre!("File not found").wrap_err("Cannot read config");
+        let log = StackBlock::from(&error);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "╭─▶ Cannot read config\n├───▶ Caused by: File not found\n╰─"
+        );
+    }
+
+    #[test]
+    fn test_parse_backtrace() {
+        let backtrace = "   0: doclog::main\n             at ./src/main.rs:10:5\n   1: core::ops::function::FnOnce::call_once\n   2: std::rt::lang_start\n             at /rustc/library/std/src/rt.rs:158:17";
+        let log = StackBlock {
+            traces: parse_backtrace(backtrace),
+            ..StackBlock::new()
+        };
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─ \n│   at ./src/main.rs:10:5(doclog::main)\n│   at <unknown location>(core::ops::function::FnOnce::call_once)\n│   at /rustc/library/std/src/rt.rs:158:17(std::rt::lang_start)\n╰─");
+        assert_eq!(log.traces[0].file_location.to_string(), "./src/main.rs");
+        assert_eq!(
+            (log.traces[0].line, log.traces[0].column),
+            (Some(10), Some(5))
+        );
+        assert_eq!(
+            parse_location("C:\\src\\main.rs:7"),
+            ("C:\\src\\main.rs", Some(7), None)
+        );
+        assert_eq!(parse_location("src/main.rs"), ("src/main.rs", None, None));
+    }
+
+    #[test]
+    fn test_collapse_frames() {
+        let backtrace = "   0: tokio::runtime::poll\n   1: tokio::runtime::run\n   2: app::main\n   3: std::rt::lang_start\n   4: core::ops::call_once\n   5: std::rt::main\n   6: app::start";
+        let log = StackBlock {
+            traces: parse_backtrace(backtrace),
+            ..StackBlock::new()
+        }
+        .collapse_frames(|trace| trace.code_path.to_string().starts_with("tokio::"))
+        .collapse_frames(|trace| {
+            let code_path = trace.code_path.to_string();
+            code_path.starts_with("std::") || code_path.starts_with("core::")
+        });
+
+        assert_eq!(log.collapsed_traces, vec![0..2, 3..6]);
+
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─ \n│      … 2 frames from tokio …\n│   at <unknown location>(app::main)\n│      … 3 frames …\n│   at <unknown location>(app::start)\n╰─");
+
+        let text = log
+            .show_stack_numbers(true)
+            .print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─ \n│      … 2 frames from tokio …\n│  [5] <unknown location>(app::main)\n│      … 3 frames …\n│  [1] <unknown location>(app::start)\n╰─");
+    }
 }
@@ -3,20 +3,41 @@ use crate::printer::{Printable, Printer, PrinterFormat};
 use crate::LogLevel;
 use std::borrow::Cow;
 use std::fmt::Display;
-use yansi::Style;
 
 /// A trace message of a stack block. It can include a file location, a path inside the code
 /// and a message.
 ///
+/// The line and column of the location are appended to it following the
+/// [LocationFormat], so terminals and IDEs can recognize it as a clickable location.
+///
 /// When printed, location and path will get all newline characters `\n`
 /// replaced by whitespaces to only occupy one line.
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackTraceBlock<'a> {
     pub file_location: TextBlock<'a>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub line: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub column: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub location_format: LocationFormat,
     pub code_path: TextBlock<'a>,
     pub message: TextBlock<'a>,
 }
 
+/// The format of the line and column appended to the location of a [StackTraceBlock].
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LocationFormat {
+    /// `path:line:column`, recognized by most terminals and editors.
+    #[default]
+    Colon,
+
+    /// `path(line,column)`, used by MSVC and Visual Studio.
+    Parentheses,
+}
+
 impl<'a> StackTraceBlock<'a> {
     // CONSTRUCTORS -----------------------------------------------------------
 
@@ -32,6 +53,24 @@ impl<'a> StackTraceBlock<'a> {
         self
     }
 
+    /// Sets the 1-based line of the file location.
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the 1-based column of the file location. It is only printed along with the line.
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the format of the line and column.
+    pub fn location_format(mut self, location_format: LocationFormat) -> Self {
+        self.location_format = location_format;
+        self
+    }
+
     /// Sets the inner code path.
     pub fn code_path(mut self, code_path: impl Into<TextBlock<'a>>) -> Self {
         self.code_path = code_path.into();
@@ -50,6 +89,9 @@ impl<'a> StackTraceBlock<'a> {
     pub fn make_owned(self) -> StackTraceBlock<'static> {
         StackTraceBlock {
             file_location: self.file_location.make_owned(),
+            line: self.line,
+            column: self.column,
+            location_format: self.location_format,
             code_path: self.code_path.make_owned(),
             message: self.message.make_owned(),
         }
@@ -68,22 +110,28 @@ impl<'a> Printable<'a> for StackTraceBlock<'a> {
             printer.push_plain_text("<unknown location>");
         }
 
+        // Print line and column.
+        if let Some(line) = self.line {
+            let position = match (self.location_format, self.column) {
+                (LocationFormat::Colon, Some(column)) => format!(":{line}:{column}"),
+                (LocationFormat::Colon, None) => format!(":{line}"),
+                (LocationFormat::Parentheses, Some(column)) => format!("({line},{column})"),
+                (LocationFormat::Parentheses, None) => format!("({line})"),
+            };
+
+            printer.push_plain_text(position);
+        }
+
         // Print code path.
         if !self.code_path.is_empty() {
-            printer.push_styled_text(
-                Cow::Borrowed("("),
-                Style::new().bold().fg(printer.level.color()),
-            );
+            printer.push_styled_text(Cow::Borrowed("("), printer.border_style());
             self.code_path.single_lined().print(printer);
-            printer.push_styled_text(
-                Cow::Borrowed(")"),
-                Style::new().bold().fg(printer.level.color()),
-            );
+            printer.push_styled_text(Cow::Borrowed(")"), printer.border_style());
         }
 
         // Print message.
         if !self.message.is_empty() {
-            printer.push_styled_text(" - ", Style::new().bold().fg(printer.level.color()));
+            printer.push_styled_text(" - ", printer.border_style());
             self.message.print(printer);
         }
     }
@@ -146,6 +194,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_position() {
+        let log = StackTraceBlock::new()
+            .file_location("src/main.rs")
+            .line(15)
+            .column(24)
+            .code_path("crate::main");
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "src/main.rs:15:24(crate::main)");
+
+        let log = log.location_format(LocationFormat::Parentheses);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "src/main.rs(15,24)(crate::main)");
+
+        let log = StackTraceBlock::new().file_location("src/main.rs").line(15);
+
+        assert_eq!(log.to_string(), "src/main.rs:15");
+        assert_eq!(
+            log.location_format(LocationFormat::Parentheses).to_string(),
+            "src/main.rs(15)"
+        );
+    }
+
     #[test]
     fn test_styled() {
         // Empty
@@ -1,21 +1,88 @@
-use crate::blocks::{LogBlock, TextBlock};
+use crate::blocks::{LogBlock, TextBlock, TextSection};
 use crate::constants::{
-    HORIZONTAL_BAR, RIGHT_ARROW, TOP_RIGHT_CORNER, VERTICAL_BAR, VERTICAL_RIGHT_BAR,
+    CHECK_MARK, CROSS_MARK, HORIZONTAL_BAR, HORIZONTAL_BOTTOM_BAR, RIGHT_ARROW, TOP_LEFT_CORNER,
+    TOP_RIGHT_CORNER, VERTICAL_BAR, VERTICAL_RIGHT_BAR, WHITE_CIRCLE,
 };
 use crate::printer::{Printable, Printer, PrinterFormat};
-use crate::{LogContent, LogLevel};
+use crate::utils::time::format_duration;
+use crate::utils::whitespaces::build_space_string;
+use crate::{LogContent, LogLevel, Theme};
 use const_format::concatcp;
 use std::borrow::Cow;
 use std::fmt::Display;
 use std::option::Option::Some;
+use std::time::Duration;
 use yansi::Style;
 
+/// The connector of a step.
+const STEP_CONNECTOR: &str = concatcp!(VERTICAL_RIGHT_BAR, HORIZONTAL_BAR, RIGHT_ARROW, ' ');
+
+/// The prefix of the lines of a step after its first one.
+const STEP_PREFIX: &str = concatcp!(VERTICAL_BAR, "   ");
+
+/// The connector of the first branch of a parallel step, that forks from the steps.
+const FORK_CONNECTOR: &str = concatcp!(
+    VERTICAL_RIGHT_BAR,
+    HORIZONTAL_BAR,
+    HORIZONTAL_BOTTOM_BAR,
+    HORIZONTAL_BAR,
+    RIGHT_ARROW,
+    ' '
+);
+
+/// The connector of the other branches of a parallel step.
+const BRANCH_CONNECTOR: &str = concatcp!(
+    VERTICAL_BAR,
+    ' ',
+    VERTICAL_RIGHT_BAR,
+    HORIZONTAL_BAR,
+    RIGHT_ARROW,
+    ' '
+);
+
+/// The prefix of the lines of a branch after its first one.
+const BRANCH_PREFIX: &str = concatcp!(VERTICAL_BAR, ' ', VERTICAL_BAR, "   ");
+
+/// The line that joins the branches of a parallel step back into the steps.
+const JOIN_LINE: &str = concatcp!(VERTICAL_RIGHT_BAR, HORIZONTAL_BAR, TOP_LEFT_CORNER);
+
 /// A block that prints a section of a document.
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StepsBlock<'a> {
     pub title: TextBlock<'a>,
     pub final_message: TextBlock<'a>,
     pub steps: Box<LogContent<'a>>,
+
+    /// The number and status of every step, by index. Missing entries are plain steps.
+    pub markers: Vec<StepMarker>,
+
+    /// Whether to print a summary of the statuses of the steps as the last line.
+    pub show_status_summary: bool,
+
+    /// Whether the steps are parallel branches, printed as a fork when this block is a
+    /// step of another [StepsBlock]. See [StepsBlock::add_parallel].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub parallel: bool,
+}
+
+/// The number and status shown before a step of a [StepsBlock], and the elapsed time
+/// shown after its first line.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepMarker {
+    pub number: Option<usize>,
+    pub status: Option<StepStatus>,
+    pub elapsed: Option<Duration>,
+}
+
+/// The status of a step of a [StepsBlock].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepStatus {
+    Pending,
+    Success,
+    Failure,
 }
 
 impl<'a> StepsBlock<'a> {
@@ -27,18 +94,77 @@ impl<'a> StepsBlock<'a> {
             title: TextBlock::new(),
             final_message: TextBlock::new(),
             steps: Box::new(LogContent::new()),
+            markers: Vec::new(),
+            show_status_summary: false,
+            parallel: false,
         }
     }
 
     // GETTERS ----------------------------------------------------------------
 
-    /// Returns the maximum line to print.
-    fn max_line(&self) -> usize {
+    /// Returns the marker of the step at `index`.
+    pub fn marker(&self, index: usize) -> StepMarker {
+        self.markers.get(index).copied().unwrap_or_default()
+    }
+
+    /// Returns a summary of the statuses of the steps, e.g. `2 succeeded, 1 failed`,
+    /// or an empty text if no step has a status.
+    pub fn status_summary(&self) -> TextBlock<'static> {
+        self.status_summary_with_theme(&Theme::global())
+    }
+
+    fn status_summary_with_theme<'b>(&self, theme: &Theme) -> TextBlock<'b> {
+        let count = |status: StepStatus| {
+            self.markers
+                .iter()
+                .filter(|v| v.status == Some(status))
+                .count()
+        };
+        let mut summary = TextBlock::new();
+
+        for (status, label) in [
+            (StepStatus::Success, "succeeded"),
+            (StepStatus::Failure, "failed"),
+            (StepStatus::Pending, "pending"),
+        ] {
+            let count = count(status);
+
+            if count == 0 {
+                continue;
+            }
+
+            if !summary.is_empty() {
+                summary = summary.add_plain_text(", ");
+            }
+
+            summary = summary.add_styled_text(
+                format!("{count} {label}"),
+                Style::new().bold().fg(theme.level_color(status.level())),
+            );
+        }
+
+        summary
+    }
+
+    /// Returns the number of columns of the widest step number.
+    fn number_width(&self) -> usize {
+        self.markers
+            .iter()
+            .filter_map(|v| v.number)
+            .max()
+            .map(|v| v.to_string().len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of columns of the widest line label of all code blocks,
+    /// including those of nested [StepsBlock]s, so all of them share the same gutter.
+    fn line_number_width(&self) -> usize {
         self.steps
             .blocks
             .iter()
             .filter_map(|v| match v {
-                LogBlock::Code(v) => Some(v.max_line()),
+                LogBlock::Code(v) => Some(v.line_number_width()),
+                LogBlock::Steps(v) => Some(v.line_number_width()),
                 _ => None,
             })
             .max()
@@ -68,6 +194,100 @@ impl<'a> StepsBlock<'a> {
         self
     }
 
+    /// Adds a new step prefixed by the next number, i.e. `1.`, `2.`, etc., and
+    /// optionally by a status marker.
+    pub fn add_numbered_step(
+        mut self,
+        block: impl Into<LogBlock<'a>>,
+        status: Option<StepStatus>,
+    ) -> Self {
+        let number = self.markers.iter().filter(|v| v.number.is_some()).count() + 1;
+        let index = self.steps.blocks.len();
+
+        self.steps.blocks.push(block.into());
+        self.set_marker(
+            index,
+            StepMarker {
+                number: Some(number),
+                status,
+                elapsed: None,
+            },
+        );
+        self
+    }
+
+    /// Adds a new step followed by the time it took, e.g. `(1.2s)`.
+    pub fn add_timed_step(mut self, block: impl Into<LogBlock<'a>>, elapsed: Duration) -> Self {
+        let index = self.steps.blocks.len();
+
+        self.steps.blocks.push(block.into());
+        self.set_marker(
+            index,
+            StepMarker {
+                elapsed: Some(elapsed),
+                ..StepMarker::default()
+            },
+        );
+        self
+    }
+
+    /// Adds a new step with `branches` executed concurrently, printed as a fork with a
+    /// branch per block followed by a line that joins them, e.g.:
+    ///
+    /// ```text
+    /// ├─┬─▶ Compile core
+    /// │ ├─▶ Compile cli
+    /// ├─╯
+    /// ```
+    ///
+    /// To number the branches or set their status, build them with another [StepsBlock]
+    /// marked as [StepsBlock::parallel] and add it with [StepsBlock::add_step].
+    pub fn add_parallel<B: Into<LogBlock<'a>>>(
+        self,
+        branches: impl IntoIterator<Item = B>,
+    ) -> Self {
+        let branches = branches
+            .into_iter()
+            .fold(StepsBlock::new().parallel(true), |block, branch| {
+                block.add_step(branch)
+            });
+
+        self.add_step(branches)
+    }
+
+    /// Sets whether to print a summary of the statuses of the steps as the last line.
+    /// See [StepsBlock::status_summary].
+    #[inline(always)]
+    pub fn show_status_summary(mut self, show_status_summary: bool) -> Self {
+        self.show_status_summary = show_status_summary;
+        self
+    }
+
+    /// Sets whether the steps are parallel branches, printed as a fork when this block is
+    /// a step of another [StepsBlock]. The title and the final message are not printed in
+    /// that case.
+    #[inline(always)]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the status of the step at `index`, e.g. to update it while a pipeline runs.
+    pub fn set_status(&mut self, index: usize, status: Option<StepStatus>) {
+        let marker = self.marker(index);
+        self.set_marker(index, StepMarker { status, ..marker });
+    }
+
+    fn set_marker(&mut self, index: usize, marker: StepMarker) {
+        if self.markers.len() <= index {
+            self.markers.resize(index + 1, StepMarker::default());
+        }
+
+        self.markers[index] = marker;
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
@@ -76,86 +296,67 @@ impl<'a> StepsBlock<'a> {
             title: self.title.make_owned(),
             final_message: self.final_message.make_owned(),
             steps: Box::new(self.steps.make_owned()),
+            markers: self.markers,
+            show_status_summary: self.show_status_summary,
+            parallel: self.parallel,
         }
     }
-}
-
-impl<'a> Printable<'a> for StepsBlock<'a> {
-    fn print<'s>(&'s self, printer: &mut Printer<'a>)
-    where
-        'a: 's,
-    {
-        let max_line_digits = format!("{}", self.max_line()).len();
-        let block_prefix = TextBlock::new().add_styled_text(
-            Cow::Borrowed(concatcp!(VERTICAL_BAR, "   ")),
-            Style::new().bold().fg(printer.level.color()),
-        );
 
+    /// Prints the block with the line number width of its code blocks. Nested blocks, i.e.
+    /// steps of another [StepsBlock], reuse the connector of their parent step instead of
+    /// printing the level symbol.
+    fn print_with_options(&self, printer: &mut Printer<'a>, max_line_digits: usize, nested: bool) {
         // Initial message.
         if !self.title.is_empty() {
-            printer.push_styled_text(
-                format!("{} ", printer.level.symbol()),
-                Style::new().bold().fg(printer.level.color()),
-            );
+            if !nested {
+                printer.push_styled_text(
+                    format!("{} ", printer.level_symbol()),
+                    printer.border_style(),
+                );
+            }
 
             let title_prefix = TextBlock::new().add_styled_text(
                 Cow::Borrowed(concatcp!(VERTICAL_BAR, " ")),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
-            let mut title_printer = printer.derive();
+            let mut title_printer = printer.derive_indented(title_prefix.column_count());
 
             self.title.print(&mut title_printer);
             title_printer.indent(&title_prefix.sections, false);
             printer.append(title_printer);
-        } else {
+        } else if !nested {
             printer.push_styled_text(
-                format!("{}", printer.level.symbol()),
-                Style::new().bold().fg(printer.level.color()),
+                format!("{}", printer.level_symbol()),
+                printer.border_style(),
             );
         }
 
-        // Print steps.
-        for block in &self.steps.blocks {
-            let print_start = !matches!(block, LogBlock::Separator(_));
-
-            if print_start {
-                printer.push_styled_text(
-                    Cow::Borrowed(concatcp!(
-                        '\n',
-                        VERTICAL_RIGHT_BAR,
-                        HORIZONTAL_BAR,
-                        RIGHT_ARROW,
-                        ' '
-                    )),
-                    Style::new().bold().fg(printer.level.color()),
-                );
-            } else {
-                printer.push_styled_text(
-                    Cow::Borrowed(concatcp!('\n', VERTICAL_BAR, "   ")),
-                    Style::new().bold().fg(printer.level.color()),
-                );
-            }
-
-            let mut block_printer = printer.derive();
+        let number_width = self.number_width();
 
+        // Print steps.
+        for (index, block) in self.steps.blocks.iter().enumerate() {
             match block {
-                LogBlock::Code(block) => {
-                    block.print_with_options(&mut block_printer, max_line_digits);
-                }
-                LogBlock::Separator(block) => {
-                    block.print(&mut block_printer);
-                }
-                _ => {
-                    block.print(&mut block_printer);
+                LogBlock::Steps(branches) if branches.parallel => {
+                    branches.print_branches(printer, max_line_digits);
                 }
+                _ => self.print_step(
+                    printer,
+                    index,
+                    (STEP_CONNECTOR, STEP_PREFIX),
+                    number_width,
+                    max_line_digits,
+                ),
             }
-
-            block_printer.indent(&block_prefix.sections, false);
-            printer.append(block_printer);
         }
 
         // Print last line.
-        if !self.final_message.is_empty() {
+        let status_summary: TextBlock<'a> = if self.show_status_summary {
+            self.status_summary_with_theme(&printer.theme)
+        } else {
+            TextBlock::new()
+        };
+
+        if !self.final_message.is_empty() || !status_summary.is_empty() {
             printer.push_styled_text(
                 Cow::Borrowed(concatcp!(
                     '\n',
@@ -164,24 +365,160 @@ impl<'a> Printable<'a> for StepsBlock<'a> {
                     RIGHT_ARROW,
                     ' '
                 )),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
             );
 
-            let message_prefix = TextBlock::new().add_styled_text(
-                Cow::Borrowed("    "),
-                Style::new().bold().fg(printer.level.color()),
-            );
-            let mut message_printer = printer.derive();
+            let message_prefix =
+                TextBlock::new().add_styled_text(Cow::Borrowed("    "), printer.border_style());
+            let mut message_printer = printer.derive_indented(message_prefix.column_count());
 
             self.final_message.print(&mut message_printer);
+
+            if !status_summary.is_empty() {
+                if !self.final_message.is_empty() {
+                    message_printer.new_line();
+                }
+
+                status_summary.print(&mut message_printer);
+            }
+
             message_printer.indent(&message_prefix.sections, false);
             printer.append(message_printer);
         } else {
             printer.push_styled_text(
                 Cow::Borrowed(concatcp!('\n', TOP_RIGHT_CORNER, HORIZONTAL_BAR)),
-                Style::new().bold().fg(printer.level.color()),
+                printer.border_style(),
+            );
+        }
+    }
+
+    /// Prints the steps as the branches of a parallel step, followed by a line that joins
+    /// them.
+    fn print_branches(&self, printer: &mut Printer<'a>, max_line_digits: usize) {
+        let number_width = self.number_width();
+
+        for index in 0..self.steps.blocks.len() {
+            let connector = if index == 0 {
+                FORK_CONNECTOR
+            } else {
+                BRANCH_CONNECTOR
+            };
+
+            self.print_step(
+                printer,
+                index,
+                (connector, BRANCH_PREFIX),
+                number_width,
+                max_line_digits,
             );
         }
+
+        printer.new_line();
+        printer.push_styled_text(Cow::Borrowed(JOIN_LINE), printer.border_style());
+    }
+
+    /// Prints the step at `index` starting with the `connector` and prefixing its other
+    /// lines with `prefix`.
+    fn print_step(
+        &self,
+        printer: &mut Printer<'a>,
+        index: usize,
+        (connector, prefix): (&'static str, &'static str),
+        number_width: usize,
+        max_line_digits: usize,
+    ) {
+        let block = &self.steps.blocks[index];
+        let print_start = !matches!(block, LogBlock::Separator(_));
+
+        printer.new_line();
+        printer.push_styled_text(
+            Cow::Borrowed(if print_start { connector } else { prefix }),
+            printer.border_style(),
+        );
+
+        let marker = self.marker(index);
+        let mut step_prefix =
+            TextBlock::new().add_styled_text(Cow::Borrowed(prefix), printer.border_style());
+
+        if print_start {
+            if let Some(number) = marker.number {
+                let number = format!("{:>width$}. ", number, width = number_width);
+                step_prefix = step_prefix.add_plain_text(build_space_string(number.len()));
+                printer.push_styled_text(number, Style::new().bold());
+            }
+
+            if let Some(status) = marker.status {
+                printer.push_styled_text(
+                    format!("{} ", printer.theme.status_symbol(status)),
+                    Style::new()
+                        .bold()
+                        .fg(printer.theme.level_color(status.level())),
+                );
+                step_prefix = step_prefix.add_plain_text("  ");
+            }
+        }
+
+        let mut block_printer = printer.derive_indented(step_prefix.column_count());
+
+        match block {
+            LogBlock::Code(block) => {
+                block.print_with_options(&mut block_printer, max_line_digits);
+            }
+            LogBlock::Steps(block) => {
+                block.print_with_options(&mut block_printer, max_line_digits, true);
+            }
+            _ => {
+                block.print(&mut block_printer);
+            }
+        }
+
+        // Append the elapsed time to the first line.
+        if let Some(elapsed) = marker.elapsed {
+            let elapsed = TextSection {
+                text: Cow::Owned(format!(" ({})", format_duration(elapsed))),
+                style: printer.theme.gutter_style(),
+            };
+
+            match block_printer.lines.first_mut() {
+                Some(line) => line.push(elapsed),
+                None => block_printer.push_text_section(elapsed),
+            }
+        }
+
+        block_printer.indent(&step_prefix.sections, false);
+        printer.append(block_printer);
+    }
+}
+
+impl<'a> Printable<'a> for StepsBlock<'a> {
+    fn print<'s>(&'s self, printer: &mut Printer<'a>)
+    where
+        'a: 's,
+    {
+        self.print_with_options(printer, self.line_number_width(), false);
+    }
+}
+
+impl StepStatus {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the default symbol that represents the status. See
+    /// [Theme::status_symbol] for the one that is printed.
+    pub const fn symbol(&self) -> char {
+        match self {
+            StepStatus::Pending => WHITE_CIRCLE,
+            StepStatus::Success => CHECK_MARK,
+            StepStatus::Failure => CROSS_MARK,
+        }
+    }
+
+    /// Returns the level whose color is used to print the status.
+    pub const fn level(&self) -> LogLevel {
+        match self {
+            StepStatus::Pending => LogLevel::trace(),
+            StepStatus::Success => LogLevel::debug(),
+            StepStatus::Failure => LogLevel::error(),
+        }
     }
 }
 
@@ -201,7 +538,7 @@ impl<'a> Display for StepsBlock<'a> {
 mod tests {
     use super::*;
     use crate::blocks::{CodeBlock, SeparatorBlock};
-    use crate::LogLevel;
+    use crate::{LevelSymbols, LogLevel, Theme};
 
     #[test]
     fn test_plain() {
@@ -261,6 +598,109 @@ mod tests {
         assert_eq!(text, "× This is\n│ a title\n├─▶  × ╭─\n│    3 │    Line 3\n│      │    ╰────╯\n│      ╰─\n│   ────────────────────\n├─▶  × ╭─\n│    8 │    Line 8\n│      │       ╰────▶\n│    9 │    Line 9\n│      │  ▶──╯\n│   10 │    Line 10\n│      ╰─\n│   \n╰─▶ This is\n    a message");
     }
 
+    #[test]
+    fn test_nested_steps() {
+        let code =
+            "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\nLine 6\nLine 7\nLine 8\nLine 9\nLine 10";
+        let log = StepsBlock::new()
+            .title("Deploy")
+            .add_step(CodeBlock::new(code).highlight_section(14..20, None))
+            .add_step(
+                StepsBlock::new()
+                    .title("Migrations")
+                    .add_step(TextBlock::new_plain("Schema"))
+                    .add_step(CodeBlock::new(code).highlight_section(63..70, None)),
+            )
+            .add_step(StepsBlock::new().add_step(TextBlock::new_plain("Cleanup")));
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "× Deploy\n├─▶  × ╭─\n│    3 │    Line 3\n│      │    ╰────╯\n│      ╰─\n├─▶ Migrations\n│   ├─▶ Schema\n│   ├─▶  × ╭─\n│   │   10 │    Line 10\n│   │      │    ╰─────╯\n│   │      ╰─\n│   ╰─\n├─▶ \n│   ├─▶ Cleanup\n│   ╰─\n╰─");
+    }
+
+    #[test]
+    fn test_parallel_steps() {
+        let log = StepsBlock::new()
+            .title("Build")
+            .add_step(TextBlock::new_plain("Fetch"))
+            .add_parallel([
+                TextBlock::new_plain("Compile core\nin release"),
+                TextBlock::new_plain("Compile cli"),
+            ])
+            .add_step(
+                StepsBlock::new()
+                    .parallel(true)
+                    .add_numbered_step(TextBlock::new_plain("Test"), Some(StepStatus::Success))
+                    .add_step(SeparatorBlock::with_width(5))
+                    .add_numbered_step(TextBlock::new_plain("Lint"), Some(StepStatus::Failure)),
+            )
+            .add_step(TextBlock::new_plain("Link"));
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "• Build\n├─▶ Fetch\n├─┬─▶ Compile core\n│ │   in release\n│ ├─▶ Compile cli\n├─╯\n├─┬─▶ 1. ✓ Test\n│ │   ─────\n│ ├─▶ 2. ✗ Lint\n├─╯\n├─▶ Link\n╰─"
+        );
+    }
+
+    #[test]
+    fn test_ascii_symbols() {
+        let log = StepsBlock::new()
+            .title("Build")
+            .add_step(CodeBlock::new("Line 1").highlight_section(0..4, None))
+            .add_numbered_step(TextBlock::new_plain("Test"), Some(StepStatus::Success))
+            .add_numbered_step(TextBlock::new_plain("Lint"), Some(StepStatus::Failure));
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain)
+            .theme(Theme::dark().symbols(LevelSymbols::ascii()));
+        log.print(&mut printer);
+
+        assert_eq!(
+            printer.to_string(),
+            "x Build\n├─▶ x ╭─\n│   1 │    Line 1\n│     │    ╰──╯\n│     ╰─\n├─▶ 1. + Test\n├─▶ 2. x Lint\n╰─"
+        );
+    }
+
+    #[test]
+    fn test_numbered_steps() {
+        let mut log = StepsBlock::new()
+            .add_numbered_step(
+                TextBlock::new_plain("Build\nin release"),
+                Some(StepStatus::Success),
+            )
+            .add_step(SeparatorBlock::with_width(5))
+            .add_numbered_step(TextBlock::new_plain("Test"), Some(StepStatus::Pending))
+            .add_numbered_step(TextBlock::new_plain("Deploy"), None)
+            .show_status_summary(true);
+
+        for _ in 0..7 {
+            log = log.add_numbered_step(TextBlock::new_plain("Step"), None);
+        }
+
+        log.set_status(2, Some(StepStatus::Failure));
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(log.marker(3).number, Some(3));
+        assert!(text.starts_with(
+            "•\n├─▶  1. ✓ Build\n│         in release\n│   ─────\n├─▶  2. ✗ Test\n├─▶  3. Deploy\n├─▶  4. Step\n"
+        ));
+        assert!(text.ends_with("├─▶ 10. Step\n╰─▶ 1 succeeded, 1 failed"));
+    }
+
+    #[test]
+    fn test_timed_steps() {
+        let log = StepsBlock::new()
+            .add_timed_step(
+                TextBlock::new_plain("Build\nin release"),
+                Duration::from_millis(340),
+            )
+            .add_timed_step(TextBlock::new(), Duration::from_secs(2));
+        let text = log.print_to_string(LogLevel::info(), PrinterFormat::Plain);
+
+        assert_eq!(
+            text,
+            "•\n├─▶ Build (340ms)\n│   in release\n├─▶  (2.0s)\n╰─"
+        );
+    }
+
     #[test]
     fn test_styled() {
         let code =
@@ -320,4 +760,21 @@ mod tests {
         println!("{}", text);
         assert_eq!(text, "\u{1b}[1;31m× \u{1b}[0mThis is\n\u{1b}[1;31m│ \u{1b}[0ma title\n\u{1b}[1;31m├─▶  × \u{1b}[0m\u{1b}[1m╭─\n\u{1b}[0m\u{1b}[1;31m│   \u{1b}[0m\u{1b}[1;90m 3 \u{1b}[0m\u{1b}[1m│    \u{1b}[0m\u{1b}[1;31mLine 3\n│      \u{1b}[0m\u{1b}[1m│    \u{1b}[0m\u{1b}[1;31m╰────╯\n│      \u{1b}[0m\u{1b}[1m╰─\n\u{1b}[0m\u{1b}[1;31m│   ────────────────────\n├─▶  × \u{1b}[0m\u{1b}[1m╭─\n\u{1b}[0m\u{1b}[1;31m│   \u{1b}[0m\u{1b}[1;90m 8 \u{1b}[0m\u{1b}[1m│    \u{1b}[0mLin\u{1b}[1;31me 8\n│      \u{1b}[0m\u{1b}[1m│       \u{1b}[0m\u{1b}[1;31m╰────▶\n│   \u{1b}[0m\u{1b}[1;90m 9 \u{1b}[0m\u{1b}[1m│    \u{1b}[0m\u{1b}[1;31mLi\u{1b}[0mne 9\n\u{1b}[1;31m│      \u{1b}[0m\u{1b}[1m│  \u{1b}[0m\u{1b}[1;31m▶──╯\n│   \u{1b}[0m\u{1b}[1;90m10 \u{1b}[0m\u{1b}[1m│    \u{1b}[0mLine 10\n\u{1b}[1;31m│      \u{1b}[0m\u{1b}[1m╰─\n\u{1b}[0m\u{1b}[1;31m│   \n╰─▶ \u{1b}[0mThis is\n    a message");
     }
+
+    #[test]
+    fn test_many_steps() {
+        let mut log = StepsBlock::new().title("Title");
+
+        for i in 0..1500 {
+            log = log.add_step(TextBlock::new_plain(format!("Step {}", i)));
+        }
+
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+        let lines: Vec<_> = text.lines().collect();
+
+        assert_eq!(lines.len(), 1502);
+        assert_eq!(lines[1], "├─▶ Step 0");
+        assert_eq!(lines[1500], "├─▶ Step 1499");
+        assert_eq!(lines[1501], "╰─");
+    }
 }
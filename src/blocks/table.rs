@@ -0,0 +1,311 @@
+use crate::blocks::TextBlock;
+use crate::constants::{
+    BOTTOM_LEFT_CORNER, BOTTOM_RIGHT_CORNER, HORIZONTAL_BAR, HORIZONTAL_BOTTOM_BAR,
+    HORIZONTAL_TOP_BAR, HORIZONTAL_VERTICAL, TOP_LEFT_CORNER, TOP_RIGHT_CORNER, VERTICAL_BAR,
+    VERTICAL_LEFT_BAR, VERTICAL_RIGHT_BAR,
+};
+use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::utils::whitespaces::build_space_string;
+use crate::LogLevel;
+use const_format::concatcp;
+use std::fmt::Display;
+use yansi::Style;
+
+/// A block that prints a table with aligned columns.
+///
+/// When printed, headers and cells will get all newline characters `\n`
+/// replaced by whitespaces to only occupy one line.
+///
+/// # Examples
+/// ```text
+/// ╭──────┬──────╮
+/// │ Name │ Time │
+/// ├──────┼──────┤
+/// │ lex  │  2ms │
+/// │ gen  │ 13ms │
+/// ╰──────┴──────╯
+/// ```
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableBlock<'a> {
+    pub columns: Vec<TableColumn<'a>>,
+    pub rows: Vec<Vec<TextBlock<'a>>>,
+}
+
+/// A column of a [TableBlock].
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableColumn<'a> {
+    pub header: TextBlock<'a>,
+    pub alignment: ColumnAlignment,
+}
+
+/// The alignment of the cells of a column.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl<'a> TableBlock<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [TableBlock].
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the width of each column, i.e. the width of its widest cell.
+    fn column_widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.column_count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(column.header.column_count())
+            })
+            .collect()
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Adds a new column.
+    #[inline(always)]
+    pub fn add_column(
+        mut self,
+        header: impl Into<TextBlock<'a>>,
+        alignment: ColumnAlignment,
+    ) -> Self {
+        self.columns.push(TableColumn {
+            header: header.into(),
+            alignment,
+        });
+        self
+    }
+
+    /// Adds a new row. Missing cells are printed empty.
+    ///
+    /// # Panics
+    /// This method panics if the row has more cells than the table columns.
+    pub fn add_row<T: Into<TextBlock<'a>>>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        let row: Vec<_> = cells.into_iter().map(|v| v.into()).collect();
+
+        assert!(
+            row.len() <= self.columns.len(),
+            "The row cannot have more cells than the table columns"
+        );
+
+        self.rows.push(row);
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> TableBlock<'static> {
+        TableBlock {
+            columns: self
+                .columns
+                .into_iter()
+                .map(|v| TableColumn {
+                    header: v.header.make_owned(),
+                    alignment: v.alignment,
+                })
+                .collect(),
+            rows: self
+                .rows
+                .into_iter()
+                .map(|row| row.into_iter().map(|v| v.make_owned()).collect())
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Printable<'a> for TableBlock<'a> {
+    fn print<'s>(&'s self, printer: &mut Printer<'a>)
+    where
+        'a: 's,
+    {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let widths = self.column_widths();
+        let border_style = Style::new().bold();
+
+        // Top border.
+        print_border(
+            printer,
+            &widths,
+            BOTTOM_RIGHT_CORNER,
+            HORIZONTAL_BOTTOM_BAR,
+            BOTTOM_LEFT_CORNER,
+        );
+
+        // Headers.
+        printer.new_line();
+        printer.push_styled_text(concatcp!(VERTICAL_BAR), border_style);
+
+        for (column, width) in self.columns.iter().zip(&widths) {
+            let mut header = column.header.single_lined();
+
+            for section in &mut header.sections {
+                section.style = section.style.bold();
+            }
+
+            print_cell(printer, &header, column.alignment, *width);
+            printer.push_styled_text(concatcp!(VERTICAL_BAR), border_style);
+        }
+
+        // Rows.
+        if !self.rows.is_empty() {
+            printer.new_line();
+            print_border(
+                printer,
+                &widths,
+                VERTICAL_RIGHT_BAR,
+                HORIZONTAL_VERTICAL,
+                VERTICAL_LEFT_BAR,
+            );
+        }
+
+        let empty_cell = TextBlock::new();
+        for row in &self.rows {
+            printer.new_line();
+            printer.push_styled_text(concatcp!(VERTICAL_BAR), border_style);
+
+            for (i, (column, width)) in self.columns.iter().zip(&widths).enumerate() {
+                let cell = row.get(i).unwrap_or(&empty_cell).single_lined();
+
+                print_cell(printer, &cell, column.alignment, *width);
+                printer.push_styled_text(concatcp!(VERTICAL_BAR), border_style);
+            }
+        }
+
+        // Bottom border.
+        printer.new_line();
+        print_border(
+            printer,
+            &widths,
+            TOP_RIGHT_CORNER,
+            HORIZONTAL_TOP_BAR,
+            TOP_LEFT_CORNER,
+        );
+    }
+}
+
+impl<'a> Display for TableBlock<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut printer = Printer::new(LogLevel::trace(), PrinterFormat::Plain);
+        self.print(&mut printer);
+        printer.fmt(f, PrinterFormat::Plain)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Prints a horizontal border of the table.
+fn print_border(printer: &mut Printer, widths: &[usize], start: char, middle: char, end: char) {
+    let mut border = String::new();
+    border.push(start);
+
+    for (i, width) in widths.iter().enumerate() {
+        if i > 0 {
+            border.push(middle);
+        }
+
+        border.extend(std::iter::repeat_n(HORIZONTAL_BAR, width + 2));
+    }
+
+    border.push(end);
+    printer.push_styled_text(border, Style::new().bold());
+}
+
+/// Prints a cell padded to `width` following the alignment.
+fn print_cell<'a>(
+    printer: &mut Printer<'a>,
+    cell: &TextBlock<'a>,
+    alignment: ColumnAlignment,
+    width: usize,
+) {
+    let padding = width - cell.column_count();
+    let (left, right) = match alignment {
+        ColumnAlignment::Left => (0, padding),
+        ColumnAlignment::Center => (padding / 2, padding - padding / 2),
+        ColumnAlignment::Right => (padding, 0),
+    };
+
+    printer.push_plain_text(build_space_string(left + 1));
+    cell.print(printer);
+    printer.push_plain_text(build_space_string(right + 1));
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        // Empty
+        let log = TableBlock::new();
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "");
+
+        // Headers
+        let log = TableBlock::new()
+            .add_column("Name", ColumnAlignment::Left)
+            .add_column("Time", ColumnAlignment::Right);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭──────┬──────╮\n│ Name │ Time │\n╰──────┴──────╯");
+
+        // Rows
+        let log = TableBlock::new()
+            .add_column("Name", ColumnAlignment::Left)
+            .add_column("Status", ColumnAlignment::Center)
+            .add_column("Time", ColumnAlignment::Right)
+            .add_row(["lexer", "ok", "2ms"])
+            .add_row(["code\ngeneration", "failed", "130ms"])
+            .add_row(["parser"]);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Plain);
+
+        assert_eq!(text, "╭─────────────────┬────────┬───────╮\n│ Name            │ Status │  Time │\n├─────────────────┼────────┼───────┤\n│ lexer           │   ok   │   2ms │\n│ code generation │ failed │ 130ms │\n│ parser          │        │       │\n╰─────────────────┴────────┴───────╯");
+    }
+
+    #[test]
+    fn test_styled() {
+        let log = TableBlock::new()
+            .add_column("Name", ColumnAlignment::Left)
+            .add_row([TextBlock::new().add_styled_text("lexer", Style::new().red())]);
+        let text = log.print_to_string(LogLevel::error(), PrinterFormat::Styled);
+
+        println!("{}", text);
+        assert_eq!(text, "\u{1b}[1m╭───────╮\n│ Name  │\n├───────┤\n│ \u{1b}[0m\u{1b}[31mlexer \u{1b}[0m\u{1b}[1m│\n╰───────╯\u{1b}[0m");
+    }
+
+    #[test]
+    #[should_panic(expected = "The row cannot have more cells than the table columns")]
+    fn test_too_many_cells() {
+        let _ = TableBlock::new()
+            .add_column("Name", ColumnAlignment::Left)
+            .add_row(["a", "b"]);
+    }
+}
@@ -1,19 +1,31 @@
+use crate::constants::ELLIPSIS;
 use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::utils::markdown::parse_markdown;
+use crate::utils::text::{column_byte_offset, count_columns, remove_jump_lines, slice_cow};
 use crate::LogLevel;
+use const_format::concatcp;
 use smallvec::{smallvec, SmallVec};
 use std::borrow::Cow;
 use std::fmt::Display;
+use std::ops::Range;
 use yansi::Style;
 
 /// A block that prints a formated text to the terminal.
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextBlock<'a> {
     pub sections: SmallVec<[TextSection<'a>; 3]>,
+
+    /// The ids of the referenced blocks along with the index of the section
+    /// they are printed before. They are resolved at print time.
+    pub references: Vec<(usize, Cow<'a, str>)>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TextSection<'a> {
     pub text: Cow<'a, str>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::serialization::style"))]
     pub style: Style,
 }
 
@@ -34,6 +46,17 @@ impl<'a> TextBlock<'a> {
                 text: text.into(),
                 style: Style::new(),
             }],
+            references: Vec::new(),
+        }
+    }
+
+    /// Creates a new [TextBlock] from a minimal subset of markdown: bold `**text**`,
+    /// italics `*text*` or `_text_`, inline code `` `code` `` and bullet lists
+    /// starting with `- ` or `* `.
+    pub fn from_markdown(text: &'a str) -> Self {
+        Self {
+            sections: parse_markdown(text),
+            references: Vec::new(),
         }
     }
 
@@ -42,7 +65,13 @@ impl<'a> TextBlock<'a> {
     /// Returns whether the text block is empty.
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
-        self.sections.is_empty()
+        self.sections.is_empty() && self.references.is_empty()
+    }
+
+    /// Returns the number of columns the text occupies when printed in a single line.
+    /// References are not included because they are resolved at print time.
+    pub fn column_count(&self) -> usize {
+        self.sections.iter().map(|v| count_columns(&v.text)).sum()
     }
 
     // METHODS ----------------------------------------------------------------
@@ -76,6 +105,119 @@ impl<'a> TextBlock<'a> {
         self
     }
 
+    /// Adds a reference to the block with `id`. It is printed as `[n]`, where `n` is the
+    /// number of the referenced block, followed by the line where it starts in plain
+    /// outputs, e.g. `[1, line 5]`. Unknown ids are printed as `[?]`.
+    ///
+    /// See [Log::add_block_with_id](crate::Log::add_block_with_id).
+    #[inline]
+    pub fn add_reference(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.references.push((self.sections.len(), id.into()));
+        self
+    }
+
+    /// Applies `style` to all the occurrences of `pattern` in the text, e.g. to emphasize
+    /// an identifier in a prebuilt message. Occurrences can span several sections, which
+    /// are split as needed, and the rest of the text keeps its style.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::blocks::TextBlock;
+    /// use yansi::Style;
+    ///
+    /// let text = TextBlock::new_plain("Variable `value` is never used")
+    ///     .highlight_matches("value", Style::new().bold());
+    ///
+    /// assert_eq!(text.sections.len(), 3);
+    /// assert_eq!(text.sections[1].text, "value");
+    /// ```
+    pub fn highlight_matches(self, pattern: &str, style: Style) -> Self {
+        if pattern.is_empty() {
+            return self;
+        }
+
+        let text = self.plain_text();
+        let ranges: Vec<_> = text
+            .match_indices(pattern)
+            .map(|(index, _)| index..index + pattern.len())
+            .collect();
+
+        self.style_ranges(&ranges, style)
+    }
+
+    /// Applies `style` to all the matches of `regex` in the text, like
+    /// [TextBlock::highlight_matches] does with literal patterns. Empty matches are ignored.
+    ///
+    /// Requires the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn highlight_regex_matches(self, regex: &regex::Regex, style: Style) -> Self {
+        let text = self.plain_text();
+        let ranges: Vec<_> = regex
+            .find_iter(&text)
+            .filter(|v| !v.is_empty())
+            .map(|v| v.range())
+            .collect();
+
+        self.style_ranges(&ranges, style)
+    }
+
+    /// Returns the text of all the sections, without the references.
+    fn plain_text(&self) -> String {
+        self.sections.iter().map(|v| v.text.as_ref()).collect()
+    }
+
+    /// Applies `style` to the sorted and non-overlapping byte `ranges` of the whole text,
+    /// splitting the sections at their bounds.
+    fn style_ranges(self, ranges: &[Range<usize>], style: Style) -> Self {
+        if ranges.is_empty() {
+            return self;
+        }
+
+        let mut sections = SmallVec::new();
+        let mut section_starts = Vec::with_capacity(self.sections.len());
+        let mut ranges = ranges.iter().peekable();
+        let mut offset = 0;
+
+        for section in self.sections {
+            let end = offset + section.text.len();
+            let mut cursor = offset;
+
+            section_starts.push(sections.len());
+
+            while cursor < end {
+                while ranges.next_if(|v| v.end <= cursor).is_some() {}
+
+                let (next, matched) = match ranges.peek() {
+                    Some(range) if range.start <= cursor => (range.end.min(end), true),
+                    Some(range) => (range.start.min(end), false),
+                    None => (end, false),
+                };
+
+                sections.push(TextSection {
+                    text: slice_cow(&section.text, cursor - offset..next - offset),
+                    style: if matched { style } else { section.style },
+                });
+                cursor = next;
+            }
+
+            offset = end;
+        }
+
+        let references = self
+            .references
+            .into_iter()
+            .map(|(index, id)| {
+                let index = section_starts.get(index).copied().unwrap_or(sections.len());
+                (index, id)
+            })
+            .collect();
+
+        Self {
+            sections,
+            references,
+        }
+    }
+
     /// Makes this [TextBlock] to be single-lined.
     #[inline]
     pub fn single_lined(&self) -> Self {
@@ -86,17 +228,117 @@ impl<'a> TextBlock<'a> {
                 .map(|section| TextSection {
                     text: match &section.text {
                         Cow::Borrowed(v) => {
-                            if memchr::memchr(b'\n', v.as_bytes()).is_some() {
-                                Cow::Owned(section.text.replace('\n', " "))
+                            if memchr::memchr2(b'\n', b'\r', v.as_bytes()).is_some() {
+                                Cow::Owned(remove_jump_lines(v))
                             } else {
                                 Cow::Borrowed(*v)
                             }
                         }
-                        Cow::Owned(v) => v.replace('\n', " ").into(),
+                        Cow::Owned(v) => remove_jump_lines(v).into(),
                     },
                     style: section.style,
                 })
                 .collect(),
+            references: self.references.clone(),
+        }
+    }
+
+    /// Truncates this [TextBlock] so it occupies at most `max_columns` columns when printed
+    /// in a single line, replacing the removed end with `ellipsis`. The ellipsis takes the
+    /// style of the section it cuts, and the remaining sections keep their styles.
+    ///
+    /// References are not taken into account, see [TextBlock::column_count], but those
+    /// placed after the cut are removed.
+    pub fn truncate(&self, max_columns: usize, ellipsis: impl Into<Cow<'a, str>>) -> Self {
+        if self.column_count() <= max_columns {
+            return self.clone();
+        }
+
+        let mut ellipsis = ellipsis.into();
+        let ellipsis_columns = count_columns(&ellipsis);
+
+        if ellipsis_columns > max_columns {
+            let end = column_byte_offset(&ellipsis, max_columns + 1).unwrap_or(ellipsis.len());
+            ellipsis = slice_cow(&ellipsis, 0..end);
+        }
+
+        let mut remaining = max_columns.saturating_sub(ellipsis_columns);
+        let mut sections = SmallVec::new();
+        let mut ellipsis_style = Style::new();
+        let mut cut_index = 0;
+
+        for (i, section) in self.sections.iter().enumerate() {
+            let columns = count_columns(&section.text);
+
+            if columns <= remaining {
+                sections.push(section.clone());
+                remaining -= columns;
+                continue;
+            }
+
+            let end = column_byte_offset(&section.text, remaining + 1).unwrap_or(0);
+
+            if end > 0 {
+                sections.push(TextSection {
+                    text: slice_cow(&section.text, 0..end),
+                    style: section.style,
+                });
+            }
+
+            ellipsis_style = section.style;
+            cut_index = i;
+            break;
+        }
+
+        let references = self
+            .references
+            .iter()
+            .filter(|(index, _)| *index <= cut_index)
+            .cloned()
+            .collect();
+
+        let mut result = Self {
+            sections,
+            references,
+        };
+
+        if !ellipsis.is_empty() {
+            result.sections.push(TextSection {
+                text: ellipsis,
+                style: ellipsis_style,
+            });
+        }
+
+        result
+    }
+
+    /// Returns this [TextBlock] in a single line truncated to `max_columns` columns, or [None]
+    /// if it does not fit and there is no room for at least one column besides the ellipsis.
+    pub(crate) fn fit_single_lined(&self, max_columns: usize) -> Option<Self> {
+        let text = self.single_lined();
+
+        if text.column_count() <= max_columns {
+            Some(text)
+        } else if max_columns >= 2 {
+            Some(text.truncate(max_columns, concatcp!(ELLIPSIS)))
+        } else {
+            None
+        }
+    }
+
+    /// Prints this [TextBlock] in a single line, truncated so that `reserved_columns` are
+    /// still left in the line when the printer has a maximum width.
+    pub(crate) fn print_single_lined(&self, printer: &mut Printer<'a>, reserved_columns: usize) {
+        let text = self.single_lined();
+
+        match printer.remaining_columns() {
+            Some(columns) => text
+                .truncate(
+                    columns.saturating_sub(reserved_columns),
+                    concatcp!(ELLIPSIS),
+                )
+                .print(printer),
+            None => text.print(printer),
         }
     }
 
@@ -111,6 +353,11 @@ impl<'a> TextBlock<'a> {
                     style: painted.style,
                 })
                 .collect(),
+            references: self
+                .references
+                .into_iter()
+                .map(|(index, id)| (index, Cow::Owned(id.into_owned())))
+                .collect(),
         }
     }
 }
@@ -120,9 +367,19 @@ impl<'a> Printable<'a> for TextBlock<'a> {
     where
         'a: 's,
     {
-        for painted in &self.sections {
+        let mut references = self.references.iter().peekable();
+
+        for (i, painted) in self.sections.iter().enumerate() {
+            while let Some((_, id)) = references.next_if(|(index, _)| *index <= i) {
+                printer.push_styled_text(printer.resolve_reference(id), Style::new().bold());
+            }
+
             printer.push_text_section(painted.clone());
         }
+
+        for (_, id) in references {
+            printer.push_styled_text(printer.resolve_reference(id), Style::new().bold());
+        }
     }
 }
 
@@ -189,4 +446,78 @@ mod tests {
             "\u{1b}[1;33mThis is\na test\u{1b}[0m- plain\u{1b}[1;31m - styled\u{1b}[0m"
         );
     }
+    #[test]
+    fn test_highlight_matches() {
+        let bold = Style::new().bold();
+        let red = Style::new().red();
+        let text = TextBlock::new()
+            .add_plain_text("let va")
+            .add_reference("a")
+            .add_styled_text("lue = value;", red)
+            .add_reference("b")
+            .highlight_matches("value", bold);
+
+        assert_eq!(
+            text,
+            TextBlock::new()
+                .add_plain_text("let ")
+                .add_styled_text("va", bold)
+                .add_reference("a")
+                .add_styled_text("lue", bold)
+                .add_styled_text(" = ", red)
+                .add_styled_text("value", bold)
+                .add_styled_text(";", red)
+                .add_reference("b")
+        );
+
+        let text = TextBlock::new_plain("value");
+        assert_eq!(text.clone().highlight_matches("", bold), text);
+        assert_eq!(text.clone().highlight_matches("other", bold), text);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_highlight_regex_matches() {
+        let bold = Style::new().bold();
+        let regex = regex::Regex::new(r"\d+|x*").unwrap();
+        let text = TextBlock::new_plain("line 12, column 3").highlight_regex_matches(&regex, bold);
+
+        assert_eq!(
+            text,
+            TextBlock::new()
+                .add_plain_text("line ")
+                .add_styled_text("12", bold)
+                .add_plain_text(", column ")
+                .add_styled_text("3", bold)
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let bold = Style::new().bold();
+        let text = TextBlock::new()
+            .add_plain_text("src/")
+            .add_styled_text("main.rs", bold)
+            .add_reference("a");
+
+        assert_eq!(text.truncate(11, "…"), text);
+        assert_eq!(
+            text.truncate(8, "…"),
+            TextBlock::new()
+                .add_plain_text("src/")
+                .add_styled_text("mai", bold)
+                .add_styled_text("…", bold)
+        );
+        assert_eq!(
+            text.truncate(5, "…"),
+            TextBlock::new()
+                .add_plain_text("src/")
+                .add_styled_text("…", bold)
+        );
+        assert_eq!(
+            text.truncate(2, "..."),
+            TextBlock::new().add_plain_text("..")
+        );
+        assert_eq!(text.truncate(0, "…"), TextBlock::new());
+    }
 }
@@ -0,0 +1,145 @@
+use crate::blocks::{HeaderBlock, TextBlock};
+use crate::Log;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+static GLOBAL_CODE_REGISTRY: RwLock<CodeRegistry> = RwLock::new(CodeRegistry::new());
+
+/// A registry of log codes with their long-form explanations, like the error index of
+/// `rustc --explain`.
+///
+/// Explanations are written in the markdown subset supported by
+/// [TextBlock::from_markdown].
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct CodeRegistry {
+    explanations: BTreeMap<Cow<'static, str>, Cow<'static, str>>,
+}
+
+impl CodeRegistry {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [CodeRegistry].
+    pub const fn new() -> Self {
+        Self {
+            explanations: BTreeMap::new(),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the explanation of `code`, if it is registered.
+    pub fn get(&self, code: &str) -> Option<&str> {
+        self.explanations.get(code).map(|v| v.as_ref())
+    }
+
+    /// Returns the registered codes in order.
+    pub fn codes(&self) -> impl Iterator<Item = &str> {
+        self.explanations.keys().map(|v| v.as_ref())
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Registers the explanation of `code`, replacing the previous one, if any.
+    pub fn register(
+        mut self,
+        code: impl Into<Cow<'static, str>>,
+        explanation: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.explanations.insert(code.into(), explanation.into());
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds a log with the explanation of `code`, if it is registered.
+    pub fn explain(&self, code: &str) -> Option<Log<'static>> {
+        let explanation = self.get(code)?;
+
+        Some(
+            Log::info()
+                .add_block(
+                    HeaderBlock::new()
+                        .code(code.to_string())
+                        .title("Explanation"),
+                )
+                .add_block(TextBlock::from_markdown(explanation).make_owned()),
+        )
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns a copy of the registry used by [Log::explain].
+    pub fn global() -> CodeRegistry {
+        GLOBAL_CODE_REGISTRY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Sets the registry used by [Log::explain].
+    pub fn set_global(registry: CodeRegistry) {
+        *GLOBAL_CODE_REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = registry;
+    }
+
+    /// Registers the explanation of `code` in the registry used by [Log::explain].
+    pub fn register_global(
+        code: impl Into<Cow<'static, str>>,
+        explanation: impl Into<Cow<'static, str>>,
+    ) {
+        GLOBAL_CODE_REGISTRY
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .explanations
+            .insert(code.into(), explanation.into());
+    }
+
+    /// Builds a log with the explanation of `code` from the global registry.
+    pub(crate) fn explain_global(code: &str) -> Option<Log<'static>> {
+        GLOBAL_CODE_REGISTRY
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .explain(code)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain() {
+        let registry = CodeRegistry::new()
+            .register("E0002", "Second")
+            .register("E0001", "A **bold** explanation.");
+
+        assert_eq!(registry.codes().collect::<Vec<_>>(), vec!["E0001", "E0002"]);
+        assert_eq!(registry.get("E0002"), Some("Second"));
+        assert!(registry.explain("E0003").is_none());
+
+        let log = registry.explain("E0001").unwrap();
+
+        assert_eq!(
+            log.to_plain_text(),
+            "INFO[E0001] Explanation\nA bold explanation."
+        );
+    }
+
+    #[test]
+    fn test_global() {
+        CodeRegistry::register_global("E9999", "Global explanation.");
+
+        assert_eq!(
+            CodeRegistry::global().get("E9999"),
+            Some("Global explanation.")
+        );
+        assert!(Log::explain("E9999").is_some());
+        assert!(Log::explain("E9998").is_none());
+    }
+}
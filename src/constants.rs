@@ -14,15 +14,20 @@ pub const HORIZONTAL_BAR: char = '─';
 pub const TOP_LEFT_CORNER: char = '╯';
 pub const TOP_RIGHT_CORNER: char = '╰';
 pub const BOTTOM_RIGHT_CORNER: char = '╭';
-// pub const BOTTOM_LEFT_CORNER: char = '╮';
+pub const BOTTOM_LEFT_CORNER: char = '╮';
 pub const VERTICAL_RIGHT_BAR: char = '├';
-// pub const VERTICAL_LEFT_BAR: char = '┤';
+pub const VERTICAL_LEFT_BAR: char = '┤';
 pub const HORIZONTAL_TOP_BAR: char = '┴';
 pub const HORIZONTAL_BOTTOM_BAR: char = '┬';
-// pub const HORIZONTAL_VERTICAL: char = '┼';
+pub const HORIZONTAL_VERTICAL: char = '┼';
 pub const MIDDLE_DOT: char = '·';
 pub const NEW_LINE_LEFT: char = '↩';
 pub const NEW_LINE_RIGHT: char = '↪';
 pub const UP_POINTER: char = '^';
+pub const ELLIPSIS: char = '…';
+pub const CHECK_MARK: char = '✓';
+pub const CROSS_MARK: char = '✗';
+pub const WHITE_CIRCLE: char = '○';
+pub const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 // pub const RIGHT_POINTER: char = '>';
 // pub const LEFT_POINTER: char = '<';
@@ -0,0 +1,144 @@
+use crate::Log;
+
+#[cfg(feature = "derive")]
+pub use doclog_derive::DocLog;
+
+/// A type that can be reported as a [Log] pointing to the source it refers to,
+/// e.g. the errors of a parser.
+///
+/// With the `derive` feature it can be derived, annotating the struct or every variant
+/// of the enum with its message, code, level and labels:
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use doclog::DocLog;
+///
+/// #[derive(DocLog)]
+/// enum ParseError {
+///     #[doclog(code = "E0001", message = "Unexpected `{token}`", label(span = "span", text = "here"))]
+///     UnexpectedToken { token: String, span: std::ops::Range<usize> },
+/// }
+///
+/// let error = ParseError::UnexpectedToken { token: "}".to_string(), span: 8..9 };
+/// let log = error.to_log("let a = }");
+/// # }
+/// ```
+pub trait DocLog {
+    /// Builds the [Log] of this value for the `source` it refers to.
+    fn to_log<'a>(&self, source: &'a str) -> Log<'a>;
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+    use crate::blocks::{CodeBlock, HeaderBlock};
+    use std::ops::Range;
+
+    #[derive(DocLog)]
+    enum ParseError {
+        #[doclog(
+            code = "E0001",
+            message = "Unexpected `{token}`",
+            label(span = "self.span", text = "expected `{expected}`")
+        )]
+        UnexpectedToken {
+            token: String,
+            expected: String,
+            span: Range<usize>,
+        },
+        #[doclog(level = "warn", message = "Unused value", label(span = "0"))]
+        Unused(Range<usize>),
+        #[doclog(message = "Empty input")]
+        Empty,
+    }
+
+    #[derive(DocLog)]
+    #[doclog(message = "Invalid number {value}", label(span = "span"))]
+    struct InvalidNumber {
+        value: i64,
+        span: Range<usize>,
+    }
+
+    #[derive(DocLog)]
+    #[doclog(
+        message = "Overlap",
+        label(span = "first", text = "first"),
+        label(span = "second", text = "second")
+    )]
+    struct Overlap {
+        first: Range<usize>,
+        second: Range<usize>,
+    }
+
+    #[test]
+    fn test_derive() {
+        let source = "let a = }";
+
+        let log = ParseError::UnexpectedToken {
+            token: "}".to_string(),
+            expected: "value".to_string(),
+            span: 8..9,
+        }
+        .to_log(source);
+        let expected = Log::error()
+            .add_block(HeaderBlock::new().title("Unexpected `}`").code("E0001"))
+            .add_block(CodeBlock::new(source).highlight_section_message(
+                8..9,
+                None,
+                "expected `value`",
+            ));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+
+        let log = ParseError::Unused(4..5).to_log(source);
+        let expected = Log::warn()
+            .add_block(HeaderBlock::new().title("Unused value"))
+            .add_block(CodeBlock::new(source).highlight_section(4..5, None));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+
+        let log = ParseError::Empty.to_log(source);
+        let expected = Log::error().add_block(HeaderBlock::new().title("Empty input"));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+
+        let log = InvalidNumber {
+            value: 12,
+            span: 0..3,
+        }
+        .to_log(source);
+        let expected = Log::error()
+            .add_block(HeaderBlock::new().title("Invalid number 12"))
+            .add_block(CodeBlock::new(source).highlight_section(0..3, None));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+    }
+
+    #[test]
+    fn test_derive_invalid_spans() {
+        let source = "let a = }";
+
+        let log = InvalidNumber {
+            value: 12,
+            span: 8..20,
+        }
+        .to_log(source);
+        let expected = Log::error()
+            .add_block(HeaderBlock::new().title("Invalid number 12"))
+            .add_block(CodeBlock::new(source).final_message(
+                "Invalid label: The end index must be less or equal than the code length: 20 > 9",
+            ));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+
+        // Colliding labels are discarded.
+        let log = Overlap {
+            first: 4..9,
+            second: 6..7,
+        }
+        .to_log(source);
+        let expected = Log::error()
+            .add_block(HeaderBlock::new().title("Overlap"))
+            .add_block(CodeBlock::new(source).highlight_section_message(4..9, None, "first"));
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+    }
+}
@@ -17,11 +17,24 @@ const WARN: LogLevel = LogLevel::new(40, Color::Yellow, "warn", '⚠');
 /// The error log level. Level = 50.
 const ERROR: LogLevel = LogLevel::new(50, Color::Red, "error", '×');
 
+/// The predefined log levels, in order.
+const PREDEFINED: [LogLevel; 5] = [TRACE, DEBUG, INFO, WARN, ERROR];
+
+/// The custom log levels that can be deserialized, see [LogLevel::register].
+#[cfg(feature = "serde")]
+static REGISTERED: std::sync::RwLock<Vec<LogLevel>> = std::sync::RwLock::new(Vec::new());
+
 /// The different levels of logging.
+///
+/// Besides the predefined levels, applications can define their own ones with
+/// [LogLevel::custom], e.g. `FATAL` or `AUDIT`, that work with all blocks and are
+/// ordered by their level number along with the predefined ones. With the `serde`
+/// feature, custom levels must be registered with [LogLevel::register] to be deserialized.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct LogLevel {
     level: u8,
     color: Color,
+    name: &'static str,
     tag: &'static str,
     symbol: char,
 }
@@ -29,16 +42,63 @@ pub struct LogLevel {
 impl LogLevel {
     // CONSTRUCTORS -----------------------------------------------------------
 
-    /// Builds a new log level.
+    /// Builds a new log level. Its name is the same as its tag.
     pub const fn new(level: u8, color: Color, tag: &'static str, symbol: char) -> LogLevel {
         LogLevel {
             level,
+            name: tag,
+            tag,
+            color,
+            symbol,
+        }
+    }
+
+    /// Builds a new user-defined log level with the same level number as [LogLevel::info].
+    /// Use [LogLevel::with_level] to place it in a different position of the order.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::LogLevel;
+    /// use yansi::Color;
+    ///
+    /// const FATAL: LogLevel = LogLevel::custom("FATAL", "fatal", '‼', Color::Magenta).with_level(60);
+    ///
+    /// assert!(FATAL > LogLevel::error());
+    /// ```
+    pub const fn custom(
+        name: &'static str,
+        tag: &'static str,
+        symbol: char,
+        color: Color,
+    ) -> LogLevel {
+        LogLevel {
+            level: INFO.level,
+            name,
             tag,
             color,
             symbol,
         }
     }
 
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the number that defines the order between log levels. The predefined levels
+    /// use multiples of 10, from 10 for [LogLevel::trace] to 50 for [LogLevel::error].
+    #[inline(always)]
+    pub const fn with_level(mut self, level: u8) -> LogLevel {
+        self.level = level;
+        self
+    }
+
+    /// Sets the symbol that represents the log level, e.g. to replace the default glyphs
+    /// with ASCII characters. See also [Theme::symbols](crate::Theme::symbols) to replace
+    /// the symbols of all predefined levels at once.
+    #[inline(always)]
+    pub const fn with_symbol(mut self, symbol: char) -> LogLevel {
+        self.symbol = symbol;
+        self
+    }
+
     // GETTERS ----------------------------------------------------------------
 
     /// Returns a number that defines an order between log levels.
@@ -46,6 +106,17 @@ impl LogLevel {
         self.level
     }
 
+    /// Returns the name of the log level.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns whether this is one of the predefined log levels, i.e. it was not
+    /// built with [LogLevel::custom] nor [LogLevel::new].
+    pub fn is_predefined(&self) -> bool {
+        PREDEFINED.contains(self)
+    }
+
     /// Returns the tag that represents the log level.
     pub const fn tag(&self) -> &'static str {
         self.tag
@@ -61,6 +132,36 @@ impl LogLevel {
         self.symbol
     }
 
+    // METHODS ----------------------------------------------------------------
+
+    /// Registers this custom level so it can be deserialized. Deserializing a level that
+    /// is neither predefined nor registered fails, so the input cannot create new levels.
+    ///
+    /// # Examples
+    /// ```
+    /// use doclog::LogLevel;
+    /// use yansi::Color;
+    ///
+    /// const FATAL: LogLevel = LogLevel::custom("FATAL", "fatal", '‼', Color::Magenta).with_level(60);
+    ///
+    /// FATAL.register();
+    ///
+    /// let json = serde_json::to_string(&FATAL).unwrap();
+    /// assert_eq!(serde_json::from_str::<LogLevel>(&json).unwrap(), FATAL);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn register(&self) {
+        if self.is_predefined() {
+            return;
+        }
+
+        let mut registered = REGISTERED.write().unwrap_or_else(|e| e.into_inner());
+
+        if !registered.contains(self) {
+            registered.push(*self);
+        }
+    }
+
     // STATIC METHODS ---------------------------------------------------------
 
     /// Returns the TRACE log level.
@@ -96,8 +197,78 @@ impl PartialOrd for LogLevel {
 }
 
 impl Ord for LogLevel {
+    /// Levels are ordered by their level number. Levels with the same number are ordered
+    /// by their tag and name, so the order is consistent with equality whenever their
+    /// symbol and color also match. Filters compare only the level numbers instead, see
+    /// [LogLevel::level].
     fn cmp(&self, other: &Self) -> Ordering {
-        self.level.cmp(&other.level)
+        self.level
+            .cmp(&other.level)
+            .then_with(|| self.tag.cmp(other.tag))
+            .then_with(|| self.name.cmp(other.name))
+    }
+}
+
+/// The representation of a [LogLevel] used by serde.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LogLevelRepr<'a> {
+    level: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<std::borrow::Cow<'a, str>>,
+    tag: std::borrow::Cow<'a, str>,
+    symbol: char,
+    #[serde(with = "crate::utils::serialization::color")]
+    color: Color,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LogLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LogLevelRepr {
+            level: self.level,
+            name: (self.name != self.tag).then(|| self.name.into()),
+            tag: self.tag.into(),
+            symbol: self.symbol,
+            color: self.color,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LogLevel {
+    /// Deserializes a [LogLevel]. Levels are looked up by their tag and name among the
+    /// predefined and the registered ones, keeping the deserialized level number, symbol
+    /// and color.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = LogLevelRepr::deserialize(deserializer)?;
+        let predefined = PREDEFINED
+            .into_iter()
+            .find(|v| v.tag == repr.tag && repr.name.is_none());
+
+        if let Some(level) = predefined {
+            return Ok(LogLevel::new(
+                repr.level,
+                repr.color,
+                level.tag,
+                repr.symbol,
+            ));
+        }
+
+        let name = repr.name.as_deref().unwrap_or(&repr.tag);
+        let registered = REGISTERED.read().unwrap_or_else(|e| e.into_inner());
+        let level = registered
+            .iter()
+            .find(|v| v.tag == repr.tag && v.name == name)
+            .ok_or_else(|| {
+                serde::de::Error::custom(format_args!(
+                    "unknown log level `{}`, custom levels must be registered with LogLevel::register",
+                    repr.tag
+                ))
+            })?;
+
+        Ok(LogLevel::custom(level.name, level.tag, repr.symbol, repr.color).with_level(repr.level))
     }
 }
 
@@ -116,4 +287,52 @@ mod tests {
         assert!(INFO < WARN, "INFO is not less than WARN");
         assert!(WARN < ERROR, "WARN is not less than ERROR");
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&WARN).unwrap();
+        assert_eq!(
+            json,
+            r#"{"level":40,"tag":"warn","symbol":"⚠","color":"yellow"}"#
+        );
+        assert_eq!(serde_json::from_str::<LogLevel>(&json).unwrap(), WARN);
+
+        let custom = LogLevel::new(60, Color::Cyan, "fatal", '×');
+        let json = serde_json::to_string(&custom).unwrap();
+        assert!(serde_json::from_str::<LogLevel>(&json).is_err());
+
+        custom.register();
+        assert_eq!(serde_json::from_str::<LogLevel>(&json).unwrap(), custom);
+
+        let custom = LogLevel::custom("AUDIT", "audit", '§', Color::Cyan).with_level(50);
+        custom.register();
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(serde_json::from_str::<LogLevel>(&json).unwrap(), custom);
+
+        // Modified predefined levels.
+        for level in [ERROR.with_symbol('E'), INFO.with_level(35)] {
+            let json = serde_json::to_string(&level).unwrap();
+            assert_eq!(serde_json::from_str::<LogLevel>(&json).unwrap(), level);
+        }
+    }
+
+    #[test]
+    fn test_custom() {
+        let fatal = LogLevel::custom("FATAL", "fatal", '‼', Color::Magenta).with_level(60);
+        let audit = LogLevel::custom("AUDIT", "audit", '§', Color::Cyan);
+
+        assert_eq!(fatal.name(), "FATAL");
+        assert_eq!(fatal.tag(), "fatal");
+        assert_eq!(audit.level(), INFO.level());
+        assert!(fatal > ERROR);
+        assert!(audit > DEBUG && audit != INFO);
+        assert!(!audit.is_predefined());
+        assert!(PREDEFINED.iter().all(|v| v.is_predefined()));
+
+        // Same level number as a predefined level.
+        let audit = audit.with_level(ERROR.level());
+        assert_ne!(audit.cmp(&ERROR), Ordering::Equal);
+        assert_eq!(crate::Theme::dark().level_color(audit), Color::Cyan);
+    }
 }
@@ -1,14 +1,60 @@
 extern crate core;
+#[cfg(all(test, feature = "derive"))]
+extern crate self as doclog;
 
+pub use code_registry::*;
+pub use doc_log::*;
 pub use levels::*;
+pub use live_log::*;
 pub use log::*;
 pub use log_content::*;
+pub use log_context::*;
+pub use log_diff::*;
+pub use log_field::*;
+pub use logger::*;
+pub use panic_hook::*;
+pub use printer::*;
+pub use redaction::*;
+pub use render_session::*;
+pub use rendered_log::*;
+pub use report::*;
+pub use source_map::*;
+pub use span::*;
+pub use suggestion::*;
+pub use template::*;
+pub use theme::*;
+pub use visitor::*;
 pub use yansi;
 
 pub mod blocks;
+mod code_registry;
 mod constants;
+mod doc_log;
 mod levels;
+mod live_log;
 mod log;
 mod log_content;
+mod log_context;
+mod log_diff;
+mod log_field;
+mod logger;
+mod macros;
+#[cfg(feature = "otel")]
+mod otel;
+mod panic_hook;
 mod printer;
+mod redaction;
+mod render_session;
+mod rendered_log;
+mod report;
+pub mod sinks;
+mod source_map;
+mod span;
+mod suggestion;
+mod template;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+pub mod testing;
+mod theme;
 mod utils;
+mod visitor;
@@ -0,0 +1,135 @@
+use crate::printer::{LiveWriter, Printable};
+use crate::{Log, RenderSession};
+use std::io::{Stderr, Write};
+
+/// Prints logs in-place, i.e. every update replaces the log printed by the previous one,
+/// so watch-mode tools like compilers or test runners can refresh their diagnostics
+/// without scrolling.
+///
+/// The logs are printed with the state of a [RenderSession]. Its width is also used to
+/// count the terminal lines occupied by the lines that wrap, so they are cleared properly.
+///
+/// The output must be a terminal that supports ANSI escape sequences.
+///
+/// # Examples
+/// ```no_run
+/// use doclog::blocks::HeaderBlock;
+/// use doclog::{LiveLog, Log};
+///
+/// let mut live_log = LiveLog::stderr();
+///
+/// live_log.update(&Log::info().add_block(HeaderBlock::new().title("Compiling...")))?;
+/// live_log.update(&Log::error().add_block(HeaderBlock::new().title("Build failed")))?;
+/// live_log.finish()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct LiveLog<W: Write> {
+    writer: LiveWriter<W>,
+    session: RenderSession,
+}
+
+impl<W: Write> LiveLog<W> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LiveLog] that prints into `writer` capturing the current terminal
+    /// state, see [RenderSession::new].
+    pub fn new(writer: W) -> Self {
+        let session = RenderSession::new();
+
+        Self {
+            writer: LiveWriter::new(writer).terminal_width(session.width()),
+            session,
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the session used to print the logs.
+    #[inline(always)]
+    pub fn with_session(mut self, session: RenderSession) -> Self {
+        self.writer = self.writer.terminal_width(session.width());
+        self.session = session;
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the session used to print the logs.
+    #[inline(always)]
+    pub fn session(&self) -> &RenderSession {
+        &self.session
+    }
+
+    /// Returns the number of terminal lines printed by the last update.
+    #[inline(always)]
+    pub fn previous_line_count(&self) -> usize {
+        self.writer.previous_line_count()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Replaces the previously printed log with `log`.
+    pub fn update(&mut self, log: &Log) -> std::io::Result<()> {
+        let mut printer = self.session.printer(log.level);
+        log.print(&mut printer);
+        self.writer.update(&printer)
+    }
+
+    /// Removes the previously printed log.
+    #[inline(always)]
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        self.writer.clear()
+    }
+
+    /// Finishes the live printing, leaving the last log in place and moving the cursor
+    /// to the next line.
+    #[inline(always)]
+    pub fn finish(self) -> std::io::Result<W> {
+        self.writer.finish()
+    }
+}
+
+impl LiveLog<Stderr> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LiveLog] that prints into the standard error.
+    pub fn stderr() -> Self {
+        Self::new(std::io::stderr())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{HeaderBlock, TextBlock};
+
+    #[test]
+    fn test_update() {
+        let mut live_log =
+            LiveLog::new(Vec::new()).with_session(RenderSession::with_state(Some(9), false));
+
+        live_log
+            .update(
+                &Log::info()
+                    .add_block(HeaderBlock::new().title("Build"))
+                    .add_block(TextBlock::new_plain("Compiling")),
+            )
+            .unwrap();
+
+        // The header is clipped to the width of the terminal.
+        assert_eq!(live_log.previous_line_count(), 2);
+
+        live_log
+            .update(&Log::info().add_block(HeaderBlock::new().title("Done")))
+            .unwrap();
+        assert_eq!(live_log.previous_line_count(), 1);
+
+        let result = String::from_utf8(live_log.finish().unwrap()).unwrap();
+        assert_eq!(result, "INFO Bui…\nCompiling\r\u{1b}[1A\u{1b}[JINFO Done\n");
+    }
+}
@@ -1,19 +1,71 @@
-use std::fmt::Display;
+use std::backtrace::Backtrace;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display};
 use std::fs;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
 use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, RwLock};
 
-use crate::blocks::LogBlock;
-use crate::printer::{Printable, Printer, PrinterFormat};
-use crate::{LogContent, LogLevel};
+#[cfg(feature = "miette")]
+use crate::blocks::{CodeBlock, HeaderBlock, NoteBlock};
+use crate::blocks::{CodeBlockLines, KeyValueBlock, LogBlock, StackBlock};
+use crate::constants::ELLIPSIS;
+use crate::printer::{
+    is_terminal, BlockReference, OutputStream, Printable, Printer, PrinterFormat,
+};
+use crate::{
+    suggestions_to_lsp_code_actions, BlockVisitor, CodeRegistry, FieldValue, LogContent, LogLevel,
+    RenderedLog, Suggestion, Theme,
+};
+
+/// The version of the format used to represent logs outside the process, e.g. when they
+/// are serialized. It must be increased whenever that representation changes in a
+/// non-backward-compatible way.
+pub const LOG_SCHEMA_VERSION: u32 = 1;
+
+/// The environment variable that sets the pager used by [Log::page].
+const PAGER_ENV_VAR: &str = "PAGER";
+
+/// The pager used by [Log::page] when [PAGER_ENV_VAR] is not set, with the flags of git's
+/// default `LESS=FRX`: `-F` exits right away if the log fits in one screen, `-R` lets the
+/// ANSI styles pass through and `-X` keeps the log on screen after exiting.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// The format and stream used by [Log::log], [Log::print_stdout] and [Log::print_stderr].
+static DEFAULT_OUTPUT: RwLock<(PrinterFormat, OutputStream)> =
+    RwLock::new((PrinterFormat::Auto, OutputStream::Stdout));
 
 /// A configured log.
+///
+/// Its parts are only accessible through methods so that the texts rendered by
+/// [Log::render_cached] are discarded whenever they change.
 #[derive(Debug, Clone)]
 pub struct Log<'a> {
     pub level: LogLevel,
-    pub content: LogContent<'a>,
-    pub cause: Option<Box<Log<'a>>>,
+    pub(crate) content: LogContent<'a>,
+    pub(crate) cause: Option<Box<Log<'a>>>,
+    pub(crate) suggestions: Vec<Suggestion<'a>>,
+
+    /// The structured fields, see [Log::field].
+    pub(crate) fields: Vec<(Cow<'a, str>, FieldValue<'a>)>,
+
+    /// The maximum number of lines to print, see [Log::truncate_lines].
+    pub(crate) max_lines: Option<usize>,
+
+    pub(crate) render_cache: RenderCache,
 }
 
+/// The texts of a log rendered by [Log::render_cached], by level, format and theme.
+#[derive(Default)]
+pub(crate) struct RenderCache(Mutex<Vec<RenderedText>>);
+
+/// A text rendered by [Log::render_cached] along with its level, format and theme.
+type RenderedText = (LogLevel, PrinterFormat, Theme, Arc<str>);
+
 impl<'a> Log<'a> {
     // CONSTRUCTORS -----------------------------------------------------------
 
@@ -23,6 +75,10 @@ impl<'a> Log<'a> {
             level,
             content: LogContent::new(),
             cause: None,
+            suggestions: Vec::new(),
+            fields: Vec::new(),
+            max_lines: None,
+            render_cache: RenderCache::default(),
         }
     }
 
@@ -51,6 +107,183 @@ impl<'a> Log<'a> {
         Self::new(LogLevel::error())
     }
 
+    /// Builds a new log with an error level containing a [StackBlock] built from `error`
+    /// and its chain of sources. A backtrace is also included if it can be captured,
+    /// see [Backtrace::capture].
+    pub fn error_from(error: &dyn Error) -> Log<'a> {
+        Self::error().add_block(StackBlock::from_error(error).backtrace(&Backtrace::capture()))
+    }
+
+    /// Builds a new log with an error level containing a [StackBlock] built from an
+    /// [anyhow::Error], including its chain of causes and its backtrace, if captured.
+    #[cfg(feature = "anyhow")]
+    pub fn from_anyhow(error: &anyhow::Error) -> Log<'a> {
+        Self::error().add_block(StackBlock::from(error))
+    }
+
+    /// Builds a new log with an error level containing a [StackBlock] built from an
+    /// [eyre::Report], including its chain of causes.
+    #[cfg(feature = "eyre")]
+    pub fn from_eyre(error: &eyre::Report) -> Log<'a> {
+        Self::error().add_block(StackBlock::from(error))
+    }
+
+    /// Builds a new log from a [miette::Diagnostic]. The level is taken from its severity,
+    /// its labels are highlighted in a [CodeBlock] over its source code, its help and url
+    /// are added as [NoteBlock]s and its diagnostic source becomes the cause of the log.
+    ///
+    /// Labels that overlap a previous one are skipped, because a [CodeBlock] cannot
+    /// highlight colliding sections.
+    #[cfg(feature = "miette")]
+    pub fn from_diagnostic(diagnostic: &dyn miette::Diagnostic) -> Log<'a> {
+        let level = match diagnostic.severity() {
+            Some(miette::Severity::Advice) => LogLevel::info(),
+            Some(miette::Severity::Warning) => LogLevel::warn(),
+            _ => LogLevel::error(),
+        };
+
+        let mut header = HeaderBlock::new().title(diagnostic.to_string());
+
+        if let Some(code) = diagnostic.code() {
+            header = header.code(code.to_string());
+        }
+
+        let mut log = Self::new(level).add_block(header);
+
+        if let Some(block) = diagnostic_code_block(diagnostic) {
+            log = log.add_block(block);
+        }
+
+        if let Some(help) = diagnostic.help() {
+            log = log.add_block(NoteBlock::new().text(format!("help: {help}")));
+        }
+
+        if let Some(url) = diagnostic.url() {
+            log = log.add_block(NoteBlock::new().text(format!("see: {url}")));
+        }
+
+        if let Some(source) = diagnostic.diagnostic_source() {
+            log.cause = Some(Box::new(Self::from_diagnostic(source)));
+        }
+
+        log
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns the version of the format used to represent logs outside the process.
+    /// See [LOG_SCHEMA_VERSION].
+    #[inline(always)]
+    pub const fn schema_version() -> u32 {
+        LOG_SCHEMA_VERSION
+    }
+
+    /// Builds a log with the long-form explanation of `code` registered in the global
+    /// [CodeRegistry], if any. See [HeaderBlock::explain_hint](crate::blocks::HeaderBlock::explain_hint).
+    pub fn explain(code: &str) -> Option<Log<'static>> {
+        CodeRegistry::explain_global(code)
+    }
+
+    /// Returns the format used to print logs into the standard streams.
+    /// Defaults to [PrinterFormat::Auto].
+    pub fn default_format() -> PrinterFormat {
+        DEFAULT_OUTPUT.read().unwrap_or_else(|e| e.into_inner()).0
+    }
+
+    /// Sets the format used to print logs into the standard streams.
+    pub fn set_default_format(format: PrinterFormat) {
+        DEFAULT_OUTPUT.write().unwrap_or_else(|e| e.into_inner()).0 = format;
+    }
+
+    /// Returns the stream [Log::log] prints into. Defaults to [OutputStream::Stdout].
+    pub fn default_stream() -> OutputStream {
+        DEFAULT_OUTPUT.read().unwrap_or_else(|e| e.into_inner()).1
+    }
+
+    /// Sets the stream [Log::log] prints into.
+    pub fn set_default_stream(stream: OutputStream) {
+        DEFAULT_OUTPUT.write().unwrap_or_else(|e| e.into_inner()).1 = stream;
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the blocks of this log.
+    #[inline(always)]
+    pub fn content(&self) -> &LogContent<'a> {
+        &self.content
+    }
+
+    /// Returns the blocks of this log to modify them.
+    pub fn content_mut(&mut self) -> &mut LogContent<'a> {
+        self.invalidate_render_cache();
+        &mut self.content
+    }
+
+    /// Returns the cause of this log, see [Log::set_cause].
+    #[inline(always)]
+    pub fn cause(&self) -> Option<&Log<'a>> {
+        self.cause.as_deref()
+    }
+
+    /// Returns the cause of this log to modify it.
+    pub fn cause_mut(&mut self) -> Option<&mut Log<'a>> {
+        self.invalidate_render_cache();
+        self.cause.as_deref_mut()
+    }
+
+    /// Returns the suggestions attached to this log. See [Log::all_suggestions] to include
+    /// those of its code blocks and causes.
+    #[inline(always)]
+    pub fn suggestions(&self) -> &[Suggestion<'a>] {
+        &self.suggestions
+    }
+
+    /// Returns the suggestions attached to this log and its causes, followed by those of
+    /// their code blocks, see [CodeBlock::get_suggestions].
+    pub fn all_suggestions(&self) -> Vec<Suggestion<'a>> {
+        let mut result = Vec::new();
+        let mut log = Some(self);
+
+        while let Some(current) = log {
+            result.extend(current.suggestions.iter().cloned());
+            collect_code_suggestions(&current.content, &mut result);
+            log = current.cause.as_deref();
+        }
+
+        result
+    }
+
+    /// Returns the suggestions attached to this log to modify them.
+    pub fn suggestions_mut(&mut self) -> &mut Vec<Suggestion<'a>> {
+        self.invalidate_render_cache();
+        &mut self.suggestions
+    }
+
+    /// Returns the structured fields, see [Log::field].
+    #[inline(always)]
+    pub fn fields(&self) -> &[(Cow<'a, str>, FieldValue<'a>)] {
+        &self.fields
+    }
+
+    /// Returns the structured fields to modify them.
+    pub fn fields_mut(&mut self) -> &mut Vec<(Cow<'a, str>, FieldValue<'a>)> {
+        self.invalidate_render_cache();
+        &mut self.fields
+    }
+
+    /// Returns the maximum number of lines to print, see [Log::truncate_lines].
+    #[inline(always)]
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    /// Returns whether the level of this log is at least `level`, e.g. to decide
+    /// which blocks to include depending on the verbosity.
+    #[inline(always)]
+    pub fn min_level(&self, level: LogLevel) -> bool {
+        self.level.level() >= level.level()
+    }
+
     // SETTERS ----------------------------------------------------------------
 
     /// Sets the cause of this log.
@@ -61,17 +294,95 @@ impl<'a> Log<'a> {
         let new_log = Log::new(self.level);
         let new_log = builder(new_log);
         self.cause = Some(Box::new(new_log));
+        self.invalidate_render_cache();
         self
     }
 
+    /// Traverses the blocks of the log and of its causes with `visitor`, see [BlockVisitor].
+    pub fn visit_mut(&mut self, visitor: &mut impl BlockVisitor<'a>) {
+        self.content.visit_mut(visitor);
+
+        if let Some(cause) = &mut self.cause {
+            cause.visit_mut(visitor);
+        }
+
+        self.invalidate_render_cache();
+    }
+
+    /// Discards the texts rendered by [Log::render_cached].
+    pub(crate) fn invalidate_render_cache(&mut self) {
+        self.render_cache.clear();
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Adds a new block.
     pub fn add_block(mut self, block: impl Into<LogBlock<'a>>) -> Self {
         self.content = self.content.add_block(block.into());
+        self.invalidate_render_cache();
+        self
+    }
+
+    /// Adds a new block in place, e.g. inside conditionals or loops. See [Log::add_block].
+    #[inline(always)]
+    pub fn push(&mut self, block: impl Into<LogBlock<'a>>) {
+        self.content.push(block);
+        self.invalidate_render_cache();
+    }
+
+    /// Adds a new block that can be referenced by `id` from
+    /// [TextBlock::add_reference](crate::blocks::TextBlock::add_reference).
+    ///
+    /// Referenced blocks are numbered in printing order, including the blocks of the causes.
+    /// Ids should be unique, otherwise references point to the first block with that id.
+    pub fn add_block_with_id(
+        mut self,
+        id: impl Into<Cow<'a, str>>,
+        block: impl Into<LogBlock<'a>>,
+    ) -> Self {
+        self.content = self.content.add_block_with_id(id, block);
+        self.invalidate_render_cache();
+        self
+    }
+
+    /// Keeps only the blocks for which `predicate` returns `true`.
+    /// See [LogContent::filter_blocks].
+    pub fn filter_blocks(mut self, predicate: impl FnMut(&LogBlock<'a>) -> bool) -> Self {
+        self.content = self.content.filter_blocks(predicate);
+        self.invalidate_render_cache();
+        self
+    }
+
+    /// Limits the log to its first `max_lines` lines when printed, replacing the rest
+    /// with a notice of how many lines were truncated, e.g. `… 120 more lines`.
+    pub fn truncate_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self.invalidate_render_cache();
         self
     }
 
+    /// Adds a structured field, e.g. a request id or a duration. Fields are printed after
+    /// the blocks as key/value pairs, see [KeyValueBlock], and exported with their type
+    /// when the log is serialized or converted into an OpenTelemetry record.
+    pub fn field(mut self, key: impl Into<Cow<'a, str>>, value: impl Into<FieldValue<'a>>) -> Self {
+        self.fields.push((key.into(), value.into()));
+        self.invalidate_render_cache();
+        self
+    }
+
+    /// Attaches a new suggestion.
+    pub fn add_suggestion(mut self, suggestion: Suggestion<'a>) -> Self {
+        self.suggestions.push(suggestion);
+        self.invalidate_render_cache();
+        self
+    }
+
+    /// Returns all the suggestions of this log, see [Log::all_suggestions], as a JSON array
+    /// of LSP quick fix `CodeAction`s. See [Suggestion::to_lsp_code_action].
+    pub fn suggestions_to_lsp_code_actions(&self) -> String {
+        suggestions_to_lsp_code_actions(&self.all_suggestions())
+    }
+
     /// Logs in the console the plain text version of the log.
     pub fn log_plain_text(&self) {
         println!("{}", self.to_plain_text());
@@ -82,10 +393,72 @@ impl<'a> Log<'a> {
         println!("{}", self.to_styled_text());
     }
 
-    /// Logs in the console the text version of the log. Whether it is styled or plain text
-    /// depends on whether the ANSI colors are supported in the executing terminal or not.
+    /// Logs in the console the text version of the log, into the stream and with the format
+    /// set by [Log::set_default_stream] and [Log::set_default_format]. By default, whether
+    /// it is styled or plain text depends on the environment and on whether the standard
+    /// output is a terminal. See [PrinterFormat::Auto].
     pub fn log(&self) {
-        println!("{}", self.to_text());
+        match Self::default_stream() {
+            OutputStream::Stdout => self.print_stdout(),
+            OutputStream::Stderr => self.print_stderr(),
+        }
+    }
+
+    /// Prints the log into the standard output with the default format,
+    /// see [Log::set_default_format].
+    ///
+    /// With the `terminal` feature, the log is wrapped to the width of the terminal.
+    pub fn print_stdout(&self) {
+        println!("{}", self.print_to_stream_string(OutputStream::Stdout));
+    }
+
+    /// Prints the log into the standard error with the default format,
+    /// see [Log::set_default_format].
+    ///
+    /// With the `terminal` feature, the log is wrapped to the width of the terminal.
+    pub fn print_stderr(&self) {
+        eprintln!("{}", self.print_to_stream_string(OutputStream::Stderr));
+    }
+
+    /// Prints the log into the standard output through a pager, so long logs can be
+    /// scrolled instead of scrolling off-screen. The pager is taken from the `PAGER`
+    /// environment variable, defaulting to `less -FRX` so short logs are printed as is and
+    /// the styles pass through.
+    ///
+    /// Falls back to [Log::print_stdout] when the standard output is not a terminal,
+    /// see [PrinterFormat::resolve_for], or when the pager cannot be started.
+    pub fn page(&self) {
+        let pager = std::env::var(PAGER_ENV_VAR)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+
+        if !is_terminal(OutputStream::Stdout) || self.write_to_pager(&pager).is_err() {
+            self.print_stdout();
+        }
+    }
+
+    /// Writes the log into the standard input of `pager`, a command with its arguments
+    /// separated by whitespaces, and waits until it exits.
+    ///
+    /// Only errors starting the pager are returned, because the user can close it before
+    /// the whole log is written.
+    fn write_to_pager(&self, pager: &str) -> std::io::Result<()> {
+        let mut args = pager.split_whitespace();
+        let program = args.next().ok_or(std::io::ErrorKind::NotFound)?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()?;
+
+        let format = Self::default_format().resolve_for(OutputStream::Stdout);
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = writeln!(stdin, "{}", self.print_to_string(self.level, format));
+        }
+
+        child.wait()?;
+        Ok(())
     }
 
     /// Appends the log into the specified file as plain text.
@@ -100,6 +473,35 @@ impl<'a> Log<'a> {
         fs::write(file, content)
     }
 
+    /// Writes the log directly into `writer` without allocating an intermediate [String].
+    /// Lines are written as soon as they are complete and code blocks are rendered line by
+    /// line, see [CodeBlock::render_lines], so the whole log is never kept in memory unless
+    /// it is truncated, see [Log::truncate_lines].
+    ///
+    /// The output is buffered, and `writer` is flushed once after the whole log.
+    pub fn write_to(&self, format: PrinterFormat, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+
+        if self.max_lines.is_some() {
+            Printable::write_to(self, self.level, format, &mut writer)?;
+        } else {
+            let mut printer = Printer::new(self.level, format);
+            printer.references = Some(Arc::new(self.resolve_references(&printer)));
+
+            self.write_content(&mut printer, &mut writer)?;
+            printer.write_to(&mut writer, format)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Returns the log as text in the given `format`. [PrinterFormat::Auto] is resolved
+    /// against the default stream, see [Log::set_default_stream].
+    pub fn format(&self, format: PrinterFormat) -> String {
+        let format = format.resolve_for(Self::default_stream());
+        self.print_to_string(self.level, format)
+    }
+
     /// Returns the log as a plain text.
     pub fn to_plain_text(&self) -> String {
         self.print_to_string(self.level, PrinterFormat::Plain)
@@ -116,12 +518,97 @@ impl<'a> Log<'a> {
         self.print_to_string(self.level, PrinterFormat::Default)
     }
 
+    /// Returns the log as text in the given `format`, like [Log::format], but keeps the
+    /// result to return it again in later calls with the same level and format, e.g. when
+    /// the same log is written into several sinks.
+    ///
+    /// The cache is discarded when the log is modified, and the texts are rendered again
+    /// when the global [Theme] changes.
+    pub fn render_cached(&self, format: PrinterFormat) -> Arc<str> {
+        let format = match format.resolve_for(Self::default_stream()) {
+            format if format.is_styled() => PrinterFormat::Styled,
+            _ => PrinterFormat::Plain,
+        };
+
+        // Take the theme once, so the text is rendered with the same theme it is cached for.
+        let theme = Theme::global();
+
+        self.render_cache
+            .get_or_render(self.level, format, theme, || {
+                let mut printer = Printer::with_theme(self.level, format, theme);
+                self.print(&mut printer);
+                printer.to_string()
+            })
+    }
+
+    /// Renders the log as plain text keeping track of the lines of every block.
+    pub fn render(&self) -> RenderedLog {
+        RenderedLog::new(self)
+    }
+
+    /// Computes the number and the starting line of every block with an id when printed
+    /// into `printer`.
+    pub(crate) fn resolve_references(&self, printer: &Printer) -> HashMap<String, BlockReference> {
+        let mut ids = Vec::new();
+        let mut block_offset = 0;
+        let mut log = Some(self);
+
+        while let Some(current) = log {
+            for (index, id) in &current.content.ids {
+                ids.push((block_offset + index, id));
+            }
+
+            block_offset += current.content.blocks.len();
+            log = current.cause.as_deref();
+        }
+
+        let mut references = HashMap::new();
+
+        if ids.is_empty() {
+            return references;
+        }
+
+        ids.sort_by_key(|(index, _)| *index);
+
+        // The first pass prints the references as unresolved.
+        let mut first_pass = printer.derive();
+        first_pass.references = Some(Arc::default());
+
+        let mut blocks = Vec::new();
+        self.print_with_blocks(&mut first_pass, Some(&mut blocks));
+
+        let line_offset = printer.lines.len().saturating_sub(1);
+
+        for (number, (index, id)) in ids.into_iter().enumerate() {
+            if let Some(lines) = blocks.get(index) {
+                references.entry(id.to_string()).or_insert(BlockReference {
+                    number: number + 1,
+                    line: line_offset + lines.start + 1,
+                });
+            }
+        }
+
+        references
+    }
+
     /// Makes this type owned, i.e. changing the lifetime to `'static`.
     pub fn make_owned(self) -> Log<'static> {
         Log {
             level: self.level,
             content: self.content.make_owned(),
             cause: self.cause.map(|v| Box::new(v.make_owned())),
+            suggestions: self
+                .suggestions
+                .into_iter()
+                .map(|v| v.make_owned())
+                .collect(),
+            fields: self
+                .fields
+                .into_iter()
+                .map(|(key, value)| (Cow::Owned(key.into_owned()), value.make_owned()))
+                .collect(),
+            max_lines: self.max_lines,
+            render_cache: RenderCache::default(),
         }
     }
 }
@@ -131,14 +618,181 @@ impl<'a> Printable<'a> for Log<'a> {
     where
         'a: 's,
     {
-        // Print content.
-        self.content.print(printer);
+        self.print_with_blocks(printer, None);
+    }
+}
+
+impl<'a> Log<'a> {
+    /// Prints the log recording into `blocks` the range of lines of every block, i.e. first
+    /// the blocks of the log and then the blocks of its causes. See [RenderedLog].
+    pub(crate) fn print_with_blocks<'s>(
+        &'s self,
+        printer: &mut Printer<'a>,
+        mut blocks: Option<&mut Vec<Range<usize>>>,
+    ) where
+        'a: 's,
+    {
+        // Resolve references in the outermost log.
+        let resolved_references = printer.references.is_none();
+
+        if resolved_references {
+            printer.references = Some(Arc::new(self.resolve_references(printer)));
+        }
+
+        match self.max_lines {
+            Some(max_lines) => {
+                let first_block = blocks.as_ref().map_or(0, |v| v.len());
+                let mut log_printer = printer.derive();
+                self.print_content(&mut log_printer, blocks.as_deref_mut());
+
+                // Move the blocks to the lines of the printer, clipping the truncated ones.
+                if let Some(blocks) = blocks {
+                    let line_offset = printer.lines.len().saturating_sub(1);
+
+                    for block in &mut blocks[first_block..] {
+                        *block = block.start.min(max_lines) + line_offset
+                            ..block.end.min(max_lines) + line_offset;
+                    }
+                }
+
+                let (mut log_printer, truncated) = log_printer.split_at_line(max_lines);
+                let truncated_lines = truncated.line_count();
+
+                if truncated_lines > 0 {
+                    log_printer.new_line();
+                    log_printer.push_styled_text(
+                        format!(
+                            "{ELLIPSIS} {truncated_lines} more line{}",
+                            if truncated_lines == 1 { "" } else { "s" }
+                        ),
+                        printer.theme.gutter_style(),
+                    );
+                }
+
+                printer.append(log_printer);
+            }
+            None => self.print_content(printer, blocks),
+        }
+
+        if resolved_references {
+            printer.references = None;
+        }
+    }
+    /// Returns the log as text to be printed into `stream` with the default format.
+    fn print_to_stream_string(&self, stream: OutputStream) -> String {
+        let format = Self::default_format().resolve_for(stream);
+        let mut printer = Printer::new(self.level, format);
+
+        #[cfg(feature = "terminal")]
+        {
+            printer = printer.max_width(crate::terminal::size_of(stream).map(|v| v.width));
+        }
+
+        self.print(&mut printer);
+        printer.to_string()
+    }
+
+    /// Prints the content, the fields and the cause of the log, recording the lines of
+    /// every block into `blocks`.
+    fn print_content(&self, printer: &mut Printer<'a>, mut blocks: Option<&mut Vec<Range<usize>>>) {
+        for (i, block) in self.content.blocks.iter().enumerate() {
+            if i > 0 {
+                printer.new_line();
+            }
+
+            let start = printer.lines.len().saturating_sub(1);
+            block.print(printer);
+
+            if let Some(blocks) = blocks.as_deref_mut() {
+                blocks.push(start..printer.lines.len().max(start + 1));
+            }
+        }
+
+        self.print_fields(printer);
+
+        if let Some(cause) = &self.cause {
+            printer.new_line();
+            cause.print_with_blocks(printer, blocks);
+        }
+    }
+
+    /// Prints the content, the fields and the cause of the log like [Log::print_content],
+    /// writing the lines into `writer` as soon as they are complete. The lines of code
+    /// blocks are written as they are rendered.
+    fn write_content(
+        &self,
+        printer: &mut Printer<'a>,
+        writer: &mut impl Write,
+    ) -> std::io::Result<()> {
+        for (i, block) in self.content.blocks.iter().enumerate() {
+            if i > 0 {
+                printer.new_line();
+            }
+
+            match block {
+                LogBlock::Code(block) => {
+                    let lines = CodeBlockLines::with_printer(
+                        block,
+                        printer.derive(),
+                        block.line_number_width(),
+                    );
+
+                    for (j, line) in lines.enumerate() {
+                        if j > 0 {
+                            printer.new_line();
+                        }
+
+                        let mut line_printer = printer.derive();
+                        line_printer.lines.push(line.sections);
+                        printer.append(line_printer);
+                        printer.write_complete_lines(writer)?;
+                    }
+                }
+                block => block.print(printer),
+            }
+
+            printer.write_complete_lines(writer)?;
+        }
+
+        self.print_fields(printer);
 
-        // Print cause.
         if let Some(cause) = &self.cause {
-            printer.push_plain_text("\n");
-            cause.print(printer);
+            printer.new_line();
+
+            match cause.max_lines {
+                Some(_) => cause.print(printer),
+                None => cause.write_content(printer, writer)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints the fields of the log as key/value pairs after its blocks.
+    fn print_fields(&self, printer: &mut Printer<'a>) {
+        if self.fields.is_empty() {
+            return;
         }
+
+        if !self.content.blocks.is_empty() {
+            printer.new_line();
+        }
+
+        let fields = self
+            .fields
+            .iter()
+            .fold(KeyValueBlock::new(), |block, (key, value)| {
+                block.add_entry(key.clone(), value.to_string())
+            });
+
+        fields.print(printer);
+    }
+}
+
+impl<'a, B: Into<LogBlock<'a>>> Extend<B> for Log<'a> {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        self.content.extend(iter);
+        self.invalidate_render_cache();
     }
 }
 
@@ -150,16 +804,609 @@ impl<'a> Display for Log<'a> {
     }
 }
 
+impl RenderCache {
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the text rendered for `level`, `format` and `theme`, calling `render` the
+    /// first time. The texts rendered with other themes are discarded.
+    fn get_or_render(
+        &self,
+        level: LogLevel,
+        format: PrinterFormat,
+        theme: Theme,
+        render: impl FnOnce() -> String,
+    ) -> Arc<str> {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(_, _, t, _)| *t == theme);
+
+        if let Some((_, _, _, text)) = entries
+            .iter()
+            .find(|(l, f, _, _)| *l == level && *f == format)
+        {
+            return text.clone();
+        }
+
+        let text: Arc<str> = render().into();
+        entries.push((level, format, theme, text.clone()));
+        text
+    }
+
+    /// Removes all the rendered texts.
+    fn clear(&mut self) {
+        self.0.get_mut().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+}
+
+impl Clone for RenderCache {
+    fn clone(&self) -> Self {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        Self(Mutex::new(entries.clone()))
+    }
+}
+
+impl Debug for RenderCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("RenderCache")
+            .field("entries", &entries.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "miette")]
+impl<'a> From<&dyn miette::Diagnostic> for Log<'a> {
+    fn from(diagnostic: &dyn miette::Diagnostic) -> Self {
+        Log::from_diagnostic(diagnostic)
+    }
+}
+
+/// Builds the [CodeBlock] that highlights the labels of `diagnostic` in its source code,
+/// if it has both.
+#[cfg(feature = "miette")]
+fn diagnostic_code_block<'a>(diagnostic: &dyn miette::Diagnostic) -> Option<CodeBlock<'a>> {
+    let source = diagnostic.source_code()?;
+    let mut labels: Vec<_> = diagnostic.labels()?.collect();
+    labels.sort_by_key(|v| (v.offset(), v.len()));
+
+    let start = labels.first()?.offset();
+    let end = labels.iter().map(|v| v.offset() + v.len()).max()?;
+    let contents = source
+        .read_span(&(start, end - start).into(), usize::MAX, usize::MAX)
+        .ok()?;
+    let code = String::from_utf8_lossy(contents.data()).into_owned();
+    let code_offset = contents.span().offset();
+
+    let mut block = CodeBlock::new(code.clone()).first_line_number(contents.line() + 1);
+
+    if let Some(name) = contents.name() {
+        block = block.file_path(name.to_string());
+    }
+
+    let mut previous: Option<Range<usize>> = None;
+
+    for label in labels {
+        let Some(range) = label
+            .offset()
+            .checked_sub(code_offset)
+            .map(|v| v..v + label.len())
+        else {
+            continue;
+        };
+
+        let collides = previous
+            .as_ref()
+            .is_some_and(|v| range.start < v.end || *v == range);
+        let valid = range.end <= code.len()
+            && code.is_char_boundary(range.start)
+            && code.is_char_boundary(range.end);
+
+        if collides || !valid {
+            continue;
+        }
+
+        block = match label.label() {
+            Some(message) => block.highlight_section_message(&range, None, message.to_string()),
+            None => block.highlight_section(&range, None),
+        };
+        previous = Some(range);
+    }
+
+    Some(block)
+}
+
+/// The representation of a [Log] used to serialize it, tagged with [LOG_SCHEMA_VERSION].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct SerializableLog<'r, 'a> {
+    schema_version: u32,
+    level: LogLevel,
+    content: &'r LogContent<'a>,
+    cause: &'r Option<Box<Log<'a>>>,
+    suggestions: &'r [Suggestion<'a>],
+    #[serde(
+        skip_serializing_if = "<[_]>::is_empty",
+        with = "crate::log_field::serde_fields"
+    )]
+    fields: &'r [(Cow<'a, str>, FieldValue<'a>)],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_lines: Option<usize>,
+}
+
+/// The representation of a [Log] used to deserialize it.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DeserializableLog<'a> {
+    schema_version: u32,
+    level: LogLevel,
+    content: LogContent<'a>,
+    #[serde(default)]
+    cause: Option<Box<Log<'a>>>,
+    #[serde(default)]
+    suggestions: Vec<Suggestion<'a>>,
+    #[serde(default, with = "crate::log_field::serde_fields")]
+    fields: Vec<(Cow<'a, str>, FieldValue<'a>)>,
+    #[serde(default)]
+    max_lines: Option<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Log<'a> {
+    /// Serializes the log along with [LOG_SCHEMA_VERSION]. Custom blocks cannot be
+    /// serialized, so they make the serialization fail.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializableLog {
+            schema_version: LOG_SCHEMA_VERSION,
+            level: self.level,
+            content: &self.content,
+            cause: &self.cause,
+            suggestions: &self.suggestions,
+            fields: &self.fields,
+            max_lines: self.max_lines,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'a> serde::Deserialize<'de> for Log<'a> {
+    /// Deserializes a log, migrating it if it was serialized with an older
+    /// [LOG_SCHEMA_VERSION] and failing if it was serialized with a newer one.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let log = DeserializableLog::deserialize(deserializer)?
+            .migrate()
+            .map_err(serde::de::Error::custom)?;
+
+        Ok(Log {
+            level: log.level,
+            content: log.content,
+            cause: log.cause,
+            suggestions: log.suggestions,
+            fields: log.fields,
+            max_lines: log.max_lines,
+            render_cache: RenderCache::default(),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> DeserializableLog<'a> {
+    /// Upgrades the log to [LOG_SCHEMA_VERSION] one version at a time. Every new version of
+    /// the schema must add an arm that converts the logs of the previous one, and a directory
+    /// of fixtures in `tests/fixtures` that are kept unchanged afterwards.
+    fn migrate(self) -> Result<Self, String> {
+        match self.schema_version {
+            LOG_SCHEMA_VERSION => Ok(self),
+            version if version > LOG_SCHEMA_VERSION => Err(format!(
+                "unsupported log schema version {version}, the maximum supported is {LOG_SCHEMA_VERSION}"
+            )),
+            version => Err(format!("unknown log schema version {version}")),
+        }
+    }
+}
+
+/// Collects the suggestions of the code blocks of `content`, including the nested ones.
+fn collect_code_suggestions<'a>(content: &LogContent<'a>, suggestions: &mut Vec<Suggestion<'a>>) {
+    for block in &content.blocks {
+        match block {
+            LogBlock::Code(v) => suggestions.extend(v.get_suggestions()),
+            LogBlock::Prefix(v) => collect_code_suggestions(&v.content, suggestions),
+            LogBlock::Steps(v) => collect_code_suggestions(&v.steps, suggestions),
+            _ => {}
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
-    use crate::Log;
+    use crate::blocks::{CodeBlock, LogBlock, TextBlock};
+    use crate::log::RenderCache;
+    use crate::printer::{OutputStream, Printable, PrinterFormat};
+    use crate::{Log, LogLevel, Theme};
+    use std::sync::Arc;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_to_pager() {
+        let log = Log::info().add_block(TextBlock::new_plain("Paged"));
+
+        assert!(log.write_to_pager("true").is_ok());
+        assert!(log.write_to_pager("doclog-missing-pager -R").is_err());
+        assert!(log.write_to_pager("   ").is_err());
+    }
 
     #[test]
     fn test_display() {
         println!("{}", Log::error());
     }
+
+    #[test]
+    fn test_write_to() {
+        fn code<'a>() -> CodeBlock<'a> {
+            CodeBlock::new("let a = 1;\nlet b = 2;")
+                .highlight_section_message(4..5, None, "first")
+                .highlight_section_message(15..16, None, "second")
+        }
+
+        let log = Log::error()
+            .add_block(TextBlock::new_plain("Title"))
+            .add_block(code())
+            .field("request_id", "a1b2")
+            .set_cause(|log| log.add_block(code()).add_block(TextBlock::new_plain("End")));
+
+        let mut output = Vec::new();
+        log.write_to(PrinterFormat::Plain, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), log.to_plain_text());
+
+        // The output is buffered.
+        struct CountingWriter(usize, usize);
+
+        impl std::io::Write for CountingWriter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += 1;
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.1 += 1;
+                Ok(())
+            }
+        }
+
+        let mut writer = CountingWriter(0, 0);
+        log.write_to(PrinterFormat::Styled, &mut writer).unwrap();
+        assert_eq!((writer.0, writer.1), (1, 1));
+    }
+
+    #[test]
+    fn test_format() {
+        let log = Log::warn().add_block(TextBlock::new_plain("Message"));
+
+        assert_eq!(log.format(PrinterFormat::Plain), log.to_plain_text());
+        assert_eq!(log.format(PrinterFormat::Styled), log.to_styled_text());
+        assert_eq!(log.format(PrinterFormat::Default), log.to_text());
+
+        let auto = log.format(PrinterFormat::Auto);
+        assert!(auto == log.to_plain_text() || auto == log.to_styled_text());
+    }
+
+    #[test]
+    fn test_default_output() {
+        assert_eq!(Log::default_format(), PrinterFormat::Auto);
+        assert_eq!(Log::default_stream(), OutputStream::Stdout);
+
+        Log::set_default_format(PrinterFormat::Plain);
+        Log::set_default_stream(OutputStream::Stderr);
+        assert_eq!(Log::default_format(), PrinterFormat::Plain);
+        assert_eq!(Log::default_stream(), OutputStream::Stderr);
+
+        let log = Log::info().add_block(TextBlock::new_plain("Message"));
+        log.log();
+        log.print_stdout();
+        log.print_stderr();
+
+        Log::set_default_format(PrinterFormat::Auto);
+        Log::set_default_stream(OutputStream::Stdout);
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_from_diagnostic() {
+        use miette::{Diagnostic, LabeledSpan, NamedSource, Severity};
+        use std::fmt::{Display, Formatter};
+
+        #[derive(Debug)]
+        struct UndefinedVariable {
+            source: NamedSource<&'static str>,
+        }
+
+        impl Display for UndefinedVariable {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "Undefined variable")
+            }
+        }
+
+        impl std::error::Error for UndefinedVariable {}
+
+        impl Diagnostic for UndefinedVariable {
+            fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                Some(Box::new("E01"))
+            }
+
+            fn severity(&self) -> Option<Severity> {
+                Some(Severity::Warning)
+            }
+
+            fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+                Some(Box::new("declare it first"))
+            }
+
+            fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+                Some(&self.source)
+            }
+
+            fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+                Some(Box::new(
+                    [
+                        LabeledSpan::at(19..20, "not found"),
+                        LabeledSpan::at(19..21, "overlapping"),
+                        LabeledSpan::underline(4..5),
+                    ]
+                    .into_iter(),
+                ))
+            }
+        }
+
+        let diagnostic = UndefinedVariable {
+            source: NamedSource::new("main.rs", "let a = 1;\nlet b = c;"),
+        };
+        let log = Log::from(&diagnostic as &dyn Diagnostic);
+
+        assert_eq!(log.level, LogLevel::warn());
+        assert_eq!(
+            log.to_plain_text(),
+            "WARN[E01] Undefined variable\n⚠ ╭─[main.rs]\n1 │    let a = 1;\n  │        ^\n2 │    let b = c;\n  │            ╰── not found\n  ╰─\n= help: declare it first"
+        );
+    }
+
+    #[test]
+    fn test_filter_blocks() {
+        let log = Log::info()
+            .add_block(TextBlock::new_plain("Header"))
+            .add_block(CodeBlock::new("let a = 3;").highlight_section(4..5, None))
+            .add_block_with_id("footer", TextBlock::new_plain("Footer"))
+            .add_block(TextBlock::new_plain("See ").add_reference("footer"));
+
+        let verbose = log.min_level(LogLevel::warn());
+        let log = log.filter_blocks(|block| verbose || !matches!(block, LogBlock::Code(_)));
+
+        assert!(!verbose);
+        assert_eq!(log.to_plain_text(), "Header\nFooter\nSee [1, line 2]");
+    }
+
+    #[test]
+    fn test_truncate_lines() {
+        let log = Log::info()
+            .add_block(TextBlock::new_plain("a\nb\nc\nd"))
+            .truncate_lines(2);
+
+        assert_eq!(log.to_plain_text(), "a\nb\n… 2 more lines");
+        assert_eq!(
+            log.truncate_lines(3).to_plain_text(),
+            "a\nb\nc\n… 1 more line"
+        );
+
+        let log = Log::info()
+            .add_block(TextBlock::new_plain("a"))
+            .truncate_lines(1);
+
+        assert_eq!(log.to_plain_text(), "a");
+    }
+
+    #[test]
+    fn test_push() {
+        let lines = ["First", "Second"];
+        let mut log = Log::info();
+        log.push(TextBlock::new_plain("Header"));
+        log.extend(lines.iter().map(|v| TextBlock::new_plain(*v)));
+
+        assert_eq!(log.to_plain_text(), "Header\nFirst\nSecond");
+
+        let content: crate::LogContent = lines.into_iter().map(TextBlock::new_plain).collect();
+
+        assert_eq!(content.blocks.len(), 2);
+        assert_eq!(content.to_string(), "First\nSecond");
+    }
+
+    #[test]
+    fn test_render_cached() {
+        let mut log = Log::info().add_block(TextBlock::new_plain("First"));
+        let plain = log.render_cached(PrinterFormat::Plain);
+        let styled = log.render_cached(PrinterFormat::Styled);
+
+        assert_eq!(&*plain, "First");
+        assert_eq!(&*styled, log.to_styled_text());
+        assert!(Arc::ptr_eq(
+            &plain,
+            &log.render_cached(PrinterFormat::Plain)
+        ));
+        assert!(Arc::ptr_eq(
+            &styled,
+            &log.render_cached(PrinterFormat::Styled)
+        ));
+
+        log.push(TextBlock::new_plain("Second"));
+        assert_eq!(&*log.render_cached(PrinterFormat::Plain), "First\nSecond");
+
+        let cached = log.render_cached(PrinterFormat::Styled);
+        log.level = LogLevel::error();
+        assert!(!Arc::ptr_eq(
+            &cached,
+            &log.render_cached(PrinterFormat::Styled)
+        ));
+
+        log.render_cached(PrinterFormat::Plain);
+        log.content_mut().push(TextBlock::new_plain("Third"));
+        assert_eq!(
+            &*log.render_cached(PrinterFormat::Plain),
+            "First\nSecond\nThird"
+        );
+
+        // Other themes render the text again.
+        let cache = RenderCache::default();
+        let render = |theme: Theme| {
+            cache.get_or_render(LogLevel::info(), PrinterFormat::Plain, theme, || {
+                format!("{:?}", theme.info)
+            })
+        };
+
+        assert_eq!(&*render(Theme::dark()), &*render(Theme::dark()));
+        assert_ne!(&*render(Theme::dark()), &*render(Theme::light()));
+    }
+
+    #[test]
+    fn test_references() {
+        let log = Log::error()
+            .add_block(
+                TextBlock::new_plain("See ")
+                    .add_reference("details")
+                    .add_plain_text(" and ")
+                    .add_reference("unknown"),
+            )
+            .add_block_with_id("first", TextBlock::new_plain("First"))
+            .set_cause(|log| {
+                log.add_block_with_id("details", TextBlock::new_plain("Details\nmore details"))
+            });
+
+        assert_eq!(
+            log.to_plain_text(),
+            "See [2, line 3] and [?]\nFirst\nDetails\nmore details"
+        );
+        assert_eq!(
+            log.print_to_string(log.level, PrinterFormat::Styled),
+            "See \u{1b}[1m[2]\u{1b}[0m and \u{1b}[1m[?]\n\u{1b}[0mFirst\nDetails\nmore details"
+        );
+        assert_eq!(log.render().lines()[0], "See [2, line 3] and [?]");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use crate::blocks::{HeaderBlock, StepStatus, StepsBlock};
+        use yansi::{Color, Style};
+
+        let log = Log::error()
+            .add_block(HeaderBlock::new().code("E01").title("Invalid variable"))
+            .add_block(
+                CodeBlock::new("let a = b")
+                    .highlight_section_message(8..9, Some(Color::Cyan), "Undefined")
+                    .help("Declare it first"),
+            )
+            .add_block(StepsBlock::new().add_numbered_step(
+                TextBlock::new().add_styled_text("Step", Style::new().bold().fg(Color::Green)),
+                Some(StepStatus::Success),
+            ))
+            .set_cause(|log| log.add_block(TextBlock::new_plain("Cause")));
+
+        let json = serde_json::to_string(&log).unwrap();
+        assert!(json.starts_with(r#"{"schema_version":1,"#));
+
+        let deserialized: Log = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.to_styled_text(), log.to_styled_text());
+
+        let json = json.replacen(r#""schema_version":1"#, r#""schema_version":2"#, 1);
+        assert!(serde_json::from_str::<Log>(&json).is_err());
+
+        let json = json.replacen(r#""schema_version":2"#, r#""schema_version":0"#, 1);
+        assert!(serde_json::from_str::<Log>(&json).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_fixtures() {
+        macro_rules! fixture {
+            ($version:literal, $name:literal) => {
+                (
+                    include_str!(concat!("../tests/fixtures/", $version, "/", $name, ".json")),
+                    include_str!(concat!("../tests/fixtures/", $version, "/", $name, ".txt")),
+                )
+            };
+        }
+
+        let fixtures = [
+            fixture!("v1", "text"),
+            fixture!("v1", "code"),
+            fixture!("v1", "nested"),
+            fixture!("v1", "truncated"),
+        ];
+
+        for (json, text) in fixtures {
+            let log: Log = serde_json::from_str(json).unwrap();
+            assert_eq!(format!("{}\n", log.to_plain_text()), text);
+
+            let json = serde_json::to_string(&log).unwrap();
+            let log: Log = serde_json::from_str(&json).unwrap();
+            assert_eq!(format!("{}\n", log.to_plain_text()), text);
+        }
+    }
+
+    #[test]
+    fn test_fields() {
+        let log = Log::warn()
+            .add_block(TextBlock::new_plain("Slow request"))
+            .field("request_id", "a1b2")
+            .field("elapsed_ms", 1250)
+            .field("retried", false)
+            .field(
+                "path",
+                crate::FieldValue::display(std::path::Path::new("/api").display()),
+            );
+
+        assert_eq!(
+            log.to_plain_text(),
+            "Slow request\nrequest_id: a1b2\nelapsed_ms: 1250\n   retried: false\n      path: /api"
+        );
+        assert_eq!(
+            Log::info().field("ratio", 0.5).to_plain_text(),
+            "ratio: 0.5"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_fields() {
+        use crate::FieldValue;
+
+        let log = Log::info()
+            .field("user", "alice")
+            .field("attempts", 3)
+            .field("ratio", 0.5)
+            .field("cached", true);
+        let json = serde_json::to_string(&log).unwrap();
+
+        assert!(
+            json.contains(r#""fields":{"user":"alice","attempts":3,"ratio":0.5,"cached":true}"#),
+            "{json}"
+        );
+
+        let deserialized: Log = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            deserialized.fields,
+            vec![
+                ("user".into(), FieldValue::from("alice")),
+                ("attempts".into(), FieldValue::Int(3)),
+                ("ratio".into(), FieldValue::Float(0.5)),
+                ("cached".into(), FieldValue::Bool(true)),
+            ]
+        );
+
+        // Logs without fields do not serialize them.
+        assert!(!serde_json::to_string(&Log::info())
+            .unwrap()
+            .contains("fields"));
+    }
 }
@@ -1,13 +1,18 @@
 use crate::blocks::LogBlock;
 use crate::printer::{Printable, Printer, PrinterFormat};
-use crate::LogLevel;
+use crate::{BlockVisitor, LogLevel};
 use smallvec::SmallVec;
+use std::borrow::Cow;
 use std::fmt::Display;
 
 /// A list of log elements.
 #[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LogContent<'a> {
     pub blocks: SmallVec<[LogBlock<'a>; 3]>,
+
+    /// The ids of the blocks that can be referenced, along with the index of the block.
+    pub ids: Vec<(usize, Cow<'a, str>)>,
 }
 
 impl<'a> LogContent<'a> {
@@ -22,18 +27,88 @@ impl<'a> LogContent<'a> {
 
     /// Adds a new block.
     pub fn add_block(mut self, block: impl Into<LogBlock<'a>>) -> Self {
+        self.push(block);
+        self
+    }
+
+    /// Adds a new block in place. See [LogContent::add_block].
+    #[inline(always)]
+    pub fn push(&mut self, block: impl Into<LogBlock<'a>>) {
+        self.blocks.push(block.into());
+    }
+
+    /// Adds a new block that can be referenced by `id` from
+    /// [TextBlock::add_reference](crate::blocks::TextBlock::add_reference).
+    pub fn add_block_with_id(
+        mut self,
+        id: impl Into<Cow<'a, str>>,
+        block: impl Into<LogBlock<'a>>,
+    ) -> Self {
+        self.ids.push((self.blocks.len(), id.into()));
         self.blocks.push(block.into());
         self
     }
 
+    /// Keeps only the blocks for which `predicate` returns `true`.
+    pub fn filter_blocks(mut self, mut predicate: impl FnMut(&LogBlock<'a>) -> bool) -> Self {
+        let blocks = std::mem::take(&mut self.blocks);
+        let mut new_indexes = Vec::with_capacity(blocks.len());
+
+        for block in blocks {
+            if predicate(&block) {
+                new_indexes.push(Some(self.blocks.len()));
+                self.blocks.push(block);
+            } else {
+                new_indexes.push(None);
+            }
+        }
+
+        // Keep the ids pointing to the same blocks.
+        self.ids
+            .retain_mut(|(index, _)| match new_indexes.get(*index) {
+                Some(Some(new_index)) => {
+                    *index = *new_index;
+                    true
+                }
+                _ => false,
+            });
+
+        self
+    }
+
+    /// Traverses the blocks with `visitor`, see [BlockVisitor].
+    #[inline(always)]
+    pub fn visit_mut(&mut self, visitor: &mut impl BlockVisitor<'a>) {
+        visitor.visit_content_mut(self);
+    }
+
     /// Makes this type owned, i.e. changing the lifetime to `static`.
     pub fn make_owned(self) -> LogContent<'static> {
         LogContent {
             blocks: self.blocks.into_iter().map(|v| v.make_owned()).collect(),
+            ids: self
+                .ids
+                .into_iter()
+                .map(|(index, id)| (index, Cow::Owned(id.into_owned())))
+                .collect(),
         }
     }
 }
 
+impl<'a, B: Into<LogBlock<'a>>> Extend<B> for LogContent<'a> {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        self.blocks.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl<'a, B: Into<LogBlock<'a>>> FromIterator<B> for LogContent<'a> {
+    fn from_iter<T: IntoIterator<Item = B>>(iter: T) -> Self {
+        let mut content = LogContent::new();
+        content.extend(iter);
+        content
+    }
+}
+
 impl<'a> Printable<'a> for LogContent<'a> {
     fn print<'s>(&'s self, printer: &mut Printer<'a>)
     where
@@ -41,7 +116,7 @@ impl<'a> Printable<'a> for LogContent<'a> {
     {
         for (i, block) in self.blocks.iter().enumerate() {
             if i > 0 {
-                printer.push_plain_text("\n");
+                printer.new_line();
             }
 
             block.print(printer);
@@ -0,0 +1,259 @@
+use crate::blocks::{HeaderBlock, LogBlock, TextBlock};
+use crate::Log;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+thread_local! {
+    /// The contexts entered in the current thread, see [LogContext::enter].
+    static SCOPED_CONTEXTS: RefCell<Vec<LogContext>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Metadata shared across logs, e.g. a correlation id, that is rendered as extra messages
+/// of the [HeaderBlock] of every log.
+///
+/// A context can be attached to a [Logger](crate::Logger), see
+/// [LoggerBuilder::context](crate::LoggerBuilder::context), or entered in a scope with
+/// [LogContext::enter] so every log written by a [Logger](crate::Logger) in the current
+/// thread includes it until the scope ends.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct LogContext {
+    target: Option<Cow<'static, str>>,
+    module_path: Option<Cow<'static, str>>,
+    request_id: Option<Cow<'static, str>>,
+    values: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl LogContext {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [LogContext].
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the target, if any.
+    #[inline(always)]
+    pub fn get_target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Returns the module path, if any.
+    #[inline(always)]
+    pub fn get_module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+
+    /// Returns the request id, if any.
+    #[inline(always)]
+    pub fn get_request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Returns the custom key-values.
+    #[inline(always)]
+    pub fn get_values(&self) -> &[(Cow<'static, str>, Cow<'static, str>)] {
+        &self.values
+    }
+
+    /// Returns whether the context does not contain any metadata.
+    pub fn is_empty(&self) -> bool {
+        self.target.is_none()
+            && self.module_path.is_none()
+            && self.request_id.is_none()
+            && self.values.is_empty()
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the target.
+    #[inline(always)]
+    pub fn target(mut self, target: impl Into<Cow<'static, str>>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Sets the module path, usually with [module_path!].
+    #[inline(always)]
+    pub fn module_path(mut self, module_path: impl Into<Cow<'static, str>>) -> Self {
+        self.module_path = Some(module_path.into());
+        self
+    }
+
+    /// Sets the request id.
+    #[inline(always)]
+    pub fn request_id(mut self, request_id: impl Into<Cow<'static, str>>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Adds a custom key-value.
+    #[inline(always)]
+    pub fn value(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.values.push((key.into(), value.into()));
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the messages that represent the context in the [HeaderBlock] of a log.
+    pub fn messages<'a>(&self) -> Vec<TextBlock<'a>> {
+        let fields = [
+            ("target", &self.target),
+            ("module", &self.module_path),
+            ("request_id", &self.request_id),
+        ];
+
+        fields
+            .into_iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| (key, value.as_ref())))
+            .chain(
+                self.values
+                    .iter()
+                    .map(|(key, value)| (key.as_ref(), value.as_ref())),
+            )
+            .map(|(key, value)| TextBlock::new_plain(format!("{key}: {value}")))
+            .collect()
+    }
+
+    /// Adds the context to the first [HeaderBlock] of `log`, inserting an empty one at the
+    /// start if it does not have any.
+    pub fn apply<'a>(&self, log: Log<'a>) -> Log<'a> {
+        add_header_messages(log, self.messages())
+    }
+
+    /// Enters the context in the current thread until the returned guard is dropped.
+    /// Contexts can be nested, in which case all of them are included in the logs.
+    pub fn enter(self) -> LogContextGuard {
+        SCOPED_CONTEXTS.with(|contexts| contexts.borrow_mut().push(self));
+
+        LogContextGuard {
+            _not_send: PhantomData,
+        }
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns the contexts entered in the current thread, from the outermost to the
+    /// innermost one.
+    pub fn scoped() -> Vec<LogContext> {
+        SCOPED_CONTEXTS.with(|contexts| contexts.borrow().clone())
+    }
+
+    /// Returns the messages of the contexts entered in the current thread.
+    pub(crate) fn scoped_messages<'a>() -> Vec<TextBlock<'a>> {
+        SCOPED_CONTEXTS.with(|contexts| {
+            contexts
+                .borrow()
+                .iter()
+                .flat_map(|context| context.messages())
+                .collect()
+        })
+    }
+}
+
+/// A guard that removes a [LogContext] from the current thread when dropped,
+/// see [LogContext::enter].
+#[must_use = "the context is removed when the guard is dropped"]
+#[derive(Debug)]
+pub struct LogContextGuard {
+    // The context is entered in the current thread so it cannot be dropped in another one.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for LogContextGuard {
+    fn drop(&mut self) {
+        SCOPED_CONTEXTS.with(|contexts| {
+            contexts.borrow_mut().pop();
+        });
+    }
+}
+
+/// Adds `messages` to the first [HeaderBlock] of `log`, inserting an empty one at the
+/// start if it does not have any.
+pub(crate) fn add_header_messages<'a>(mut log: Log<'a>, messages: Vec<TextBlock<'a>>) -> Log<'a> {
+    if messages.is_empty() {
+        return log;
+    }
+
+    let content = log.content_mut();
+    let header = content.blocks.iter_mut().find_map(|block| match block {
+        LogBlock::Header(header) => Some(header),
+        _ => None,
+    });
+
+    match header {
+        Some(header) => header.extra_messages.extend(messages),
+        None => {
+            let mut header = HeaderBlock::new();
+            header.extra_messages = messages;
+            content.blocks.insert(0, header.into());
+
+            for (index, _) in &mut content.ids {
+                *index += 1;
+            }
+        }
+    }
+
+    log
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let context = LogContext::new()
+            .target("db")
+            .request_id("42")
+            .value("user", "admin");
+        let log = context.apply(Log::info().add_block(HeaderBlock::new().title("Query")));
+        let expected = Log::info().add_block(
+            HeaderBlock::new()
+                .title("Query")
+                .add_extra_message("target: db")
+                .add_extra_message("request_id: 42")
+                .add_extra_message("user: admin"),
+        );
+
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+
+        let log = context.apply(Log::info().add_block_with_id("text", TextBlock::new_plain("a")));
+
+        assert!(matches!(log.content.blocks[0], LogBlock::Header(_)));
+        assert_eq!(log.content.ids, vec![(1, Cow::Borrowed("text"))]);
+    }
+
+    #[test]
+    fn test_enter() {
+        assert!(LogContext::scoped().is_empty());
+
+        {
+            let _outer = LogContext::new().request_id("1").enter();
+            let _inner = LogContext::new().target("db").enter();
+
+            assert_eq!(
+                LogContext::scoped(),
+                vec![
+                    LogContext::new().request_id("1"),
+                    LogContext::new().target("db")
+                ]
+            );
+            assert_eq!(LogContext::scoped_messages().len(), 2);
+        }
+
+        assert!(LogContext::scoped().is_empty());
+    }
+}
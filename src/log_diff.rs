@@ -0,0 +1,342 @@
+use crate::blocks::{diff_lines, DiffBlock, DiffOperation, LogBlock};
+use crate::printer::{Printable, Printer, PrinterFormat};
+use crate::{Log, LogLevel};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Compares two logs block by block, e.g. to debug why a generated diagnostic changed
+/// between versions in a golden test.
+///
+/// The blocks are rendered as plain text and aligned with the longest common subsequence,
+/// so an inserted block does not mark the following ones as changed. The causes are
+/// compared recursively.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::{HeaderBlock, TextBlock};
+/// use doclog::{diff_logs, Log};
+///
+/// let expected = Log::error()
+///     .add_block(HeaderBlock::new().title("Invalid value"))
+///     .add_block(TextBlock::new_plain("Expected a number"));
+/// let actual = Log::error()
+///     .add_block(HeaderBlock::new().title("Invalid value"))
+///     .add_block(TextBlock::new_plain("Expected a string"));
+/// let diff = diff_logs(&expected, &actual);
+///
+/// assert!(!diff.is_equal());
+/// println!("{}", diff.to_diff_block().context_lines(1));
+/// ```
+pub fn diff_logs(expected: &Log, actual: &Log) -> LogDiff {
+    let expected_blocks = render_blocks(expected);
+    let actual_blocks = render_blocks(actual);
+    let keys = |blocks: &[(&'static str, String)]| {
+        blocks
+            .iter()
+            .map(|(name, text)| Cow::Owned(format!("{name}\n{text}")))
+            .collect::<Vec<_>>()
+    };
+    let operations = diff_lines(&keys(&expected_blocks), &keys(&actual_blocks));
+    let mut blocks = Vec::with_capacity(operations.len());
+    let mut index = 0;
+
+    while index < operations.len() {
+        if let DiffOperation::Equal(old, new) = operations[index] {
+            let (name, text) = &expected_blocks[old];
+
+            blocks.push(BlockDiff {
+                kind: BlockDiffKind::Equal,
+                name,
+                expected_index: Some(old),
+                actual_index: Some(new),
+                expected: text.clone(),
+                actual: actual_blocks[new].1.clone(),
+            });
+            index += 1;
+            continue;
+        }
+
+        // Pair the removed blocks with the added ones of the same type.
+        let end = operations[index..]
+            .iter()
+            .position(|v| matches!(v, DiffOperation::Equal(..)))
+            .map_or(operations.len(), |v| v + index);
+        let removed: Vec<_> = operations[index..end]
+            .iter()
+            .filter_map(|v| match v {
+                DiffOperation::Delete(old) => Some(*old),
+                _ => None,
+            })
+            .collect();
+        let added: Vec<_> = operations[index..end]
+            .iter()
+            .filter_map(|v| match v {
+                DiffOperation::Insert(new) => Some(*new),
+                _ => None,
+            })
+            .collect();
+
+        for row in 0..removed.len().max(added.len()) {
+            let old = removed.get(row).copied();
+            let new = added.get(row).copied();
+
+            match (old, new) {
+                (Some(old), Some(new)) if expected_blocks[old].0 == actual_blocks[new].0 => {
+                    blocks.push(BlockDiff {
+                        kind: BlockDiffKind::Changed,
+                        name: expected_blocks[old].0,
+                        expected_index: Some(old),
+                        actual_index: Some(new),
+                        expected: expected_blocks[old].1.clone(),
+                        actual: actual_blocks[new].1.clone(),
+                    });
+                }
+                _ => {
+                    if let Some(old) = old {
+                        blocks.push(BlockDiff {
+                            kind: BlockDiffKind::Removed,
+                            name: expected_blocks[old].0,
+                            expected_index: Some(old),
+                            actual_index: None,
+                            expected: expected_blocks[old].1.clone(),
+                            actual: String::new(),
+                        });
+                    }
+
+                    if let Some(new) = new {
+                        blocks.push(BlockDiff {
+                            kind: BlockDiffKind::Added,
+                            name: actual_blocks[new].0,
+                            expected_index: None,
+                            actual_index: Some(new),
+                            expected: String::new(),
+                            actual: actual_blocks[new].1.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        index = end;
+    }
+
+    let cause = match (&expected.cause, &actual.cause) {
+        (None, None) => None,
+        (Some(expected), Some(actual)) => Some(diff_logs(expected, actual)),
+        (Some(expected), None) => Some(diff_logs(expected, &Log::new(expected.level))),
+        (None, Some(actual)) => Some(diff_logs(&Log::new(actual.level), actual)),
+    };
+
+    LogDiff {
+        expected_level: expected.level,
+        actual_level: actual.level,
+        blocks,
+        cause: cause.map(Box::new),
+    }
+}
+
+/// The differences between two logs, see [diff_logs].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LogDiff {
+    pub expected_level: LogLevel,
+    pub actual_level: LogLevel,
+
+    /// The blocks of both logs, aligned.
+    pub blocks: Vec<BlockDiff>,
+
+    /// The differences between the causes, if any of the logs has a cause. A missing
+    /// cause is compared as a log without blocks.
+    pub cause: Option<Box<LogDiff>>,
+}
+
+impl LogDiff {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns whether both logs are equal.
+    pub fn is_equal(&self) -> bool {
+        self.expected_level == self.actual_level
+            && self.blocks.iter().all(|v| v.kind == BlockDiffKind::Equal)
+            && self.cause.as_ref().is_none_or(|v| v.is_equal())
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Renders the differences as a [DiffBlock] between the texts of both logs, where every
+    /// block is preceded by a line with its type, e.g. `[code]`, and the cause is preceded
+    /// by a `caused by:` line.
+    pub fn to_diff_block(&self) -> DiffBlock<'static> {
+        let mut expected = String::new();
+        let mut actual = String::new();
+
+        self.write_texts(&mut expected, &mut actual);
+
+        DiffBlock::new(expected, actual)
+    }
+
+    /// Writes the texts of both logs compared by [LogDiff::to_diff_block].
+    fn write_texts(&self, expected: &mut String, actual: &mut String) {
+        expected.push_str(&format!("level: {}\n", self.expected_level.name()));
+        actual.push_str(&format!("level: {}\n", self.actual_level.name()));
+
+        for block in &self.blocks {
+            if block.expected_index.is_some() {
+                expected.push_str(&format!("[{}]\n{}\n", block.name, block.expected));
+            }
+
+            if block.actual_index.is_some() {
+                actual.push_str(&format!("[{}]\n{}\n", block.name, block.actual));
+            }
+        }
+
+        if let Some(cause) = &self.cause {
+            expected.push_str("caused by:\n");
+            actual.push_str("caused by:\n");
+            cause.write_texts(expected, actual);
+        }
+    }
+}
+
+/// The comparison of a block between two logs, see [diff_logs].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BlockDiff {
+    pub kind: BlockDiffKind,
+
+    /// The type of the block, e.g. `code` or `header`.
+    pub name: &'static str,
+
+    /// The index of the block in the expected log, if present.
+    pub expected_index: Option<usize>,
+
+    /// The index of the block in the actual log, if present.
+    pub actual_index: Option<usize>,
+
+    /// The block of the expected log as plain text, or empty if not present.
+    pub expected: String,
+
+    /// The block of the actual log as plain text, or empty if not present.
+    pub actual: String,
+}
+
+/// How a block differs between two logs.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BlockDiffKind {
+    /// The block is present and equal in both logs.
+    Equal,
+
+    /// The block is present in both logs with a different content.
+    Changed,
+
+    /// The block is only present in the expected log.
+    Removed,
+
+    /// The block is only present in the actual log.
+    Added,
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Renders the blocks of a log as plain text along with their type, resolving the
+/// references between them.
+fn render_blocks(log: &Log) -> Vec<(&'static str, String)> {
+    let printer = Printer::new(log.level, PrinterFormat::Plain);
+    let references = Arc::new(log.resolve_references(&printer));
+
+    log.content
+        .blocks
+        .iter()
+        .map(|block| {
+            let mut block_printer = printer.derive();
+            block_printer.references = Some(references.clone());
+            block.print(&mut block_printer);
+
+            (block_name(block), block_printer.to_string())
+        })
+        .collect()
+}
+
+/// Returns the type of a block.
+fn block_name(block: &LogBlock) -> &'static str {
+    match block {
+        LogBlock::Text(_) => "text",
+        LogBlock::Prefix(_) => "prefix",
+        LogBlock::Separator(_) => "separator",
+        LogBlock::Header(_) => "header",
+        LogBlock::Note(_) => "note",
+        LogBlock::Stack(_) => "stack",
+        LogBlock::Code(_) => "code",
+        LogBlock::Steps(_) => "steps",
+        LogBlock::Diff(_) => "diff",
+        LogBlock::Table(_) => "table",
+        LogBlock::KeyValue(_) => "key_value",
+        LogBlock::Progress(_) => "progress",
+        LogBlock::Custom(_) => "custom",
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{CodeBlock, HeaderBlock, NoteBlock, TextBlock};
+
+    #[test]
+    fn test_diff_logs() {
+        let expected = Log::error()
+            .add_block(HeaderBlock::new().title("Invalid value"))
+            .add_block(CodeBlock::new("let a = 1;").highlight_section(8..9, None))
+            .add_block(TextBlock::new_plain("Expected a number"));
+        let actual = Log::error()
+            .add_block(HeaderBlock::new().title("Invalid value"))
+            .add_block(NoteBlock::new().text("Inserted"))
+            .add_block(TextBlock::new_plain("Expected a string"));
+        let diff = diff_logs(&expected, &actual);
+
+        assert!(!diff.is_equal());
+        assert_eq!(
+            diff.blocks
+                .iter()
+                .map(|v| (v.kind, v.name, v.expected_index, v.actual_index))
+                .collect::<Vec<_>>(),
+            vec![
+                (BlockDiffKind::Equal, "header", Some(0), Some(0)),
+                (BlockDiffKind::Removed, "code", Some(1), None),
+                (BlockDiffKind::Added, "note", None, Some(1)),
+                (BlockDiffKind::Changed, "text", Some(2), Some(2)),
+            ]
+        );
+        assert_eq!(diff.cause, None);
+
+        let text = diff.to_diff_block().context_lines(0).to_string();
+        assert!(text.contains("- Expected a number"), "{text}");
+        assert!(text.contains("+ Expected a string"), "{text}");
+        assert!(!text.contains("Invalid value"), "{text}");
+
+        assert!(diff_logs(&expected, &expected).is_equal());
+    }
+
+    #[test]
+    fn test_diff_causes() {
+        let expected = Log::error()
+            .add_block(TextBlock::new_plain("Failed"))
+            .set_cause(|v| v.add_block(TextBlock::new_plain("Not found")));
+        let actual = Log::warn().add_block(TextBlock::new_plain("Failed"));
+        let diff = diff_logs(&expected, &actual);
+
+        assert!(!diff.is_equal());
+        assert_eq!(diff.blocks[0].kind, BlockDiffKind::Equal);
+
+        let cause = diff.cause.as_ref().unwrap();
+        assert_eq!(cause.blocks.len(), 1);
+        assert_eq!(cause.blocks[0].kind, BlockDiffKind::Removed);
+        assert_eq!(cause.blocks[0].expected, "Not found");
+        assert_eq!(
+            diff.to_diff_block().get_expected(),
+            "level: error\n[text]\nFailed\ncaused by:\nlevel: error\n[text]\nNot found\n"
+        );
+    }
+}
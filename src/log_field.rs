@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+
+/// The value of a structured field of a [Log](crate::Log), see
+/// [Log::field](crate::Log::field).
+///
+/// Fields are printed as text along with the log, but sinks that export structured data,
+/// e.g. the serialized JSON or OpenTelemetry records, keep their type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(untagged)
+)]
+pub enum FieldValue<'a> {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Str(Cow<'a, str>),
+}
+
+impl<'a> FieldValue<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a text value from anything that implements [Display].
+    #[inline(always)]
+    pub fn display(value: impl Display) -> Self {
+        FieldValue::Str(Cow::Owned(value.to_string()))
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> FieldValue<'static> {
+        match self {
+            FieldValue::Bool(v) => FieldValue::Bool(v),
+            FieldValue::Int(v) => FieldValue::Int(v),
+            FieldValue::UInt(v) => FieldValue::UInt(v),
+            FieldValue::Float(v) => FieldValue::Float(v),
+            FieldValue::Str(v) => FieldValue::Str(Cow::Owned(v.into_owned())),
+        }
+    }
+}
+
+impl<'a> Display for FieldValue<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Bool(v) => write!(f, "{v}"),
+            FieldValue::Int(v) => write!(f, "{v}"),
+            FieldValue::UInt(v) => write!(f, "{v}"),
+            FieldValue::Float(v) => write!(f, "{v}"),
+            FieldValue::Str(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for FieldValue<'a> {
+    fn from(value: &'a str) -> Self {
+        FieldValue::Str(Cow::Borrowed(value))
+    }
+}
+
+impl<'a> From<String> for FieldValue<'a> {
+    fn from(value: String) -> Self {
+        FieldValue::Str(Cow::Owned(value))
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for FieldValue<'a> {
+    fn from(value: Cow<'a, str>) -> Self {
+        FieldValue::Str(value)
+    }
+}
+
+impl<'a> From<bool> for FieldValue<'a> {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+impl<'a> From<f32> for FieldValue<'a> {
+    fn from(value: f32) -> Self {
+        FieldValue::Float(value as f64)
+    }
+}
+
+impl<'a> From<f64> for FieldValue<'a> {
+    fn from(value: f64) -> Self {
+        FieldValue::Float(value)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($variant:ident, $repr:ty, $($type:ty),*) => {
+        $(
+            impl<'a> From<$type> for FieldValue<'a> {
+                fn from(value: $type) -> Self {
+                    FieldValue::$variant(value as $repr)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(Int, i64, i8, i16, i32, i64, isize);
+impl_from_int!(UInt, u64, u8, u16, u32, u64, usize);
+
+/// Serializes the fields of a log as a map, keeping their order.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_fields {
+    use super::FieldValue;
+    use serde::de::{MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserializer, Serializer};
+    use std::borrow::Cow;
+
+    pub fn serialize<S: Serializer>(
+        fields: &[(Cow<str>, FieldValue)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(fields.len()))?;
+
+        for (key, value) in fields {
+            map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+    }
+
+    pub fn deserialize<'de, 'a, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(Cow<'a, str>, FieldValue<'a>)>, D::Error> {
+        struct FieldsVisitor;
+
+        impl<'de> Visitor<'de> for FieldsVisitor {
+            type Value = Vec<(Cow<'static, str>, FieldValue<'static>)>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map of fields")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = Vec::with_capacity(map.size_hint().unwrap_or(0));
+
+                while let Some((key, value)) = map.next_entry::<String, FieldValue>()? {
+                    fields.push((Cow::Owned(key), value.make_owned()));
+                }
+
+                Ok(fields)
+            }
+        }
+
+        deserializer.deserialize_map(FieldsVisitor)
+    }
+}
@@ -0,0 +1,724 @@
+use crate::blocks::{HeaderBlock, LogBlock};
+use crate::log_context::add_header_messages;
+use crate::sinks::LogSink;
+use crate::{Log, LogContext, LogLevel, PrinterFormat, Redaction, Theme};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static GLOBAL_LOGGER: OnceLock<Logger> = OnceLock::new();
+
+/// A logger that filters logs by their level and writes them into a set of sinks.
+///
+/// Use [Logger::builder] to configure it.
+pub struct Logger {
+    min_level: LogLevel,
+    theme: Option<Theme>,
+    context: Option<LogContext>,
+    level_formats: Vec<(LogLevel, PrinterFormat)>,
+    rate_limits: HashMap<String, RateLimiter>,
+    /// The source of the current time of the rate limits.
+    clock: fn() -> Instant,
+    redaction: Option<Redaction>,
+    sinks: Vec<Box<dyn LogSink>>,
+}
+
+/// The limit of logs with a header code per time window, see [LoggerBuilder::rate_limit].
+#[derive(Debug)]
+struct RateLimiter {
+    max_per_window: usize,
+    window: Duration,
+    state: Mutex<RateLimitState>,
+}
+
+#[derive(Debug)]
+struct RateLimitState {
+    window_start: Option<Instant>,
+    count: usize,
+    suppressed: usize,
+    level: LogLevel,
+}
+
+impl Logger {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LoggerBuilder] with a minimum level of [LogLevel::trace] and without sinks.
+    #[inline(always)]
+    pub fn builder() -> LoggerBuilder {
+        LoggerBuilder::new()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the minimum level a log must have to be written.
+    #[inline(always)]
+    pub fn min_level(&self) -> LogLevel {
+        self.min_level
+    }
+
+    /// Returns the theme of the logger, if any.
+    #[inline(always)]
+    pub fn theme(&self) -> Option<Theme> {
+        self.theme
+    }
+
+    /// Returns the context added to every log, if any.
+    #[inline(always)]
+    pub fn context(&self) -> Option<&LogContext> {
+        self.context.as_ref()
+    }
+
+    /// Returns the redaction applied to every log, if any.
+    #[inline(always)]
+    pub fn redaction(&self) -> Option<&Redaction> {
+        self.redaction.as_ref()
+    }
+
+    /// Returns the format the sinks must use for a log with `level`, if any rule applies.
+    /// See [LoggerBuilder::level_format].
+    pub fn format_for(&self, level: LogLevel) -> Option<PrinterFormat> {
+        self.level_formats
+            .iter()
+            .filter(|(min_level, _)| level.level() >= min_level.level())
+            .max_by_key(|(min_level, _)| min_level.level())
+            .map(|(_, format)| *format)
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Sets `logger` as the global logger, returning it back if one was already set.
+    /// The theme of the logger, if any, becomes the global [Theme].
+    ///
+    /// The global logger is never dropped, so the summaries of its rate limits are only
+    /// written by [Logger::log] and [Logger::flush_rate_limits], e.g. before exiting.
+    // Mirrors the API of OnceLock::set, so the logger is returned back as is.
+    #[allow(clippy::result_large_err)]
+    pub fn set_global(logger: Logger) -> Result<(), Logger> {
+        let theme = logger.theme;
+        GLOBAL_LOGGER.set(logger)?;
+
+        if let Some(theme) = theme {
+            Theme::set_global(theme);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the global logger, if it was set.
+    pub fn global() -> Option<&'static Logger> {
+        GLOBAL_LOGGER.get()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns whether a log with `level` would be written.
+    #[inline(always)]
+    pub fn is_enabled(&self, level: LogLevel) -> bool {
+        level.level() >= self.min_level.level()
+    }
+
+    /// Writes `log` into all the sinks if its level is enabled.
+    ///
+    /// The context of the logger and the ones entered in the current thread, see
+    /// [LogContext::enter], are added to the header of the log before writing it.
+    ///
+    /// All sinks receive the log even if any of them fails, in which case
+    /// the first error is returned.
+    ///
+    /// Logs that exceed a rate limit are suppressed, see [LoggerBuilder::rate_limit], and
+    /// the summaries of the rate limits whose window is over are written first.
+    ///
+    /// The secrets are masked after adding the context, see [LoggerBuilder::redaction].
+    pub fn log(&self, log: &Log) -> std::io::Result<()> {
+        let mut result = self.write_summaries(true);
+
+        if !self.is_enabled(log.level) {
+            return result;
+        }
+
+        if let Some((code, limiter)) = self.rate_limiter_of(log) {
+            let (allowed, summary) = limiter.check(code, log.level, (self.clock)());
+
+            if let Some(summary) = summary {
+                result = result.and(self.write(&summary));
+            }
+
+            if !allowed {
+                return result;
+            }
+        }
+
+        let mut messages = self
+            .context
+            .as_ref()
+            .map(LogContext::messages)
+            .unwrap_or_default();
+        messages.extend(LogContext::scoped_messages());
+
+        let mut log = if messages.is_empty() {
+            Cow::Borrowed(log)
+        } else {
+            Cow::Owned(add_header_messages(log.clone(), messages))
+        };
+
+        if let Some(redaction) = &self.redaction {
+            redaction.redact_log(log.to_mut());
+        }
+
+        let write_result = self.write(&log);
+        result.and(write_result)
+    }
+
+    /// Writes the summaries of the logs suppressed in the current windows of the rate
+    /// limits, see [LoggerBuilder::rate_limit], returning the first error. They are also
+    /// written when the logger is dropped, except for the global one.
+    pub fn flush_rate_limits(&self) -> std::io::Result<()> {
+        self.write_summaries(false)
+    }
+
+    /// Writes the summaries of the rate limits, only of those whose window is over if
+    /// `only_expired`, returning the first error.
+    fn write_summaries(&self, only_expired: bool) -> std::io::Result<()> {
+        let mut result = Ok(());
+
+        if self.rate_limits.is_empty() {
+            return result;
+        }
+
+        let now = (self.clock)();
+
+        for (code, limiter) in &self.rate_limits {
+            if let Some(summary) = limiter.take_summary(code, only_expired, now) {
+                result = result.and(self.write(&summary));
+            }
+        }
+
+        result
+    }
+
+    /// Returns the code of the first header of `log` along with its rate limiter, if any.
+    fn rate_limiter_of<'s>(&'s self, log: &'s Log) -> Option<(&'s str, &'s RateLimiter)> {
+        if self.rate_limits.is_empty() {
+            return None;
+        }
+
+        let code = log.content.blocks.iter().find_map(|block| match block {
+            LogBlock::Header(header) if !header.code.is_empty() => Some(&header.code),
+            _ => None,
+        })?;
+
+        self.rate_limits
+            .get(code.as_ref())
+            .map(|limiter| (code.as_ref(), limiter))
+    }
+
+    /// Writes `log` into all the sinks, returning the first error.
+    fn write(&self, log: &Log) -> std::io::Result<()> {
+        let format = self.format_for(log.level);
+        let mut result = Ok(());
+
+        // Render the log once, outside the locks of the sinks, so the ones that write
+        // text reuse it.
+        if let Some(format) = format.filter(|_| self.sinks.len() > 1) {
+            log.render_cached(format);
+        }
+
+        for sink in &self.sinks {
+            let sink_result = match format {
+                Some(format) => sink.write_log_with_format(log, format),
+                None => sink.write_log(log),
+            };
+
+            if let Err(e) = sink_result {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl std::fmt::Debug for Logger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Logger")
+            .field("min_level", &self.min_level)
+            .field("theme", &self.theme)
+            .field("context", &self.context)
+            .field("level_formats", &self.level_formats)
+            .field("rate_limits", &self.rate_limits)
+            .field("redaction", &self.redaction)
+            .field("sinks", &self.sinks.len())
+            .finish()
+    }
+}
+
+impl Drop for Logger {
+    /// Writes the summaries of the logs suppressed in the current windows.
+    fn drop(&mut self) {
+        let _ = self.flush_rate_limits();
+    }
+}
+
+impl RateLimiter {
+    // METHODS ----------------------------------------------------------------
+
+    /// Counts a log with `level` written at `now`, returning whether it can be written and
+    /// the summary of the logs suppressed in the previous window, if its time is over.
+    fn check(&self, code: &str, level: LogLevel, now: Instant) -> (bool, Option<Log<'static>>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let mut summary = None;
+
+        let is_expired = state
+            .window_start
+            .is_none_or(|start| now.duration_since(start) >= self.window);
+
+        if is_expired {
+            summary = state.summary(code);
+            state.window_start = Some(now);
+            state.count = 0;
+        }
+
+        if state.count < self.max_per_window {
+            state.count += 1;
+            (true, summary)
+        } else {
+            state.suppressed += 1;
+            state.level = state.level.max(level);
+            (false, summary)
+        }
+    }
+
+    /// Returns the summary of the logs suppressed in the current window, if any, only if
+    /// its time is over at `now` when `only_expired`. Expired windows are closed, so the
+    /// next log starts a new one.
+    fn take_summary(&self, code: &str, only_expired: bool, now: Instant) -> Option<Log<'static>> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let is_expired = state
+            .window_start
+            .is_none_or(|start| now.duration_since(start) >= self.window);
+
+        if is_expired {
+            state.window_start = None;
+        } else if only_expired {
+            return None;
+        }
+
+        state.summary(code)
+    }
+}
+
+impl RateLimitState {
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the log that summarizes the suppressed logs and resets their count.
+    fn summary(&mut self, code: &str) -> Option<Log<'static>> {
+        if self.suppressed == 0 {
+            return None;
+        }
+
+        let suppressed = std::mem::take(&mut self.suppressed);
+        let level = std::mem::replace(&mut self.level, LogLevel::trace());
+
+        Some(
+            Log::new(level).add_block(HeaderBlock::new().code(code.to_string()).title(format!(
+                "suppressed {suppressed} similar diagnostic{}",
+                if suppressed == 1 { "" } else { "s" }
+            ))),
+        )
+    }
+}
+
+/// A builder of [Logger]s.
+pub struct LoggerBuilder {
+    min_level: LogLevel,
+    theme: Option<Theme>,
+    context: Option<LogContext>,
+    level_formats: Vec<(LogLevel, PrinterFormat)>,
+    rate_limits: HashMap<String, RateLimiter>,
+    /// The source of the current time of the rate limits.
+    clock: fn() -> Instant,
+    redaction: Option<Redaction>,
+    sinks: Vec<Box<dyn LogSink>>,
+}
+
+impl LoggerBuilder {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LoggerBuilder] with a minimum level of [LogLevel::trace] and without sinks.
+    pub fn new() -> Self {
+        Self {
+            min_level: LogLevel::trace(),
+            theme: None,
+            context: None,
+            level_formats: Vec::new(),
+            rate_limits: HashMap::new(),
+            clock: Instant::now,
+            redaction: None,
+            sinks: Vec::new(),
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the minimum level a log must have to be written.
+    pub fn min_level(mut self, min_level: LogLevel) -> Self {
+        self.min_level = min_level;
+        self
+    }
+
+    /// Sets the theme applied globally when the logger is set as the global one.
+    /// See [Logger::set_global].
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Sets the context added to every log. See [Logger::log].
+    pub fn context(mut self, context: LogContext) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Sets the `format` the sinks must use for the logs with at least `level`, e.g. to
+    /// print trace and debug logs as plain text so they do not pay the cost of the styles.
+    /// When several rules apply to a log, the one with the highest level is used.
+    ///
+    /// Sinks can ignore the format, see [LogSink::write_log_with_format].
+    pub fn level_format(mut self, level: LogLevel, format: PrinterFormat) -> Self {
+        self.level_formats.retain(|(v, _)| *v != level);
+        self.level_formats.push((level, format));
+        self
+    }
+
+    /// Limits the logs whose first [HeaderBlock] has the code `code` to `max_per_window` per
+    /// `window`, to protect hot paths from log storms. The logs over the limit are suppressed
+    /// and summarized in a single log, e.g. `suppressed 240 similar diagnostics`, which is
+    /// written by the first log after the window, by [Logger::flush_rate_limits] or when
+    /// the logger is dropped.
+    ///
+    /// `wasm32-unknown-unknown` has no clock, so the logs are never rate limited there.
+    pub fn rate_limit(
+        mut self,
+        code: impl Into<String>,
+        max_per_window: usize,
+        window: Duration,
+    ) -> Self {
+        if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+            return self;
+        }
+
+        self.rate_limits.insert(
+            code.into(),
+            RateLimiter {
+                max_per_window,
+                window,
+                state: Mutex::new(RateLimitState {
+                    window_start: None,
+                    count: 0,
+                    suppressed: 0,
+                    level: LogLevel::trace(),
+                }),
+            },
+        );
+        self
+    }
+
+    /// Sets the redaction that masks the secrets of every log before writing it into the
+    /// sinks. Use a [RedactingSink](crate::sinks::RedactingSink) to redact only the logs of
+    /// some sinks.
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = Some(redaction);
+        self
+    }
+
+    /// Adds a new sink.
+    pub fn sink(mut self, sink: impl LogSink + 'static) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Sets the source of the current time of the rate limits, e.g. to control it in tests.
+    #[cfg(test)]
+    fn clock(mut self, clock: fn() -> Instant) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the [Logger].
+    pub fn build(self) -> Logger {
+        Logger {
+            min_level: self.min_level,
+            theme: self.theme,
+            context: self.context,
+            level_formats: self.level_formats,
+            rate_limits: self.rate_limits,
+            clock: self.clock,
+            redaction: self.redaction,
+            sinks: self.sinks,
+        }
+    }
+}
+
+impl Default for LoggerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{HeaderBlock, TextBlock};
+    use crate::sinks::{RingBufferSink, WriterSink};
+    use std::cell::Cell;
+    use std::sync::Arc;
+    use yansi::Style;
+
+    thread_local! {
+        /// The time elapsed in the clock of the tests of the current thread.
+        static FAKE_ELAPSED: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    }
+
+    /// A clock that only advances with [advance_fake_clock], so the rate limits do not
+    /// depend on the speed of the tests.
+    fn fake_now() -> Instant {
+        static START: OnceLock<Instant> = OnceLock::new();
+        *START.get_or_init(Instant::now) + FAKE_ELAPSED.with(Cell::get)
+    }
+
+    fn advance_fake_clock(duration: Duration) {
+        FAKE_ELAPSED.with(|v| v.set(v.get() + duration));
+    }
+
+    #[test]
+    fn test_min_level() {
+        let sink = Arc::new(RingBufferSink::new(10));
+        let logger = Logger::builder()
+            .min_level(LogLevel::info())
+            .sink(sink.clone())
+            .build();
+
+        logger
+            .log(&Log::debug().add_block(TextBlock::new_plain("Debug")))
+            .unwrap();
+        logger
+            .log(&Log::info().add_block(TextBlock::new_plain("Info")))
+            .unwrap();
+        logger
+            .log(&Log::error().add_block(TextBlock::new_plain("Error")))
+            .unwrap();
+
+        let logs = sink.snapshot();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].to_plain_text(), "Info");
+        assert_eq!(logs[1].to_plain_text(), "Error");
+    }
+
+    #[test]
+    fn test_min_level_custom() {
+        // Same level number as INFO.
+        let audit = LogLevel::custom("AUDIT", "audit", '§', yansi::Color::Cyan);
+        let logger = Logger::builder()
+            .min_level(LogLevel::info())
+            .level_format(LogLevel::info(), PrinterFormat::Plain)
+            .build();
+
+        assert!(logger.is_enabled(audit));
+        assert_eq!(logger.format_for(audit), Some(PrinterFormat::Plain));
+        assert!(Log::new(audit).min_level(LogLevel::info()));
+
+        let logger = Logger::builder().min_level(audit).build();
+
+        assert!(logger.is_enabled(LogLevel::info()));
+        assert!(!logger.is_enabled(LogLevel::debug()));
+    }
+
+    #[test]
+    fn test_context() {
+        let sink = Arc::new(RingBufferSink::new(10));
+        let logger = Logger::builder()
+            .context(LogContext::new().target("server"))
+            .sink(sink.clone())
+            .build();
+        let log = Log::info().add_block(HeaderBlock::new().title("Request"));
+
+        {
+            let _guard = LogContext::new().request_id("42").enter();
+            logger.log(&log).unwrap();
+        }
+        logger.log(&log).unwrap();
+
+        let logs = sink.snapshot();
+        let expected = HeaderBlock::new()
+            .title("Request")
+            .add_extra_message("target: server");
+        assert_eq!(
+            logs[0].to_plain_text(),
+            Log::info()
+                .add_block(expected.clone().add_extra_message("request_id: 42"))
+                .to_plain_text()
+        );
+        assert_eq!(
+            logs[1].to_plain_text(),
+            Log::info().add_block(expected).to_plain_text()
+        );
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let sink = Arc::new(RingBufferSink::new(10));
+        let logger = Logger::builder()
+            .rate_limit("E001", 2, Duration::from_secs(3600))
+            .rate_limit("E002", 1, Duration::from_secs(60))
+            .clock(fake_now)
+            .sink(sink.clone())
+            .build();
+        let log = |code: &'static str| Log::warn().add_block(HeaderBlock::new().code(code));
+
+        for _ in 0..5 {
+            logger.log(&log("E001")).unwrap();
+        }
+
+        logger.log(&log("E003")).unwrap();
+        assert_eq!(sink.len(), 3);
+
+        // Expired windows write the summary before the next log.
+        logger.log(&log("E002")).unwrap();
+        logger.log(&log("E002")).unwrap();
+        assert_eq!(sink.len(), 4);
+
+        advance_fake_clock(Duration::from_secs(60));
+        logger.log(&log("E002")).unwrap();
+        assert_eq!(sink.len(), 6);
+
+        drop(logger);
+
+        let logs: Vec<_> = sink.snapshot().iter().map(|v| v.to_plain_text()).collect();
+        assert_eq!(
+            logs,
+            vec![
+                "WARN[E001]",
+                "WARN[E001]",
+                "WARN[E003]",
+                "WARN[E002]",
+                "WARN[E002] suppressed 1 similar diagnostic",
+                "WARN[E002]",
+                "WARN[E001] suppressed 3 similar diagnostics",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flush_rate_limits() {
+        let sink = Arc::new(RingBufferSink::new(10));
+        let logger = Logger::builder()
+            .rate_limit("E001", 1, Duration::from_secs(3600))
+            .rate_limit("E002", 1, Duration::from_secs(60))
+            .clock(fake_now)
+            .sink(sink.clone())
+            .build();
+        let log = |code: &'static str| Log::warn().add_block(HeaderBlock::new().code(code));
+
+        for code in ["E001", "E001", "E002", "E002"] {
+            logger.log(&log(code)).unwrap();
+        }
+
+        // Any log writes the summaries of the expired windows.
+        advance_fake_clock(Duration::from_secs(60));
+        logger.log(&log("E003")).unwrap();
+        assert_eq!(sink.len(), 4);
+
+        logger.flush_rate_limits().unwrap();
+        logger.flush_rate_limits().unwrap();
+        drop(logger);
+
+        let logs: Vec<_> = sink.snapshot().iter().map(|v| v.to_plain_text()).collect();
+        assert_eq!(
+            logs,
+            vec![
+                "WARN[E001]",
+                "WARN[E002]",
+                "WARN[E002] suppressed 1 similar diagnostic",
+                "WARN[E003]",
+                "WARN[E001] suppressed 1 similar diagnostic",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redaction() {
+        let sink = Arc::new(RingBufferSink::new(10));
+        let logger = Logger::builder()
+            .context(LogContext::new().target("token=abc"))
+            .redaction(Redaction::new().key("token"))
+            .sink(sink.clone())
+            .build();
+        let log = Log::info().add_block(HeaderBlock::new().title("Connecting with token=def"));
+
+        logger.log(&log).unwrap();
+
+        assert_eq!(
+            sink.snapshot()[0].to_plain_text(),
+            Log::info()
+                .add_block(
+                    HeaderBlock::new()
+                        .title("Connecting with token=[REDACTED]")
+                        .add_extra_message("target: token=[REDACTED]")
+                )
+                .to_plain_text()
+        );
+    }
+
+    #[test]
+    fn test_level_format() {
+        let logger = Logger::builder()
+            .level_format(LogLevel::trace(), PrinterFormat::Plain)
+            .level_format(LogLevel::warn(), PrinterFormat::Styled)
+            .build();
+
+        assert_eq!(
+            logger.format_for(LogLevel::debug()),
+            Some(PrinterFormat::Plain)
+        );
+        assert_eq!(
+            logger.format_for(LogLevel::warn()),
+            Some(PrinterFormat::Styled)
+        );
+        assert_eq!(
+            logger.format_for(LogLevel::error()),
+            Some(PrinterFormat::Styled)
+        );
+        assert_eq!(
+            Logger::builder().build().format_for(LogLevel::error()),
+            None
+        );
+
+        let sink = Arc::new(WriterSink::new(Vec::new(), PrinterFormat::Styled));
+        let logger = Logger::builder()
+            .level_format(LogLevel::trace(), PrinterFormat::Plain)
+            .level_format(LogLevel::warn(), PrinterFormat::Styled)
+            .sink(sink.clone())
+            .build();
+        let text = TextBlock::new().add_styled_text("Text", Style::new().bold());
+
+        logger.log(&Log::debug().add_block(text.clone())).unwrap();
+        logger.log(&Log::warn().add_block(text)).unwrap();
+
+        drop(logger);
+
+        let output = String::from_utf8(Arc::into_inner(sink).unwrap().into_inner()).unwrap();
+        let mut lines = output.lines();
+
+        assert_eq!(lines.next(), Some("Text"));
+        assert_ne!(lines.next(), Some("Text"));
+    }
+}
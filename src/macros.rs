@@ -0,0 +1,254 @@
+/// Builds a [Log](crate::Log) of the given level from a concise list of items followed by
+/// the title, which accepts the same arguments as [format!].
+///
+/// The supported items, which can be repeated and appear in any order, are:
+/// - `code = <expr>`: the code of the header.
+/// - `location = <expr>`: the location of the header.
+/// - `snippet = (<source> [, <path>]) { <span> [=> <message>], ... }`: a
+///   [CodeBlock](crate::blocks::CodeBlock) that highlights every span, with an optional message.
+///   Spans must be sorted and cannot collide, like in [CodeBlock::highlight_section](crate::blocks::CodeBlock::highlight_section).
+/// - `note = <expr>`: a [NoteBlock](crate::blocks::NoteBlock).
+/// - `help = <expr>`: a [NoteBlock](crate::blocks::NoteBlock) prefixed with `help: `.
+///
+/// # Examples
+/// ```
+/// use doclog::LogLevel;
+///
+/// let name = "x";
+/// let log = doclog::log!(
+///     LogLevel::error(),
+///     code = "E0425",
+///     snippet = ("let a = x;", "src/main.rs") {
+///         8..9 => "not found in this scope",
+///     },
+///     help = "declare it with `let`",
+///     "Undefined variable `{}`", name
+/// );
+/// ```
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($rest:tt)+) => {{
+        let mut header = $crate::blocks::HeaderBlock::new();
+        #[allow(unused_mut)]
+        let mut blocks: ::std::vec::Vec<$crate::blocks::LogBlock> = ::std::vec::Vec::new();
+        $crate::__log_items!(header, blocks; $($rest)+);
+
+        blocks
+            .into_iter()
+            .fold($crate::Log::new($level).add_block(header), |log, block| {
+                log.add_block(block)
+            })
+    }};
+}
+
+/// Same as [log!] with the [error](crate::LogLevel::error) level.
+#[macro_export]
+macro_rules! error_log {
+    ($($rest:tt)+) => {
+        $crate::log!($crate::LogLevel::error(), $($rest)+)
+    };
+}
+
+/// Same as [log!] with the [warn](crate::LogLevel::warn) level.
+#[macro_export]
+macro_rules! warn_log {
+    ($($rest:tt)+) => {
+        $crate::log!($crate::LogLevel::warn(), $($rest)+)
+    };
+}
+
+/// Same as [log!] with the [info](crate::LogLevel::info) level.
+#[macro_export]
+macro_rules! info_log {
+    ($($rest:tt)+) => {
+        $crate::log!($crate::LogLevel::info(), $($rest)+)
+    };
+}
+
+/// Builds a [TextBlock](crate::blocks::TextBlock) interleaving string literals, which
+/// accept the same inline arguments as [format!], with the styles they are printed with.
+///
+/// A style is set with `{<style>}` and applies to the following literals until another
+/// one is set, while `{}` goes back to plain text. Literals without arguments are not
+/// allocated.
+///
+/// # Examples
+/// ```
+/// use doclog::yansi::Style;
+///
+/// let expected = "u32";
+/// let found = "&str";
+/// let text = doclog::text!(
+///     "expected " {Style::new().bold()} "{expected}" {} ", found " {Style::new().bold()} "{found}"
+/// );
+///
+/// assert_eq!(text.to_string(), "expected u32, found &str");
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($($rest:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut block = $crate::blocks::TextBlock::new();
+        #[allow(unused_mut, unused_variables, unused_assignments)]
+        let mut style = $crate::yansi::Style::new();
+        $crate::__text_items!(block, style; $($rest)*);
+        block
+    }};
+}
+
+/// Parses the items of [text!].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __text_items {
+    ($block:ident, $style:ident;) => {};
+    ($block:ident, $style:ident; {} $($rest:tt)*) => {
+        $style = $crate::yansi::Style::new();
+        $crate::__text_items!($block, $style; $($rest)*);
+    };
+    ($block:ident, $style:ident; {$new_style:expr} $($rest:tt)*) => {
+        $style = $new_style;
+        $crate::__text_items!($block, $style; $($rest)*);
+    };
+    ($block:ident, $style:ident; $text:literal $($rest:tt)*) => {
+        $block = $block.add_styled_text(
+            match ::std::format_args!($text) {
+                args => match args.as_str() {
+                    Some(text) => ::std::borrow::Cow::Borrowed(text),
+                    None => ::std::borrow::Cow::Owned(args.to_string()),
+                },
+            },
+            $style,
+        );
+        $crate::__text_items!($block, $style; $($rest)*);
+    };
+}
+
+/// Parses the items of [log!].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_items {
+    // Labels of a snippet.
+    (@labels $block:ident;) => {};
+    (@labels $block:ident; $span:expr => $message:expr $(, $($rest:tt)*)?) => {
+        $block = $block.highlight_section_message($span, None, $message);
+        $crate::__log_items!(@labels $block; $($($rest)*)?);
+    };
+    (@labels $block:ident; $span:expr $(, $($rest:tt)*)?) => {
+        $block = $block.highlight_section($span, None);
+        $crate::__log_items!(@labels $block; $($($rest)*)?);
+    };
+
+    // Items.
+    ($header:ident, $blocks:ident; code = $code:expr, $($rest:tt)+) => {
+        $header = $header.code($code);
+        $crate::__log_items!($header, $blocks; $($rest)+);
+    };
+    ($header:ident, $blocks:ident; location = $location:expr, $($rest:tt)+) => {
+        $header = $header.location($location);
+        $crate::__log_items!($header, $blocks; $($rest)+);
+    };
+    (
+        $header:ident, $blocks:ident;
+        snippet = ($source:expr $(, $path:expr)? $(,)?) { $($labels:tt)* },
+        $($rest:tt)+
+    ) => {
+        #[allow(unused_mut)]
+        let mut snippet = $crate::blocks::CodeBlock::new($source);
+        $(snippet = snippet.file_path($path);)?
+        $crate::__log_items!(@labels snippet; $($labels)*);
+        $blocks.push(snippet.into());
+        $crate::__log_items!($header, $blocks; $($rest)+);
+    };
+    ($header:ident, $blocks:ident; note = $text:expr, $($rest:tt)+) => {
+        $blocks.push($crate::blocks::NoteBlock::new().text($text).into());
+        $crate::__log_items!($header, $blocks; $($rest)+);
+    };
+    ($header:ident, $blocks:ident; help = $text:expr, $($rest:tt)+) => {
+        $blocks.push(
+            $crate::blocks::NoteBlock::new()
+                .text(::std::format!("help: {}", $text))
+                .into(),
+        );
+        $crate::__log_items!($header, $blocks; $($rest)+);
+    };
+
+    // Title.
+    ($header:ident, $blocks:ident; $($title:tt)+) => {
+        $header = $header.title(::std::format!($($title)+));
+    };
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::blocks::{CodeBlock, HeaderBlock, NoteBlock, TextBlock};
+    use crate::{Log, LogLevel};
+    use std::borrow::Cow;
+    use yansi::Style;
+
+    #[test]
+    fn test_log() {
+        let code = "let a = b;\nlet c = d;";
+        let name = "b";
+        let log = error_log!(
+            code = "E0425",
+            snippet = (code, "src/main.rs") {
+                0..3,
+                8..9 => "not found in this scope",
+            },
+            note = "Variables must be declared",
+            help = "declare it with `let`",
+            "Undefined variable `{}`", name,
+        );
+        let expected = Log::error()
+            .add_block(
+                HeaderBlock::new()
+                    .title("Undefined variable `b`")
+                    .code("E0425"),
+            )
+            .add_block(
+                CodeBlock::new(code)
+                    .file_path("src/main.rs")
+                    .highlight_section(0..3, None)
+                    .highlight_section_message(8..9, None, "not found in this scope"),
+            )
+            .add_block(NoteBlock::new().text("Variables must be declared"))
+            .add_block(NoteBlock::new().text("help: declare it with `let`"));
+
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+    }
+
+    #[test]
+    fn test_only_title() {
+        let log = log!(LogLevel::warn(), "Deprecated");
+        let snippet_log = warn_log!(snippet = ("a") {}, "Deprecated");
+        let expected = Log::warn().add_block(HeaderBlock::new().title("Deprecated"));
+
+        assert_eq!(log.to_plain_text(), expected.to_plain_text());
+        assert_eq!(
+            snippet_log.to_plain_text(),
+            expected.add_block(CodeBlock::new("a")).to_plain_text()
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let found = "&str";
+        let text = text!(
+            "expected " {Style::new().bold()} "u32" {} ", found " {Style::new().italic()} "{found}" ""
+        );
+        let expected = TextBlock::new()
+            .add_plain_text("expected ")
+            .add_styled_text("u32", Style::new().bold())
+            .add_plain_text(", found ")
+            .add_styled_text("&str", Style::new().italic());
+
+        assert_eq!(text, expected);
+        assert!(matches!(text.sections[1].text, Cow::Borrowed(_)));
+        assert!(matches!(text.sections[3].text, Cow::Owned(_)));
+        assert!(text!().is_empty());
+    }
+}
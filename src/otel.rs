@@ -0,0 +1,231 @@
+use crate::blocks::LogBlock;
+use crate::{FieldValue, Log, LogLevel};
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, Severity};
+
+impl<'a> Log<'a> {
+    // METHODS ----------------------------------------------------------------
+
+    /// Fills an OpenTelemetry `record` with the log:
+    /// - The severity is mapped from the level, see [LogLevel::otel_severity], and its text
+    ///   is the name of the level.
+    /// - The body is the log rendered as plain text.
+    /// - The code, location and extra messages of the first
+    ///   [HeaderBlock](crate::blocks::HeaderBlock) are added as the `doclog.code`,
+    ///   `doclog.location` and `doclog.extra_messages` attributes.
+    /// - The fields, see [Log::field], are added as attributes with their key and type.
+    pub fn fill_otel_record(&self, record: &mut impl LogRecord) {
+        record.set_severity_number(self.level.otel_severity());
+        record.set_severity_text(self.level.name());
+        record.set_body(AnyValue::from(self.to_plain_text()));
+
+        let header = self.content.blocks.iter().find_map(|block| match block {
+            LogBlock::Header(header) => Some(header),
+            _ => None,
+        });
+
+        if let Some(header) = header {
+            if !header.code.is_empty() {
+                record.add_attribute("doclog.code", header.code.to_string());
+            }
+
+            if !header.location.is_empty() {
+                record.add_attribute("doclog.location", header.location.to_string());
+            }
+
+            if !header.extra_messages.is_empty() {
+                record.add_attribute(
+                    "doclog.extra_messages",
+                    header
+                        .extra_messages
+                        .iter()
+                        .map(|v| v.to_string())
+                        .collect::<AnyValue>(),
+                );
+            }
+        }
+
+        for (key, value) in &self.fields {
+            let value = match value {
+                FieldValue::Bool(v) => AnyValue::Boolean(*v),
+                FieldValue::Int(v) => AnyValue::Int(*v),
+                FieldValue::UInt(v) => match i64::try_from(*v) {
+                    Ok(v) => AnyValue::Int(v),
+                    Err(_) => AnyValue::from(v.to_string()),
+                },
+                FieldValue::Float(v) => AnyValue::Double(*v),
+                FieldValue::Str(v) => AnyValue::from(v.to_string()),
+            };
+
+            record.add_attribute(key.to_string(), value);
+        }
+    }
+
+    /// Emits the log into an OpenTelemetry `logger`, see [Log::fill_otel_record].
+    pub fn emit_otel(&self, logger: &impl Logger) {
+        let mut record = logger.create_log_record();
+        self.fill_otel_record(&mut record);
+        logger.emit(record);
+    }
+}
+
+impl LogLevel {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the OpenTelemetry severity of the level. Custom levels get the severity of
+    /// the highest predefined level they reach, and levels above [LogLevel::error] are
+    /// mapped to [Severity::Fatal].
+    pub fn otel_severity(&self) -> Severity {
+        let level = self.level();
+
+        if level > LogLevel::error().level() {
+            Severity::Fatal
+        } else if level >= LogLevel::error().level() {
+            Severity::Error
+        } else if level >= LogLevel::warn().level() {
+            Severity::Warn
+        } else if level >= LogLevel::info().level() {
+            Severity::Info
+        } else if level >= LogLevel::debug().level() {
+            Severity::Debug
+        } else {
+            Severity::Trace
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{HeaderBlock, TextBlock};
+    use opentelemetry::Key;
+    use std::borrow::Cow;
+    use std::time::SystemTime;
+    use yansi::Color;
+
+    #[derive(Default)]
+    struct TestRecord {
+        severity_text: Option<&'static str>,
+        severity: Option<Severity>,
+        body: Option<AnyValue>,
+        attributes: Vec<(Key, AnyValue)>,
+    }
+
+    impl LogRecord for TestRecord {
+        fn set_event_name(&mut self, _name: &'static str) {}
+
+        fn set_target<T>(&mut self, _target: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+
+        fn set_timestamp(&mut self, _timestamp: SystemTime) {}
+
+        fn set_observed_timestamp(&mut self, _timestamp: SystemTime) {}
+
+        fn set_severity_text(&mut self, text: &'static str) {
+            self.severity_text = Some(text);
+        }
+
+        fn set_severity_number(&mut self, number: Severity) {
+            self.severity = Some(number);
+        }
+
+        fn set_body(&mut self, body: AnyValue) {
+            self.body = Some(body);
+        }
+
+        fn add_attributes<I, K, V>(&mut self, attributes: I)
+        where
+            I: IntoIterator<Item = (K, V)>,
+            K: Into<Key>,
+            V: Into<AnyValue>,
+        {
+            for (key, value) in attributes {
+                self.add_attribute(key, value);
+            }
+        }
+
+        fn add_attribute<K, V>(&mut self, key: K, value: V)
+        where
+            K: Into<Key>,
+            V: Into<AnyValue>,
+        {
+            self.attributes.push((key.into(), value.into()));
+        }
+    }
+
+    #[test]
+    fn test_fill_otel_record() {
+        let log = Log::warn()
+            .add_block(
+                HeaderBlock::new()
+                    .title("Deprecated")
+                    .code("W001")
+                    .location("src/main.rs")
+                    .add_extra_message("request_id: 42"),
+            )
+            .add_block(TextBlock::new_plain("Use the new API"));
+        let mut record = TestRecord::default();
+        log.fill_otel_record(&mut record);
+
+        assert_eq!(record.severity, Some(Severity::Warn));
+        assert_eq!(record.severity_text, Some("warn"));
+        assert_eq!(record.body, Some(AnyValue::from(log.to_plain_text())));
+        assert_eq!(
+            record.attributes,
+            vec![
+                (Key::from("doclog.code"), AnyValue::from("W001")),
+                (Key::from("doclog.location"), AnyValue::from("src/main.rs")),
+                (
+                    Key::from("doclog.extra_messages"),
+                    ["request_id: 42"].into_iter().collect()
+                ),
+            ]
+        );
+
+        let mut record = TestRecord::default();
+        Log::info().fill_otel_record(&mut record);
+
+        assert!(record.attributes.is_empty());
+
+        // Fields.
+        let log = Log::info()
+            .field("user", "alice")
+            .field("attempts", 3)
+            .field("ratio", 0.5)
+            .field("cached", true)
+            .field("huge", u64::MAX);
+        let mut record = TestRecord::default();
+        log.fill_otel_record(&mut record);
+
+        assert_eq!(
+            record.attributes,
+            vec![
+                (Key::from("user"), AnyValue::from("alice")),
+                (Key::from("attempts"), AnyValue::Int(3)),
+                (Key::from("ratio"), AnyValue::Double(0.5)),
+                (Key::from("cached"), AnyValue::Boolean(true)),
+                (Key::from("huge"), AnyValue::from(u64::MAX.to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_otel_severity() {
+        let fatal = LogLevel::custom("FATAL", "fatal", '‼', Color::Magenta).with_level(60);
+        let audit = LogLevel::custom("AUDIT", "audit", '•', Color::Cyan);
+
+        assert_eq!(LogLevel::trace().otel_severity(), Severity::Trace);
+        assert_eq!(LogLevel::debug().otel_severity(), Severity::Debug);
+        assert_eq!(LogLevel::info().otel_severity(), Severity::Info);
+        assert_eq!(LogLevel::warn().otel_severity(), Severity::Warn);
+        assert_eq!(LogLevel::error().otel_severity(), Severity::Error);
+        assert_eq!(fatal.otel_severity(), Severity::Fatal);
+        assert_eq!(audit.otel_severity(), Severity::Info);
+    }
+}
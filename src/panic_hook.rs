@@ -0,0 +1,98 @@
+use crate::blocks::{HeaderBlock, StackBlock};
+use crate::Log;
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::panic::{Location, PanicHookInfo};
+
+/// Replaces the panic hook with one that prints the panics into the standard error as
+/// logs, see [panic_log]. The backtrace is included if it can be captured, see
+/// [Backtrace::capture].
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        panic_log(info, &Backtrace::capture()).print_stderr();
+    }));
+}
+
+/// Builds an error log from a panic: its header contains the thread that panicked, with
+/// the `thread` feature, and the location of the panic, and its [StackBlock] contains the
+/// panic message and the frames of `backtrace`.
+pub fn panic_log(info: &PanicHookInfo, backtrace: &Backtrace) -> Log<'static> {
+    build_panic_log(info.location(), info.payload(), backtrace)
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Builds the log of [panic_log] from the parts of a [PanicHookInfo].
+fn build_panic_log(
+    location: Option<&Location>,
+    payload: &(dyn Any + Send),
+    backtrace: &Backtrace,
+) -> Log<'static> {
+    #[cfg(feature = "thread")]
+    let title = format!(
+        "thread '{}' panicked",
+        std::thread::current().name().unwrap_or("<unnamed>")
+    );
+    #[cfg(not(feature = "thread"))]
+    let title = "panicked";
+    let mut header = HeaderBlock::new().title(title);
+
+    if let Some(location) = location {
+        header = header.location(format!(
+            "{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        ));
+    }
+
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    };
+
+    Log::error()
+        .add_block(header)
+        .add_block(StackBlock::new().message(message).backtrace(backtrace))
+}
+
+#[cfg(all(test, feature = "thread"))]
+mod tests {
+    use super::*;
+
+    /// Returns the location of its invocation along with its line and column.
+    macro_rules! here {
+        () => {
+            (Location::caller(), line!(), column!())
+        };
+    }
+
+    #[test]
+    fn test_panic_log() {
+        let (location, line, column) = here!();
+        let payload = format!("doclog test panic {}", 42);
+        let log = build_panic_log(Some(location), &payload, &Backtrace::disabled());
+
+        let thread = std::thread::current();
+        let expected = format!(
+            "ERROR thread '{}' panicked\n ↪ in {}:{}:{}\n{}",
+            thread.name().unwrap(),
+            file!(),
+            line,
+            column,
+            StackBlock::new().message("doclog test panic 42")
+        );
+
+        assert_eq!(log.to_plain_text(), expected);
+
+        let log = build_panic_log(None, &"static message", &Backtrace::disabled());
+        assert!(log
+            .to_plain_text()
+            .ends_with(&StackBlock::new().message("static message").to_string()));
+    }
+}
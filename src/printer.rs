@@ -1,29 +1,166 @@
 use crate::blocks::TextSection;
-use crate::LogLevel;
+use crate::constants::ELLIPSIS;
+use crate::utils::text::{
+    column_byte_offset, count_columns, ends_with_line_break, slice_cow, split_columns, split_lines,
+};
+use crate::{LogLevel, Theme};
+use const_format::concatcp;
+use smallvec::{smallvec, SmallVec};
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::OsString;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, OnceLock};
 use yansi::Style;
 
+/// The environment variable that disables the styles of [PrinterFormat::Auto].
+const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// The environment variable that forces the styles of [PrinterFormat::Auto].
+const CLICOLOR_FORCE_ENV_VAR: &str = "CLICOLOR_FORCE";
+
+/// The environment variable that disables the styles of [PrinterFormat::Auto] when set to `0`.
+const CLICOLOR_ENV_VAR: &str = "CLICOLOR";
+
+/// The environment variable that enables the hyperlinks of styled outputs when set to `1`.
+const HYPERLINKS_ENV_VAR: &str = "DOCLOG_HYPERLINKS";
+
+/// The value of [HYPERLINKS] when it is not set.
+const UNSET_HYPERLINKS: u8 = u8::MAX;
+
+static HYPERLINKS: AtomicU8 = AtomicU8::new(UNSET_HYPERLINKS);
+static ENV_HYPERLINKS: OnceLock<bool> = OnceLock::new();
+
+/// The sections of a line of a [Printer]. Most lines contain only a few sections,
+/// so they are stored inline to avoid an allocation per line.
+pub type PrinterLine<'a> = SmallVec<[TextSection<'a>; 4]>;
+
 #[derive(Debug, Clone)]
 pub struct Printer<'a> {
     pub level: LogLevel,
     pub format: PrinterFormat,
-    pub lines: Vec<Vec<TextSection<'a>>>,
+    pub lines: Vec<PrinterLine<'a>>,
+
+    /// The maximum number of columns the content of this printer should occupy, if any.
+    /// Derived printers whose content is going to be indented get a smaller budget,
+    /// so width-aware blocks can compute their layout against the remaining space.
+    pub max_width: Option<usize>,
+
+    /// The palette used to style the content. Defaults to [Theme::global].
+    pub theme: Theme,
+
+    /// The blocks that can be referenced from the content, by id.
+    pub(crate) references: Option<Arc<HashMap<String, BlockReference>>>,
+}
+
+/// The resolved location of a block that can be referenced by id.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) struct BlockReference {
+    /// The number of the block, starting at 1.
+    pub number: usize,
+
+    /// The line where the block starts, starting at 1.
+    pub line: usize,
 }
 
 impl<'a> Printer<'a> {
     // CONSTRUCTORS -----------------------------------------------------------
 
-    /// Creates a new [Printer] for the given initial configuration.
+    /// Creates a new [Printer] for the given initial configuration using [Theme::global].
     pub fn new(level: LogLevel, format: PrinterFormat) -> Self {
+        Self::with_theme(level, format, Theme::global())
+    }
+
+    /// Creates a new [Printer] that uses `theme` instead of reading the global one, e.g. to
+    /// print several times with the same snapshot of the theme.
+    pub fn with_theme(level: LogLevel, format: PrinterFormat, theme: Theme) -> Self {
         Self {
             level,
             format,
             lines: Vec::new(),
+            max_width: None,
+            theme,
+            references: None,
+        }
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns whether styled outputs include OSC 8 hyperlinks, e.g. in
+    /// [NoteBlock::url](crate::blocks::NoteBlock::url). It is the value set by
+    /// [Printer::set_hyperlinks] or, if not set, whether the `DOCLOG_HYPERLINKS` environment
+    /// variable is `1`.
+    pub fn hyperlinks() -> bool {
+        match HYPERLINKS.load(Ordering::Relaxed) {
+            UNSET_HYPERLINKS => *ENV_HYPERLINKS
+                .get_or_init(|| std::env::var(HYPERLINKS_ENV_VAR).is_ok_and(|v| v.trim() == "1")),
+            value => value != 0,
         }
     }
 
+    /// Sets whether styled outputs include OSC 8 hyperlinks, overriding the
+    /// `DOCLOG_HYPERLINKS` environment variable. `None` restores the environment variable
+    /// value.
+    pub fn set_hyperlinks(hyperlinks: Option<bool>) {
+        let value = match hyperlinks {
+            Some(v) => v as u8,
+            None => UNSET_HYPERLINKS,
+        };
+
+        HYPERLINKS.store(value, Ordering::Relaxed);
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the maximum number of columns the content should occupy.
+    #[inline(always)]
+    pub fn max_width(mut self, max_width: Option<usize>) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    /// Sets the theme used to style the content.
+    #[inline(always)]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the number of lines pushed to the printer.
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns the color of the level of this printer in its theme.
+    #[inline(always)]
+    pub fn level_color(&self) -> yansi::Color {
+        self.theme.level_color(self.level)
+    }
+
+    /// Returns the style of the borders for the level of this printer in its theme.
+    #[inline(always)]
+    pub fn border_style(&self) -> Style {
+        self.theme.border_style(self.level)
+    }
+
+    /// Returns the symbol of the level of this printer in its theme.
+    #[inline(always)]
+    pub fn level_symbol(&self) -> char {
+        self.theme.level_symbol(self.level)
+    }
+
+    /// Returns the tag of the level of this printer in its theme.
+    #[inline(always)]
+    pub fn level_tag(&self) -> Cow<'static, str> {
+        self.theme.tag_for(self.level)
+    }
+
     // METHODS ----------------------------------------------------------------
 
     /// Derives a new [Printer] from this one.
@@ -32,9 +169,59 @@ impl<'a> Printer<'a> {
             level: self.level,
             format: self.format,
             lines: Vec::new(),
+            max_width: self.max_width,
+            theme: self.theme,
+            references: self.references.clone(),
+        }
+    }
+
+    /// Derives a new [Printer] from this one whose content is going to be indented
+    /// by `columns`, reducing its width budget accordingly.
+    pub fn derive_indented<'b>(&self, columns: usize) -> Printer<'b> {
+        Printer {
+            level: self.level,
+            format: self.format,
+            lines: Vec::new(),
+            max_width: self.max_width.map(|v| v.saturating_sub(columns)),
+            theme: self.theme,
+            references: self.references.clone(),
         }
     }
 
+    /// Returns the columns left in the last line before reaching the maximum width,
+    /// if any.
+    pub(crate) fn remaining_columns(&self) -> Option<usize> {
+        let max_width = self.max_width?;
+        let used = self.lines.last().map_or(0, |line| {
+            line.iter()
+                .map(|section| count_columns(&section.text))
+                .sum()
+        });
+
+        Some(max_width.saturating_sub(used))
+    }
+
+    /// Returns the text that represents a reference to the block with `id`.
+    pub(crate) fn resolve_reference(&self, id: &str) -> String {
+        match self.references.as_ref().and_then(|v| v.get(id)) {
+            Some(reference) if self.format == PrinterFormat::Plain => {
+                format!("[{}, line {}]", reference.number, reference.line)
+            }
+            Some(reference) => format!("[{}]", reference.number),
+            None => "[?]".to_string(),
+        }
+    }
+
+    /// Splits the printer in two: the first one contains the first `line` lines and the
+    /// second one the rest. Both keep the configuration of this printer, so long outputs can
+    /// be paginated.
+    pub fn split_at_line(mut self, line: usize) -> (Printer<'a>, Printer<'a>) {
+        let mut tail = self.derive();
+        tail.lines = self.lines.split_off(line.min(self.lines.len()));
+
+        (self, tail)
+    }
+
     /// Appends another [Printer] to this one.
     pub fn append(&mut self, other: Printer<'a>) {
         if other.lines.is_empty() {
@@ -58,6 +245,11 @@ impl<'a> Printer<'a> {
     }
 
     /// Pushes a text section to the printer.
+    ///
+    /// The text is appended to the last line and every line terminator, i.e. `\n`, `\r\n`
+    /// or a lone `\r`, starts a new line, so a trailing terminator leaves an empty last line
+    /// where the next content will start. An empty printer behaves as if it contained a single empty line.
+    /// Prefer [Printer::new_line] or [Printer::ensure_blank_line] to handle line breaks.
     pub fn push_text_section(&mut self, element: TextSection<'a>) {
         if element.text.is_empty() {
             return;
@@ -65,7 +257,7 @@ impl<'a> Printer<'a> {
 
         match element.text {
             Cow::Borrowed(text) => {
-                for (i, line) in text.lines().enumerate() {
+                for (i, line) in split_lines(text).enumerate() {
                     // Push to the last if first.
                     if let (0, Some(last)) = (i, self.lines.last_mut()) {
                         if !line.is_empty() {
@@ -78,21 +270,21 @@ impl<'a> Printer<'a> {
                     }
 
                     if line.is_empty() {
-                        self.lines.push(vec![]);
+                        self.lines.push(SmallVec::new());
                     } else {
-                        self.lines.push(vec![TextSection {
+                        self.lines.push(smallvec![TextSection {
                             text: Cow::Borrowed(line),
                             style: element.style,
                         }]);
                     }
                 }
 
-                if text.ends_with('\n') {
-                    self.lines.push(vec![]);
+                if ends_with_line_break(text) {
+                    self.lines.push(SmallVec::new());
                 }
             }
             Cow::Owned(text) => {
-                for (i, line) in text.lines().enumerate() {
+                for (i, line) in split_lines(&text).enumerate() {
                     // Push to the last if first.
                     if let (0, Some(last)) = (i, self.lines.last_mut()) {
                         if !line.is_empty() {
@@ -105,17 +297,17 @@ impl<'a> Printer<'a> {
                     }
 
                     if line.is_empty() {
-                        self.lines.push(vec![]);
+                        self.lines.push(SmallVec::new());
                     } else {
-                        self.lines.push(vec![TextSection {
+                        self.lines.push(smallvec![TextSection {
                             text: Cow::Owned(line.to_string()),
                             style: element.style,
                         }]);
                     }
                 }
 
-                if text.ends_with('\n') {
-                    self.lines.push(vec![]);
+                if ends_with_line_break(&text) {
+                    self.lines.push(SmallVec::new());
                 }
             }
         }
@@ -137,6 +329,41 @@ impl<'a> Printer<'a> {
         });
     }
 
+    /// Removes the content of the printer but keeps its configuration and allocated
+    /// memory, so the same printer can be reused to print many logs in a hot loop.
+    #[inline(always)]
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Starts a new empty line where the next content will be pushed.
+    /// It is equivalent to pushing a `\n`.
+    pub fn new_line(&mut self) {
+        if self.lines.is_empty() {
+            self.lines.push(SmallVec::new());
+        }
+
+        self.lines.push(SmallVec::new());
+    }
+
+    /// Ensures the last line is empty so the next content starts at the beginning of a line.
+    /// Unlike [Printer::new_line], it does nothing if the last line is already empty,
+    /// including when the printer is empty.
+    pub fn ensure_blank_line(&mut self) {
+        if let Some(last) = self.lines.last() {
+            if !last.is_empty() {
+                self.lines.push(SmallVec::new());
+            }
+        }
+    }
+
+    /// Pushes `sections` as a new line after the last one, or as the first line if the
+    /// printer is empty. The sections are pushed as they are, so they must not contain
+    /// newline characters.
+    pub fn push_raw_line(&mut self, sections: impl IntoIterator<Item = TextSection<'a>>) {
+        self.lines.push(sections.into_iter().collect());
+    }
+
     /// Indents the content of this [Printer] with a list of text sections.
     pub fn indent(&mut self, sections: &[TextSection<'a>], indent_first_line: bool) {
         if sections.is_empty() {
@@ -148,75 +375,332 @@ impl<'a> Printer<'a> {
             .iter_mut()
             .skip(if indent_first_line { 0 } else { 1 })
         {
-            line.splice(0..0, sections.iter().cloned());
+            line.insert_many(0, sections.iter().cloned());
         }
     }
 
+    /// Truncates every line longer than `max_columns` columns by replacing its middle
+    /// part with an ellipsis `…`, so both the start of the line (e.g. the gutter) and its
+    /// end remain visible. Styles of the kept parts are preserved.
+    pub fn truncate_lines(&mut self, max_columns: usize) {
+        for line in &mut self.lines {
+            let columns: usize = line.iter().map(|v| count_columns(&v.text)).sum();
+
+            if columns <= max_columns {
+                continue;
+            }
+
+            if max_columns == 0 {
+                line.clear();
+                continue;
+            }
+
+            let available = max_columns - 1;
+            let tail_columns = available / 2;
+            let head_columns = available - tail_columns;
+            let mut new_line = PrinterLine::with_capacity(line.len());
+
+            // Head.
+            let mut remaining = head_columns;
+            for section in line.iter() {
+                if remaining == 0 {
+                    break;
+                }
+
+                let section_columns = count_columns(&section.text);
+                if section_columns <= remaining {
+                    new_line.push(section.clone());
+                    remaining -= section_columns;
+                } else {
+                    let end = column_byte_offset(&section.text, remaining + 1)
+                        .unwrap_or(section.text.len());
+
+                    new_line.push(TextSection {
+                        text: slice_cow(&section.text, 0..end),
+                        style: section.style,
+                    });
+                    remaining = 0;
+                }
+            }
+
+            new_line.push(TextSection {
+                text: Cow::Borrowed(concatcp!(ELLIPSIS)),
+                style: Style::new(),
+            });
+
+            // Tail.
+            let tail_start = new_line.len();
+            let mut remaining = tail_columns;
+            for section in line.iter().rev() {
+                if remaining == 0 {
+                    break;
+                }
+
+                let section_columns = count_columns(&section.text);
+                if section_columns <= remaining {
+                    new_line.push(section.clone());
+                    remaining -= section_columns;
+                } else {
+                    let start = column_byte_offset(&section.text, section_columns - remaining + 1)
+                        .unwrap_or(0);
+
+                    new_line.push(TextSection {
+                        text: slice_cow(&section.text, start..section.text.len()),
+                        style: section.style,
+                    });
+                    remaining = 0;
+                }
+            }
+            new_line[tail_start..].reverse();
+
+            *line = new_line;
+        }
+    }
+
+    /// Clips every line longer than `max_columns` columns by cutting its end and
+    /// replacing the last visible character with an ellipsis `…`. Unlike [Printer::truncate_lines],
+    /// the columns of the kept part do not move, so vertically aligned content stays aligned.
+    pub fn clip_lines(&mut self, max_columns: usize) {
+        self.clip_lines_from(0, max_columns);
+    }
+
+    /// Same as [Printer::clip_lines] but only for the lines starting at `first_line`.
+    pub(crate) fn clip_lines_from(&mut self, first_line: usize, max_columns: usize) {
+        for line in self.lines.iter_mut().skip(first_line) {
+            clip_line(line, max_columns);
+        }
+    }
+
+    /// Same as [Printer::clip_lines] but only for the first line.
+    pub(crate) fn clip_first_line(&mut self, max_columns: usize) {
+        if let Some(line) = self.lines.first_mut() {
+            clip_line(line, max_columns);
+        }
+    }
+
+    /// Splits every line longer than `max_columns` columns into several lines, breaking
+    /// at the last whitespace that fits when possible. Styles are preserved.
+    pub(crate) fn wrap_lines(&mut self, max_columns: usize) {
+        if max_columns == 0 {
+            return;
+        }
+
+        let mut lines = Vec::with_capacity(self.lines.len());
+
+        for line in std::mem::take(&mut self.lines) {
+            let columns: usize = line.iter().map(|v| count_columns(&v.text)).sum();
+
+            if columns <= max_columns {
+                lines.push(line);
+                continue;
+            }
+
+            let chars: Vec<(&str, Style)> = line
+                .iter()
+                .flat_map(|v| split_columns(&v.text).map(move |c| (c, v.style)))
+                .collect();
+            let mut start = 0;
+
+            while chars.len() - start > max_columns {
+                let limit = start + max_columns;
+                let end = (start + 1..=limit)
+                    .rev()
+                    .find(|&i| is_whitespace_column(chars[i].0))
+                    .unwrap_or(limit);
+
+                lines.push(group_chars(&chars[start..end]));
+                start = end;
+
+                while start < chars.len() && is_whitespace_column(chars[start].0) {
+                    start += 1;
+                }
+            }
+
+            if start < chars.len() {
+                lines.push(group_chars(&chars[start..]));
+            }
+        }
+
+        self.lines = lines;
+    }
+
     /// Implement this to provide custom formatting for this type.
+    #[inline(always)]
     pub fn fmt(&self, fmt: &mut Formatter<'_>, format: PrinterFormat) -> fmt::Result {
-        let styled = match format {
-            PrinterFormat::Default => yansi::is_enabled(),
-            PrinterFormat::Plain => false,
-            PrinterFormat::Styled => true,
-        };
+        self.write_fmt_to(fmt, format)
+    }
 
-        if styled {
-            let mut prev_style: Option<&Style> = None;
+    /// Writes the content of this [Printer] directly into `writer`, emitting the ANSI codes
+    /// incrementally instead of building an intermediate [String].
+    #[inline(always)]
+    pub fn write_to(&self, writer: &mut impl Write, format: PrinterFormat) -> std::io::Result<()> {
+        write_lines_to(writer, &self.lines, format)
+    }
 
-            for (i, line) in self.lines.iter().enumerate() {
-                if i != 0 {
-                    writeln!(fmt)?;
-                }
+    /// Writes every line but the last one into `writer`, followed by a newline character,
+    /// and removes them, so content can be written as soon as its lines are complete.
+    pub(crate) fn write_complete_lines(&mut self, writer: &mut impl Write) -> std::io::Result<()> {
+        if self.lines.len() < 2 {
+            return Ok(());
+        }
 
-                for section in line {
-                    if section.style.enabled() {
-                        let all_whitespace =
-                            section.text.chars().all(|c| char::is_ascii_whitespace(&c));
-
-                        // Print previous suffix and current prefix only if the style is different.
-                        if !all_whitespace {
-                            if let Some(prev_style) = prev_style.take() {
-                                if prev_style != &section.style {
-                                    prev_style.fmt_suffix(fmt)?;
-                                    section.style.fmt_prefix(fmt)?;
-                                }
-                            } else {
-                                section.style.fmt_prefix(fmt)?;
-                            }
-                        }
+        let complete = self.lines.len() - 1;
+        write_lines_to(writer, &self.lines[..complete], self.format)?;
+        writer.write_all(b"\n")?;
+        self.lines.drain(..complete);
+        Ok(())
+    }
+
+    /// Writes the content of this [Printer] into any [fmt::Write].
+    #[inline(always)]
+    fn write_fmt_to(&self, fmt: &mut dyn fmt::Write, format: PrinterFormat) -> fmt::Result {
+        write_lines(fmt, &self.lines, format)
+    }
+}
 
-                        write!(fmt, "{}", section.text)?;
+/// Clips `line` if it is longer than `max_columns` columns, see [Printer::clip_lines].
+fn clip_line(line: &mut PrinterLine, max_columns: usize) {
+    let columns: usize = line.iter().map(|v| count_columns(&v.text)).sum();
 
-                        if !all_whitespace {
-                            prev_style = Some(&section.style);
-                        }
-                    } else {
-                        // Print previous suffix.
+    if columns <= max_columns {
+        return;
+    }
+
+    if max_columns == 0 {
+        line.clear();
+        return;
+    }
+
+    let mut remaining = max_columns - 1;
+    let mut new_line = PrinterLine::with_capacity(line.len());
+
+    for section in line.iter() {
+        if remaining == 0 {
+            break;
+        }
+
+        let section_columns = count_columns(&section.text);
+        if section_columns <= remaining {
+            new_line.push(section.clone());
+            remaining -= section_columns;
+        } else {
+            let end =
+                column_byte_offset(&section.text, remaining + 1).unwrap_or(section.text.len());
+
+            new_line.push(TextSection {
+                text: slice_cow(&section.text, 0..end),
+                style: section.style,
+            });
+            remaining = 0;
+        }
+    }
+
+    new_line.push(TextSection {
+        text: Cow::Borrowed(concatcp!(ELLIPSIS)),
+        style: Style::new(),
+    });
+
+    *line = new_line;
+}
+
+/// Writes `lines` into `writer`, separated by newline characters.
+fn write_lines_to(
+    writer: &mut impl Write,
+    lines: &[PrinterLine],
+    format: PrinterFormat,
+) -> std::io::Result<()> {
+    let mut adapter = IoAdapter {
+        writer,
+        error: None,
+    };
+
+    match write_lines(&mut adapter, lines, format) {
+        Ok(()) => Ok(()),
+        Err(_) => Err(adapter
+            .error
+            .unwrap_or_else(|| std::io::Error::other("formatter error"))),
+    }
+}
+
+/// Writes `lines` into any [fmt::Write], separated by newline characters.
+fn write_lines(
+    fmt: &mut dyn fmt::Write,
+    lines: &[PrinterLine],
+    format: PrinterFormat,
+) -> fmt::Result {
+    let styled = format.is_styled();
+
+    if styled {
+        let mut prev_style: Option<&Style> = None;
+
+        for (i, line) in lines.iter().enumerate() {
+            if i != 0 {
+                writeln!(fmt)?;
+            }
+
+            for section in line {
+                if section.style.enabled() {
+                    let all_whitespace =
+                        section.text.chars().all(|c| char::is_ascii_whitespace(&c));
+
+                    // Print previous suffix and current prefix only if the style is different.
+                    if !all_whitespace {
                         if let Some(prev_style) = prev_style.take() {
-                            prev_style.fmt_suffix(fmt)?;
+                            if prev_style != &section.style {
+                                prev_style.fmt_suffix(fmt)?;
+                                section.style.fmt_prefix(fmt)?;
+                            }
+                        } else {
+                            section.style.fmt_prefix(fmt)?;
                         }
+                    }
+
+                    write!(fmt, "{}", section.text)?;
 
-                        write!(fmt, "{}", section.text)?;
+                    if !all_whitespace {
+                        prev_style = Some(&section.style);
                     }
+                } else {
+                    // Print previous suffix.
+                    if let Some(prev_style) = prev_style.take() {
+                        prev_style.fmt_suffix(fmt)?;
+                    }
+
+                    write!(fmt, "{}", section.text)?;
                 }
             }
+        }
 
-            if let Some(prev_style) = prev_style.take() {
-                prev_style.fmt_suffix(fmt)?;
+        if let Some(prev_style) = prev_style.take() {
+            prev_style.fmt_suffix(fmt)?;
+        }
+    } else {
+        for (i, line) in lines.iter().enumerate() {
+            if i != 0 {
+                writeln!(fmt)?;
             }
-        } else {
-            for (i, line) in self.lines.iter().enumerate() {
-                if i != 0 {
-                    writeln!(fmt)?;
-                }
 
-                for section in line {
-                    write!(fmt, "{}", section.text)?;
-                }
+            for section in line {
+                write!(fmt, "{}", section.text)?;
             }
         }
+    }
 
-        Ok(())
+    Ok(())
+}
+
+/// Adapts an [std::io::Write] to be used as a [fmt::Write], keeping the underlying error.
+struct IoAdapter<'w, W: Write> {
+    writer: &'w mut W,
+    error: Option<std::io::Error>,
+}
+
+impl<'w, W: Write> fmt::Write for IoAdapter<'w, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
     }
 }
 
@@ -230,6 +714,39 @@ impl<'a> Display for Printer<'a> {
 // ----------------------------------------------------------------------------
 // ----------------------------------------------------------------------------
 
+/// A single line of rendered content, without the trailing newline character.
+/// See [CodeBlock::render_lines](crate::blocks::CodeBlock::render_lines).
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct RenderedLine<'a> {
+    pub sections: PrinterLine<'a>,
+}
+
+impl<'a> RenderedLine<'a> {
+    // METHODS ----------------------------------------------------------------
+
+    /// Implement this to provide custom formatting for this type.
+    #[inline(always)]
+    pub fn fmt(&self, fmt: &mut Formatter<'_>, format: PrinterFormat) -> fmt::Result {
+        write_lines(fmt, std::slice::from_ref(&self.sections), format)
+    }
+
+    /// Writes the line directly into `writer`. Styles are closed at the end of the line.
+    #[inline(always)]
+    pub fn write_to(&self, writer: &mut impl Write, format: PrinterFormat) -> std::io::Result<()> {
+        write_lines_to(writer, std::slice::from_ref(&self.sections), format)
+    }
+}
+
+impl<'a> Display for RenderedLine<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt(f, PrinterFormat::Plain)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PrinterFormat {
     /// Format depends on system settings.
@@ -240,6 +757,206 @@ pub enum PrinterFormat {
 
     /// Styled text format.
     Styled,
+
+    /// Format depends on the `NO_COLOR`, `CLICOLOR_FORCE` and `CLICOLOR` environment variables
+    /// and on whether the output stream is a terminal. Without an explicit stream,
+    /// the standard output is assumed. See [PrinterFormat::resolve_for].
+    Auto,
+}
+
+impl PrinterFormat {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns whether this format prints styles.
+    pub fn is_styled(&self) -> bool {
+        match self {
+            PrinterFormat::Default => yansi::is_enabled(),
+            PrinterFormat::Plain => false,
+            PrinterFormat::Styled => true,
+            PrinterFormat::Auto => detect_styles(OutputStream::Stdout, |v| std::env::var_os(v)),
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Resolves [PrinterFormat::Auto] into either [PrinterFormat::Styled] or
+    /// [PrinterFormat::Plain] for the given `stream`. Other formats are returned as is.
+    ///
+    /// The environment variables take precedence over the terminal detection:
+    /// - `NO_COLOR` with any non-empty value disables the styles.
+    /// - `CLICOLOR_FORCE` with any value other than `0` enables the styles.
+    /// - `CLICOLOR=0` disables the styles.
+    ///
+    /// Whether the stream is a terminal is only checked with the `is-terminal` feature,
+    /// otherwise this falls back to [yansi::is_enabled]. On Windows, the styles are also
+    /// disabled when the console does not support ANSI escape sequences, see
+    /// [enable_ansi_support].
+    pub fn resolve_for(self, stream: OutputStream) -> PrinterFormat {
+        match self {
+            PrinterFormat::Auto if detect_styles(stream, |v| std::env::var_os(v)) => {
+                PrinterFormat::Styled
+            }
+            PrinterFormat::Auto => PrinterFormat::Plain,
+            _ => self,
+        }
+    }
+}
+
+/// The standard streams a [PrinterFormat] can be resolved against.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Detects whether the output written into `stream` should be styled, reading the
+/// environment variables through `env`.
+fn detect_styles(stream: OutputStream, env: impl Fn(&str) -> Option<OsString>) -> bool {
+    let var = |name: &str| env(name).filter(|v| !v.is_empty());
+
+    if var(NO_COLOR_ENV_VAR).is_some() {
+        return false;
+    }
+
+    if var(CLICOLOR_FORCE_ENV_VAR).is_some_and(|v| v != "0") {
+        return true;
+    }
+
+    if var(CLICOLOR_ENV_VAR).is_some_and(|v| v == "0") {
+        return false;
+    }
+
+    is_terminal(stream) && enable_ansi_support()
+}
+
+/// Enables the processing of ANSI escape sequences in the console, returning whether they
+/// are supported, so styled logs are not printed as raw escape codes in legacy Windows
+/// consoles. Call it once at startup before printing styled logs.
+///
+/// On Windows, the first call tries to enable the virtual terminal processing of the
+/// console and the result is cached for later calls. On other platforms it always returns
+/// `true`.
+#[inline(always)]
+pub fn enable_ansi_support() -> bool {
+    yansi::Condition::os_support()
+}
+
+#[cfg(feature = "is-terminal")]
+pub(crate) fn is_terminal(stream: OutputStream) -> bool {
+    use is_terminal::IsTerminal;
+
+    match stream {
+        OutputStream::Stdout => std::io::stdout().is_terminal(),
+        OutputStream::Stderr => std::io::stderr().is_terminal(),
+    }
+}
+
+#[cfg(not(feature = "is-terminal"))]
+pub(crate) fn is_terminal(_stream: OutputStream) -> bool {
+    yansi::is_enabled()
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Writes [Printer]s into a terminal in-place, i.e. every update replaces the content
+/// written by the previous one using ANSI cursor movements. It is useful to refresh
+/// blocks like [ProgressBlock](crate::blocks::ProgressBlock) while a task is running.
+///
+/// The output must be a terminal that supports ANSI escape sequences. See also
+/// [LiveLog](crate::LiveLog) to refresh whole logs.
+#[derive(Debug)]
+pub struct LiveWriter<W: Write> {
+    writer: W,
+    terminal_width: Option<usize>,
+    previous_line_count: usize,
+}
+
+impl<W: Write> LiveWriter<W> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LiveWriter] that writes into `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            terminal_width: None,
+            previous_line_count: 0,
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the width of the terminal in columns, so the lines that are longer than it are
+    /// counted as the multiple terminal lines they wrap into.
+    #[inline(always)]
+    pub fn terminal_width(mut self, terminal_width: Option<usize>) -> Self {
+        self.terminal_width = terminal_width;
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the number of terminal lines written by the last update.
+    #[inline(always)]
+    pub fn previous_line_count(&self) -> usize {
+        self.previous_line_count
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Replaces the previously written content with the content of `printer`.
+    pub fn update(&mut self, printer: &Printer) -> std::io::Result<()> {
+        self.clear()?;
+        write!(self.writer, "{}", printer)?;
+        self.previous_line_count = self.terminal_line_count(printer).max(1);
+        self.writer.flush()
+    }
+
+    /// Removes the previously written content.
+    pub fn clear(&mut self) -> std::io::Result<()> {
+        if self.previous_line_count == 0 {
+            return Ok(());
+        }
+
+        // Move to the start of the first line and clear until the end of the screen.
+        write!(self.writer, "\r")?;
+
+        if self.previous_line_count > 1 {
+            write!(self.writer, "\x1b[{}A", self.previous_line_count - 1)?;
+        }
+
+        write!(self.writer, "\x1b[J")?;
+        self.previous_line_count = 0;
+        Ok(())
+    }
+
+    /// Finishes the live writing, leaving the last content in place and moving the cursor
+    /// to the next line.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        if self.previous_line_count > 0 {
+            writeln!(self.writer)?;
+            self.writer.flush()?;
+        }
+
+        Ok(self.writer)
+    }
+
+    /// Returns the number of terminal lines the content of `printer` occupies.
+    fn terminal_line_count(&self, printer: &Printer) -> usize {
+        let Some(terminal_width) = self.terminal_width.filter(|v| *v > 0) else {
+            return printer.lines.len();
+        };
+
+        printer
+            .lines
+            .iter()
+            .map(|line| {
+                let columns: usize = line.iter().map(|v| count_columns(&v.text)).sum();
+                columns.div_ceil(terminal_width).max(1)
+            })
+            .sum()
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -258,6 +975,44 @@ pub trait Printable<'a> {
         self.print(&mut printer);
         format!("{}", printer)
     }
+
+    /// Writes the content of this type directly into `writer` without allocating
+    /// an intermediate [String].
+    fn write_to(
+        &self,
+        level: LogLevel,
+        format: PrinterFormat,
+        writer: &mut impl Write,
+    ) -> std::io::Result<()>
+    where
+        Self: Sized,
+    {
+        let mut printer = Printer::new(level, format);
+        self.print(&mut printer);
+        printer.write_to(writer, format)
+    }
+}
+
+/// Returns whether a column, see [split_columns], is a whitespace.
+fn is_whitespace_column(column: &str) -> bool {
+    column.chars().all(char::is_whitespace)
+}
+
+/// Groups consecutive columns with the same style into text sections.
+fn group_chars<'a>(chars: &[(&str, Style)]) -> PrinterLine<'a> {
+    let mut sections = PrinterLine::new();
+
+    for &(char, style) in chars {
+        match sections.last_mut() {
+            Some(section) if section.style == style => section.text.to_mut().push_str(char),
+            _ => sections.push(TextSection {
+                text: Cow::Owned(char.to_string()),
+                style,
+            }),
+        }
+    }
+
+    sections
 }
 
 // ----------------------------------------------------------------------------
@@ -268,6 +1023,242 @@ pub trait Printable<'a> {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_graphemes() {
+        let mut base = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        base.push_plain_text("e\u{301}e\u{301} 👩\u{200d}💻👩\u{200d}💻");
+
+        let mut printer = base.clone();
+        printer.clip_lines(4);
+        assert_eq!(printer.to_string(), "e\u{301}e\u{301} …");
+
+        let mut printer = base.clone();
+        printer.truncate_lines(4);
+        assert_eq!(printer.to_string(), "e\u{301}e\u{301}…👩\u{200d}💻");
+
+        let mut printer = base;
+        printer.wrap_lines(3);
+        assert_eq!(
+            printer.to_string(),
+            "e\u{301}e\u{301}\n👩\u{200d}💻👩\u{200d}💻"
+        );
+    }
+
+    #[test]
+    fn test_truncate_lines() {
+        let mut base = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        base.push_styled_text("12 │ ", Style::new().bold());
+        base.push_plain_text("this is a very long line\nshort\n");
+        base.push_styled_text("ab", Style::new().bold());
+
+        base.truncate_lines(12);
+        let result = format!("{}", base);
+
+        println!("{}", result);
+        assert_eq!(result, "12 │ t… line\nshort\nab");
+
+        base.truncate_lines(1);
+        let result = format!("{}", base);
+
+        println!("{}", result);
+        assert_eq!(result, "…\n…\n…");
+    }
+
+    #[test]
+    fn test_truncate_lines_styled() {
+        let mut base = Printer::new(LogLevel::error(), PrinterFormat::Styled);
+        base.push_styled_text("12 │ ", Style::new().bold());
+        base.push_plain_text("this is a very long line");
+
+        base.truncate_lines(8);
+        let result = format!("{}", base);
+
+        println!("{}", result);
+        assert_eq!(result, "\u{1b}[1m12 │\u{1b}[0m…ine");
+    }
+
+    #[test]
+    fn test_new_lines() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.new_line();
+        assert_eq!(printer.lines.len(), 2);
+
+        printer.ensure_blank_line();
+        assert_eq!(printer.lines.len(), 2);
+
+        printer.push_plain_text("a");
+        printer.ensure_blank_line();
+        printer.push_plain_text("b");
+        printer.push_raw_line([TextSection {
+            text: Cow::Borrowed("c"),
+            style: Style::new(),
+        }]);
+        printer.new_line();
+
+        assert_eq!(printer.to_string(), "\na\nb\nc\n");
+
+        let mut expected = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        expected.push_plain_text("\n");
+        let mut actual = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        actual.new_line();
+        assert_eq!(actual.lines, expected.lines);
+    }
+
+    #[test]
+    fn test_carriage_returns() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.push_plain_text("a\r\nb\rc\r");
+        printer.push_plain_text(String::from("d\r\n"));
+
+        assert_eq!(printer.lines.len(), 5);
+        assert_eq!(printer.to_string(), "a\nb\nc\nd\n");
+    }
+
+    #[test]
+    fn test_split_at_line() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(10));
+        printer.push_plain_text("a\nb\nc");
+
+        assert_eq!(printer.line_count(), 3);
+
+        let (head, tail) = printer.clone().split_at_line(2);
+
+        assert_eq!(head.to_string(), "a\nb");
+        assert_eq!(tail.to_string(), "c");
+        assert_eq!(tail.max_width, Some(10));
+
+        let (head, tail) = printer.split_at_line(5);
+
+        assert_eq!(head.line_count(), 3);
+        assert_eq!(tail.line_count(), 0);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain).max_width(Some(10));
+        printer.push_plain_text("a\nb");
+        printer.clear();
+
+        assert!(printer.lines.is_empty());
+        assert_eq!(printer.max_width, Some(10));
+
+        printer.push_plain_text("c");
+        assert_eq!(printer.to_string(), "c");
+    }
+
+    #[test]
+    fn test_clip_lines() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.push_plain_text("1 │ abcdefghij\n  │ ^^^^^^^^^^\nshort");
+        printer.clip_lines(8);
+
+        assert_eq!(printer.to_string(), "1 │ abc…\n  │ ^^^…\nshort");
+
+        printer.clip_lines(0);
+        assert_eq!(printer.to_string(), "\n\n");
+    }
+
+    #[test]
+    fn test_write_to() {
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Styled);
+        printer.push_styled_text("Styled", Style::new().bold());
+        printer.push_plain_text("\nPlain");
+
+        let mut buffer = Vec::new();
+        printer
+            .write_to(&mut buffer, PrinterFormat::Styled)
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), printer.to_string());
+
+        let mut buffer = Vec::new();
+        printer.write_to(&mut buffer, PrinterFormat::Plain).unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Styled\nPlain");
+    }
+
+    #[test]
+    fn test_derive_width_budget() {
+        let base = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        assert_eq!(base.derive_indented(4).max_width, None);
+
+        let base = base.max_width(Some(10));
+        assert_eq!(base.derive().max_width, Some(10));
+
+        let derived = base.derive_indented(4);
+        assert_eq!(derived.max_width, Some(6));
+        assert_eq!(derived.derive_indented(10).max_width, Some(0));
+    }
+
+    #[test]
+    fn test_auto_format() {
+        let detect = |stream, vars: &[(&str, &str)]| {
+            detect_styles(stream, |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| OsString::from(value))
+            })
+        };
+
+        let force = (CLICOLOR_FORCE_ENV_VAR, "1");
+        assert!(!detect(
+            OutputStream::Stdout,
+            &[(NO_COLOR_ENV_VAR, "1"), force]
+        ));
+        assert!(detect(
+            OutputStream::Stdout,
+            &[(NO_COLOR_ENV_VAR, ""), force]
+        ));
+        assert!(detect(
+            OutputStream::Stderr,
+            &[force, (CLICOLOR_ENV_VAR, "0")]
+        ));
+        assert!(!detect(
+            OutputStream::Stderr,
+            &[(CLICOLOR_FORCE_ENV_VAR, "0"), (CLICOLOR_ENV_VAR, "0")]
+        ));
+
+        assert_eq!(
+            PrinterFormat::Plain.resolve_for(OutputStream::Stdout),
+            PrinterFormat::Plain
+        );
+        assert_eq!(
+            PrinterFormat::Styled.resolve_for(OutputStream::Stdout),
+            PrinterFormat::Styled
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_enable_ansi_support() {
+        assert!(enable_ansi_support());
+    }
+
+    #[test]
+    fn test_live_writer() {
+        let mut writer = LiveWriter::new(Vec::new());
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.push_plain_text("first\nupdate");
+        writer.update(&printer).unwrap();
+        assert_eq!(writer.previous_line_count(), 2);
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.push_plain_text("second");
+        writer.update(&printer).unwrap();
+        assert_eq!(writer.previous_line_count(), 1);
+
+        let result = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(result, "first\nupdate\r\u{1b}[1A\u{1b}[Jsecond\n");
+
+        // Wrapped lines.
+        let mut writer = LiveWriter::new(Vec::new()).terminal_width(Some(4));
+
+        let mut printer = Printer::new(LogLevel::error(), PrinterFormat::Plain);
+        printer.push_plain_text("abcdefghij\n\nabcd");
+        writer.update(&printer).unwrap();
+        assert_eq!(writer.previous_line_count(), 5);
+    }
+
     #[test]
     fn test_indent_plain() {
         let mut base = Printer::new(LogLevel::error(), PrinterFormat::Plain);
@@ -0,0 +1,459 @@
+use crate::blocks::{CodeBlock, LogBlock, TextBlock};
+use crate::utils::mask::MaskedText;
+use crate::visitor::{mask_block, mask_code, mask_text_block, Masker};
+use crate::{BlockVisitor, FieldValue, Log, Redactor};
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// The configuration of the secrets to mask in the logs before they are rendered, e.g.
+/// tokens or passwords, to make them safe to ship. See
+/// [LoggerBuilder::redaction](crate::LoggerBuilder::redaction) and
+/// [RedactingSink](crate::sinks::RedactingSink).
+///
+/// Secrets are found in every text section of the logs, including the nested blocks, the
+/// code of code blocks, the texts of diff blocks and the causes, and in the structured
+/// fields and suggestions:
+/// - Literal secrets, see [Redaction::secret], and patterns with the `regex` feature.
+/// - Keys, see [Redaction::key], that mask the value that follows them in texts like
+///   `password=hunter2` or `"token": "abc"`, the values of [KeyValueBlock](crate::blocks::KeyValueBlock)
+///   entries and the structured fields with that key.
+///
+/// User-defined blocks are removed because their content cannot be inspected. See
+/// [Redactor] for other limitations.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::TextBlock;
+/// use doclog::{Log, Redaction};
+///
+/// let redaction = Redaction::new().key("password").secret("abc123");
+/// let mut log = Log::error()
+///     .add_block(TextBlock::new_plain("Login failed: user=root password=hunter2 token=abc123"));
+/// redaction.redact_log(&mut log);
+///
+/// assert_eq!(
+///     log.to_plain_text(),
+///     "Login failed: user=root password=[REDACTED] token=[REDACTED]"
+/// );
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct Redaction {
+    redactor: Redactor,
+
+    /// The keys whose values are masked, in lowercase.
+    keys: Vec<Cow<'static, str>>,
+}
+
+impl Redaction {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [Redaction] without secrets that masks them with `[REDACTED]`.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the text that replaces the secrets.
+    #[inline(always)]
+    pub fn get_mask(&self) -> &str {
+        self.redactor.get_mask()
+    }
+
+    /// Returns whether the values of `key` are masked. Keys are compared ignoring the
+    /// ASCII case and the surrounding whitespaces.
+    pub fn is_sensitive_key(&self, key: &str) -> bool {
+        let key = key.trim();
+        self.keys.iter().any(|v| key.eq_ignore_ascii_case(v))
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Adds a literal secret to mask. Empty secrets are ignored.
+    pub fn secret(mut self, secret: impl Into<Cow<'static, str>>) -> Self {
+        self.redactor = self.redactor.secret(secret);
+        self
+    }
+
+    /// Adds a pattern whose matches are masked, e.g. `ghp_[A-Za-z0-9]{36}`.
+    #[cfg(feature = "regex")]
+    pub fn pattern(mut self, pattern: regex::Regex) -> Self {
+        self.redactor = self.redactor.pattern(pattern);
+        self
+    }
+
+    /// Adds a key whose values are masked, e.g. `password`. Empty keys are ignored.
+    pub fn key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        let key = key.into();
+        let key = key.trim();
+
+        if !key.is_empty() {
+            self.keys.push(Cow::Owned(key.to_ascii_lowercase()));
+        }
+
+        self
+    }
+
+    /// Sets the text that replaces the secrets.
+    #[inline(always)]
+    pub fn mask(mut self, mask: impl Into<Cow<'static, str>>) -> Self {
+        self.redactor = self.redactor.mask(mask);
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns `text` with the secrets and the values of the keys masked, borrowing it if
+    /// it does not contain any.
+    pub fn redact<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        MaskedText::new(text, self.secret_ranges(text), self.get_mask()).text
+    }
+
+    /// Masks the secrets of `log`, its causes and their structured fields and suggestions.
+    pub fn redact_log(&self, log: &mut Log) {
+        self.redact_metadata(log);
+        log.visit_mut(&mut RedactionVisitor(self));
+    }
+
+    /// Returns the ranges of the values of the keys in `text`.
+    fn key_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+
+        if self.keys.is_empty() {
+            return ranges;
+        }
+
+        let lowercase = text.to_ascii_lowercase();
+
+        for key in &self.keys {
+            for (start, _) in lowercase.match_indices(key.as_ref()) {
+                let end = start + key.len();
+
+                if text[..start].chars().next_back().is_some_and(is_key_char)
+                    || text[end..].chars().next().is_some_and(is_key_char)
+                {
+                    continue;
+                }
+
+                ranges.extend(value_range(text, end));
+            }
+        }
+
+        ranges
+    }
+
+    /// Masks the structured fields and suggestions of `log` and its causes.
+    fn redact_metadata(&self, log: &mut Log) {
+        for (key, value) in log.fields_mut() {
+            if self.is_sensitive_key(key) {
+                *value = FieldValue::Str(Cow::Owned(self.get_mask().to_string()));
+            } else if let FieldValue::Str(text) = value {
+                self.mask_cow(text);
+            }
+        }
+
+        for suggestion in log.suggestions_mut() {
+            self.mask_cow(&mut suggestion.title);
+            self.mask_cow(&mut suggestion.replacement);
+        }
+
+        if let Some(cause) = log.cause_mut() {
+            self.redact_metadata(cause);
+        }
+    }
+}
+
+impl Masker for Redaction {
+    fn secret_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = self.key_ranges(text);
+        ranges.extend(self.redactor.secret_ranges(text));
+        ranges
+    }
+
+    fn mask_text(&self) -> &str {
+        self.get_mask()
+    }
+}
+
+/// The [BlockVisitor] that applies a [Redaction] to the blocks of a log.
+struct RedactionVisitor<'r>(&'r Redaction);
+
+impl<'r, 'a> BlockVisitor<'a> for RedactionVisitor<'r> {
+    fn visit_block_mut(&mut self, block: &mut LogBlock<'a>) -> bool {
+        if let LogBlock::KeyValue(block) = block {
+            for (key, value) in &mut block.entries {
+                let key: String = key.sections.iter().map(|v| v.text.as_ref()).collect();
+
+                if self.0.is_sensitive_key(&key) {
+                    *value = TextBlock::new_plain(self.0.get_mask().to_string());
+                }
+            }
+        }
+
+        mask_block(self, block)
+    }
+
+    fn visit_code_mut(&mut self, code: &mut CodeBlock<'a>) {
+        mask_code(self, code);
+    }
+
+    fn visit_text_mut(&mut self, text: &mut TextBlock<'a>) {
+        mask_text_block(self, text);
+    }
+}
+
+impl Masker for RedactionVisitor<'_> {
+    fn secret_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        self.0.secret_ranges(text)
+    }
+
+    fn mask_text(&self) -> &str {
+        self.0.get_mask()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Returns whether `char` can be part of a key, so keys only match whole words.
+fn is_key_char(char: char) -> bool {
+    char.is_alphanumeric() || char == '_' || char == '-'
+}
+
+/// Returns the range of the value assigned to the key that ends at `key_end`, i.e. after
+/// a `=` or `:` separator, excluding the quotes of quoted values.
+fn value_range(text: &str, key_end: usize) -> Option<Range<usize>> {
+    let rest = text[key_end..].trim_start_matches(['"', '\'']);
+    let rest = rest.trim_start_matches([' ', '\t']);
+    let value = rest
+        .strip_prefix([':', '='])?
+        .trim_start_matches([' ', '\t']);
+    let start = text.len() - value.len();
+
+    let range = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let inner = &value[1..];
+            let length = inner.find(quote).unwrap_or(inner.len());
+            start + 1..start + 1 + length
+        }
+        _ => {
+            let length = value
+                .find(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '&' | ')' | ']' | '}'))
+                .unwrap_or(value.len());
+            start..start + length
+        }
+    };
+
+    (!range.is_empty()).then_some(range)
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{
+        ColumnAlignment, DiffBlock, DynPrintable, HeaderBlock, KeyValueBlock, NoteBlock,
+        PrefixBlock, ProgressBlock, SeparatorBlock, StackBlock, StackTraceBlock, StepsBlock,
+        TableBlock,
+    };
+    use crate::printer::{Printable, Printer, PrinterFormat};
+    use crate::{LogContent, LogLevel, Suggestion};
+
+    #[test]
+    fn test_redact_keys() {
+        let redaction = Redaction::new().key("Password").key(" token ").mask("***");
+
+        assert!(matches!(redaction.redact("no secrets"), Cow::Borrowed(_)));
+        assert_eq!(
+            redaction.redact("password=abc user=root"),
+            "password=*** user=root"
+        );
+        assert_eq!(
+            redaction.redact("PASSWORD: abc, next"),
+            "PASSWORD: ***, next"
+        );
+        assert_eq!(
+            redaction.redact(r#"{"token": "a b c", "id": 1}"#),
+            r#"{"token": "***", "id": 1}"#
+        );
+        assert_eq!(
+            redaction.redact("?token=abc&password=def"),
+            "?token=***&password=***"
+        );
+        assert_eq!(
+            redaction.redact("old_password=abc token"),
+            "old_password=abc token"
+        );
+        assert_eq!(redaction.redact("password="), "password=");
+    }
+
+    #[test]
+    fn test_redact_log() {
+        let redaction = Redaction::new().key("password").secret("s3cr3t");
+        let mut log = Log::error()
+            .add_block(
+                PrefixBlock::new().prefix("> ").content(
+                    LogContent::new().add_block(
+                        KeyValueBlock::new()
+                            .add_entry("user", "root")
+                            .add_entry("password", "hunter2"),
+                    ),
+                ),
+            )
+            .field("password", "hunter2")
+            .field("query", "key=s3cr3t")
+            .field("attempts", 3)
+            .set_cause(|v| {
+                v.add_block(TextBlock::new_plain("Invalid s3cr3t"))
+                    .field("password", 1)
+            });
+        redaction.redact_log(&mut log);
+
+        assert_eq!(
+            log.fields,
+            vec![
+                (Cow::Borrowed("password"), FieldValue::from("[REDACTED]")),
+                (Cow::Borrowed("query"), FieldValue::from("key=[REDACTED]")),
+                (Cow::Borrowed("attempts"), FieldValue::Int(3)),
+            ]
+        );
+        assert_eq!(
+            log.cause.as_ref().unwrap().fields[0].1,
+            FieldValue::from("[REDACTED]")
+        );
+
+        let text = log.to_plain_text();
+        assert!(text.contains(">     user: root"), "{text}");
+        assert!(text.contains("> password: [REDACTED]"), "{text}");
+        assert!(text.contains("Invalid [REDACTED]"), "{text}");
+        assert!(
+            !text.contains("hunter2") && !text.contains("s3cr3t"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_redact_every_block() {
+        #[derive(Debug, Clone)]
+        struct Custom;
+
+        impl<'a> Printable<'a> for Custom {
+            fn print<'s>(&'s self, printer: &mut Printer<'a>)
+            where
+                'a: 's,
+            {
+                printer.push_plain_text("custom s3cr3t");
+            }
+        }
+
+        impl<'a> DynPrintable<'a> for Custom {
+            fn clone_box(&self) -> Box<dyn DynPrintable<'a> + 'a> {
+                Box::new(self.clone())
+            }
+
+            fn make_owned_box(self: Box<Self>) -> Box<dyn DynPrintable<'static>> {
+                self
+            }
+        }
+
+        let code = "let token = s3cr3t;\nlet b = token;";
+        let blocks: Vec<LogBlock> = vec![
+            TextBlock::new_plain("text s3cr3t").into(),
+            PrefixBlock::new()
+                .prefix("s3cr3t ")
+                .content(LogContent::new().add_block(TextBlock::new_plain("prefix")))
+                .into(),
+            SeparatorBlock::with_width(30)
+                .with_label("label s3cr3t")
+                .into(),
+            HeaderBlock::new()
+                .title("title s3cr3t")
+                .code("E-s3cr3t")
+                .location("src/s3cr3t.rs")
+                .add_extra_message("extra s3cr3t")
+                .into(),
+            NoteBlock::new()
+                .text("note s3cr3t")
+                .url("https://example.com/?token=s3cr3t")
+                .into(),
+            StackBlock::new()
+                .message("stack s3cr3t")
+                .add_stack_trace(
+                    StackTraceBlock::new()
+                        .file_location("src/s3cr3t.rs")
+                        .code_path("s3cr3t::main")
+                        .message("trace s3cr3t"),
+                )
+                .into(),
+            CodeBlock::new(code)
+                .title("code s3cr3t")
+                .highlight_section_message(12..18, None, "message s3cr3t")
+                .suggest_replacement(28..33, "s3cr3t", "use s3cr3t")
+                .note("note s3cr3t")
+                .related(CodeBlock::new("s3cr3t").highlight_section(0..6, None))
+                .into(),
+            StepsBlock::new()
+                .title("steps s3cr3t")
+                .add_step(TextBlock::new_plain("step s3cr3t"))
+                .into(),
+            DiffBlock::new("expected s3cr3t", "actual s3cr3t").into(),
+            TableBlock::new()
+                .add_column("column s3cr3t", ColumnAlignment::Left)
+                .add_row(["cell s3cr3t"])
+                .into(),
+            KeyValueBlock::new().add_entry("key", "s3cr3t").into(),
+            ProgressBlock::new("progress s3cr3t").into(),
+            LogBlock::custom(Custom),
+        ];
+
+        let redaction = Redaction::new().secret("s3cr3t");
+        let mut log =
+            Log::error().add_suggestion(Suggestion::new("a", 0..1, "s3cr3t").title("use s3cr3t"));
+
+        for block in blocks {
+            assert!(
+                block
+                    .print_to_string(LogLevel::error(), PrinterFormat::Plain)
+                    .contains("s3cr3t"),
+                "{block:?}"
+            );
+
+            log.push(block);
+        }
+
+        redaction.redact_log(&mut log);
+
+        let text = log.to_plain_text();
+        assert!(!text.contains("s3cr3t"), "{text}");
+        assert!(!format!("{log:?}").contains("s3cr3t"), "{log:?}");
+        assert!(!text.contains("custom"), "{text}");
+
+        let LogBlock::Code(block) = &log.content.blocks[6] else {
+            panic!("The code block was removed");
+        };
+
+        assert_eq!(block.get_code(), "let token = [REDACTED];\nlet b = token;");
+        assert_eq!(
+            block.print_to_string(LogLevel::error(), PrinterFormat::Plain),
+            "× code [REDACTED]\n  ╭─\n1 │    let token = [REDACTED];\n  │                ╰────────┴── message [REDACTED]\n2 │    let b = token;\n  │            ╰───┴── use [REDACTED]\n  ├─\n1 │    [REDACTED]\n  │    ╰────────╯\n2 +    let b = [REDACTED];\n  ╰─\n  = note: note [REDACTED]"
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_redact_pattern() {
+        let redaction = Redaction::new()
+            .key("token")
+            .pattern(regex::Regex::new("ghp_[a-z0-9]+").unwrap());
+
+        assert_eq!(
+            redaction.redact("token=abc ghp_xyz9"),
+            "token=[REDACTED] [REDACTED]"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use crate::printer::{OutputStream, Printable, Printer, PrinterFormat};
+use crate::{Log, LogLevel, Theme};
+use std::io::{BufWriter, Write};
+
+/// The environment variable that contains the width of the terminal, in columns.
+const COLUMNS_ENV_VAR: &str = "COLUMNS";
+
+/// A snapshot of the terminal state, i.e. its width and whether it supports colors,
+/// shared by all the logs printed through it.
+///
+/// The state is captured once, so a long-running program keeps a consistent layout even
+/// if the environment changes, e.g. the terminal is resized. Use [RenderSession::refresh]
+/// to capture it again.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RenderSession {
+    width: Option<usize>,
+    styled: bool,
+    theme: Theme,
+}
+
+impl RenderSession {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [RenderSession] capturing the current terminal state.
+    pub fn new() -> Self {
+        Self {
+            width: detect_terminal_width(),
+            styled: PrinterFormat::Auto
+                .resolve_for(OutputStream::Stdout)
+                .is_styled(),
+            theme: Theme::global(),
+        }
+    }
+
+    /// Creates a new [RenderSession] with an explicit state instead of capturing it.
+    pub fn with_state(width: Option<usize>, styled: bool) -> Self {
+        Self {
+            width,
+            styled,
+            theme: Theme::global(),
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the theme used to style the logs.
+    #[inline(always)]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the theme used to style the logs.
+    #[inline(always)]
+    pub fn theme(&self) -> Theme {
+        self.theme
+    }
+
+    /// Returns the width of the terminal in columns, if known.
+    #[inline(always)]
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Returns whether the logs are printed with styles.
+    #[inline(always)]
+    pub fn is_styled(&self) -> bool {
+        self.styled
+    }
+
+    /// Returns the format used to print the logs.
+    pub fn format(&self) -> PrinterFormat {
+        if self.styled {
+            PrinterFormat::Styled
+        } else {
+            PrinterFormat::Plain
+        }
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Captures the current terminal state again.
+    pub fn refresh(&mut self) {
+        *self = Self::new().with_theme(self.theme);
+    }
+
+    /// Creates a new [Printer] configured with the state of this session.
+    pub fn printer<'a>(&self, level: LogLevel) -> Printer<'a> {
+        Printer::with_theme(level, self.format(), self.theme).max_width(self.width)
+    }
+
+    /// Returns `log` as text using the state of this session.
+    pub fn render(&self, log: &Log) -> String {
+        let mut printer = self.printer(log.level);
+        log.print(&mut printer);
+        printer.to_string()
+    }
+
+    /// Writes `log` into `writer` using the state of this session, followed by a newline.
+    /// The output is buffered, and `writer` is flushed once after the whole log.
+    pub fn write_log(&self, log: &Log, writer: &mut impl Write) -> std::io::Result<()> {
+        let mut printer = self.printer(log.level);
+        log.print(&mut printer);
+
+        let mut writer = BufWriter::new(writer);
+        printer.write_to(&mut writer, self.format())?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+
+    /// Logs in the console `log` using the state of this session.
+    pub fn log(&self, log: &Log) {
+        println!("{}", self.render(log));
+    }
+}
+
+impl Default for RenderSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Detects the width of the terminal from the `COLUMNS` environment variable or, with the
+/// `terminal` feature, asking the terminal itself.
+pub(crate) fn detect_terminal_width() -> Option<usize> {
+    let width = std::env::var(COLUMNS_ENV_VAR)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|v| *v > 0);
+
+    #[cfg(feature = "terminal")]
+    let width = width.or_else(crate::terminal::width);
+
+    width
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::SeparatorBlock;
+
+    #[test]
+    fn test_render() {
+        let log = Log::info().add_block(SeparatorBlock::with_width(20));
+
+        let session = RenderSession::with_state(Some(5), false);
+        assert_eq!(session.render(&log), "─────");
+
+        let session = RenderSession::with_state(None, true);
+        assert_eq!(
+            session.render(&log),
+            "\u{1b}[1;34m────────────────────\u{1b}[0m"
+        );
+
+        let mut buffer = Vec::new();
+        RenderSession::with_state(Some(3), false)
+            .write_log(&log, &mut buffer)
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "───\n");
+    }
+
+    #[test]
+    fn test_theme() {
+        let log = Log::info().add_block(SeparatorBlock::with_width(3));
+        let session = RenderSession::with_state(None, true).with_theme(Theme::light());
+
+        assert_eq!(session.render(&log), "\u{1b}[1;38;5;25m───\u{1b}[0m");
+    }
+}
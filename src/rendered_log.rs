@@ -0,0 +1,185 @@
+use crate::printer::{Printer, PrinterFormat};
+use crate::utils::text::count_columns;
+use crate::Log;
+use std::ops::Range;
+
+/// The plain rendering of a [Log] split in lines, keeping track of which lines
+/// belong to each block. It is intended for frontends like pagers or TUIs that need
+/// to locate content in the rendered output without reimplementing the layout.
+///
+/// Blocks are numbered in printing order, i.e. first the blocks of the log and then
+/// the blocks of its causes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RenderedLog {
+    lines: Vec<String>,
+    blocks: Vec<Range<usize>>,
+}
+
+/// A match of a search over a [RenderedLog].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SearchMatch {
+    /// The line of the match, starting at 0.
+    pub line: usize,
+
+    /// The column of the match, starting at 0.
+    pub column: usize,
+
+    /// The number of columns the match occupies.
+    pub columns: usize,
+
+    /// The index of the block that contains the match, if any.
+    pub block: Option<usize>,
+}
+
+impl RenderedLog {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Renders `log` as plain text.
+    pub fn new<'a>(log: &Log<'a>) -> Self {
+        let mut printer = Printer::new(log.level, PrinterFormat::Plain);
+        let mut blocks = Vec::new();
+        log.print_with_blocks(&mut printer, Some(&mut blocks));
+
+        let lines = printer
+            .lines
+            .iter()
+            .map(|line| line.iter().map(|v| v.text.as_ref()).collect())
+            .collect();
+
+        Self { lines, blocks }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the rendered lines.
+    #[inline(always)]
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Returns the range of lines occupied by every block.
+    #[inline(always)]
+    pub fn blocks(&self) -> &[Range<usize>] {
+        &self.blocks
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the index of the block that contains `line`, if any.
+    pub fn block_at_line(&self, line: usize) -> Option<usize> {
+        self.blocks.iter().position(|v| v.contains(&line))
+    }
+
+    /// Finds all non-overlapping occurrences of `pattern` in the rendered lines.
+    /// Matches cannot span multiple lines.
+    pub fn find(&self, pattern: &str) -> Vec<SearchMatch> {
+        if pattern.is_empty() {
+            return Vec::new();
+        }
+
+        let pattern_columns = count_columns(pattern);
+        let mut result = Vec::new();
+
+        for (line_index, line) in self.lines.iter().enumerate() {
+            for (byte_offset, _) in line.match_indices(pattern) {
+                result.push(SearchMatch {
+                    line: line_index,
+                    column: count_columns(&line[..byte_offset]),
+                    columns: pattern_columns,
+                    block: self.block_at_line(line_index),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+impl<'a> From<&Log<'a>> for RenderedLog {
+    fn from(log: &Log<'a>) -> Self {
+        Self::new(log)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{HeaderBlock, TextBlock};
+
+    #[test]
+    fn test_lines() {
+        let log = Log::error()
+            .add_block(HeaderBlock::new().title(TextBlock::new_plain("Title")))
+            .add_block(TextBlock::new_plain("Line 1\nLine 2"))
+            .set_cause(|log| log.add_block(TextBlock::new_plain("Cause")));
+        let rendered = RenderedLog::new(&log);
+
+        assert_eq!(rendered.lines().join("\n"), log.to_plain_text());
+        assert_eq!(rendered.blocks(), &[0..1, 1..3, 3..4]);
+    }
+
+    #[test]
+    fn test_fields_and_truncated_lines() {
+        let log = Log::error()
+            .add_block(TextBlock::new_plain("Line 1\nLine 2"))
+            .field("request_id", "a1b2")
+            .set_cause(|log| {
+                log.add_block(TextBlock::new_plain("Cause 1\nCause 2\nCause 3"))
+                    .add_block(TextBlock::new_plain("Cause 4"))
+                    .truncate_lines(2)
+            })
+            .truncate_lines(5);
+        let rendered = log.render();
+
+        assert_eq!(rendered.lines().join("\n"), log.to_plain_text());
+        assert_eq!(
+            rendered.lines(),
+            &[
+                "Line 1",
+                "Line 2",
+                "request_id: a1b2",
+                "Cause 1",
+                "Cause 2",
+                "… 1 more line"
+            ]
+        );
+        assert_eq!(rendered.blocks(), &[0..2, 3..5, 5..5]);
+    }
+
+    #[test]
+    fn test_find() {
+        let log = Log::error()
+            .add_block(TextBlock::new_plain("día a día"))
+            .add_block(TextBlock::new_plain("no match\nel día"));
+        let rendered = RenderedLog::new(&log);
+
+        assert_eq!(
+            rendered.find("día"),
+            vec![
+                SearchMatch {
+                    line: 0,
+                    column: 0,
+                    columns: 3,
+                    block: Some(0),
+                },
+                SearchMatch {
+                    line: 0,
+                    column: 6,
+                    columns: 3,
+                    block: Some(0),
+                },
+                SearchMatch {
+                    line: 2,
+                    column: 3,
+                    columns: 3,
+                    block: Some(1),
+                },
+            ]
+        );
+        assert!(rendered.find("").is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+use crate::blocks::{HeaderBlock, LogBlock};
+use crate::printer::PrinterFormat;
+use crate::{Log, LogLevel};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+/// Aggregates the logs emitted during a run, e.g. by a compiler, counting them by level
+/// to print a final summary like `3 errors, 12 warnings emitted`.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::HeaderBlock;
+/// use doclog::{Log, PrinterFormat, Report};
+///
+/// let mut report = Report::new();
+/// report.push(Log::error().add_block(HeaderBlock::new().title("Undefined variable")));
+/// report.push(Log::warn().add_block(HeaderBlock::new().title("Unused variable")));
+///
+/// assert_eq!(
+///     report.summary().to_plain_text(),
+///     "ERROR 1 error, 1 warning emitted"
+/// );
+/// println!("{}", report.format(None, PrinterFormat::Default));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Report {
+    logs: Vec<Log<'static>>,
+    counts: BTreeMap<LogLevel, usize>,
+}
+
+/// The order used to re-render the logs of a [Report].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ReportOrder {
+    /// Keeps the order in which the logs were pushed.
+    Emission,
+
+    /// Sorts the logs from the most to the least severe level.
+    Severity,
+
+    /// Sorts the logs by the file path of their first code block and then by severity.
+    /// Logs without file path go last.
+    File,
+}
+
+impl Report {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [Report].
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the collected logs in the order they were pushed.
+    #[inline(always)]
+    pub fn logs(&self) -> &[Log<'static>] {
+        &self.logs
+    }
+
+    /// Returns the number of collected logs.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.logs.len()
+    }
+
+    /// Returns whether the report does not contain any log.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.logs.is_empty()
+    }
+
+    /// Returns the number of collected logs of `level`.
+    pub fn count(&self, level: LogLevel) -> usize {
+        self.counts.get(&level).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of collected logs per level, from the most to the least severe.
+    pub fn counts(&self) -> impl Iterator<Item = (LogLevel, usize)> + '_ {
+        self.counts
+            .iter()
+            .rev()
+            .map(|(level, count)| (*level, *count))
+    }
+
+    /// Returns the most severe level of the collected logs, if any.
+    pub fn max_level(&self) -> Option<LogLevel> {
+        self.counts.keys().next_back().copied()
+    }
+
+    /// Returns whether any of the collected logs is at least of `level`, e.g. to decide
+    /// the exit code of the program.
+    pub fn has_level(&self, level: LogLevel) -> bool {
+        self.max_level().is_some_and(|v| v.level() >= level.level())
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Adds a log to the report.
+    pub fn push(&mut self, log: Log) {
+        *self.counts.entry(log.level).or_default() += 1;
+        self.logs.push(log.make_owned());
+    }
+
+    /// Returns the collected logs sorted by `order`. The sort is stable, so logs that are
+    /// equal in `order` keep the order in which they were pushed.
+    pub fn sorted_logs(&self, order: ReportOrder) -> Vec<&Log<'static>> {
+        let mut logs: Vec<_> = self.logs.iter().collect();
+
+        match order {
+            ReportOrder::Emission => {}
+            ReportOrder::Severity => logs.sort_by_key(|log| Reverse(log.level)),
+            ReportOrder::File => logs.sort_by_cached_key(|log| {
+                let file_path = log_file_path(log);
+                (file_path.is_none(), file_path, Reverse(log.level))
+            }),
+        }
+
+        logs
+    }
+
+    /// Builds the summary of the report, i.e. a log with the number of logs per level,
+    /// like `3 errors, 12 warnings emitted`, at the most severe level collected.
+    pub fn summary(&self) -> Log<'static> {
+        let counts: Vec<_> = self
+            .counts()
+            .map(|(level, count)| {
+                let name = if level == LogLevel::warn() {
+                    "warning"
+                } else {
+                    level.tag()
+                };
+
+                if count == 1 {
+                    format!("{count} {name}")
+                } else {
+                    format!("{count} {name}s")
+                }
+            })
+            .collect();
+        let title = if counts.is_empty() {
+            "no logs emitted".to_string()
+        } else {
+            format!("{} emitted", counts.join(", "))
+        };
+
+        Log::new(self.max_level().unwrap_or(LogLevel::info()))
+            .add_block(HeaderBlock::new().title(title))
+    }
+
+    /// Renders the summary of the report in `format`. If `order` is set, all the collected
+    /// logs are rendered before the summary sorted by it.
+    pub fn format(&self, order: Option<ReportOrder>, format: PrinterFormat) -> String {
+        let mut result = String::new();
+
+        if let Some(order) = order {
+            for log in self.sorted_logs(order) {
+                result.push_str(&log.format(format));
+                result.push_str("\n\n");
+            }
+        }
+
+        result.push_str(&self.summary().format(format));
+        result
+    }
+}
+
+impl<'a> Extend<Log<'a>> for Report {
+    fn extend<T: IntoIterator<Item = Log<'a>>>(&mut self, iter: T) {
+        for log in iter {
+            self.push(log);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Returns the file path of the first code block of a log with a file path.
+fn log_file_path(log: &Log) -> Option<String> {
+    log.content.blocks.iter().find_map(|block| match block {
+        LogBlock::Code(code) if !code.file_path.is_empty() => Some(code.file_path.to_string()),
+        _ => None,
+    })
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{CodeBlock, TextBlock};
+
+    #[test]
+    fn test_counts_and_summary() {
+        let mut report = Report::new();
+
+        assert_eq!(report.summary().to_plain_text(), "INFO no logs emitted");
+        assert!(!report.has_level(LogLevel::warn()));
+
+        report.extend([
+            Log::warn().add_block(TextBlock::new_plain("Warn 1")),
+            Log::error().add_block(TextBlock::new_plain("Error 1")),
+            Log::warn().add_block(TextBlock::new_plain("Warn 2")),
+            Log::info().add_block(TextBlock::new_plain("Info")),
+        ]);
+
+        assert_eq!(report.len(), 4);
+        assert_eq!(report.count(LogLevel::warn()), 2);
+        assert_eq!(report.count(LogLevel::debug()), 0);
+        assert_eq!(
+            report.counts().collect::<Vec<_>>(),
+            vec![
+                (LogLevel::error(), 1),
+                (LogLevel::warn(), 2),
+                (LogLevel::info(), 1),
+            ]
+        );
+        assert_eq!(report.max_level(), Some(LogLevel::error()));
+        assert!(report.has_level(LogLevel::warn()));
+        assert_eq!(
+            report.summary().to_plain_text(),
+            "ERROR 1 error, 2 warnings, 1 info emitted"
+        );
+    }
+
+    #[test]
+    fn test_sorted_logs() {
+        let mut report = Report::new();
+        report.push(Log::warn().add_block(CodeBlock::new("a").file_path("b.rs")));
+        report.push(Log::info().add_block(TextBlock::new_plain("No file")));
+        report.push(Log::warn().add_block(CodeBlock::new("b").file_path("a.rs")));
+        report.push(Log::error().add_block(CodeBlock::new("c").file_path("b.rs")));
+
+        let order = |order| {
+            report
+                .sorted_logs(order)
+                .into_iter()
+                .map(|log| {
+                    report
+                        .logs()
+                        .iter()
+                        .position(|v| std::ptr::eq(v, log))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(ReportOrder::Emission), vec![0, 1, 2, 3]);
+        assert_eq!(order(ReportOrder::Severity), vec![3, 0, 2, 1]);
+        assert_eq!(order(ReportOrder::File), vec![2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_format() {
+        let mut report = Report::new();
+        report.push(Log::warn().add_block(TextBlock::new_plain("Warn")));
+        report.push(Log::error().add_block(TextBlock::new_plain("Error")));
+
+        assert_eq!(
+            report.format(None, PrinterFormat::Plain),
+            "ERROR 1 error, 1 warning emitted"
+        );
+        assert_eq!(
+            report.format(Some(ReportOrder::Severity), PrinterFormat::Plain),
+            "Error\n\nWarn\n\nERROR 1 error, 1 warning emitted"
+        );
+    }
+}
@@ -0,0 +1,374 @@
+use crate::blocks::{LogBlock, TextBlock};
+use crate::sinks::LogSink;
+use crate::Log;
+#[cfg(feature = "thread")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "thread")]
+use std::thread::JoinHandle;
+#[cfg(feature = "thread")]
+use std::time::Duration;
+use yansi::Style;
+
+/// A thread-safe buffer that aggregates the logs produced by multiple threads until
+/// they are drained, either manually or by a background flusher.
+///
+/// Cloning a [LogCollector] returns a new handle to the same buffer.
+#[derive(Debug, Clone)]
+pub struct LogCollector {
+    inner: Arc<CollectorInner>,
+}
+
+#[derive(Debug)]
+struct CollectorInner {
+    capacity: Option<usize>,
+    state: Mutex<CollectorState>,
+    dropped: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+struct CollectorState {
+    logs: Vec<Log<'static>>,
+    dedup: Option<DedupKey>,
+    last_key: Option<String>,
+    repetitions: usize,
+}
+
+/// The key used by a [LogCollector] to decide whether two consecutive logs are identical.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DedupKey {
+    /// Compares the level and the plain text of the logs.
+    Content,
+    /// Compares the level and the code of the first header of the logs.
+    /// Logs without a header code are compared by content.
+    HeaderCode,
+}
+
+/// A handle to the background thread started by [LogCollector::spawn_flusher].
+/// The thread is stopped when the handle is dropped.
+#[cfg(feature = "thread")]
+#[derive(Debug)]
+pub struct FlusherHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LogCollector {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LogCollector] without capacity limit.
+    pub fn new() -> Self {
+        Self::new_inner(None)
+    }
+
+    /// Creates a new [LogCollector] that retains at most `capacity` logs. Logs pushed while
+    /// the collector is full are discarded, keeping the first ones, which usually are the
+    /// most relevant diagnostics.
+    ///
+    /// # Panics
+    /// This method panics if `capacity` is 0.
+    pub fn with_capacity_limit(capacity: usize) -> Self {
+        assert_ne!(capacity, 0, "The capacity must be greater than 0");
+        Self::new_inner(Some(capacity))
+    }
+
+    fn new_inner(capacity: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(CollectorInner {
+                capacity,
+                state: Mutex::new(CollectorState::default()),
+                dropped: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Collapses consecutive identical logs, compared by `key`, into a single entry
+    /// followed by a "(repeated N times)" message.
+    pub fn dedup(self, key: DedupKey) -> Self {
+        self.set_dedup(Some(key));
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the key used to deduplicate consecutive logs, if enabled.
+    pub fn get_dedup(&self) -> Option<DedupKey> {
+        self.lock().dedup
+    }
+
+    /// Returns the maximum number of logs retained by the collector, if any.
+    #[inline(always)]
+    pub fn capacity_limit(&self) -> Option<usize> {
+        self.inner.capacity
+    }
+
+    /// Returns the number of logs currently retained by the collector.
+    pub fn len(&self) -> usize {
+        self.lock().logs.len()
+    }
+
+    /// Returns whether the collector does not retain any log.
+    pub fn is_empty(&self) -> bool {
+        self.lock().logs.is_empty()
+    }
+
+    /// Returns the number of logs discarded because the collector was full.
+    pub fn dropped_count(&self) -> usize {
+        self.inner.dropped.load(Ordering::Relaxed)
+    }
+
+    // SETTERS ----------------------------------------------------------------
+
+    /// Sets the key used to deduplicate consecutive logs, or disables it with `None`.
+    /// See [LogCollector::dedup].
+    pub fn set_dedup(&self, key: Option<DedupKey>) {
+        let mut state = self.lock();
+        state.finish_repetitions();
+        state.dedup = key;
+        state.last_key = None;
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Pushes a new log into the collector, returning whether it was retained.
+    /// A log collapsed into the previous one is considered retained.
+    pub fn push(&self, log: Log) -> bool {
+        let mut state = self.lock();
+
+        if let Some(dedup) = state.dedup {
+            let key = dedup_key(&log, dedup);
+
+            if state.last_key.as_ref() == Some(&key) {
+                state.repetitions += 1;
+                return true;
+            }
+
+            state.finish_repetitions();
+            state.last_key = Some(key);
+        }
+
+        if let Some(capacity) = self.inner.capacity {
+            if state.logs.len() >= capacity {
+                self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+                state.last_key = None;
+                return false;
+            }
+        }
+
+        state.logs.push(log.make_owned());
+        true
+    }
+
+    /// Removes and returns all the retained logs, from the oldest to the newest.
+    pub fn drain(&self) -> Vec<Log<'static>> {
+        let mut state = self.lock();
+        state.finish_repetitions();
+        state.last_key = None;
+        std::mem::take(&mut state.logs)
+    }
+
+    /// Starts a background thread that drains the collector into `sink` every `interval`.
+    /// Errors of the sink are ignored. The remaining logs are flushed when the returned
+    /// handle is stopped or dropped.
+    ///
+    /// Requires the `thread` feature.
+    #[cfg(feature = "thread")]
+    pub fn spawn_flusher(
+        &self,
+        sink: impl LogSink + 'static,
+        interval: Duration,
+    ) -> std::io::Result<FlusherHandle> {
+        let collector = self.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let thread = std::thread::Builder::new()
+            .name("doclog-flusher".to_string())
+            .spawn(move || loop {
+                let stopped = thread_stop.load(Ordering::Acquire);
+
+                for log in collector.drain() {
+                    let _ = sink.write_log(&log);
+                }
+
+                if stopped {
+                    break;
+                }
+
+                std::thread::park_timeout(interval);
+            })?;
+
+        Ok(FlusherHandle {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// Locks the inner state, recovering it if a thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, CollectorState> {
+        self.inner.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for LogCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LogSink for LogCollector {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.push(log.clone());
+        Ok(())
+    }
+}
+
+impl CollectorState {
+    // METHODS ----------------------------------------------------------------
+
+    /// Appends the repetition counter to the last log, if it was repeated.
+    fn finish_repetitions(&mut self) {
+        let repetitions = std::mem::take(&mut self.repetitions);
+
+        if repetitions == 0 {
+            return;
+        }
+
+        if let Some(log) = self.logs.pop() {
+            let message = format!("(repeated {} times)", repetitions + 1);
+            self.logs
+                .push(log.add_block(TextBlock::new().add_styled_text(message, Style::new().dim())));
+        }
+    }
+}
+
+#[cfg(feature = "thread")]
+impl FlusherHandle {
+    // METHODS ----------------------------------------------------------------
+
+    /// Stops the background thread after flushing the remaining logs, and waits for it.
+    pub fn stop(mut self) {
+        self.stop_inner();
+    }
+
+    fn stop_inner(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            self.stop.store(true, Ordering::Release);
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(feature = "thread")]
+impl Drop for FlusherHandle {
+    fn drop(&mut self) {
+        self.stop_inner();
+    }
+}
+
+/// Builds the key used to compare consecutive logs.
+fn dedup_key(log: &Log, dedup: DedupKey) -> String {
+    if dedup == DedupKey::HeaderCode {
+        let code = log.content.blocks.iter().find_map(|block| match block {
+            LogBlock::Header(header) if !header.code.is_empty() => Some(&header.code),
+            _ => None,
+        });
+
+        if let Some(code) = code {
+            return format!("{}:{}", log.level.tag(), code);
+        }
+    }
+
+    format!("{}\n{}", log.level.tag(), log.to_plain_text())
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::HeaderBlock;
+
+    #[test]
+    fn test_push_and_drain() {
+        let collector = LogCollector::with_capacity_limit(3);
+
+        let threads: Vec<_> = (0..2)
+            .map(|i| {
+                let collector = collector.clone();
+                std::thread::spawn(move || {
+                    collector
+                        .push(Log::info().add_block(TextBlock::new_plain(format!("Log {}", i))))
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            assert!(thread.join().unwrap());
+        }
+
+        assert_eq!(collector.len(), 2);
+        assert!(collector.push(Log::info()));
+        assert!(!collector.push(Log::info()));
+        assert_eq!(collector.dropped_count(), 1);
+
+        assert_eq!(collector.drain().len(), 3);
+        assert!(collector.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "thread")]
+    fn test_flusher() {
+        use crate::sinks::RingBufferSink;
+
+        let collector = LogCollector::new();
+        let sink = Arc::new(RingBufferSink::new(10));
+        let handle = collector
+            .spawn_flusher(Arc::clone(&sink), Duration::from_secs(60))
+            .unwrap();
+
+        collector.push(Log::info().add_block(TextBlock::new_plain("Log 1")));
+        collector.push(Log::info().add_block(TextBlock::new_plain("Log 2")));
+        handle.stop();
+
+        assert!(collector.is_empty());
+        assert_eq!(sink.len(), 2);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let collector = LogCollector::new().dedup(DedupKey::Content);
+
+        for _ in 0..3 {
+            collector.push(Log::warn().add_block(TextBlock::new_plain("Repeated")));
+        }
+
+        collector.push(Log::warn().add_block(TextBlock::new_plain("Other")));
+        collector.push(Log::warn().add_block(TextBlock::new_plain("Other")));
+
+        let logs = collector.drain();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].to_plain_text(), "Repeated\n(repeated 3 times)");
+        assert_eq!(logs[1].to_plain_text(), "Other\n(repeated 2 times)");
+    }
+
+    #[test]
+    fn test_dedup_header_code() {
+        let collector = LogCollector::new().dedup(DedupKey::HeaderCode);
+
+        collector.push(Log::error().add_block(HeaderBlock::new().code("E01").title("First")));
+        collector.push(Log::error().add_block(HeaderBlock::new().code("E01").title("Second")));
+        collector.push(Log::error().add_block(HeaderBlock::new().code("E02").title("Third")));
+
+        let logs = collector.drain();
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].to_plain_text().ends_with("(repeated 2 times)"));
+        assert!(!logs[1].to_plain_text().contains("repeated"));
+    }
+}
@@ -0,0 +1,51 @@
+pub use collector::*;
+pub use redacting::*;
+pub use ring_buffer::*;
+#[cfg(all(unix, feature = "syslog"))]
+pub use syslog::*;
+pub use writer::*;
+
+mod collector;
+mod redacting;
+mod ring_buffer;
+#[cfg(all(unix, feature = "syslog"))]
+mod syslog;
+mod writer;
+
+use crate::{Log, PrinterFormat};
+use std::sync::Arc;
+
+/// A destination for logs, e.g. the console, a file or an in-memory buffer.
+///
+/// Sinks are shared between threads by the [Logger](crate::Logger) so they must
+/// synchronize their own state.
+pub trait LogSink: Send + Sync {
+    /// Writes `log` into the sink.
+    fn write_log(&self, log: &Log) -> std::io::Result<()>;
+
+    /// Writes `log` into the sink with the `format` chosen by the [Logger](crate::Logger)
+    /// for the level of the log, see [LoggerBuilder::level_format](crate::LoggerBuilder::level_format).
+    ///
+    /// Sinks that do not print text ignore the format, which is the default behaviour.
+    fn write_log_with_format(&self, log: &Log, format: PrinterFormat) -> std::io::Result<()> {
+        let _ = format;
+        self.write_log(log)
+    }
+}
+
+impl LogSink for RingBufferSink {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.push(log.clone());
+        Ok(())
+    }
+}
+
+impl<T: LogSink + ?Sized> LogSink for Arc<T> {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.as_ref().write_log(log)
+    }
+
+    fn write_log_with_format(&self, log: &Log, format: PrinterFormat) -> std::io::Result<()> {
+        self.as_ref().write_log_with_format(log, format)
+    }
+}
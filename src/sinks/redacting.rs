@@ -0,0 +1,86 @@
+use crate::printer::PrinterFormat;
+use crate::sinks::LogSink;
+use crate::{Log, Redaction};
+
+/// A sink that masks the secrets of the logs with a [Redaction] before forwarding them
+/// to another sink, e.g. to redact only the logs shipped to a remote service.
+#[derive(Debug)]
+pub struct RedactingSink<S: LogSink> {
+    sink: S,
+    redaction: Redaction,
+}
+
+impl<S: LogSink> RedactingSink<S> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [RedactingSink] that forwards the logs redacted by `redaction`
+    /// into `sink`.
+    pub fn new(sink: S, redaction: Redaction) -> Self {
+        Self { sink, redaction }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the redaction applied to the logs.
+    #[inline(always)]
+    pub fn redaction(&self) -> &Redaction {
+        &self.redaction
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Consumes the sink returning the inner one.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    /// Returns a redacted copy of `log`.
+    fn redact<'a>(&self, log: &Log<'a>) -> Log<'a> {
+        let mut log = log.clone();
+        self.redaction.redact_log(&mut log);
+        log
+    }
+}
+
+impl<S: LogSink> LogSink for RedactingSink<S> {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.sink.write_log(&self.redact(log))
+    }
+
+    fn write_log_with_format(&self, log: &Log, format: PrinterFormat) -> std::io::Result<()> {
+        self.sink.write_log_with_format(&self.redact(log), format)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::TextBlock;
+    use crate::sinks::RingBufferSink;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_write_log() {
+        let inner = Arc::new(RingBufferSink::new(10));
+        let sink = RedactingSink::new(inner.clone(), Redaction::new().key("token"));
+        let log = Log::info().add_block(TextBlock::new_plain("token=abc"));
+
+        sink.write_log(&log).unwrap();
+        sink.write_log_with_format(&log, PrinterFormat::Plain)
+            .unwrap();
+
+        assert_eq!(log.to_plain_text(), "token=abc");
+        assert_eq!(
+            inner
+                .snapshot()
+                .iter()
+                .map(|v| v.to_plain_text())
+                .collect::<Vec<_>>(),
+            vec!["token=[REDACTED]", "token=[REDACTED]"]
+        );
+    }
+}
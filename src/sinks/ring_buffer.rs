@@ -0,0 +1,154 @@
+use crate::Log;
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// An in-memory sink that retains the last `capacity` logs, discarding the oldest ones.
+/// It behaves as a flight recorder: logs are kept in memory until something goes wrong
+/// and then they can be dumped into a crash report.
+///
+/// The sink is thread-safe so it can be shared between threads using an [Arc].
+#[derive(Debug)]
+pub struct RingBufferSink {
+    capacity: usize,
+    logs: Mutex<VecDeque<Log<'static>>>,
+}
+
+impl RingBufferSink {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [RingBufferSink] that retains at most `capacity` logs.
+    ///
+    /// # Panics
+    /// This method panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert_ne!(capacity, 0, "The capacity must be greater than 0");
+
+        Self {
+            capacity,
+            logs: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the maximum number of logs retained by the sink.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of logs currently retained by the sink.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns whether the sink does not retain any log.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns a copy of the retained logs, from the oldest to the newest.
+    pub fn snapshot(&self) -> Vec<Log<'static>> {
+        self.lock().iter().cloned().collect()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Pushes a new log into the sink, discarding the oldest one if the sink is full.
+    pub fn push(&self, log: Log) {
+        let log = log.make_owned();
+        let mut logs = self.lock();
+
+        if logs.len() == self.capacity {
+            logs.pop_front();
+        }
+
+        logs.push_back(log);
+    }
+
+    /// Removes all retained logs.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Writes the retained logs as plain text into `writer`, from the oldest to the newest.
+    pub fn dump(&self, writer: &mut impl Write) -> std::io::Result<()> {
+        for log in self.lock().iter() {
+            writeln!(writer, "{}", log.to_plain_text())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Appends the retained logs as plain text into the specified file, creating it
+    /// if it does not exist.
+    pub fn dump_to_file(&self, file: &Path) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+        self.dump(&mut file)
+    }
+
+    /// Installs a panic hook that dumps the retained logs into `file` before
+    /// calling the previously installed hook.
+    pub fn dump_on_panic(self: &Arc<Self>, file: impl Into<PathBuf>) {
+        let sink = Arc::clone(self);
+        let file = file.into();
+        let previous_hook = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            // Errors are ignored because there is nothing to do while panicking.
+            let _ = sink.dump_to_file(&file);
+            previous_hook(info);
+        }));
+    }
+
+    /// Locks the inner buffer, recovering it if a thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Log<'static>>> {
+        self.logs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::TextBlock;
+
+    #[test]
+    fn test_push() {
+        let sink = RingBufferSink::new(2);
+
+        sink.push(Log::info().add_block(TextBlock::new_plain("Log 1")));
+        sink.push(Log::warn().add_block(TextBlock::new_plain("Log 2")));
+        assert_eq!(sink.len(), 2);
+
+        sink.push(Log::error().add_block(TextBlock::new_plain("Log 3")));
+        assert_eq!(sink.len(), 2);
+
+        let logs = sink.snapshot();
+        assert_eq!(logs[0].to_plain_text(), "Log 2");
+        assert_eq!(logs[1].to_plain_text(), "Log 3");
+
+        sink.clear();
+        assert!(sink.is_empty());
+    }
+
+    #[test]
+    fn test_dump() {
+        let sink = RingBufferSink::new(2);
+
+        sink.push(Log::info().add_block(TextBlock::new_plain("Log 1")));
+        sink.push(Log::warn().add_block(TextBlock::new_plain("Log 2\nLine 2")));
+        sink.push(Log::error().add_block(TextBlock::new_plain("Log 3")));
+
+        let mut buffer = Vec::new();
+        sink.dump(&mut buffer).unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Log 2\nLine 2\nLog 3\n");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::sinks::LogSink;
+use crate::{Log, LogLevel};
+use std::borrow::Cow;
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+
+/// The socket of the local syslog daemon.
+const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// The socket of the native protocol of systemd-journald.
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// The `user` facility, used by default.
+const USER_FACILITY: u8 = 1;
+
+/// The protocol used by a [SyslogSink].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SyslogProtocol {
+    /// The BSD syslog protocol, i.e. `<PRI>identifier[pid]: message`, understood by any
+    /// local syslog daemon.
+    Syslog,
+
+    /// The native protocol of systemd-journald, that keeps multiline messages intact and
+    /// stores the priority and identifier as structured fields.
+    Journald,
+}
+
+/// A sink that forwards the logs rendered as plain text to the system log, either a
+/// syslog daemon or systemd-journald, mapping their level to the priority.
+/// See [LogLevel::syslog_severity].
+///
+/// Logs are sent as datagrams, so messages longer than the limit of the socket fail
+/// with an error.
+#[derive(Debug)]
+pub struct SyslogSink {
+    socket: UnixDatagram,
+    protocol: SyslogProtocol,
+    identifier: Cow<'static, str>,
+    facility: u8,
+}
+
+impl SyslogSink {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [SyslogSink] connected to the local syslog daemon, i.e. `/dev/log`.
+    /// The `identifier` is usually the name of the program.
+    pub fn syslog(identifier: impl Into<Cow<'static, str>>) -> std::io::Result<Self> {
+        Self::connect(SYSLOG_SOCKET, SyslogProtocol::Syslog, identifier)
+    }
+
+    /// Creates a new [SyslogSink] connected to systemd-journald through its native protocol.
+    /// The `identifier` is usually the name of the program.
+    pub fn journald(identifier: impl Into<Cow<'static, str>>) -> std::io::Result<Self> {
+        Self::connect(JOURNALD_SOCKET, SyslogProtocol::Journald, identifier)
+    }
+
+    /// Creates a new [SyslogSink] connected to the socket at `path`.
+    pub fn connect(
+        path: impl AsRef<Path>,
+        protocol: SyslogProtocol,
+        identifier: impl Into<Cow<'static, str>>,
+    ) -> std::io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(path)?;
+
+        Ok(Self {
+            socket,
+            protocol,
+            identifier: identifier.into(),
+            facility: USER_FACILITY,
+        })
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the facility code, e.g. 1 for `user`, the default, or 16 to 23 for `local0`
+    /// to `local7`.
+    #[inline(always)]
+    pub fn facility(mut self, facility: u8) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the protocol used to send the logs.
+    #[inline(always)]
+    pub fn protocol(&self) -> SyslogProtocol {
+        self.protocol
+    }
+
+    /// Returns the identifier of the program.
+    #[inline(always)]
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Encodes `log` in the protocol of the sink.
+    fn encode(&self, log: &Log) -> Vec<u8> {
+        let message = log.to_plain_text();
+        let severity = log.level.syslog_severity();
+
+        match self.protocol {
+            SyslogProtocol::Syslog => format!(
+                "<{}>{}[{}]: {}",
+                (self.facility << 3) | severity,
+                self.identifier,
+                std::process::id(),
+                message
+            )
+            .into_bytes(),
+            SyslogProtocol::Journald => {
+                let mut result = Vec::new();
+
+                add_journald_field(&mut result, "MESSAGE", &message);
+                add_journald_field(&mut result, "PRIORITY", &severity.to_string());
+                add_journald_field(&mut result, "SYSLOG_FACILITY", &self.facility.to_string());
+                add_journald_field(&mut result, "SYSLOG_IDENTIFIER", &self.identifier);
+                add_journald_field(&mut result, "SYSLOG_PID", &std::process::id().to_string());
+                result
+            }
+        }
+    }
+}
+
+impl LogSink for SyslogSink {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.socket.send(&self.encode(log))?;
+        Ok(())
+    }
+}
+
+impl LogLevel {
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the syslog severity of the level, from 0 (emergency) to 7 (debug). Custom
+    /// levels get the severity of the highest predefined level they reach, and levels above
+    /// [LogLevel::error] are mapped to critical.
+    pub fn syslog_severity(&self) -> u8 {
+        let level = self.level();
+
+        if level > LogLevel::error().level() {
+            2
+        } else if level >= LogLevel::error().level() {
+            3
+        } else if level >= LogLevel::warn().level() {
+            4
+        } else if level >= LogLevel::info().level() {
+            6
+        } else {
+            7
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Adds a field to a journald message. Values with line breaks are length-prefixed.
+fn add_journald_field(result: &mut Vec<u8>, name: &str, value: &str) {
+    result.extend_from_slice(name.as_bytes());
+
+    if value.contains('\n') {
+        result.push(b'\n');
+        result.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        result.push(b'=');
+    }
+
+    result.extend_from_slice(value.as_bytes());
+    result.push(b'\n');
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{HeaderBlock, TextBlock};
+
+    #[test]
+    fn test_write_log() {
+        let path = std::env::temp_dir().join(format!("doclog-syslog-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let server = UnixDatagram::bind(&path).unwrap();
+        let log = Log::warn()
+            .add_block(HeaderBlock::new().title("Disk"))
+            .add_block(TextBlock::new_plain("Almost full"));
+        let mut buffer = [0; 1024];
+
+        // Syslog.
+        let sink = SyslogSink::connect(&path, SyslogProtocol::Syslog, "app").unwrap();
+        sink.write_log(&log).unwrap();
+
+        let len = server.recv(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&buffer[..len]),
+            format!("<12>app[{}]: WARN Disk\nAlmost full", std::process::id())
+        );
+
+        // Journald.
+        let sink = SyslogSink::connect(&path, SyslogProtocol::Journald, "app")
+            .unwrap()
+            .facility(16);
+        sink.write_log(&log).unwrap();
+
+        let len = server.recv(&mut buffer).unwrap();
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&21u64.to_le_bytes());
+        expected.extend_from_slice(
+            format!(
+                "WARN Disk\nAlmost full\nPRIORITY=4\nSYSLOG_FACILITY=16\nSYSLOG_IDENTIFIER=app\nSYSLOG_PID={}\n",
+                std::process::id()
+            )
+            .as_bytes(),
+        );
+        assert_eq!(&buffer[..len], expected.as_slice());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_syslog_severity() {
+        let fatal = LogLevel::custom("FATAL", "fatal", '‼', yansi::Color::Magenta).with_level(60);
+
+        assert_eq!(LogLevel::trace().syslog_severity(), 7);
+        assert_eq!(LogLevel::debug().syslog_severity(), 7);
+        assert_eq!(LogLevel::info().syslog_severity(), 6);
+        assert_eq!(LogLevel::warn().syslog_severity(), 4);
+        assert_eq!(LogLevel::error().syslog_severity(), 3);
+        assert_eq!(fatal.syslog_severity(), 2);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::printer::{OutputStream, PrinterFormat};
+use crate::sinks::LogSink;
+use crate::Log;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// A sink that writes logs as text into any [Write] implementation, one after another.
+#[derive(Debug)]
+pub struct WriterSink<W: Write + Send> {
+    format: PrinterFormat,
+    writer: Mutex<W>,
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [WriterSink] that writes the logs into `writer` using `format`.
+    pub fn new(writer: W, format: PrinterFormat) -> Self {
+        Self {
+            format,
+            writer: Mutex::new(writer),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the format used to write the logs.
+    #[inline(always)]
+    pub fn format(&self) -> PrinterFormat {
+        self.format
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Consumes the sink returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl WriterSink<std::io::Stdout> {
+    /// Creates a new [WriterSink] that writes the logs into the standard output.
+    /// Whether they are styled or plain text is resolved once, using [PrinterFormat::Auto].
+    pub fn stdout() -> Self {
+        let format = PrinterFormat::Auto.resolve_for(OutputStream::Stdout);
+        Self::new(std::io::stdout(), format)
+    }
+}
+
+impl WriterSink<std::io::Stderr> {
+    /// Creates a new [WriterSink] that writes the logs into the standard error.
+    /// Whether they are styled or plain text is resolved once, using [PrinterFormat::Auto].
+    pub fn stderr() -> Self {
+        let format = PrinterFormat::Auto.resolve_for(OutputStream::Stderr);
+        Self::new(std::io::stderr(), format)
+    }
+}
+
+impl WriterSink<std::fs::File> {
+    /// Creates a new [WriterSink] that appends the logs as plain text into the specified
+    /// file, creating it if it does not exist.
+    pub fn file(file: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(file)?;
+        Ok(Self::new(file, PrinterFormat::Plain))
+    }
+}
+
+impl<W: Write + Send> WriterSink<W> {
+    /// Writes `log` using `format`, reusing the text rendered for other sinks, see
+    /// [Log::render_cached].
+    fn write_log_as(&self, log: &Log, format: PrinterFormat) -> std::io::Result<()> {
+        let text = log.render_cached(format);
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let mut writer = BufWriter::with_capacity(text.len() + 1, &mut *writer);
+
+        writeln!(writer, "{text}")?;
+        writer.flush()
+    }
+}
+
+impl<W: Write + Send> LogSink for WriterSink<W> {
+    fn write_log(&self, log: &Log) -> std::io::Result<()> {
+        self.write_log_as(log, self.format)
+    }
+
+    /// The format is only used when the format of the sink is styled, so logs can drop
+    /// their styles but never add them to a plain output, e.g. a file.
+    fn write_log_with_format(&self, log: &Log, format: PrinterFormat) -> std::io::Result<()> {
+        if self.format.is_styled() {
+            self.write_log_as(log, format)
+        } else {
+            self.write_log(log)
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::TextBlock;
+
+    #[test]
+    fn test_write_log() {
+        let sink = WriterSink::new(Vec::new(), PrinterFormat::Plain);
+
+        sink.write_log(&Log::info().add_block(TextBlock::new_plain("Log 1")))
+            .unwrap();
+        sink.write_log(&Log::warn().add_block(TextBlock::new_plain("Log 2")))
+            .unwrap();
+
+        let buffer = sink.into_inner();
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Log 1\nLog 2\n");
+    }
+}
@@ -0,0 +1,194 @@
+use crate::utils::line_index::LineIndex;
+use crate::utils::text::{count_columns, count_multiline_columns};
+use crate::Span;
+use std::ops::Range;
+
+/// Maps the byte offsets of a text to lines and columns, with the same rules used to
+/// render [CodeBlock](crate::blocks::CodeBlock)s.
+///
+/// Lines and columns are 1-based. Columns are measured in chars or, when the
+/// `unicode-segmentation` feature is enabled, in grapheme clusters, and `\r\n` terminators
+/// count as a single column.
+///
+/// # Examples
+/// ```
+/// use doclog::SourceMap;
+///
+/// let map = SourceMap::new("let a = 1;\nlet b = a;");
+///
+/// assert_eq!(map.position(19), (2, 9));
+/// assert_eq!(map.span_lines(8..15).count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SourceMap<'a> {
+    text: &'a str,
+    index: LineIndex,
+}
+
+impl<'a> SourceMap<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds the [SourceMap] of a text.
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            index: LineIndex::new(text),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the mapped text.
+    #[inline(always)]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Returns the number of lines of the text.
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.index.line_count()
+    }
+
+    /// Returns the byte range of the 1-based `line`, excluding its `\n` or `\r\n`
+    /// terminator.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = self.index.line_start(line)?;
+        let end = match self.index.line_start(line + 1) {
+            Some(next_start) => {
+                let end = next_start - 1;
+
+                if end > start && self.text.as_bytes()[end - 1] == b'\r' {
+                    end - 1
+                } else {
+                    end
+                }
+            }
+            None => self.text.len(),
+        };
+
+        Some(start..end)
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the 1-based line and column of `byte_offset`.
+    ///
+    /// # Panics
+    /// This method panics if `byte_offset` is out of bounds or not at a char boundary.
+    pub fn position(&self, byte_offset: usize) -> (usize, usize) {
+        let cursor = self.index.cursor(self.text, byte_offset);
+        (cursor.line, cursor.column)
+    }
+
+    /// Splits `span` into the parts it covers in each line, excluding the line terminators.
+    /// A span that ends right after a line terminator does not include the next line, and
+    /// an empty span yields a single empty part.
+    ///
+    /// # Panics
+    /// This method panics if the bounds of `span` are out of bounds or not at a char
+    /// boundary.
+    pub fn span_lines(&self, span: impl Span) -> impl Iterator<Item = LineSpan> + '_ {
+        let range = span.range();
+        let start = self.index.cursor(self.text, range.start);
+        let end = self.index.cursor(self.text, range.end.max(range.start));
+        let last_line = if end.line > start.line && end.column == 1 {
+            end.line - 1
+        } else {
+            end.line
+        };
+
+        (start.line..=last_line).map(move |line| {
+            let line_range = self.line_range(line).unwrap();
+            let from = range.start.clamp(line_range.start, line_range.end);
+            let to = range.end.clamp(from, line_range.end);
+            let start_column = count_columns(&self.text[line_range.start..from]) + 1;
+
+            LineSpan {
+                line,
+                bytes: from..to,
+                columns: start_column..start_column + count_multiline_columns(&self.text[from..to]),
+            }
+        })
+    }
+}
+
+/// The part of a span inside a single line, see [SourceMap::span_lines].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LineSpan {
+    /// The 1-based line.
+    pub line: usize,
+
+    /// The byte range inside the text.
+    pub bytes: Range<usize>,
+
+    /// The 1-based column range inside the line, end exclusive.
+    pub columns: Range<usize>,
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let map = SourceMap::new("ab\r\nñc\n");
+
+        assert_eq!(map.line_count(), 3);
+        assert_eq!(map.position(0), (1, 1));
+        assert_eq!(map.position(2), (1, 3));
+        assert_eq!(map.position(4), (2, 1));
+        assert_eq!(map.position(6), (2, 2));
+        assert_eq!(map.position(8), (3, 1));
+        assert_eq!(map.line_range(1), Some(0..2));
+        assert_eq!(map.line_range(2), Some(4..7));
+        assert_eq!(map.line_range(3), Some(8..8));
+        assert_eq!(map.line_range(4), None);
+    }
+
+    #[test]
+    fn test_span_lines() {
+        let map = SourceMap::new("ab\r\nñc\nde");
+
+        assert_eq!(
+            map.span_lines(1..9).collect::<Vec<_>>(),
+            vec![
+                LineSpan {
+                    line: 1,
+                    bytes: 1..2,
+                    columns: 2..3,
+                },
+                LineSpan {
+                    line: 2,
+                    bytes: 4..7,
+                    columns: 1..3,
+                },
+                LineSpan {
+                    line: 3,
+                    bytes: 8..9,
+                    columns: 1..2,
+                },
+            ]
+        );
+
+        // Ends after a line terminator.
+        assert_eq!(
+            map.span_lines(4..8).map(|v| v.line).collect::<Vec<_>>(),
+            vec![2]
+        );
+
+        // Empty.
+        assert_eq!(
+            map.span_lines(6..6).collect::<Vec<_>>(),
+            vec![LineSpan {
+                line: 2,
+                bytes: 6..6,
+                columns: 2..2,
+            }]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+use std::ops::{Range, RangeInclusive};
+
+/// A byte range inside a text, e.g. the span of a token returned by a parser.
+///
+/// It allows highlighting sections of a [CodeBlock](crate::blocks::CodeBlock) directly
+/// from the span types of other libraries without converting them at every call site.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::CodeBlock;
+/// use doclog::Span;
+///
+/// struct Token {
+///     offset: usize,
+///     len: usize,
+/// }
+///
+/// impl Span for Token {
+///     fn start(&self) -> usize {
+///         self.offset
+///     }
+///
+///     fn end(&self) -> usize {
+///         self.offset + self.len
+///     }
+/// }
+///
+/// let block = CodeBlock::new("let a = b").highlight_section(Token { offset: 8, len: 1 }, None);
+/// ```
+pub trait Span {
+    /// Returns the byte offset where the span starts.
+    fn start(&self) -> usize;
+
+    /// Returns the byte offset where the span ends, exclusive.
+    fn end(&self) -> usize;
+
+    /// Returns the span as a [Range].
+    fn range(&self) -> Range<usize> {
+        self.start()..self.end()
+    }
+}
+
+impl Span for Range<usize> {
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl Span for RangeInclusive<usize> {
+    fn start(&self) -> usize {
+        *RangeInclusive::start(self)
+    }
+
+    fn end(&self) -> usize {
+        *RangeInclusive::end(self) + 1
+    }
+}
+
+impl<S: Span + ?Sized> Span for &S {
+    fn start(&self) -> usize {
+        (**self).start()
+    }
+
+    fn end(&self) -> usize {
+        (**self).end()
+    }
+}
+
+#[cfg(feature = "codespan")]
+impl Span for codespan::Span {
+    fn start(&self) -> usize {
+        codespan::Span::start(*self).to_usize()
+    }
+
+    fn end(&self) -> usize {
+        codespan::Span::end(*self).to_usize()
+    }
+}
+
+#[cfg(feature = "miette")]
+impl Span for miette::SourceSpan {
+    fn start(&self) -> usize {
+        self.offset()
+    }
+
+    fn end(&self) -> usize {
+        self.offset() + self.len()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!((2..5).range(), 2..5);
+        assert_eq!((2..=5).range(), 2..6);
+        assert_eq!(Span::range(&&(2..5)), 2..5);
+    }
+
+    #[cfg(feature = "codespan")]
+    #[test]
+    fn test_codespan() {
+        assert_eq!(codespan::Span::new(2, 5).range(), 2..5);
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_miette() {
+        assert_eq!(miette::SourceSpan::from((2, 3)).range(), 2..5);
+    }
+}
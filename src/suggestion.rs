@@ -0,0 +1,196 @@
+use crate::utils::text::escape_json_string;
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::ops::Range;
+
+/// A position inside a text following the LSP convention, i.e. both `line` and `character`
+/// start at 0 and `character` is measured in UTF-16 code units.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextPosition {
+    pub line: usize,
+    pub character: usize,
+}
+
+impl TextPosition {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds the [TextPosition] of `byte_offset` inside `text`.
+    ///
+    /// # Panics
+    /// This method panics if `byte_offset` is not a char boundary of `text`.
+    pub fn from_byte_offset(text: &str, byte_offset: usize) -> Self {
+        let prev_text = &text[..byte_offset];
+        let start_line_offset = match memchr::memrchr(b'\n', prev_text.as_bytes()) {
+            Some(v) => v + 1,
+            None => 0,
+        };
+
+        Self {
+            line: bytecount::count(prev_text.as_bytes(), b'\n'),
+            character: prev_text[start_line_offset..].encode_utf16().count(),
+        }
+    }
+}
+
+/// A fix for a piece of code that replaces a range of it with a new text.
+/// It can be exported as an LSP `CodeAction` so editors can apply it directly.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion<'a> {
+    pub title: Cow<'a, str>,
+    pub uri: Cow<'a, str>,
+    pub byte_range: Range<usize>,
+    pub start: TextPosition,
+    pub end: TextPosition,
+    pub replacement: Cow<'a, str>,
+}
+
+impl<'a> Suggestion<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds a new [Suggestion] that replaces the bytes of `code` in `range` with `replacement`.
+    ///
+    /// # Panics
+    /// This method panics if `range` is not a valid range of `code`.
+    pub fn new(code: &str, range: Range<usize>, replacement: impl Into<Cow<'a, str>>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= code.len(),
+            "The range must be inside the code"
+        );
+
+        Self {
+            title: Cow::Borrowed(""),
+            uri: Cow::Borrowed(""),
+            start: TextPosition::from_byte_offset(code, range.start),
+            end: TextPosition::from_byte_offset(code, range.end),
+            byte_range: range,
+            replacement: replacement.into(),
+        }
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the title that describes the suggestion.
+    pub fn title(mut self, title: impl Into<Cow<'a, str>>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the URI of the document the suggestion applies to.
+    pub fn uri(mut self, uri: impl Into<Cow<'a, str>>) -> Self {
+        self.uri = uri.into();
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Applies the suggestion to `code`, which must be the code it was built from.
+    pub fn apply(&self, code: &str) -> String {
+        let mut result = String::with_capacity(code.len() + self.replacement.len());
+        result.push_str(&code[..self.byte_range.start]);
+        result.push_str(&self.replacement);
+        result.push_str(&code[self.byte_range.end..]);
+        result
+    }
+
+    /// Returns the suggestion as an LSP `TextEdit` JSON object.
+    pub fn to_lsp_text_edit(&self) -> String {
+        format!(
+            "{{\"range\":{{\"start\":{{\"line\":{},\"character\":{}}},\"end\":{{\"line\":{},\"character\":{}}}}},\"newText\":{}}}",
+            self.start.line,
+            self.start.character,
+            self.end.line,
+            self.end.character,
+            escape_json_string(&self.replacement)
+        )
+    }
+
+    /// Returns the suggestion as an LSP quick fix `CodeAction` JSON object.
+    pub fn to_lsp_code_action(&self) -> String {
+        format!(
+            "{{\"title\":{},\"kind\":\"quickfix\",\"edit\":{{\"changes\":{{{}:[{}]}}}}}}",
+            escape_json_string(&self.title),
+            escape_json_string(&self.uri),
+            self.to_lsp_text_edit()
+        )
+    }
+
+    /// Makes this type owned, i.e. changing the lifetime to `'static`.
+    pub fn make_owned(self) -> Suggestion<'static> {
+        Suggestion {
+            title: Cow::Owned(self.title.into_owned()),
+            uri: Cow::Owned(self.uri.into_owned()),
+            byte_range: self.byte_range,
+            start: self.start,
+            end: self.end,
+            replacement: Cow::Owned(self.replacement.into_owned()),
+        }
+    }
+}
+
+/// Returns `suggestions` as a JSON array of LSP quick fix `CodeAction`s.
+pub fn suggestions_to_lsp_code_actions(suggestions: &[Suggestion]) -> String {
+    let mut result = String::from("[");
+
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        if i > 0 {
+            result.push(',');
+        }
+
+        write!(result, "{}", suggestion.to_lsp_code_action()).unwrap();
+    }
+
+    result.push(']');
+    result
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_position() {
+        let code = "let a = 1;\nlet 𝔟 = \"x\";";
+
+        assert_eq!(
+            TextPosition::from_byte_offset(code, 4),
+            TextPosition {
+                line: 0,
+                character: 4
+            }
+        );
+        assert_eq!(
+            TextPosition::from_byte_offset(code, code.rfind('=').unwrap()),
+            TextPosition {
+                line: 1,
+                character: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_lsp_code_action() {
+        let code = "let a = 1;\nlet b = \"x\";";
+        let suggestion = Suggestion::new(code, 15..16, "c")
+            .title("Rename to \"c\"")
+            .uri("file:///main.rs");
+
+        assert_eq!(
+            suggestion.to_lsp_code_action(),
+            "{\"title\":\"Rename to \\\"c\\\"\",\"kind\":\"quickfix\",\"edit\":{\"changes\":{\"file:///main.rs\":[{\"range\":{\"start\":{\"line\":1,\"character\":4},\"end\":{\"line\":1,\"character\":5}},\"newText\":\"c\"}]}}}"
+        );
+        assert_eq!(
+            suggestions_to_lsp_code_actions(&[suggestion.clone(), suggestion.clone()]),
+            format!(
+                "[{},{}]",
+                suggestion.to_lsp_code_action(),
+                suggestion.to_lsp_code_action()
+            )
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::blocks::{LogBlock, TextBlock};
+use crate::{Log, LogContent};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// A reusable log layout with named placeholders, e.g. `{file}` or `{message}`, that can be
+/// instantiated repeatedly with different values.
+///
+/// Placeholders are replaced inside the texts of the blocks: texts of [TextBlock]s and the
+/// titles, locations and messages of the other blocks. A [TextBlock] whose only content is a
+/// placeholder can also be replaced by a whole block, see [TemplateValues::block].
+/// Placeholders without a value are kept as they are.
+///
+/// ```
+/// use doclog::blocks::{CodeBlock, HeaderBlock, TextBlock};
+/// use doclog::{Log, LogTemplate, TemplateValues};
+///
+/// let template = LogTemplate::new(
+///     Log::error()
+///         .add_block(HeaderBlock::new().title("{message}").location("{file}"))
+///         .add_block(TextBlock::new_plain("{code_span}")),
+/// );
+///
+/// let log = template.instantiate(
+///     &TemplateValues::new()
+///         .text("message", "Invalid variable")
+///         .text("file", "/lib.rs")
+///         .block("code_span", CodeBlock::new("let a = 3;").highlight_section(4..5, None)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogTemplate<'a> {
+    log: Log<'a>,
+}
+
+/// The values used to instantiate a [LogTemplate].
+#[derive(Default, Debug, Clone)]
+pub struct TemplateValues<'a> {
+    values: HashMap<Cow<'a, str>, TemplateValue<'a>>,
+}
+
+#[derive(Debug, Clone)]
+enum TemplateValue<'a> {
+    Text(Cow<'a, str>),
+    Block(Box<LogBlock<'a>>),
+}
+
+impl<'a> LogTemplate<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [LogTemplate] from a log that contains placeholders.
+    pub fn new(log: Log<'a>) -> Self {
+        Self { log }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the log with the placeholders.
+    #[inline(always)]
+    pub fn log(&self) -> &Log<'a> {
+        &self.log
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds a new log replacing the placeholders with `values`.
+    pub fn instantiate(&self, values: &TemplateValues<'a>) -> Log<'a> {
+        values.fill_log(&self.log)
+    }
+}
+
+impl<'a> TemplateValues<'a> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new empty [TemplateValues].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the text that replaces the placeholder `{name}`.
+    pub fn text(mut self, name: impl Into<Cow<'a, str>>, text: impl Into<Cow<'a, str>>) -> Self {
+        self.values
+            .insert(name.into(), TemplateValue::Text(text.into()));
+        self
+    }
+
+    /// Sets the block that replaces the [TextBlock]s whose only content is
+    /// the placeholder `{name}`.
+    pub fn block(mut self, name: impl Into<Cow<'a, str>>, block: impl Into<LogBlock<'a>>) -> Self {
+        self.values
+            .insert(name.into(), TemplateValue::Block(Box::new(block.into())));
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    fn fill_log(&self, log: &Log<'a>) -> Log<'a> {
+        Log {
+            level: log.level,
+            content: self.fill_content(&log.content),
+            cause: log.cause.as_ref().map(|v| Box::new(self.fill_log(v))),
+            suggestions: log.suggestions.clone(),
+            fields: log.fields.clone(),
+            max_lines: log.max_lines,
+            render_cache: Default::default(),
+        }
+    }
+
+    fn fill_content(&self, content: &LogContent<'a>) -> LogContent<'a> {
+        LogContent {
+            blocks: content.blocks.iter().map(|v| self.fill_block(v)).collect(),
+            ids: content.ids.clone(),
+        }
+    }
+
+    fn fill_block(&self, block: &LogBlock<'a>) -> LogBlock<'a> {
+        match block {
+            LogBlock::Text(v) => {
+                let placeholder = v
+                    .to_string()
+                    .strip_prefix('{')
+                    .and_then(|v| v.strip_suffix('}'))
+                    .and_then(|v| self.values.get(v))
+                    .cloned();
+
+                match placeholder {
+                    Some(TemplateValue::Block(block)) => *block,
+                    _ => LogBlock::Text(self.fill_text(v)),
+                }
+            }
+            LogBlock::Prefix(v) => {
+                let mut v = v.clone();
+                v.prefix = self.fill_text(&v.prefix);
+                v.middle_prefix = v.middle_prefix.as_ref().map(|v| self.fill_text(v));
+                v.last_prefix = v.last_prefix.as_ref().map(|v| self.fill_text(v));
+                v.content = Box::new(self.fill_content(&v.content));
+                LogBlock::Prefix(v)
+            }
+            LogBlock::Header(v) => {
+                let mut v = v.clone();
+                v.title = self.fill_text(&v.title);
+                v.location = self.fill_text(&v.location);
+                v.extra_messages = v.extra_messages.iter().map(|v| self.fill_text(v)).collect();
+                LogBlock::Header(v)
+            }
+            LogBlock::Note(v) => {
+                let mut v = v.clone();
+                v.text = self.fill_text(&v.text);
+                LogBlock::Note(v)
+            }
+            LogBlock::Code(v) => {
+                let mut v = v.clone();
+                v.title = self.fill_text(&v.title);
+                v.file_path = self.fill_text(&v.file_path);
+                v.final_message = self.fill_text(&v.final_message);
+                LogBlock::Code(v)
+            }
+            LogBlock::Steps(v) => {
+                let mut v = v.clone();
+                v.title = self.fill_text(&v.title);
+                v.final_message = self.fill_text(&v.final_message);
+                v.steps = Box::new(self.fill_content(&v.steps));
+                LogBlock::Steps(v)
+            }
+            LogBlock::KeyValue(v) => {
+                let mut v = v.clone();
+                v.entries = v
+                    .entries
+                    .iter()
+                    .map(|(key, value)| (self.fill_text(key), self.fill_text(value)))
+                    .collect();
+                LogBlock::KeyValue(v)
+            }
+            _ => block.clone(),
+        }
+    }
+
+    fn fill_text(&self, text: &TextBlock<'a>) -> TextBlock<'a> {
+        let mut text = text.clone();
+
+        for section in &mut text.sections {
+            if let Some(filled) = self.fill_str(&section.text) {
+                section.text = Cow::Owned(filled);
+            }
+        }
+
+        text
+    }
+
+    /// Replaces the placeholders of `text` returning `None` if there is nothing to replace.
+    fn fill_str(&self, text: &str) -> Option<String> {
+        let mut result = String::new();
+        let mut last_index = 0;
+
+        for (start, _) in text.match_indices('{') {
+            if start < last_index {
+                continue;
+            }
+
+            let Some(length) = text[start + 1..].find('}') else {
+                break;
+            };
+            let name = &text[start + 1..start + 1 + length];
+
+            if let Some(TemplateValue::Text(value)) = self.values.get(name) {
+                result.push_str(&text[last_index..start]);
+                result.push_str(value);
+                last_index = start + length + 2;
+            }
+        }
+
+        if last_index == 0 {
+            return None;
+        }
+
+        result.push_str(&text[last_index..]);
+        Some(result)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{CodeBlock, NoteBlock};
+
+    #[test]
+    fn test_instantiate() {
+        let template = LogTemplate::new(
+            Log::error()
+                .add_block(TextBlock::new_plain("{message} in {file}, {unknown}"))
+                .add_block(TextBlock::new_plain("{code_span}"))
+                .add_block(NoteBlock::new().text("Expected {expected}")),
+        );
+
+        let log = template.instantiate(
+            &TemplateValues::new()
+                .text("message", "Invalid variable")
+                .text("file", "/lib.rs")
+                .text("expected", "a number")
+                .block(
+                    "code_span",
+                    CodeBlock::new("let a = 3;").highlight_section(4..5, None),
+                ),
+        );
+
+        assert_eq!(
+            log.to_plain_text(),
+            "Invalid variable in /lib.rs, {unknown}\n× ╭─\n1 │    let a = 3;\n  │        ^\n  ╰─\n= Expected a number"
+        );
+
+        // The template can be reused.
+        let log = template.instantiate(&TemplateValues::new().text("message", "Other"));
+
+        assert_eq!(
+            log.to_plain_text(),
+            "Other in {file}, {unknown}\n{code_span}\n= Expected {expected}"
+        );
+    }
+}
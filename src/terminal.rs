@@ -0,0 +1,205 @@
+//! Detection of the size of the terminal, requires the `terminal` feature.
+//!
+//! The size is cached per stream for [CACHE_DURATION]. On Unix, applications can call
+//! [listen_resizes] to keep it cached until the process receives a `SIGWINCH` signal,
+//! i.e. until the terminal is resized.
+
+use crate::printer::OutputStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+#[cfg(unix)]
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+/// How long a detected size is cached when resizes are not listened to, see [listen_resizes].
+pub const CACHE_DURATION: Duration = Duration::from_secs(1);
+
+/// The cached size of the standard output and error.
+static CACHE: Mutex<SizeCache> = Mutex::new(SizeCache::new());
+
+/// Whether the terminal has been resized since the sizes were cached.
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// Whether resizes are being listened to, see [listen_resizes].
+static LISTENING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+static LISTEN_RESIZES: Once = Once::new();
+
+/// The size of a terminal, in columns and rows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TerminalSize {
+    pub width: usize,
+    pub height: usize,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct CachedSize {
+    size: Option<TerminalSize>,
+    detected_at: Instant,
+}
+
+/// The sizes of the standard output and error, respectively.
+#[derive(Debug)]
+struct SizeCache([Option<CachedSize>; 2]);
+
+/// Returns the size of the terminal attached to the standard output or, if it is not a
+/// terminal, to the standard error.
+pub fn size() -> Option<TerminalSize> {
+    size_of(OutputStream::Stdout).or_else(|| size_of(OutputStream::Stderr))
+}
+
+/// Returns the width of the terminal in columns, see [size].
+pub fn width() -> Option<usize> {
+    size().map(|v| v.width)
+}
+
+/// Returns the size of the terminal attached to `stream`, or `None` if it is not a terminal.
+pub fn size_of(stream: OutputStream) -> Option<TerminalSize> {
+    let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+
+    if RESIZED.swap(false, Ordering::Relaxed) {
+        cache.clear();
+    }
+
+    cache.get_or_detect(stream, LISTENING.load(Ordering::Relaxed), || {
+        detect_size(stream)
+    })
+}
+
+/// Discards the cached sizes, so they are detected again the next time they are requested.
+pub fn invalidate() {
+    CACHE.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Starts listening to `SIGWINCH` so the sizes stay cached until the terminal is resized,
+/// instead of expiring after [CACHE_DURATION]. Any handler already installed by the
+/// application is chained. It has no effect on other platforms and after the first call.
+///
+/// The handler is process-wide, so it must be called by the application, e.g. at startup.
+#[cfg(unix)]
+pub fn listen_resizes() {
+    LISTEN_RESIZES.call_once(|| {
+        // SAFETY: the action only stores into an atomic, which is async-signal-safe.
+        let result = unsafe {
+            signal_hook_registry::register(libc::SIGWINCH, || {
+                RESIZED.store(true, Ordering::Relaxed)
+            })
+        };
+
+        LISTENING.store(result.is_ok(), Ordering::Relaxed);
+    });
+}
+
+/// Starts listening to the resizes of the terminal. It has no effect on this platform,
+/// where the sizes expire after [CACHE_DURATION].
+#[cfg(not(unix))]
+pub fn listen_resizes() {}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Detects the size of the terminal attached to `stream`.
+fn detect_size(stream: OutputStream) -> Option<TerminalSize> {
+    let (width, height) = match stream {
+        OutputStream::Stdout => terminal_size::terminal_size_of(std::io::stdout()),
+        OutputStream::Stderr => terminal_size::terminal_size_of(std::io::stderr()),
+    }?;
+
+    Some(TerminalSize {
+        width: width.0 as usize,
+        height: height.0 as usize,
+    })
+    .filter(|v| v.width > 0)
+}
+
+impl SizeCache {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    const fn new() -> Self {
+        Self([None, None])
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns the cached size of `stream` or, if it is not cached or it has expired, the
+    /// one returned by `detect`. Sizes never expire while `listening` to the resizes.
+    fn get_or_detect(
+        &mut self,
+        stream: OutputStream,
+        listening: bool,
+        detect: impl FnOnce() -> Option<TerminalSize>,
+    ) -> Option<TerminalSize> {
+        let index = match stream {
+            OutputStream::Stdout => 0,
+            OutputStream::Stderr => 1,
+        };
+
+        match self.0[index] {
+            Some(cached) if listening || cached.detected_at.elapsed() < CACHE_DURATION => {
+                cached.size
+            }
+            _ => {
+                let size = detect();
+
+                self.0[index] = Some(CachedSize {
+                    size,
+                    detected_at: Instant::now(),
+                });
+                size
+            }
+        }
+    }
+
+    /// Removes the cached sizes.
+    fn clear(&mut self) {
+        self.0 = [None, None];
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_cache() {
+        let size = |width| Some(TerminalSize { width, height: 1 });
+        let mut cache = SizeCache::new();
+
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stdout, true, || size(1)),
+            size(1)
+        );
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stdout, true, || size(2)),
+            size(1)
+        );
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stderr, true, || None),
+            None
+        );
+
+        // Resizes clear the cache.
+        cache.clear();
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stdout, true, || size(3)),
+            size(3)
+        );
+
+        // Without listening to the resizes, the sizes expire.
+        cache.0[0].as_mut().unwrap().detected_at -= CACHE_DURATION;
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stdout, true, || size(4)),
+            size(3)
+        );
+        assert_eq!(
+            cache.get_or_detect(OutputStream::Stdout, false, || size(4)),
+            size(4)
+        );
+    }
+}
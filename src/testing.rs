@@ -0,0 +1,246 @@
+//! Helpers to write stable tests over rendered logs.
+//!
+//! Rendered logs can contain parts that change between runs, like the date of a
+//! [HeaderBlock](crate::blocks::HeaderBlock) with [show_date](crate::blocks::HeaderBlock::show_date),
+//! the name of the current thread or absolute paths. [normalize] replaces them with
+//! placeholders so logs can be compared against golden texts.
+
+use crate::Log;
+use std::path::Path;
+
+/// The placeholder of dates, see [normalize].
+pub const DATE_PLACEHOLDER: &str = "[date]";
+
+/// The placeholder of thread names, see [normalize].
+pub const THREAD_PLACEHOLDER: &str = "[thread]";
+
+/// The placeholder of the current directory, see [normalize].
+pub const CWD_PLACEHOLDER: &str = "[cwd]";
+
+/// The environment variable that makes [assert_log_snapshot] overwrite the snapshots
+/// instead of comparing them.
+const UPDATE_SNAPSHOTS_ENV_VAR: &str = "DOCLOG_UPDATE_SNAPSHOTS";
+
+/// The text that precedes the thread name in a [HeaderBlock](crate::blocks::HeaderBlock).
+const THREAD_PREFIX: &str = " in thread ";
+
+/// Replaces the volatile parts of a rendered log with placeholders:
+/// - RFC 3339 dates, e.g. `2024-01-31T10:20:30.123Z`, with [DATE_PLACEHOLDER].
+/// - Thread names, i.e. the rest of the line after ` in thread `, with [THREAD_PLACEHOLDER].
+/// - The current directory, with [CWD_PLACEHOLDER].
+pub fn normalize(text: &str) -> String {
+    let text = match std::env::current_dir() {
+        // The root directory is skipped because it is a prefix of every absolute path.
+        Ok(cwd) if cwd.parent().is_some() => {
+            text.replace(cwd.to_string_lossy().as_ref(), CWD_PLACEHOLDER)
+        }
+        _ => text.to_string(),
+    };
+
+    text.split('\n')
+        .map(normalize_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Normalizes the dates and thread name of a single line.
+fn normalize_line(line: &str) -> String {
+    let line = match line.find(THREAD_PREFIX) {
+        Some(index) => &format!("{}{THREAD_PREFIX}{THREAD_PLACEHOLDER}", &line[..index]),
+        None => line,
+    };
+
+    let bytes = line.as_bytes();
+    let mut result = String::with_capacity(line.len());
+    let mut last_index = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match date_len(&bytes[index..]) {
+            Some(len) => {
+                result.push_str(&line[last_index..index]);
+                result.push_str(DATE_PLACEHOLDER);
+                index += len;
+                last_index = index;
+            }
+            None => index += 1,
+        }
+    }
+
+    result.push_str(&line[last_index..]);
+    result
+}
+
+/// Returns the length of the RFC 3339 date at the start of `bytes`, if any.
+fn date_len(bytes: &[u8]) -> Option<usize> {
+    const PATTERN: &[u8] = b"0000-00-00T00:00:00";
+
+    if bytes.len() < PATTERN.len()
+        || !PATTERN
+            .iter()
+            .zip(bytes)
+            .all(|(pattern, byte)| match pattern {
+                b'0' => byte.is_ascii_digit(),
+                _ => pattern == byte,
+            })
+    {
+        return None;
+    }
+
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|v| v.is_ascii_digit())
+            .count()
+    };
+    let mut len = PATTERN.len();
+
+    // Fraction of seconds.
+    if bytes.get(len) == Some(&b'.') && digits(len + 1) > 0 {
+        len += 1 + digits(len + 1);
+    }
+
+    // Offset.
+    match bytes.get(len) {
+        Some(b'Z') => len += 1,
+        Some(b'+' | b'-')
+            if bytes.len() >= len + 6
+                && digits(len + 1) == 2
+                && bytes[len + 3] == b':'
+                && digits(len + 4) == 2 =>
+        {
+            len += 6
+        }
+        _ => {}
+    }
+
+    Some(len)
+}
+
+/// Asserts that the plain text of a [Log] matches the snapshot stored in `path` once
+/// both are normalized, see [normalize].
+///
+/// The snapshot is created when it does not exist, and overwritten when the
+/// `DOCLOG_UPDATE_SNAPSHOTS` environment variable is set to any value other than `0`.
+///
+/// # Panics
+/// This method panics if the log does not match the snapshot or if the snapshot cannot
+/// be read or written.
+#[track_caller]
+pub fn assert_log_snapshot(log: &Log, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let actual = normalize(&log.to_plain_text());
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some_and(|v| v != "0");
+
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|e| panic!("Cannot create {}: {e}", parent.display()));
+        }
+
+        std::fs::write(path, &actual)
+            .unwrap_or_else(|e| panic!("Cannot write the snapshot {}: {e}", path.display()));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Cannot read the snapshot {}: {e}", path.display()));
+
+    assert!(
+        normalize(&expected) == actual,
+        "The log does not match the snapshot {}, set {UPDATE_SNAPSHOTS_ENV_VAR}=1 to update it\n\nExpected:\n{}\n\nActual:\n{}",
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+/// Asserts that the plain text of a [Log](crate::Log) is equal to the expected text once
+/// both are normalized, see [testing::normalize](crate::testing::normalize).
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::HeaderBlock;
+/// use doclog::Log;
+///
+/// # #[cfg(feature = "thread")]
+/// # {
+/// let log = Log::info().add_block(HeaderBlock::new().title("Started").show_thread(true));
+///
+/// doclog::assert_log_eq!(log, "INFO Started\n ↪ in thread [thread]");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_log_eq {
+    ($log:expr, $expected:expr $(,)?) => {
+        ::std::assert_eq!(
+            $crate::testing::normalize(&$log.to_plain_text()),
+            $crate::testing::normalize(::std::convert::AsRef::<str>::as_ref(&$expected)),
+        )
+    };
+    ($log:expr, $expected:expr, $($arg:tt)+) => {
+        ::std::assert_eq!(
+            $crate::testing::normalize(&$log.to_plain_text()),
+            $crate::testing::normalize(::std::convert::AsRef::<str>::as_ref(&$expected)),
+            $($arg)+
+        )
+    };
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "date", feature = "thread"))]
+    use crate::blocks::HeaderBlock;
+
+    #[test]
+    fn test_normalize() {
+        let cwd = std::env::current_dir().unwrap();
+        let text = format!(
+            "at 2024-01-31T10:20:30.123Z and 2024-01-31T10:20:30+02:00 or 2024-01-31\n\
+             ↪ in thread main\n\
+             in {}/src/main.rs",
+            cwd.display()
+        );
+
+        assert_eq!(
+            normalize(&text),
+            "at [date] and [date] or 2024-01-31\n↪ in thread [thread]\nin [cwd]/src/main.rs"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "date")]
+    fn test_assert_log_eq() {
+        let log = Log::error().add_block(HeaderBlock::new().title("Title").show_date(true));
+
+        assert_log_eq!(log, "ERROR Title\n ↪ at 2000-01-01T00:00:00.000Z");
+        assert_log_eq!(
+            log,
+            String::from("ERROR Title\n ↪ at [date]"),
+            "with message"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "thread")]
+    fn test_assert_log_snapshot() {
+        let path = std::env::temp_dir()
+            .join(format!("doclog-snapshot-{}", std::process::id()))
+            .join("log.txt");
+        let log = Log::info().add_block(HeaderBlock::new().title("Title").show_thread(true));
+
+        assert_log_snapshot(&log, &path);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "INFO Title\n ↪ in thread [thread]"
+        );
+        assert_log_snapshot(&log, &path);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
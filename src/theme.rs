@@ -0,0 +1,335 @@
+use crate::blocks::StepStatus;
+use crate::LogLevel;
+use std::borrow::Cow;
+use std::sync::RwLock;
+use yansi::{Color, Style};
+
+/// The theme used by the printers that do not set one explicitly.
+static GLOBAL_THEME: RwLock<Theme> = RwLock::new(Theme::dark());
+
+/// The palette used to style the logs, so that whole applications can restyle their
+/// output consistently.
+///
+/// Only the predefined levels are recolored by a theme; custom levels keep their own color,
+/// symbol and tag.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Theme {
+    pub trace: Color,
+    pub debug: Color,
+    pub info: Color,
+    pub warn: Color,
+    pub error: Color,
+
+    /// The color of the line numbers and gutters, e.g. of code blocks.
+    pub gutter: Color,
+
+    /// The default secondary color of code blocks.
+    pub secondary: Color,
+
+    /// The style of the borders and decorations, that is combined with the level color.
+    pub border: Style,
+
+    /// The symbols of the predefined levels.
+    pub symbols: LevelSymbols,
+
+    /// The tags of the predefined levels.
+    pub tags: LevelTags,
+}
+
+impl Theme {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates the default theme, designed for dark terminals.
+    pub const fn dark() -> Self {
+        Self {
+            trace: LogLevel::trace().color(),
+            debug: LogLevel::debug().color(),
+            info: LogLevel::info().color(),
+            warn: LogLevel::warn().color(),
+            error: LogLevel::error().color(),
+            gutter: Color::BrightBlack,
+            secondary: Color::Magenta,
+            border: Style::new().bold(),
+            symbols: LevelSymbols::unicode(),
+            tags: LevelTags::english(),
+        }
+    }
+
+    /// Creates a theme with darker colors that remain readable on light terminals.
+    pub const fn light() -> Self {
+        Self {
+            trace: Color::Fixed(244),
+            debug: Color::Fixed(28),
+            info: Color::Fixed(25),
+            warn: Color::Fixed(130),
+            error: Color::Fixed(124),
+            gutter: Color::Fixed(240),
+            secondary: Color::Fixed(90),
+            border: Style::new().bold(),
+            symbols: LevelSymbols::unicode(),
+            tags: LevelTags::english(),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the color of `level` in this theme. Predefined levels whose symbol was
+    /// replaced with [LogLevel::with_symbol] are still recolored.
+    pub fn level_color(&self, level: LogLevel) -> Color {
+        let is = |predefined: LogLevel| is_predefined_level(level, predefined);
+
+        if is(LogLevel::trace()) {
+            self.trace
+        } else if is(LogLevel::debug()) {
+            self.debug
+        } else if is(LogLevel::info()) {
+            self.info
+        } else if is(LogLevel::warn()) {
+            self.warn
+        } else if is(LogLevel::error()) {
+            self.error
+        } else {
+            level.color()
+        }
+    }
+
+    /// Returns the symbol of `level` in this theme. Custom levels and the levels whose
+    /// symbol was replaced with [LogLevel::with_symbol] keep their own symbol.
+    pub fn level_symbol(&self, level: LogLevel) -> char {
+        match level {
+            v if v == LogLevel::trace() => self.symbols.trace,
+            v if v == LogLevel::debug() => self.symbols.debug,
+            v if v == LogLevel::info() => self.symbols.info,
+            v if v == LogLevel::warn() => self.symbols.warn,
+            v if v == LogLevel::error() => self.symbols.error,
+            _ => level.symbol(),
+        }
+    }
+
+    /// Returns the symbol of `status` in this theme.
+    pub fn status_symbol(&self, status: StepStatus) -> char {
+        match status {
+            StepStatus::Pending => self.symbols.pending,
+            StepStatus::Success => self.symbols.success,
+            StepStatus::Failure => self.symbols.failure,
+        }
+    }
+
+    /// Returns the tag that [HeaderBlock](crate::blocks::HeaderBlock)s print for `level` in
+    /// this theme. Custom levels use their own tag in uppercase.
+    pub fn tag_for(&self, level: LogLevel) -> Cow<'static, str> {
+        let is = |predefined: LogLevel| is_predefined_level(level, predefined);
+
+        if is(LogLevel::trace()) {
+            Cow::Borrowed(self.tags.trace)
+        } else if is(LogLevel::debug()) {
+            Cow::Borrowed(self.tags.debug)
+        } else if is(LogLevel::info()) {
+            Cow::Borrowed(self.tags.info)
+        } else if is(LogLevel::warn()) {
+            Cow::Borrowed(self.tags.warn)
+        } else if is(LogLevel::error()) {
+            Cow::Borrowed(self.tags.error)
+        } else {
+            Cow::Owned(level.tag().to_ascii_uppercase())
+        }
+    }
+
+    /// Returns the style of the borders for `level` in this theme.
+    pub fn border_style(&self, level: LogLevel) -> Style {
+        self.border.fg(self.level_color(level))
+    }
+
+    /// Returns the style of the gutters in this theme.
+    pub fn gutter_style(&self) -> Style {
+        Style::new().bold().fg(self.gutter)
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Sets the symbols of the predefined levels and the step statuses.
+    #[inline(always)]
+    pub const fn symbols(mut self, symbols: LevelSymbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Sets the tags of the predefined levels.
+    #[inline(always)]
+    pub const fn tags(mut self, tags: LevelTags) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    // STATIC METHODS ---------------------------------------------------------
+
+    /// Returns the theme used by the printers that do not set one explicitly.
+    pub fn global() -> Theme {
+        *GLOBAL_THEME.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sets the theme used by the printers that do not set one explicitly.
+    pub fn set_global(theme: Theme) {
+        *GLOBAL_THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// The symbols of the predefined levels and the step statuses in a [Theme].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LevelSymbols {
+    pub trace: char,
+    pub debug: char,
+    pub info: char,
+    pub warn: char,
+    pub error: char,
+
+    /// The symbols of the [StepStatus]es.
+    pub pending: char,
+    pub success: char,
+    pub failure: char,
+}
+
+impl LevelSymbols {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates the default symbols, i.e. those of the predefined levels.
+    pub const fn unicode() -> Self {
+        Self {
+            trace: LogLevel::trace().symbol(),
+            debug: LogLevel::debug().symbol(),
+            info: LogLevel::info().symbol(),
+            warn: LogLevel::warn().symbol(),
+            error: LogLevel::error().symbol(),
+            pending: StepStatus::Pending.symbol(),
+            success: StepStatus::Success.symbol(),
+            failure: StepStatus::Failure.symbol(),
+        }
+    }
+
+    /// Creates ASCII-only symbols for the terminals that cannot render the default ones,
+    /// e.g. some CI environments.
+    pub const fn ascii() -> Self {
+        Self {
+            trace: '*',
+            debug: '*',
+            info: '*',
+            warn: '!',
+            error: 'x',
+            pending: 'o',
+            success: '+',
+            failure: 'x',
+        }
+    }
+}
+
+impl Default for LevelSymbols {
+    fn default() -> Self {
+        Self::unicode()
+    }
+}
+
+/// The tags of the predefined levels in a [Theme], printed as is by
+/// [HeaderBlock](crate::blocks::HeaderBlock)s, e.g. to translate them or to use in-house
+/// names like `NOTICE`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LevelTags {
+    pub trace: &'static str,
+    pub debug: &'static str,
+    pub info: &'static str,
+    pub warn: &'static str,
+    pub error: &'static str,
+}
+
+impl LevelTags {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates the default tags, i.e. the tags of the predefined levels in uppercase.
+    pub const fn english() -> Self {
+        Self {
+            trace: "TRACE",
+            debug: "DEBUG",
+            info: "INFO",
+            warn: "WARN",
+            error: "ERROR",
+        }
+    }
+}
+
+impl Default for LevelTags {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// Returns whether `level` is the `predefined` one, ignoring the symbol replaced with
+/// [LogLevel::with_symbol].
+fn is_predefined_level(level: LogLevel, predefined: LogLevel) -> bool {
+    level.with_symbol(predefined.symbol()) == predefined
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_color() {
+        let theme = Theme::light();
+        let custom = LogLevel::new(60, Color::Cyan, "fatal", '×');
+
+        assert_eq!(theme.level_color(LogLevel::warn()), Color::Fixed(130));
+        assert_eq!(theme.level_color(custom), Color::Cyan);
+        assert_eq!(
+            Theme::dark().level_color(LogLevel::error()),
+            LogLevel::error().color()
+        );
+        assert_eq!(
+            theme.level_color(LogLevel::warn().with_symbol('!')),
+            Color::Fixed(130)
+        );
+    }
+
+    #[test]
+    fn test_tag_for() {
+        let theme = Theme::dark().tags(LevelTags {
+            info: "NOTICE",
+            error: "FEHLER",
+            ..LevelTags::english()
+        });
+        let custom = LogLevel::new(60, Color::Cyan, "fatal", '×');
+
+        assert_eq!(Theme::dark().tag_for(LogLevel::warn()), "WARN");
+        assert_eq!(theme.tag_for(LogLevel::info()), "NOTICE");
+        assert_eq!(theme.tag_for(LogLevel::error().with_symbol('x')), "FEHLER");
+        assert_eq!(theme.tag_for(custom), "FATAL");
+    }
+
+    #[test]
+    fn test_level_symbol() {
+        let theme = Theme::dark().symbols(LevelSymbols::ascii());
+        let custom = LogLevel::new(60, Color::Cyan, "fatal", '‼');
+
+        assert_eq!(Theme::dark().level_symbol(LogLevel::error()), '×');
+        assert_eq!(theme.level_symbol(LogLevel::info()), '*');
+        assert_eq!(theme.level_symbol(LogLevel::warn()), '!');
+        assert_eq!(theme.level_symbol(LogLevel::error()), 'x');
+        assert_eq!(theme.level_symbol(LogLevel::error().with_symbol('E')), 'E');
+        assert_eq!(theme.level_symbol(custom), '‼');
+        assert_eq!(Theme::dark().status_symbol(StepStatus::Success), '✓');
+        assert_eq!(theme.status_symbol(StepStatus::Success), '+');
+        assert_eq!(theme.status_symbol(StepStatus::Failure), 'x');
+    }
+}
@@ -1,6 +1,11 @@
+use crate::utils::text::{count_columns, count_multiline_columns};
 use std::ops::Add;
 
 /// A specific position in a text.
+///
+/// `char_offset` and `column` are measured in chars or, when the `unicode-segmentation`
+/// feature is enabled, in grapheme clusters, so they can be used for width computations.
+/// `byte_offset` is always measured in bytes.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Cursor {
     pub byte_offset: usize,
@@ -13,6 +18,7 @@ impl Cursor {
     // CONSTRUCTORS -----------------------------------------------------------
 
     /// Builds the [Cursor] from a byte offset.
+    #[cfg(test)]
     pub fn from_byte_offset(text: &str, byte_offset: usize) -> Cursor {
         let prev_text = &text[..byte_offset];
         let start_line_offset = match memchr::memrchr(b'\n', prev_text.as_bytes()) {
@@ -22,9 +28,9 @@ impl Cursor {
 
         Cursor {
             byte_offset,
-            char_offset: bytecount::num_chars(prev_text.as_bytes()),
+            char_offset: count_multiline_columns(prev_text),
             line: bytecount::count(prev_text.as_bytes(), b'\n') + 1,
-            column: bytecount::num_chars(prev_text[start_line_offset..].as_bytes()) + 1,
+            column: count_columns(&prev_text[start_line_offset..]) + 1,
         }
     }
 
@@ -45,10 +51,9 @@ impl Cursor {
 
             Cursor {
                 byte_offset,
-                char_offset: cursor.char_offset
-                    + bytecount::num_chars(slice_from_cursor.as_bytes()),
+                char_offset: cursor.char_offset + count_multiline_columns(slice_from_cursor),
                 line: cursor.line + bytecount::count(slice_from_cursor.as_bytes(), b'\n'),
-                column: bytecount::num_chars(prev_text[start_line_offset..].as_bytes()) + 1,
+                column: count_columns(&prev_text[start_line_offset..]) + 1,
             }
         } else {
             let slice_to_cursor = &text[byte_offset..cursor.byte_offset];
@@ -59,9 +64,9 @@ impl Cursor {
 
             Cursor {
                 byte_offset,
-                char_offset: cursor.char_offset - bytecount::num_chars(slice_to_cursor.as_bytes()),
+                char_offset: cursor.char_offset - count_multiline_columns(slice_to_cursor),
                 line: cursor.line - bytecount::count(slice_to_cursor.as_bytes(), b'\n'),
-                column: bytecount::num_chars(prev_text[start_line_offset..].as_bytes()) + 1,
+                column: count_columns(&prev_text[start_line_offset..]) + 1,
             }
         }
     }
@@ -126,7 +131,7 @@ impl Cursor {
 
     /// Gets the cursor at the start of the next line.
     pub fn next_start_line_cursor(&self, text: &str) -> Option<Cursor> {
-        let line_start_offset = line_end_offset(text, self.byte_offset).add(1);
+        let line_start_offset = line_terminator_offset(text, self.byte_offset).add(1);
 
         if line_start_offset > text.len() {
             return None;
@@ -138,20 +143,19 @@ impl Cursor {
         ))
     }
 
-    /// Gets the cursor at the end of the line.
+    /// Gets the cursor at the end of the line, before its `\n` or `\r\n` terminator.
     pub fn end_line_cursor(&self, text: &str) -> Cursor {
         let line_end_offset = line_end_offset(text, self.byte_offset);
         Self::from_byte_offset_and_cursor(text, line_end_offset, self)
     }
 
     /// Gets the content from the start of line.
-    #[cfg(test)]
     pub fn slice_from_line_start<'a>(&self, text: &'a str) -> &'a str {
         let line_start_offset = line_start_offset(text, self.byte_offset);
         &text[line_start_offset..self.byte_offset]
     }
 
-    /// Gets the content to the end of line.
+    /// Gets the content to the end of line, excluding its `\n` or `\r\n` terminator.
     pub fn slice_to_line_end<'a>(&self, text: &'a str) -> &'a str {
         let line_end_offset = line_end_offset(text, self.byte_offset);
         &text[self.byte_offset..line_end_offset]
@@ -180,8 +184,20 @@ fn line_start_offset(text: &str, byte_offset: usize) -> usize {
     }
 }
 
-/// Gets the byte_offset at the end of the line.
+/// Gets the byte_offset at the end of the line, excluding the `\r` of a `\r\n` terminator.
 fn line_end_offset(text: &str, byte_offset: usize) -> usize {
+    let end = line_terminator_offset(text, byte_offset);
+
+    if end > byte_offset && end < text.len() && text.as_bytes()[end - 1] == b'\r' {
+        end - 1
+    } else {
+        end
+    }
+}
+
+/// Gets the byte_offset of the `\n` that ends the line, or the length of the text if it is
+/// the last line.
+fn line_terminator_offset(text: &str, byte_offset: usize) -> usize {
     let next_text = &text[byte_offset..];
     byte_offset
         + match memchr::memchr(b'\n', next_text.as_bytes()) {
@@ -271,6 +287,33 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "unicode-segmentation")]
+    #[test]
+    fn test_from_byte_offset_graphemes() {
+        let content = "cafe\u{301}\ne\u{301}e\u{301}x";
+        let cursor = Cursor::from_byte_offset(content, content.len() - 1);
+
+        assert_eq!(
+            cursor,
+            Cursor {
+                byte_offset: content.len() - 1,
+                char_offset: 7,
+                line: 2,
+                column: 3,
+            }
+        );
+
+        assert_eq!(
+            Cursor::from_byte_offset_and_cursor(content, 7, &cursor),
+            Cursor {
+                byte_offset: 7,
+                char_offset: 5,
+                line: 2,
+                column: 1,
+            }
+        );
+    }
+
     #[test]
     fn test_from_byte_offset_and_cursor() {
         let content = "This\nis\n- メカジキ - a\ntest";
@@ -415,6 +458,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_crlf() {
+        let content = "ab\r\ncd";
+        let cursor = Cursor::from_byte_offset(content, 1);
+
+        assert_eq!(cursor.slice_to_line_end(content), "b");
+        assert_eq!(cursor.end_line_cursor(content).byte_offset, 2);
+        assert_eq!(
+            cursor.next_start_line_cursor(content),
+            Some(Cursor {
+                byte_offset: 4,
+                char_offset: 3,
+                line: 2,
+                column: 1,
+            })
+        );
+    }
+
     #[test]
     fn test_slice() {
         let content = "This\nis\n- メカジキ - a\ntest";
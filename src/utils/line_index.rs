@@ -0,0 +1,112 @@
+use crate::utils::cursor::Cursor;
+use crate::utils::text::{count_columns, count_multiline_columns};
+use std::fmt::Debug;
+
+/// The precomputed offsets of the lines of a text, so [Cursor]s can be built without
+/// rescanning the text from its start.
+#[derive(Clone, Eq, PartialEq)]
+pub struct LineIndex {
+    /// The byte offset of the start of each line.
+    line_starts: Vec<usize>,
+    /// The char offset of the start of each line, measured like [count_multiline_columns].
+    char_offsets: Vec<usize>,
+}
+
+impl LineIndex {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Builds the [LineIndex] of a text.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut char_offsets = vec![0];
+        let mut char_offset = 0;
+
+        for new_line in memchr::memchr_iter(b'\n', text.as_bytes()) {
+            let line_start = new_line + 1;
+
+            char_offset += count_multiline_columns(&text[*line_starts.last().unwrap()..line_start]);
+            line_starts.push(line_start);
+            char_offsets.push(char_offset);
+        }
+
+        Self {
+            line_starts,
+            char_offsets,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the number of lines of the text.
+    #[inline(always)]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the byte offset of the start of the 1-based `line`.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.line_starts.get(line.checked_sub(1)?).copied()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Builds the [Cursor] of a byte offset of the indexed `text`.
+    pub fn cursor(&self, text: &str, byte_offset: usize) -> Cursor {
+        let line_index = self
+            .line_starts
+            .partition_point(|line_start| *line_start <= byte_offset)
+            - 1;
+        let column = count_columns(&text[self.line_starts[line_index]..byte_offset]);
+
+        Cursor {
+            byte_offset,
+            char_offset: self.char_offsets[line_index] + column,
+            line: line_index + 1,
+            column: column + 1,
+        }
+    }
+}
+
+impl Debug for LineIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LineIndex")
+            .field("line_count", &self.line_count())
+            .finish()
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor() {
+        let text = "Lorem\n\nipsum ñ\n dolor\n";
+        let index = LineIndex::new(text);
+
+        assert_eq!(index.line_count(), 5);
+
+        for byte_offset in (0..=text.len()).filter(|v| text.is_char_boundary(*v)) {
+            assert_eq!(
+                index.cursor(text, byte_offset),
+                Cursor::from_byte_offset(text, byte_offset),
+                "Incorrect cursor at {byte_offset}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_line_start() {
+        let index = LineIndex::new("a\nbc\n");
+
+        assert_eq!(index.line_start(0), None);
+        assert_eq!(index.line_start(1), Some(0));
+        assert_eq!(index.line_start(2), Some(2));
+        assert_eq!(index.line_start(3), Some(5));
+        assert_eq!(index.line_start(4), None);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::blocks::TextSection;
+use smallvec::SmallVec;
+use std::borrow::Cow;
+use yansi::{Color, Style};
+
+/// The text that replaces the markers of bullet list items.
+const BULLET: &str = "• ";
+
+/// Parses a minimal subset of markdown into styled text sections: bold `**text**`,
+/// italics `*text*` or `_text_`, inline code `` `code` `` and bullet lists starting
+/// with `- ` or `* `. Markers without their closing pair are kept as plain text.
+pub fn parse_markdown(text: &str) -> SmallVec<[TextSection<'_>; 3]> {
+    let mut sections = SmallVec::new();
+
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            push_section(&mut sections, "\n", Style::new());
+        }
+
+        parse_line(line, &mut sections);
+    }
+
+    sections
+}
+
+/// Parses a single line of markdown.
+fn parse_line<'a>(line: &'a str, sections: &mut SmallVec<[TextSection<'a>; 3]>) {
+    // Bullet lists.
+    let content_start = line.len() - line.trim_start_matches(' ').len();
+    let line = match &line[content_start..] {
+        v if v.starts_with("- ") || v.starts_with("* ") => {
+            push_section(sections, &line[..content_start], Style::new());
+            push_section(sections, BULLET, Style::new());
+            &line[content_start + 2..]
+        }
+        _ => line,
+    };
+
+    // Inline styles.
+    let bytes = line.as_bytes();
+    let mut bold = false;
+    let mut italic: Option<u8> = None;
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let style = build_style(bold, italic.is_some());
+
+        match bytes[i] {
+            b'`' => {
+                if let Some(end) = line[i + 1..].find('`') {
+                    push_section(sections, &line[start..i], style);
+                    push_section(sections, &line[i + 1..i + 1 + end], style.fg(Color::Cyan));
+                    i += end + 2;
+                    start = i;
+                    continue;
+                }
+            }
+            b'*' if line[i..].starts_with("**") => {
+                if bold || line[i + 2..].contains("**") {
+                    push_section(sections, &line[start..i], style);
+                    bold = !bold;
+                    start = i + 2;
+                }
+
+                i += 2;
+                continue;
+            }
+            marker @ (b'*' | b'_') => {
+                let is_boundary = |index: Option<usize>| {
+                    index
+                        .and_then(|v| bytes.get(v))
+                        .is_none_or(|v| !v.is_ascii_alphanumeric())
+                };
+                let toggles = match italic {
+                    Some(opener) => {
+                        opener == marker
+                            && is_boundary(Some(i + 1))
+                            && !bytes[i - 1].is_ascii_whitespace()
+                    }
+                    None => {
+                        is_boundary(i.checked_sub(1))
+                            && bytes.get(i + 1).is_some_and(|v| !v.is_ascii_whitespace())
+                            && line[i + 1..].contains(marker as char)
+                    }
+                };
+
+                if toggles {
+                    push_section(sections, &line[start..i], style);
+                    italic = match italic {
+                        Some(_) => None,
+                        None => Some(marker),
+                    };
+                    i += 1;
+                    start = i;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    push_section(
+        sections,
+        &line[start..],
+        build_style(bold, italic.is_some()),
+    );
+}
+
+fn build_style(bold: bool, italic: bool) -> Style {
+    let mut style = Style::new();
+
+    if bold {
+        style = style.bold();
+    }
+
+    if italic {
+        style = style.italic();
+    }
+
+    style
+}
+
+fn push_section<'a>(sections: &mut SmallVec<[TextSection<'a>; 3]>, text: &'a str, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+
+    sections.push(TextSection {
+        text: Cow::Borrowed(text),
+        style,
+    });
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_pairs<'a>(sections: &'a [TextSection<'a>]) -> Vec<(&'a str, Style)> {
+        sections
+            .iter()
+            .map(|v| (v.text.as_ref(), v.style))
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        let sections = parse_markdown("a **bold** and *italic* or _it_ with `code` in snake_case");
+
+        assert_eq!(
+            to_pairs(&sections),
+            vec![
+                ("a ", Style::new()),
+                ("bold", Style::new().bold()),
+                (" and ", Style::new()),
+                ("italic", Style::new().italic()),
+                (" or ", Style::new()),
+                ("it", Style::new().italic()),
+                (" with ", Style::new()),
+                ("code", Style::new().fg(Color::Cyan)),
+                (" in snake_case", Style::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_markers() {
+        let sections = parse_markdown("2 * 3 = **6 and `x");
+
+        assert_eq!(
+            to_pairs(&sections),
+            vec![("2 * 3 = **6 and `x", Style::new())]
+        );
+    }
+
+    #[test]
+    fn test_bullet_lists() {
+        let sections = parse_markdown("List:\n- one\n  * **two**");
+
+        assert_eq!(
+            to_pairs(&sections),
+            vec![
+                ("List:", Style::new()),
+                ("\n", Style::new()),
+                ("• ", Style::new()),
+                ("one", Style::new()),
+                ("\n", Style::new()),
+                ("  ", Style::new()),
+                ("• ", Style::new()),
+                ("two", Style::new().bold()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// A text with some of its ranges replaced by a mask, that maps the byte offsets of the
+/// original text to the masked one.
+pub struct MaskedText<'t> {
+    pub text: Cow<'t, str>,
+    /// The masked ranges of the original text along with the range of the mask that
+    /// replaces them in the new one, sorted and without overlaps.
+    replacements: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl<'t> MaskedText<'t> {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Replaces the `ranges` of `text` with `mask`. Overlapping and adjacent ranges are
+    /// merged and empty ones are ignored.
+    pub fn new(text: &'t str, mut ranges: Vec<Range<usize>>, mask: &str) -> Self {
+        ranges.retain(|v| !v.is_empty());
+
+        if ranges.is_empty() {
+            return Self {
+                text: Cow::Borrowed(text),
+                replacements: Vec::new(),
+            };
+        }
+
+        ranges.sort_by_key(|v| v.start);
+
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut replacements = Vec::with_capacity(merged.len());
+        let mut last = 0;
+
+        for range in merged {
+            result.push_str(&text[last..range.start]);
+
+            let start = result.len();
+            result.push_str(mask);
+            replacements.push((range.clone(), start..result.len()));
+            last = range.end;
+        }
+
+        result.push_str(&text[last..]);
+
+        Self {
+            text: Cow::Owned(result),
+            replacements,
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns whether any range was masked.
+    #[inline(always)]
+    pub fn is_masked(&self) -> bool {
+        !self.replacements.is_empty()
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Maps a byte offset of the original text to the masked one. Offsets inside a masked
+    /// range are moved to the start of its mask or, if `is_end`, to its end, so spans that
+    /// overlap a masked range cover the whole mask.
+    pub fn map_offset(&self, offset: usize, is_end: bool) -> usize {
+        let index = self
+            .replacements
+            .partition_point(|(range, _)| range.end <= offset);
+
+        let Some((range, mask)) = self.replacements.get(index) else {
+            return match self.replacements.last() {
+                Some((range, mask)) => offset - range.end + mask.end,
+                None => offset,
+            };
+        };
+
+        if offset <= range.start {
+            mask.start - (range.start - offset)
+        } else if is_end {
+            mask.end
+        } else {
+            mask.start
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_text() {
+        let masked = MaskedText::new("a=secret b=xy", vec![9..13, 2..8, 11..13, 0..0], "***");
+
+        assert_eq!(masked.text, "a=*** ***");
+        assert_eq!(masked.map_offset(0, false), 0);
+        assert_eq!(masked.map_offset(2, false), 2);
+        assert_eq!(masked.map_offset(4, false), 2);
+        assert_eq!(masked.map_offset(4, true), 5);
+        assert_eq!(masked.map_offset(8, false), 5);
+        assert_eq!(masked.map_offset(13, true), 9);
+
+        let masked = MaskedText::new("no secrets", Vec::new(), "***");
+        assert!(!masked.is_masked());
+        assert_eq!(masked.map_offset(3, true), 3);
+    }
+}
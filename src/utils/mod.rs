@@ -1,3 +1,9 @@
 pub mod cursor;
+pub mod line_index;
+pub mod markdown;
+pub mod mask;
+#[cfg(feature = "serde")]
+pub mod serialization;
 pub mod text;
+pub mod time;
 pub mod whitespaces;
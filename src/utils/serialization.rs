@@ -0,0 +1,276 @@
+//! Serde representations of the types of other crates that do not implement
+//! [Serialize] and [Deserialize], to be used with `#[serde(with = "...")]`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use yansi::{Color, Style};
+
+/// The representation of a [Color].
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ColorRepr {
+    Primary,
+    Fixed(u8),
+    Rgb(u8, u8, u8),
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+/// The representation of the attributes of a [Style].
+#[derive(Serialize, Deserialize, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+enum AttributeRepr {
+    Bold,
+    Dim,
+    Italic,
+    Underline,
+    Blink,
+    RapidBlink,
+    Invert,
+    Conceal,
+    Strike,
+}
+
+/// The representation of a [Style]. Quirks and conditions are not preserved.
+#[derive(Serialize, Deserialize)]
+struct StyleRepr {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    foreground: Option<ColorRepr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<ColorRepr>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attributes: Vec<AttributeRepr>,
+}
+
+impl From<Color> for ColorRepr {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Primary => ColorRepr::Primary,
+            Color::Fixed(v) => ColorRepr::Fixed(v),
+            Color::Rgb(r, g, b) => ColorRepr::Rgb(r, g, b),
+            Color::Black => ColorRepr::Black,
+            Color::Red => ColorRepr::Red,
+            Color::Green => ColorRepr::Green,
+            Color::Yellow => ColorRepr::Yellow,
+            Color::Blue => ColorRepr::Blue,
+            Color::Magenta => ColorRepr::Magenta,
+            Color::Cyan => ColorRepr::Cyan,
+            Color::White => ColorRepr::White,
+            Color::BrightBlack => ColorRepr::BrightBlack,
+            Color::BrightRed => ColorRepr::BrightRed,
+            Color::BrightGreen => ColorRepr::BrightGreen,
+            Color::BrightYellow => ColorRepr::BrightYellow,
+            Color::BrightBlue => ColorRepr::BrightBlue,
+            Color::BrightMagenta => ColorRepr::BrightMagenta,
+            Color::BrightCyan => ColorRepr::BrightCyan,
+            Color::BrightWhite => ColorRepr::BrightWhite,
+        }
+    }
+}
+
+impl From<ColorRepr> for Color {
+    fn from(color: ColorRepr) -> Self {
+        match color {
+            ColorRepr::Primary => Color::Primary,
+            ColorRepr::Fixed(v) => Color::Fixed(v),
+            ColorRepr::Rgb(r, g, b) => Color::Rgb(r, g, b),
+            ColorRepr::Black => Color::Black,
+            ColorRepr::Red => Color::Red,
+            ColorRepr::Green => Color::Green,
+            ColorRepr::Yellow => Color::Yellow,
+            ColorRepr::Blue => Color::Blue,
+            ColorRepr::Magenta => Color::Magenta,
+            ColorRepr::Cyan => Color::Cyan,
+            ColorRepr::White => Color::White,
+            ColorRepr::BrightBlack => Color::BrightBlack,
+            ColorRepr::BrightRed => Color::BrightRed,
+            ColorRepr::BrightGreen => Color::BrightGreen,
+            ColorRepr::BrightYellow => Color::BrightYellow,
+            ColorRepr::BrightBlue => Color::BrightBlue,
+            ColorRepr::BrightMagenta => Color::BrightMagenta,
+            ColorRepr::BrightCyan => Color::BrightCyan,
+            ColorRepr::BrightWhite => Color::BrightWhite,
+        }
+    }
+}
+
+impl AttributeRepr {
+    const ALL: [AttributeRepr; 9] = [
+        AttributeRepr::Bold,
+        AttributeRepr::Dim,
+        AttributeRepr::Italic,
+        AttributeRepr::Underline,
+        AttributeRepr::Blink,
+        AttributeRepr::RapidBlink,
+        AttributeRepr::Invert,
+        AttributeRepr::Conceal,
+        AttributeRepr::Strike,
+    ];
+
+    /// Adds this attribute to `style`.
+    fn apply(self, style: Style) -> Style {
+        match self {
+            AttributeRepr::Bold => style.bold(),
+            AttributeRepr::Dim => style.dim(),
+            AttributeRepr::Italic => style.italic(),
+            AttributeRepr::Underline => style.underline(),
+            AttributeRepr::Blink => style.blink(),
+            AttributeRepr::RapidBlink => style.rapid_blink(),
+            AttributeRepr::Invert => style.invert(),
+            AttributeRepr::Conceal => style.conceal(),
+            AttributeRepr::Strike => style.strike(),
+        }
+    }
+}
+
+impl From<Style> for StyleRepr {
+    fn from(style: Style) -> Self {
+        Self {
+            foreground: style.foreground.map(ColorRepr::from),
+            background: style.background.map(ColorRepr::from),
+            // Styles compare their attributes, so an attribute is set if adding it is a no-op.
+            attributes: AttributeRepr::ALL
+                .into_iter()
+                .filter(|v| v.apply(style) == style)
+                .collect(),
+        }
+    }
+}
+
+impl From<StyleRepr> for Style {
+    fn from(style: StyleRepr) -> Self {
+        let mut result = Style::new();
+        result.foreground = style.foreground.map(Color::from);
+        result.background = style.background.map(Color::from);
+
+        style
+            .attributes
+            .into_iter()
+            .fold(result, |style, attribute| attribute.apply(style))
+    }
+}
+
+/// Serializes a [Style].
+pub mod style {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(style: &Style, serializer: S) -> Result<S::Ok, S::Error> {
+        StyleRepr::from(*style).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Style, D::Error> {
+        StyleRepr::deserialize(deserializer).map(Style::from)
+    }
+}
+
+/// Serializes a [Color].
+pub mod color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        ColorRepr::from(*color).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        ColorRepr::deserialize(deserializer).map(Color::from)
+    }
+}
+
+/// Serializes an optional [Color].
+pub mod option_color {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        color: &Option<Color>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        color.map(ColorRepr::from).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Color>, D::Error> {
+        Option::<ColorRepr>::deserialize(deserializer).map(|v| v.map(Color::from))
+    }
+}
+
+/// Serializes a list of [Color]s.
+pub mod colors {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(colors: &[Color], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(colors.iter().map(|v| ColorRepr::from(*v)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Color>, D::Error> {
+        Vec::<ColorRepr>::deserialize(deserializer)
+            .map(|v| v.into_iter().map(Color::from).collect())
+    }
+}
+
+/// Serializes a list of ranges with a [Style].
+pub mod range_styles {
+    use super::*;
+    use std::ops::Range;
+
+    pub fn serialize<S: Serializer>(
+        styles: &[(Range<usize>, Style)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            styles
+                .iter()
+                .map(|(range, style)| (range.clone(), StyleRepr::from(*style))),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(Range<usize>, Style)>, D::Error> {
+        Vec::<(Range<usize>, StyleRepr)>::deserialize(deserializer).map(|v| {
+            v.into_iter()
+                .map(|(range, style)| (range, Style::from(style)))
+                .collect()
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style() {
+        let style = Style::new()
+            .bold()
+            .italic()
+            .fg(Color::Fixed(102))
+            .bg(Color::Red);
+        let json = serde_json::to_string(&StyleRepr::from(style)).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"foreground":{"fixed":102},"background":"red","attributes":["bold","italic"]}"#
+        );
+
+        let repr: StyleRepr = serde_json::from_str(&json).unwrap();
+        assert_eq!(Style::from(repr), style);
+    }
+}
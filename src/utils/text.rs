@@ -1,6 +1,172 @@
-/// Removes the jump lines of `text`, changing them to spaces.
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Removes the jump lines of `text`, changing them to spaces. `\r\n` is changed to a
+/// single space.
 pub fn remove_jump_lines(text: &str) -> String {
-    text.replace('\n', " ")
+    text.replace("\r\n", " ").replace(['\n', '\r'], " ")
+}
+
+/// Splits `text` into lines like [str::lines] but also accepting lone carriage returns `\r`
+/// as line terminators.
+pub fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+
+        let bytes = rest.as_bytes();
+        let line = match memchr::memchr2(b'\n', b'\r', bytes) {
+            Some(i) => {
+                let terminator_len = if bytes[i] == b'\r' && bytes.get(i + 1) == Some(&b'\n') {
+                    2
+                } else {
+                    1
+                };
+                let line = &rest[..i];
+                rest = &rest[i + terminator_len..];
+                line
+            }
+            None => std::mem::take(&mut rest),
+        };
+
+        Some(line)
+    })
+}
+
+/// Returns whether `text` ends with a line terminator, see [split_lines].
+#[inline(always)]
+pub fn ends_with_line_break(text: &str) -> bool {
+    text.ends_with(['\n', '\r'])
+}
+
+/// Replaces the lone carriage returns `\r` of `text`, i.e. those not followed by `\n`,
+/// with `\n` so every line ends with either `\n` or `\r\n`. Byte offsets are preserved.
+pub fn replace_lone_carriage_returns(text: Cow<str>) -> Cow<str> {
+    let bytes = text.as_bytes();
+    let lone_offsets: Vec<usize> = memchr::memchr_iter(b'\r', bytes)
+        .filter(|i| bytes.get(i + 1) != Some(&b'\n'))
+        .collect();
+
+    if lone_offsets.is_empty() {
+        return text;
+    }
+
+    let mut bytes = text.into_owned().into_bytes();
+
+    for i in lone_offsets {
+        bytes[i] = b'\n';
+    }
+
+    // Only ASCII bytes have been replaced so the text is still valid UTF-8.
+    Cow::Owned(String::from_utf8(bytes).unwrap())
+}
+
+/// Counts the number of columns `text` occupies, i.e. its number of chars or, when the
+/// `unicode-segmentation` feature is enabled, its number of grapheme clusters.
+///
+/// This is called for every section in every width check, so it does not look for `\r\n`
+/// terminators: the printer splits the lines before measuring them. Use
+/// [count_multiline_columns] for text that can span several lines.
+#[cfg(not(feature = "unicode-segmentation"))]
+#[inline(always)]
+pub fn count_columns(text: &str) -> usize {
+    bytecount::num_chars(text.as_bytes())
+}
+
+/// Counts the number of columns `text` occupies, i.e. its number of chars or, when the
+/// `unicode-segmentation` feature is enabled, its number of grapheme clusters.
+///
+/// This is called for every section in every width check, so it does not look for `\r\n`
+/// terminators: the printer splits the lines before measuring them. Use
+/// [count_multiline_columns] for text that can span several lines.
+#[cfg(feature = "unicode-segmentation")]
+#[inline(always)]
+pub fn count_columns(text: &str) -> usize {
+    unicode_segmentation::UnicodeSegmentation::graphemes(text, true).count()
+}
+
+/// Counts the number of columns `text` occupies like [count_columns], but `\r\n`
+/// terminators occupy a single column, e.g. to measure slices of a code spanning several
+/// lines.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub fn count_multiline_columns(text: &str) -> usize {
+    let crlf_count = memchr::memmem::find_iter(text.as_bytes(), b"\r\n").count();
+    count_columns(text) - crlf_count
+}
+
+/// Counts the number of columns `text` occupies like [count_columns], but `\r\n`
+/// terminators occupy a single column, e.g. to measure slices of a code spanning several
+/// lines.
+#[cfg(feature = "unicode-segmentation")]
+#[inline(always)]
+pub fn count_multiline_columns(text: &str) -> usize {
+    // Grapheme clusters already join `\r\n`.
+    count_columns(text)
+}
+
+/// Returns the byte offset of the 1-based `column` of `text`, measured like [count_columns].
+/// The column right after the last one is the length of `text`.
+#[cfg(not(feature = "unicode-segmentation"))]
+pub fn column_byte_offset(text: &str, column: usize) -> Option<usize> {
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .nth(column.checked_sub(1)?)
+}
+
+/// Returns the byte offset of the 1-based `column` of `text`, measured like [count_columns].
+/// The column right after the last one is the length of `text`.
+#[cfg(feature = "unicode-segmentation")]
+pub fn column_byte_offset(text: &str, column: usize) -> Option<usize> {
+    unicode_segmentation::UnicodeSegmentation::grapheme_indices(text, true)
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .nth(column.checked_sub(1)?)
+}
+
+/// Splits `text` into the pieces that occupy a column each, measured like [count_columns].
+#[cfg(not(feature = "unicode-segmentation"))]
+pub fn split_columns(text: &str) -> impl Iterator<Item = &str> {
+    text.char_indices()
+        .map(move |(i, c)| &text[i..i + c.len_utf8()])
+}
+
+/// Splits `text` into the pieces that occupy a column each, measured like [count_columns].
+#[cfg(feature = "unicode-segmentation")]
+pub fn split_columns(text: &str) -> impl Iterator<Item = &str> {
+    unicode_segmentation::UnicodeSegmentation::graphemes(text, true)
+}
+
+/// Escapes `text` as a JSON string, including the surrounding quotes.
+pub fn escape_json_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+/// Slices `text` keeping it borrowed when possible.
+pub fn slice_cow<'a>(text: &Cow<'a, str>, range: Range<usize>) -> Cow<'a, str> {
+    match text {
+        Cow::Borrowed(v) => Cow::Borrowed(&v[range]),
+        Cow::Owned(v) => Cow::Owned(v[range].to_string()),
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -15,5 +181,68 @@ mod tests {
     fn test_remove_jump_lines() {
         let result = remove_jump_lines("this\nis\na\ntest");
         assert_eq!(result, "this is a test");
+
+        let result = remove_jump_lines("this\r\nis\ra\ntest");
+        assert_eq!(result, "this is a test");
+    }
+
+    #[test]
+    fn test_split_lines() {
+        let lines: Vec<_> = split_lines("a\r\nb\rc\n\nd\r").collect();
+        assert_eq!(lines, vec!["a", "b", "c", "", "d"]);
+
+        assert_eq!(split_lines("").count(), 0);
+        assert!(ends_with_line_break("a\r"));
+        assert!(!ends_with_line_break("a"));
+    }
+
+    #[test]
+    fn test_replace_lone_carriage_returns() {
+        let result = replace_lone_carriage_returns(Cow::Borrowed("a\r\nb"));
+        assert!(matches!(result, Cow::Borrowed("a\r\nb")));
+
+        let result = replace_lone_carriage_returns(Cow::Borrowed("a\rb\r\nc\r"));
+        assert_eq!(result, "a\nb\r\nc\n");
+    }
+
+    #[test]
+    fn test_column_byte_offset() {
+        assert_eq!(column_byte_offset("aメb", 1), Some(0));
+        assert_eq!(column_byte_offset("aメb", 3), Some(4));
+        assert_eq!(column_byte_offset("aメb", 4), Some(5));
+        assert_eq!(column_byte_offset("aメb", 5), None);
+        assert_eq!(column_byte_offset("aメb", 0), None);
+    }
+
+    #[test]
+    fn test_escape_json_string() {
+        let result = escape_json_string("a \"b\"\\\n\u{1}");
+        assert_eq!(result, "\"a \\\"b\\\"\\\\\\n\\u0001\"");
+    }
+
+    #[test]
+    fn test_count_columns() {
+        assert_eq!(count_columns("メカジキ"), 4);
+
+        #[cfg(not(feature = "unicode-segmentation"))]
+        assert_eq!(count_columns("e\u{301}a"), 3);
+
+        #[cfg(feature = "unicode-segmentation")]
+        assert_eq!(count_columns("e\u{301}a"), 2);
+    }
+
+    #[test]
+    fn test_count_multiline_columns() {
+        assert_eq!(count_multiline_columns("a\r\nb\r"), 4);
+        assert_eq!(count_multiline_columns("メ\nカ"), 3);
+    }
+
+    #[test]
+    fn test_slice_cow() {
+        let result = slice_cow(&Cow::Borrowed("this is a test"), 5..7);
+        assert_eq!(result, Cow::Borrowed("is"));
+
+        let result = slice_cow(&Cow::Owned("this is a test".to_string()), 5..7);
+        assert_eq!(result, Cow::<str>::Owned("is".to_string()));
     }
 }
@@ -0,0 +1,612 @@
+use crate::blocks::{CodeBlock, LogBlock, StackBlock, TextBlock};
+use crate::utils::mask::MaskedText;
+use crate::LogContent;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// The text that replaces the secrets by default, see [Redactor::mask].
+const DEFAULT_MASK: &str = "[REDACTED]";
+
+/// A visitor that traverses the blocks of a log modifying them, e.g. to post-process logs
+/// before exporting them. See [Log::visit_mut](crate::Log::visit_mut) and
+/// [LogContent::visit_mut].
+///
+/// Every method continues the traversal by default, so implementors only override the ones
+/// they are interested in. An overridden method can call the corresponding `walk_*`
+/// function to keep traversing the nested elements.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::{CodeBlock, LogBlock, TextBlock};
+/// use doclog::{BlockVisitor, Log};
+///
+/// /// Removes the code blocks.
+/// struct StripCode;
+///
+/// impl<'a> BlockVisitor<'a> for StripCode {
+///     fn visit_block_mut(&mut self, block: &mut LogBlock<'a>) -> bool {
+///         !matches!(block, LogBlock::Code(_))
+///     }
+/// }
+///
+/// let mut log = Log::error()
+///     .add_block(TextBlock::new_plain("Invalid value"))
+///     .add_block(CodeBlock::new("let a = b;").highlight_section(8..9, None));
+/// log.visit_mut(&mut StripCode);
+///
+/// assert_eq!(log.to_plain_text(), "Invalid value");
+/// ```
+pub trait BlockVisitor<'a> {
+    /// Visits the blocks of a content, removing those for which
+    /// [BlockVisitor::visit_block_mut] returns `false`. See [walk_content_mut].
+    fn visit_content_mut(&mut self, content: &mut LogContent<'a>) {
+        walk_content_mut(self, content);
+    }
+
+    /// Visits a block and returns whether to keep it. See [walk_block_mut].
+    fn visit_block_mut(&mut self, block: &mut LogBlock<'a>) -> bool {
+        walk_block_mut(self, block);
+        true
+    }
+
+    /// Visits a code block. Its code is not visited because the highlighted sections
+    /// depend on it. See [walk_code_mut].
+    fn visit_code_mut(&mut self, code: &mut CodeBlock<'a>) {
+        walk_code_mut(self, code);
+    }
+
+    /// Visits a text that contains a file path, i.e. the file path of code blocks, the
+    /// file location of stack traces and the location of headers. By default it is
+    /// visited as any other text.
+    fn visit_path_mut(&mut self, path: &mut TextBlock<'a>) {
+        self.visit_text_mut(path);
+    }
+
+    /// Visits a text.
+    fn visit_text_mut(&mut self, _text: &mut TextBlock<'a>) {}
+}
+
+/// Visits every block of `content` with `visitor`, removing those for which
+/// [BlockVisitor::visit_block_mut] returns `false`. The ids keep pointing to the same
+/// blocks.
+pub fn walk_content_mut<'a, V: BlockVisitor<'a> + ?Sized>(
+    visitor: &mut V,
+    content: &mut LogContent<'a>,
+) {
+    retain_blocks(visitor, content);
+}
+
+/// Visits the texts, paths and nested contents of `block` with `visitor`.
+///
+/// The steps of a [StepsBlock](crate::blocks::StepsBlock) are visited block by block,
+/// instead of through [BlockVisitor::visit_content_mut], to keep their markers aligned
+/// with them. Diff and user-defined blocks are not traversed.
+pub fn walk_block_mut<'a, V: BlockVisitor<'a> + ?Sized>(visitor: &mut V, block: &mut LogBlock<'a>) {
+    match block {
+        // Basic blocks.
+        LogBlock::Text(v) => visitor.visit_text_mut(v),
+        LogBlock::Prefix(v) => {
+            visitor.visit_text_mut(&mut v.prefix);
+
+            if let Some(prefix) = &mut v.middle_prefix {
+                visitor.visit_text_mut(prefix);
+            }
+
+            if let Some(prefix) = &mut v.last_prefix {
+                visitor.visit_text_mut(prefix);
+            }
+
+            visitor.visit_content_mut(&mut v.content);
+        }
+
+        // Custom blocks.
+        LogBlock::Separator(_) => {}
+        LogBlock::Header(v) => {
+            visitor.visit_text_mut(&mut v.title);
+            visitor.visit_path_mut(&mut v.location);
+
+            for message in &mut v.extra_messages {
+                visitor.visit_text_mut(message);
+            }
+        }
+        LogBlock::Note(v) => visitor.visit_text_mut(&mut v.text),
+        LogBlock::Stack(v) => walk_stack_mut(visitor, v),
+        LogBlock::Code(v) => visitor.visit_code_mut(v),
+        LogBlock::Steps(v) => {
+            visitor.visit_text_mut(&mut v.title);
+
+            let kept = retain_blocks(visitor, &mut v.steps);
+            let mut kept = kept.into_iter();
+            v.markers.retain(|_| kept.next().unwrap_or(true));
+
+            visitor.visit_text_mut(&mut v.final_message);
+        }
+        LogBlock::Diff(_) => {}
+        LogBlock::Table(v) => {
+            for column in &mut v.columns {
+                visitor.visit_text_mut(&mut column.header);
+            }
+
+            for cell in v.rows.iter_mut().flatten() {
+                visitor.visit_text_mut(cell);
+            }
+        }
+        LogBlock::KeyValue(v) => {
+            for (key, value) in &mut v.entries {
+                visitor.visit_text_mut(key);
+                visitor.visit_text_mut(value);
+            }
+        }
+        LogBlock::Progress(v) => visitor.visit_text_mut(&mut v.message),
+
+        // User-defined blocks.
+        LogBlock::Custom(_) => {}
+    }
+}
+
+/// Visits the title, file path, messages and notes of `code` with `visitor`, and its
+/// related blocks through [BlockVisitor::visit_code_mut].
+pub fn walk_code_mut<'a, V: BlockVisitor<'a> + ?Sized>(visitor: &mut V, code: &mut CodeBlock<'a>) {
+    visitor.visit_text_mut(&mut code.title);
+    visitor.visit_path_mut(&mut code.file_path);
+
+    for section in code.sections_mut() {
+        visitor.visit_text_mut(&mut section.message);
+    }
+
+    for note in &mut code.notes {
+        visitor.visit_text_mut(&mut note.text);
+    }
+
+    visitor.visit_text_mut(&mut code.final_message);
+
+    for related in code.related_mut() {
+        visitor.visit_code_mut(related);
+    }
+}
+
+/// A [BlockVisitor] that replaces the secrets found in the texts with a mask, e.g. tokens
+/// or passwords, before shipping the logs.
+///
+/// Every section of a text is redacted independently, so secrets split across sections
+/// with different styles are not found. The code of code blocks is redacted line by line,
+/// moving the highlighted sections to cover the masks, and user-defined blocks are removed
+/// because their content cannot be inspected. The fields and suggestions of the logs are
+/// not visited, see [Redaction](crate::Redaction) to mask them too.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::TextBlock;
+/// use doclog::{Log, Redactor};
+///
+/// let mut log = Log::error().add_block(TextBlock::new_plain("Invalid token abc123"));
+/// log.visit_mut(&mut Redactor::new().secret("abc123"));
+///
+/// assert_eq!(log.to_plain_text(), "Invalid token [REDACTED]");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    secrets: Vec<Cow<'static, str>>,
+    #[cfg(feature = "regex")]
+    patterns: Vec<regex::Regex>,
+    mask: Cow<'static, str>,
+}
+
+impl Redactor {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [Redactor] without secrets that masks them with `[REDACTED]`.
+    pub fn new() -> Self {
+        Self {
+            secrets: Vec::new(),
+            #[cfg(feature = "regex")]
+            patterns: Vec::new(),
+            mask: Cow::Borrowed(DEFAULT_MASK),
+        }
+    }
+
+    // GETTERS ----------------------------------------------------------------
+
+    /// Returns the text that replaces the secrets.
+    #[inline(always)]
+    pub fn get_mask(&self) -> &str {
+        &self.mask
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Adds a literal secret to redact. Empty secrets are ignored.
+    pub fn secret(mut self, secret: impl Into<Cow<'static, str>>) -> Self {
+        let secret = secret.into();
+
+        if !secret.is_empty() {
+            self.secrets.push(secret);
+        }
+
+        self
+    }
+
+    /// Adds a pattern whose matches are redacted, e.g. `ghp_[A-Za-z0-9]{36}`.
+    #[cfg(feature = "regex")]
+    pub fn pattern(mut self, pattern: regex::Regex) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Sets the text that replaces the secrets.
+    #[inline(always)]
+    pub fn mask(mut self, mask: impl Into<Cow<'static, str>>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns `text` with the secrets replaced by the mask, borrowing it if it does not
+    /// contain any.
+    pub fn redact<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        MaskedText::new(text, self.secret_ranges(text), &self.mask).text
+    }
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Masker for Redactor {
+    fn secret_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+
+        for secret in &self.secrets {
+            ranges.extend(
+                text.match_indices(secret.as_ref())
+                    .map(|(start, v)| start..start + v.len()),
+            );
+        }
+
+        #[cfg(feature = "regex")]
+        for pattern in &self.patterns {
+            ranges.extend(pattern.find_iter(text).map(|v| v.range()));
+        }
+
+        ranges
+    }
+
+    fn mask_text(&self) -> &str {
+        &self.mask
+    }
+}
+
+impl<'a> BlockVisitor<'a> for Redactor {
+    fn visit_block_mut(&mut self, block: &mut LogBlock<'a>) -> bool {
+        mask_block(self, block)
+    }
+
+    fn visit_code_mut(&mut self, code: &mut CodeBlock<'a>) {
+        mask_code(self, code);
+    }
+
+    fn visit_text_mut(&mut self, text: &mut TextBlock<'a>) {
+        mask_text_block(self, text);
+    }
+}
+
+/// A [BlockVisitor] that rewrites the prefix of the file paths, e.g. to make absolute
+/// paths relative to the project or to hide the home directory.
+///
+/// Only the first section of every path is checked, which is where the prefix is placed
+/// unless the path is built from several styled sections.
+///
+/// # Examples
+/// ```
+/// use doclog::blocks::CodeBlock;
+/// use doclog::{Log, PathRewriter};
+///
+/// let mut log = Log::error().add_block(
+///     CodeBlock::new("let a = b;")
+///         .file_path("/home/user/project/src/main.rs")
+///         .highlight_section(8..9, None),
+/// );
+/// log.visit_mut(&mut PathRewriter::new().strip_prefix("/home/user/project/"));
+///
+/// assert!(log.to_plain_text().contains("[src/main.rs"));
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct PathRewriter {
+    prefixes: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl PathRewriter {
+    // CONSTRUCTORS -----------------------------------------------------------
+
+    /// Creates a new [PathRewriter] without rules.
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // BUILDERS ---------------------------------------------------------------
+
+    /// Replaces the prefix `from` of the paths with `to`. Rules are checked in the order
+    /// they are added and only the first matching one is applied.
+    pub fn replace_prefix(
+        mut self,
+        from: impl Into<Cow<'static, str>>,
+        to: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.prefixes.push((from.into(), to.into()));
+        self
+    }
+
+    /// Removes the prefix `prefix` from the paths. See [PathRewriter::replace_prefix].
+    #[inline(always)]
+    pub fn strip_prefix(self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.replace_prefix(prefix, "")
+    }
+
+    // METHODS ----------------------------------------------------------------
+
+    /// Returns `path` rewritten by the first matching rule, if any.
+    pub fn rewrite(&self, path: &str) -> Option<String> {
+        self.prefixes.iter().find_map(|(from, to)| {
+            path.strip_prefix(from.as_ref())
+                .map(|rest| format!("{to}{rest}"))
+        })
+    }
+}
+
+impl<'a> BlockVisitor<'a> for PathRewriter {
+    fn visit_path_mut(&mut self, path: &mut TextBlock<'a>) {
+        if let Some(section) = path.sections.first_mut() {
+            if let Some(v) = self.rewrite(&section.text) {
+                section.text = Cow::Owned(v);
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+/// A visitor that masks secrets, i.e. [Redactor] and [Redaction](crate::Redaction), which
+/// share how every kind of block is redacted.
+pub(crate) trait Masker {
+    /// Returns the ranges of `text` to mask, in any order and possibly overlapping.
+    fn secret_ranges(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Returns the text that replaces the secrets.
+    fn mask_text(&self) -> &str;
+
+    /// Masks the secrets of `text` in place.
+    fn mask_cow(&self, text: &mut Cow<'_, str>) {
+        let masked = MaskedText::new(text, self.secret_ranges(text), self.mask_text());
+
+        if masked.is_masked() {
+            *text = Cow::Owned(masked.text.into_owned());
+        }
+    }
+}
+
+/// Masks the secrets of `block` that are not visited as texts, i.e. the texts of diff
+/// blocks, the labels of separators, the codes of headers and the URLs of notes, before
+/// walking it. Returns whether to keep the block: user-defined blocks cannot be inspected
+/// so they are removed.
+pub(crate) fn mask_block<'a, V: BlockVisitor<'a> + Masker>(
+    visitor: &mut V,
+    block: &mut LogBlock<'a>,
+) -> bool {
+    match block {
+        LogBlock::Separator(v) => {
+            if let Some(label) = &mut v.label {
+                visitor.mask_cow(label);
+            }
+        }
+        LogBlock::Header(v) => visitor.mask_cow(&mut v.code),
+        LogBlock::Note(v) => {
+            if let Some(url) = &mut v.url {
+                visitor.mask_cow(url);
+            }
+        }
+        LogBlock::Diff(v) => {
+            for text in v.texts_mut() {
+                visitor.mask_cow(text);
+            }
+        }
+        LogBlock::Custom(_) => return false,
+        _ => {}
+    }
+
+    walk_block_mut(visitor, block);
+    true
+}
+
+/// Masks the secrets of the texts, code and suggestions of `code`.
+pub(crate) fn mask_code<'a, V: BlockVisitor<'a> + Masker>(
+    visitor: &mut V,
+    code: &mut CodeBlock<'a>,
+) {
+    walk_code_mut(visitor, code);
+    code.mask_code(|text| visitor.secret_ranges(text), visitor.mask_text());
+}
+
+/// Masks the secrets of every section of `text`.
+pub(crate) fn mask_text_block<V: Masker>(visitor: &V, text: &mut TextBlock) {
+    for section in &mut text.sections {
+        visitor.mask_cow(&mut section.text);
+    }
+}
+
+/// Visits the blocks of `content`, removing those for which
+/// [BlockVisitor::visit_block_mut] returns `false`, and returns whether each was kept.
+fn retain_blocks<'a, V: BlockVisitor<'a> + ?Sized>(
+    visitor: &mut V,
+    content: &mut LogContent<'a>,
+) -> Vec<bool> {
+    let kept: Vec<_> = content
+        .blocks
+        .iter_mut()
+        .map(|block| visitor.visit_block_mut(block))
+        .collect();
+
+    if kept.contains(&false) {
+        let mut flags = kept.iter();
+        *content = std::mem::take(content).filter_blocks(|_| *flags.next().unwrap());
+    }
+
+    kept
+}
+
+/// Visits the message, traces and causes of a stack block.
+fn walk_stack_mut<'a, V: BlockVisitor<'a> + ?Sized>(visitor: &mut V, stack: &mut StackBlock<'a>) {
+    visitor.visit_text_mut(&mut stack.message);
+
+    for trace in &mut stack.traces {
+        visitor.visit_path_mut(&mut trace.file_location);
+        visitor.visit_text_mut(&mut trace.code_path);
+        visitor.visit_text_mut(&mut trace.message);
+    }
+
+    if let Some(cause) = &mut stack.cause {
+        walk_stack_mut(visitor, cause);
+    }
+}
+
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{
+        HeaderBlock, KeyValueBlock, PrefixBlock, StackTraceBlock, StepStatus, StepsBlock,
+    };
+    use crate::Log;
+
+    #[test]
+    fn test_walk_nested_blocks() {
+        struct Upper;
+
+        impl<'a> BlockVisitor<'a> for Upper {
+            fn visit_text_mut(&mut self, text: &mut TextBlock<'a>) {
+                for section in &mut text.sections {
+                    section.text = Cow::Owned(section.text.to_uppercase());
+                }
+            }
+        }
+
+        let mut log = Log::info()
+            .add_block(HeaderBlock::new().title("title"))
+            .add_block(PrefixBlock::new().prefix("> ").content(
+                LogContent::new().add_block(KeyValueBlock::new().add_entry("key", "value")),
+            ))
+            .add_block(
+                StackBlock::new().message("message").add_stack_trace(
+                    StackTraceBlock::new()
+                        .file_location("src/main.rs")
+                        .message("trace"),
+                ),
+            );
+        log.visit_mut(&mut Upper);
+
+        assert_eq!(
+            log.to_plain_text(),
+            "INFO TITLE\n> KEY: VALUE\n╭─▶ MESSAGE\n│   at SRC/MAIN.RS - TRACE\n╰─"
+        );
+    }
+
+    #[test]
+    fn test_remove_blocks() {
+        struct StripSecrets;
+
+        impl<'a> BlockVisitor<'a> for StripSecrets {
+            fn visit_block_mut(&mut self, block: &mut LogBlock<'a>) -> bool {
+                walk_block_mut(self, block);
+                !matches!(block, LogBlock::Text(v) if v.sections.iter().any(|v| v.text == "secret"))
+            }
+        }
+
+        let mut steps = StepsBlock::new()
+            .add_step(TextBlock::new_plain("secret"))
+            .add_step(TextBlock::new_plain("public"));
+        steps.set_status(0, Some(StepStatus::Success));
+        steps.set_status(1, Some(StepStatus::Failure));
+
+        let mut log = Log::info()
+            .add_block(TextBlock::new_plain("secret"))
+            .add_block_with_id("steps", steps);
+        log.visit_mut(&mut StripSecrets);
+
+        assert_eq!(log.content.blocks.len(), 1);
+        assert_eq!(log.content.ids, vec![(0, Cow::Borrowed("steps"))]);
+
+        let LogBlock::Steps(steps) = &log.content.blocks[0] else {
+            panic!("Expected a steps block");
+        };
+        assert_eq!(steps.steps.blocks.len(), 1);
+        assert_eq!(steps.markers.len(), 1);
+        assert_eq!(steps.markers[0].status, Some(StepStatus::Failure));
+    }
+
+    #[test]
+    fn test_redactor() {
+        let redactor = Redactor::new().secret("abc").secret("").mask("***");
+
+        assert!(matches!(redactor.redact("nothing"), Cow::Borrowed(_)));
+        assert_eq!(redactor.redact("abc and abc"), "*** and ***");
+
+        let mut log = Log::error()
+            .add_block(TextBlock::new_plain("Token abc"))
+            .add_block(CodeBlock::new("let abc = 1;").highlight_section_message(4..7, None, "abc"));
+        log.visit_mut(&mut redactor.clone());
+
+        assert_eq!(
+            log.to_plain_text(),
+            "Token ***\n× ╭─\n1 │    let *** = 1;\n  │        ╰─┴── ***\n  ╰─"
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_redactor_pattern() {
+        let redactor = Redactor::new()
+            .pattern(regex::Regex::new("tok_[0-9]+").unwrap())
+            .mask("$0");
+
+        assert_eq!(redactor.redact("tok_12 and tok_3"), "$0 and $0");
+    }
+
+    #[test]
+    fn test_path_rewriter() {
+        let mut rewriter = PathRewriter::new()
+            .replace_prefix("/home/user/", "~/")
+            .strip_prefix("/home/");
+
+        assert_eq!(
+            rewriter.rewrite("/home/user/a.rs"),
+            Some("~/a.rs".to_string())
+        );
+        assert_eq!(
+            rewriter.rewrite("/home/other/a.rs"),
+            Some("other/a.rs".to_string())
+        );
+        assert_eq!(rewriter.rewrite("/tmp/a.rs"), None);
+
+        let mut log = Log::error()
+            .add_block(TextBlock::new_plain("/home/user/a.rs"))
+            .add_block(
+                HeaderBlock::new()
+                    .title("Error")
+                    .location("/home/user/b.rs"),
+            );
+        log.visit_mut(&mut rewriter);
+
+        assert_eq!(
+            log.to_plain_text(),
+            "/home/user/a.rs\nERROR Error\n ↪ in ~/b.rs"
+        );
+    }
+}